@@ -57,6 +57,7 @@ fn benchmark_files_no_partitions(c: &mut Criterion) {
                         idle_timeout_secs: None,
                         encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
                         compression: sinks::file::Compression::None,
+                        rotation: None,
                         acknowledgements: Default::default(),
                     },
                 );
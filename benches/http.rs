@@ -48,11 +48,14 @@ fn benchmark_http(c: &mut Criterion) {
                             "out",
                             &["in"],
                             sinks::http::HttpSinkConfig {
-                                uri: out_addr.to_string().parse::<http::Uri>().unwrap().into(),
+                                uri: sinks::http::HttpSinkUri::Static(
+                                    out_addr.to_string().parse::<http::Uri>().unwrap().into(),
+                                ),
                                 compression: *compression,
                                 method: Default::default(),
                                 auth: Default::default(),
                                 headers: Default::default(),
+                                header_templates: Default::default(),
                                 batch,
                                 encoding: (None::<FramingConfig>, TextSerializerConfig::default())
                                     .into(),
@@ -0,0 +1,677 @@
+use std::fmt::Write as _;
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, SecondsFormat, Utc};
+use lookup::event_path;
+use tokio_util::codec::Encoder;
+use vector_core::{
+    config::DataType,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+/// The maximum length of the `APP-NAME` header field, per RFC 5424.
+const APP_NAME_MAX_LENGTH: usize = 48;
+
+/// The maximum length of the `PROCID` header field, per RFC 5424.
+const PROC_ID_MAX_LENGTH: usize = 128;
+
+/// The maximum length of the `MSGID` header field, per RFC 5424.
+const MSG_ID_MAX_LENGTH: usize = 32;
+
+/// The maximum length of the `HOSTNAME` header field, per RFC 5424.
+const HOSTNAME_MAX_LENGTH: usize = 255;
+
+/// The maximum length of an `SD-NAME`, per RFC 5424.
+const SD_NAME_MAX_LENGTH: usize = 32;
+
+/// The value used in place of a missing RFC 5424 header field.
+const NIL_VALUE: &str = "-";
+
+/// Config used to build a `SyslogSerializer`.
+#[crate::configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct SyslogSerializerConfig {
+    /// Options for the syslog serializer.
+    #[serde(default)]
+    pub syslog: SyslogSerializerOptions,
+}
+
+impl SyslogSerializerConfig {
+    /// Creates a new `SyslogSerializerConfig`.
+    pub const fn new(syslog: SyslogSerializerOptions) -> Self {
+        Self { syslog }
+    }
+
+    /// Build the `SyslogSerializer` from this configuration.
+    pub fn build(&self) -> SyslogSerializer {
+        SyslogSerializer::new(self.syslog.clone())
+    }
+
+    /// The data type of events that are accepted by `SyslogSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Options for the syslog serializer.
+///
+/// Each header field is read from the event field of the same name that the `syslog` source and
+/// deserializer produce (`facility`, `severity`, `hostname`, `appname`, `procid`, `msgid`, and
+/// `structured_data`), falling back to the defaults below when the field is absent or invalid.
+#[crate::configurable_component]
+#[derive(Debug, Clone)]
+pub struct SyslogSerializerOptions {
+    /// The syslog message format to produce.
+    #[serde(default)]
+    pub rfc: SyslogRfc,
+
+    /// The facility to use when the event has no valid `facility` field.
+    #[serde(default)]
+    pub default_facility: Facility,
+
+    /// The severity to use when the event has no valid `severity` field.
+    #[serde(default)]
+    pub default_severity: Severity,
+
+    /// The application name to use when the event has no `appname` field.
+    #[serde(default = "default_app_name")]
+    pub default_app_name: String,
+}
+
+impl Default for SyslogSerializerOptions {
+    fn default() -> Self {
+        Self {
+            rfc: SyslogRfc::default(),
+            default_facility: Facility::default(),
+            default_severity: Severity::default(),
+            default_app_name: default_app_name(),
+        }
+    }
+}
+
+fn default_app_name() -> String {
+    "vector".to_string()
+}
+
+/// The syslog message format.
+#[crate::configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogRfc {
+    /// The legacy BSD syslog format, as described in [RFC 3164][rfc3164].
+    ///
+    /// [rfc3164]: https://datatracker.ietf.org/doc/html/rfc3164
+    Rfc3164,
+
+    /// The syslog protocol format, as described in [RFC 5424][rfc5424].
+    ///
+    /// [rfc5424]: https://datatracker.ietf.org/doc/html/rfc5424
+    #[default]
+    Rfc5424,
+}
+
+/// A syslog facility.
+#[crate::configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Facility {
+    /// Kernel messages.
+    Kern,
+    /// User-level messages.
+    #[default]
+    User,
+    /// Mail system.
+    Mail,
+    /// System daemons.
+    Daemon,
+    /// Security/authorization messages.
+    Auth,
+    /// Messages generated internally by syslogd.
+    Syslog,
+    /// Line printer subsystem.
+    Lpr,
+    /// Network news subsystem.
+    News,
+    /// UUCP subsystem.
+    Uucp,
+    /// Clock daemon.
+    Cron,
+    /// Security/authorization messages (private).
+    Authpriv,
+    /// FTP daemon.
+    Ftp,
+    /// NTP subsystem.
+    Ntp,
+    /// Log audit.
+    Audit,
+    /// Log alert.
+    Alert,
+    /// Clock daemon (note 2).
+    Clockd,
+    /// Local use 0.
+    Local0,
+    /// Local use 1.
+    Local1,
+    /// Local use 2.
+    Local2,
+    /// Local use 3.
+    Local3,
+    /// Local use 4.
+    Local4,
+    /// Local use 5.
+    Local5,
+    /// Local use 6.
+    Local6,
+    /// Local use 7.
+    Local7,
+}
+
+const FACILITIES: [Facility; 24] = [
+    Facility::Kern,
+    Facility::User,
+    Facility::Mail,
+    Facility::Daemon,
+    Facility::Auth,
+    Facility::Syslog,
+    Facility::Lpr,
+    Facility::News,
+    Facility::Uucp,
+    Facility::Cron,
+    Facility::Authpriv,
+    Facility::Ftp,
+    Facility::Ntp,
+    Facility::Audit,
+    Facility::Alert,
+    Facility::Clockd,
+    Facility::Local0,
+    Facility::Local1,
+    Facility::Local2,
+    Facility::Local3,
+    Facility::Local4,
+    Facility::Local5,
+    Facility::Local6,
+    Facility::Local7,
+];
+
+impl Facility {
+    /// The numerical code of this facility.
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// The name of this facility, as produced by the `syslog` deserializer.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Facility::Kern => "kern",
+            Facility::User => "user",
+            Facility::Mail => "mail",
+            Facility::Daemon => "daemon",
+            Facility::Auth => "auth",
+            Facility::Syslog => "syslog",
+            Facility::Lpr => "lpr",
+            Facility::News => "news",
+            Facility::Uucp => "uucp",
+            Facility::Cron => "cron",
+            Facility::Authpriv => "authpriv",
+            Facility::Ftp => "ftp",
+            Facility::Ntp => "ntp",
+            Facility::Audit => "audit",
+            Facility::Alert => "alert",
+            Facility::Clockd => "clockd",
+            Facility::Local0 => "local0",
+            Facility::Local1 => "local1",
+            Facility::Local2 => "local2",
+            Facility::Local3 => "local3",
+            Facility::Local4 => "local4",
+            Facility::Local5 => "local5",
+            Facility::Local6 => "local6",
+            Facility::Local7 => "local7",
+        }
+    }
+
+    /// Parses a facility from either its name or its numerical code.
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Integer(code) => usize::try_from(*code)
+                .ok()
+                .and_then(|code| FACILITIES.get(code).copied()),
+            Value::Bytes(bytes) => {
+                let name = String::from_utf8_lossy(bytes);
+                let name = name.trim().to_ascii_lowercase();
+                match name.parse::<usize>() {
+                    Ok(code) => FACILITIES.get(code).copied(),
+                    Err(_) => FACILITIES.into_iter().find(|f| f.as_str() == name),
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A syslog severity.
+#[crate::configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// System is unusable.
+    Emerg,
+    /// Action must be taken immediately.
+    Alert,
+    /// Critical conditions.
+    Crit,
+    /// Error conditions.
+    Err,
+    /// Warning conditions.
+    Warning,
+    /// Normal but significant conditions.
+    Notice,
+    /// Informational messages.
+    #[default]
+    Info,
+    /// Debug-level messages.
+    Debug,
+}
+
+const SEVERITIES: [Severity; 8] = [
+    Severity::Emerg,
+    Severity::Alert,
+    Severity::Crit,
+    Severity::Err,
+    Severity::Warning,
+    Severity::Notice,
+    Severity::Info,
+    Severity::Debug,
+];
+
+impl Severity {
+    /// The numerical code of this severity.
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// The name of this severity, as produced by the `syslog` deserializer.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Severity::Emerg => "emerg",
+            Severity::Alert => "alert",
+            Severity::Crit => "crit",
+            Severity::Err => "err",
+            Severity::Warning => "warning",
+            Severity::Notice => "notice",
+            Severity::Info => "info",
+            Severity::Debug => "debug",
+        }
+    }
+
+    /// Parses a severity from either its name, a common alias of its name, or its numerical code.
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Integer(code) => usize::try_from(*code)
+                .ok()
+                .and_then(|code| SEVERITIES.get(code).copied()),
+            Value::Bytes(bytes) => {
+                let name = String::from_utf8_lossy(bytes);
+                let name = name.trim().to_ascii_lowercase();
+                if let Ok(code) = name.parse::<usize>() {
+                    return SEVERITIES.get(code).copied();
+                }
+                match name.as_str() {
+                    "emergency" | "panic" => Some(Severity::Emerg),
+                    "critical" => Some(Severity::Crit),
+                    "error" => Some(Severity::Err),
+                    "warn" => Some(Severity::Warning),
+                    "informational" => Some(Severity::Info),
+                    name => SEVERITIES.into_iter().find(|s| s.as_str() == name),
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using the syslog format.
+#[derive(Debug, Clone)]
+pub struct SyslogSerializer {
+    options: SyslogSerializerOptions,
+}
+
+impl SyslogSerializer {
+    /// Creates a new `SyslogSerializer`.
+    pub const fn new(options: SyslogSerializerOptions) -> Self {
+        Self { options }
+    }
+
+    fn write_rfc5424(&self, log: &LogEvent, pri: u8, buffer: &mut String) {
+        let timestamp = timestamp(log).to_rfc3339_opts(SecondsFormat::Micros, true);
+        let hostname = header_field(hostname(log), HOSTNAME_MAX_LENGTH);
+        let app_name = header_field(
+            string_field(log, "appname").or_else(|| Some(self.options.default_app_name.clone())),
+            APP_NAME_MAX_LENGTH,
+        );
+        let proc_id = header_field(string_field(log, "procid"), PROC_ID_MAX_LENGTH);
+        let msg_id = header_field(string_field(log, "msgid"), MSG_ID_MAX_LENGTH);
+
+        _ = write!(
+            buffer,
+            "<{}>1 {} {} {} {} {} ",
+            pri, timestamp, hostname, app_name, proc_id, msg_id
+        );
+        write_structured_data(log, buffer);
+
+        if let Some(message) = message(log) {
+            buffer.push(' ');
+            buffer.push_str(&message);
+        }
+    }
+
+    fn write_rfc3164(&self, log: &LogEvent, pri: u8, buffer: &mut String) {
+        let timestamp = timestamp(log).format("%b %e %H:%M:%S");
+        let hostname = hostname(log)
+            .map(|hostname| sanitize_header(&hostname, HOSTNAME_MAX_LENGTH))
+            .filter(|hostname| !hostname.is_empty())
+            .unwrap_or_else(|| "localhost".to_string());
+        let tag = string_field(log, "appname")
+            .map(|app_name| sanitize_tag(&app_name))
+            .filter(|tag| !tag.is_empty())
+            .unwrap_or_else(|| sanitize_tag(&self.options.default_app_name));
+
+        _ = write!(buffer, "<{}>{} {} {}", pri, timestamp, hostname, tag);
+        if let Some(proc_id) = string_field(log, "procid") {
+            _ = write!(
+                buffer,
+                "[{}]",
+                sanitize_header(&proc_id, PROC_ID_MAX_LENGTH)
+            );
+        }
+        buffer.push(':');
+
+        if let Some(message) = message(log) {
+            buffer.push(' ');
+            buffer.push_str(&message);
+        }
+    }
+}
+
+impl Encoder<Event> for SyslogSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+
+        let facility = log
+            .get(event_path!("facility"))
+            .and_then(Facility::from_value)
+            .unwrap_or(self.options.default_facility);
+        let severity = log
+            .get(event_path!("severity"))
+            .and_then(Severity::from_value)
+            .unwrap_or(self.options.default_severity);
+        let pri = facility.code() * 8 + severity.code();
+
+        let mut output = String::new();
+        match self.options.rfc {
+            SyslogRfc::Rfc3164 => self.write_rfc3164(&log, pri, &mut output),
+            SyslogRfc::Rfc5424 => self.write_rfc5424(&log, pri, &mut output),
+        }
+
+        buffer.put_slice(output.as_bytes());
+        Ok(())
+    }
+}
+
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::Bytes(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        Value::Integer(_) | Value::Float(_) | Value::Boolean(_) => {
+            Some(value.to_string_lossy().into_owned())
+        }
+        Value::Timestamp(timestamp) => Some(timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+        _ => None,
+    }
+}
+
+fn string_field(log: &LogEvent, field: &'static str) -> Option<String> {
+    log.get(event_path!(field))
+        .and_then(value_to_string)
+        .filter(|value| !value.is_empty())
+}
+
+fn hostname(log: &LogEvent) -> Option<String> {
+    string_field(log, "hostname")
+        .or_else(|| log.get_host().and_then(value_to_string))
+        .filter(|value| !value.is_empty())
+}
+
+fn timestamp(log: &LogEvent) -> DateTime<Utc> {
+    match log.get_timestamp() {
+        Some(Value::Timestamp(timestamp)) => *timestamp,
+        _ => Utc::now(),
+    }
+}
+
+fn message(log: &LogEvent) -> Option<String> {
+    log.get_message()
+        .and_then(value_to_string)
+        .filter(|message| !message.is_empty())
+}
+
+/// Restricts a header field to printable US-ASCII without spaces and truncates it to `max_length`.
+fn sanitize_header(value: &str, max_length: usize) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max_length)
+        .collect()
+}
+
+/// Renders an RFC 5424 header field, using the nil value if it's missing or empty.
+fn header_field(value: Option<String>, max_length: usize) -> String {
+    value
+        .map(|value| sanitize_header(&value, max_length))
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| NIL_VALUE.to_string())
+}
+
+/// RFC 3164 tags are limited to 32 alphanumeric characters.
+fn sanitize_tag(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+        .take(32)
+        .collect()
+}
+
+/// Restricts an `SD-ID` or `PARAM-NAME` to the characters allowed by RFC 5424.
+fn sanitize_sd_name(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"'))
+        .take(SD_NAME_MAX_LENGTH)
+        .collect()
+}
+
+fn write_structured_data(log: &LogEvent, buffer: &mut String) {
+    let start = buffer.len();
+
+    if let Some(Value::Object(elements)) = log.get(event_path!("structured_data")) {
+        for (id, params) in elements {
+            let id = sanitize_sd_name(id);
+            if id.is_empty() {
+                continue;
+            }
+
+            _ = write!(buffer, "[{}", id);
+            if let Value::Object(params) = params {
+                for (name, value) in params {
+                    let name = sanitize_sd_name(name);
+                    let value = value_to_string(value);
+                    if let (false, Some(value)) = (name.is_empty(), value) {
+                        _ = write!(buffer, " {}=\"", name);
+                        escape_param_value(&value, buffer);
+                        buffer.push('"');
+                    }
+                }
+            }
+            buffer.push(']');
+        }
+    }
+
+    if buffer.len() == start {
+        buffer.push_str(NIL_VALUE);
+    }
+}
+
+fn escape_param_value(value: &str, buffer: &mut String) {
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            buffer.push('\\');
+        }
+        buffer.push(c);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_core::event::LogEvent;
+    use vrl::btreemap;
+
+    use super::*;
+
+    fn serialize(options: SyslogSerializerOptions, log: LogEvent) -> String {
+        let mut serializer = SyslogSerializerConfig::new(options).build();
+        let mut buffer = BytesMut::new();
+        serializer.encode(log.into(), &mut buffer).unwrap();
+        String::from_utf8(buffer.to_vec()).unwrap()
+    }
+
+    fn log_event() -> LogEvent {
+        let mut log = LogEvent::from("hello world");
+        log.insert(
+            "timestamp",
+            Utc.with_ymd_and_hms(2023, 4, 5, 6, 7, 8).unwrap(),
+        );
+        log.insert("hostname", "web-1");
+        log
+    }
+
+    #[test]
+    fn serialize_rfc5424_with_defaults() {
+        let output = serialize(SyslogSerializerOptions::default(), log_event());
+
+        assert_eq!(
+            output,
+            "<14>1 2023-04-05T06:07:08.000000Z web-1 vector - - - hello world"
+        );
+    }
+
+    #[test]
+    fn serialize_rfc5424_with_event_fields() {
+        let mut log = log_event();
+        log.insert("facility", "local3");
+        log.insert("severity", "err");
+        log.insert("appname", "my app");
+        log.insert("procid", 4242_i64);
+        log.insert("msgid", "ID47");
+        log.insert(
+            "structured_data",
+            btreemap! {
+                "exampleSDID@32473" => btreemap! {
+                    "eventSource" => "Application",
+                    "iut" => 3_i64,
+                    "note" => r#"a "quoted] \ value"#,
+                },
+            },
+        );
+
+        let output = serialize(SyslogSerializerOptions::default(), log);
+
+        assert_eq!(
+            output,
+            concat!(
+                "<155>1 2023-04-05T06:07:08.000000Z web-1 myapp 4242 ID47 ",
+                r#"[exampleSDID@32473 eventSource="Application" iut="3" "#,
+                r#"note="a \"quoted\] \\ value"] hello world"#,
+            )
+        );
+    }
+
+    #[test]
+    fn serialize_rfc3164() {
+        let mut log = log_event();
+        log.insert("facility", 4_i64);
+        log.insert("severity", "2");
+        log.insert("appname", "sshd");
+        log.insert("procid", 123_i64);
+
+        let options = SyslogSerializerOptions {
+            rfc: SyslogRfc::Rfc3164,
+            ..Default::default()
+        };
+        let output = serialize(options, log);
+
+        assert_eq!(output, "<34>Apr  5 06:07:08 web-1 sshd[123]: hello world");
+    }
+
+    #[test]
+    fn serialize_uses_configured_defaults_for_invalid_fields() {
+        let mut log = log_event();
+        log.insert("facility", "bogus");
+        log.insert("severity", 12_i64);
+
+        let options = SyslogSerializerOptions {
+            default_facility: Facility::Local7,
+            default_severity: Severity::Notice,
+            default_app_name: "forwarder".to_string(),
+            ..Default::default()
+        };
+        let output = serialize(options, log);
+
+        assert_eq!(
+            output,
+            "<189>1 2023-04-05T06:07:08.000000Z web-1 forwarder - - - hello world"
+        );
+    }
+
+    #[test]
+    fn serialize_truncates_header_fields() {
+        let mut log = log_event();
+        log.insert("appname", "a".repeat(64));
+        log.insert("msgid", "m".repeat(40));
+
+        let output = serialize(SyslogSerializerOptions::default(), log);
+
+        assert_eq!(
+            output,
+            format!(
+                "<14>1 2023-04-05T06:07:08.000000Z web-1 {} - {} - hello world",
+                "a".repeat(APP_NAME_MAX_LENGTH),
+                "m".repeat(MSG_ID_MAX_LENGTH)
+            )
+        );
+    }
+
+    #[test]
+    fn parse_facility_and_severity() {
+        assert_eq!(
+            Facility::from_value(&Value::from("LOCAL0")),
+            Some(Facility::Local0)
+        );
+        assert_eq!(
+            Facility::from_value(&Value::from(23_i64)),
+            Some(Facility::Local7)
+        );
+        assert_eq!(Facility::from_value(&Value::from(24_i64)), None);
+        assert_eq!(
+            Severity::from_value(&Value::from("warn")),
+            Some(Severity::Warning)
+        );
+        assert_eq!(Severity::from_value(&Value::from(-1_i64)), None);
+    }
+}
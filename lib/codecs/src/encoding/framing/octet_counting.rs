@@ -0,0 +1,74 @@
+use bytes::{BufMut, BytesMut};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+
+use super::BoxedFramingError;
+
+/// Config used to build a `OctetCountingEncoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct OctetCountingEncoderConfig;
+
+impl OctetCountingEncoderConfig {
+    /// Creates a `OctetCountingEncoderConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `OctetCountingEncoder` from this configuration.
+    pub const fn build(&self) -> OctetCountingEncoder {
+        OctetCountingEncoder::new()
+    }
+}
+
+/// An encoder for handling bytes that are prefixed by their length as ASCII decimal digits
+/// followed by a space, as described in [RFC 6587][rfc6587].
+///
+/// [rfc6587]: https://datatracker.ietf.org/doc/html/rfc6587#section-3.4.1
+#[derive(Debug, Clone, Default)]
+pub struct OctetCountingEncoder;
+
+impl OctetCountingEncoder {
+    /// Creates a `OctetCountingEncoder`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder<()> for OctetCountingEncoder {
+    type Error = BoxedFramingError;
+
+    fn encode(&mut self, _: (), buffer: &mut BytesMut) -> Result<(), BoxedFramingError> {
+        let message = buffer.split();
+        let length = message.len().to_string();
+        buffer.reserve(length.len() + 1 + message.len());
+        buffer.put_slice(length.as_bytes());
+        buffer.put_u8(b' ');
+        buffer.unsplit(message);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        let mut codec = OctetCountingEncoder::new();
+
+        let mut buffer = BytesMut::from("<13>1 - - - - - - hello");
+        codec.encode((), &mut buffer).unwrap();
+
+        assert_eq!(&buffer[..], b"23 <13>1 - - - - - - hello");
+    }
+
+    #[test]
+    fn encode_empty() {
+        let mut codec = OctetCountingEncoder::new();
+
+        let mut buffer = BytesMut::new();
+        codec.encode((), &mut buffer).unwrap();
+
+        assert_eq!(&buffer[..], b"0 ");
+    }
+}
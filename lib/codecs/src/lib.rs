@@ -25,7 +25,9 @@ pub use encoding::{
     JsonSerializerConfig, LengthDelimitedEncoder, LengthDelimitedEncoderConfig, LogfmtSerializer,
     LogfmtSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
     NativeSerializerConfig, NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig,
-    RawMessageSerializer, RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
+    OctetCountingEncoder, OctetCountingEncoderConfig, RawMessageSerializer,
+    RawMessageSerializerConfig, SyslogSerializer, SyslogSerializerConfig, SyslogSerializerOptions,
+    TextSerializer, TextSerializerConfig,
 };
 pub use gelf::{gelf_fields, VALID_FIELD_REGEX};
 use vector_config::configurable_component;
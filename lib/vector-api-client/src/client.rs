@@ -3,6 +3,8 @@ use graphql_client::GraphQLQuery;
 use indoc::indoc;
 use url::Url;
 
+use crate::Credentials;
+
 /// Wrapped `Result` type, that returns deserialized GraphQL response data.
 pub type QueryResult<T> =
     anyhow::Result<graphql_client::Response<<T as GraphQLQuery>::ResponseData>>;
@@ -11,25 +13,64 @@ pub type QueryResult<T> =
 #[derive(Debug)]
 pub struct Client {
     url: Url,
+    http: reqwest::Client,
+    credentials: Credentials,
 }
 
 impl Client {
     /// Returns a new GraphQL query client, bound to the provided URL.
     pub fn new(url: Url) -> Self {
-        Self { url }
+        Self {
+            url,
+            http: reqwest::Client::new(),
+            credentials: Credentials::default(),
+        }
+    }
+
+    /// Returns a new GraphQL query client, bound to the provided URL, that presents the given
+    /// credentials to the server.
+    pub fn with_credentials(url: Url, credentials: Credentials) -> anyhow::Result<Self> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(connector) = credentials.tls_connector()? {
+            builder = builder.use_preconfigured_tls(connector);
+        }
+        let http = builder.build().context("Couldn't build HTTP client")?;
+
+        Ok(Self {
+            url,
+            http,
+            credentials,
+        })
     }
 
-    pub async fn new_with_healthcheck(url: Url) -> Option<Self> {
+    pub async fn new_with_healthcheck(url: Url, credentials: Credentials) -> Option<Self> {
         #![allow(clippy::print_stderr)]
 
         use crate::gql::HealthQueryExt;
 
         // Create a new API client for connecting to the local/remote Vector instance.
-        let client = Self::new(url.clone());
+        let client = match Self::with_credentials(url.clone(), credentials) {
+            Ok(client) => client,
+            Err(error) => {
+                eprintln!("Couldn't set up the Vector API client: {:#}", error);
+                return None;
+            }
+        };
 
         // Check that the GraphQL server is reachable
         match client.health_query().await {
             Ok(_) => Some(client),
+            Err(error) if is_unauthorized(&error) => {
+                eprintln!(
+                    indoc! {"
+                    Vector API server ({}) rejected the request as unauthorized.
+
+                    Set `--api-token`, or the `VECTOR_API_TOKEN` environment variable, to the
+                    token in the `api.auth.token` option of its config."},
+                    url
+                );
+                None
+            }
             _ => {
                 eprintln!(
                     indoc! {"
@@ -54,11 +95,12 @@ impl Client {
         &self,
         request_body: &graphql_client::QueryBody<T::Variables>,
     ) -> QueryResult<T> {
-        let client = reqwest::Client::new();
+        let mut request = self.http.post(self.url.clone()).json(request_body);
+        if let Some(authorization) = self.credentials.authorization() {
+            request = request.header(reqwest::header::AUTHORIZATION, authorization);
+        }
 
-        client
-            .post(self.url.clone())
-            .json(request_body)
+        request
             .send()
             .await
             .with_context(|| {
@@ -68,6 +110,13 @@ impl Client {
                     &self.url.as_str()
                 )
             })?
+            .error_for_status()
+            .with_context(|| {
+                format!(
+                    "Vector API server rejected '{}' query",
+                    request_body.operation_name
+                )
+            })?
             .json()
             .await
             .with_context(|| {
@@ -78,3 +127,11 @@ impl Client {
             })
     }
 }
+
+/// Whether or not the query failed because the server rejected the credentials.
+fn is_unauthorized(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<reqwest::Error>()
+        .and_then(reqwest::Error::status)
+        == Some(reqwest::StatusCode::UNAUTHORIZED)
+}
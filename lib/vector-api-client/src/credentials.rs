@@ -0,0 +1,94 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+/// Credentials presented to a Vector API server that requires authentication, by way of a bearer
+/// token, a client certificate for mutual TLS, or both.
+#[derive(clap::Args, Clone, Default)]
+pub struct Credentials {
+    /// Bearer token to present to the Vector API server, as set in its `api.auth.token` option
+    #[arg(long = "api-token", env = "VECTOR_API_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// CA certificate (PEM) to verify the Vector API server's certificate with, in addition to
+    /// the system's trusted certificates
+    #[arg(long = "api-ca-file", env = "VECTOR_API_CA_FILE")]
+    pub ca_file: Option<PathBuf>,
+
+    /// Client certificate (PEM) to present to the Vector API server, for mutual TLS
+    #[arg(
+        long = "api-crt-file",
+        env = "VECTOR_API_CRT_FILE",
+        requires = "key_file"
+    )]
+    pub crt_file: Option<PathBuf>,
+
+    /// Private key (PEM, PKCS#8) of the client certificate
+    #[arg(
+        long = "api-key-file",
+        env = "VECTOR_API_KEY_FILE",
+        requires = "crt_file"
+    )]
+    pub key_file: Option<PathBuf>,
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("token", &self.token.as_ref().map(|_| "**REDACTED**"))
+            .field("ca_file", &self.ca_file)
+            .field("crt_file", &self.crt_file)
+            .field("key_file", &self.key_file)
+            .finish()
+    }
+}
+
+impl Credentials {
+    /// Returns the value of the `Authorization` header carrying the bearer token, if any.
+    pub(crate) fn authorization(&self) -> Option<String> {
+        self.token.as_ref().map(|token| format!("Bearer {}", token))
+    }
+
+    /// Returns a TLS connector that trusts the configured CA certificate and presents the client
+    /// certificate, or `None` if neither is configured.
+    pub(crate) fn tls_connector(&self) -> anyhow::Result<Option<native_tls::TlsConnector>> {
+        if self.ca_file.is_none() && self.crt_file.is_none() && self.key_file.is_none() {
+            return Ok(None);
+        }
+
+        let mut builder = native_tls::TlsConnector::builder();
+
+        if let Some(ca_file) = &self.ca_file {
+            let certificate = native_tls::Certificate::from_pem(&read(ca_file)?)
+                .with_context(|| format!("Couldn't parse CA certificate {:?}", ca_file))?;
+            builder.add_root_certificate(certificate);
+        }
+
+        match (&self.crt_file, &self.key_file) {
+            (Some(crt_file), Some(key_file)) => {
+                let identity = native_tls::Identity::from_pkcs8(&read(crt_file)?, &read(key_file)?)
+                    .with_context(|| {
+                        format!(
+                            "Couldn't parse client certificate {:?} and key {:?}",
+                            crt_file, key_file
+                        )
+                    })?;
+                builder.identity(identity);
+            }
+            (None, None) => {}
+            _ => anyhow::bail!("Mutual TLS requires both a client certificate and key"),
+        }
+
+        builder
+            .build()
+            .context("Couldn't build TLS connector")
+            .map(Some)
+    }
+}
+
+fn read(path: &Path) -> anyhow::Result<Vec<u8>> {
+    fs::read(path).with_context(|| format!("Couldn't read {:?}", path))
+}
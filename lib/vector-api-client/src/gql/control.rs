@@ -0,0 +1,183 @@
+//! Control mutations, for pausing and resuming sources, and draining and resuming sinks.
+
+use async_trait::async_trait;
+use graphql_client::GraphQLQuery;
+
+/// PauseSourceMutation pauses a source. Returns whether or not it was running beforehand.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/mutations/pause_source.graphql",
+    response_derives = "Debug"
+)]
+pub struct PauseSourceMutation;
+
+/// ResumeSourceMutation resumes a paused source. Returns whether or not it was paused beforehand.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/mutations/resume_source.graphql",
+    response_derives = "Debug"
+)]
+pub struct ResumeSourceMutation;
+
+/// DrainSinkMutation drains a sink, which stops it once it has sent the events it pulled from its
+/// buffer. Returns the state of the sink beforehand.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/mutations/drain_sink.graphql",
+    response_derives = "Debug, PartialEq"
+)]
+pub struct DrainSinkMutation;
+
+/// ResumeSinkMutation resumes a draining or stopped sink. Returns the state of the sink
+/// beforehand.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/mutations/resume_sink.graphql",
+    response_derives = "Debug, PartialEq"
+)]
+pub struct ResumeSinkMutation;
+
+/// Extension methods for control mutations. Unlike queries, these return the data of the
+/// response, or an error carrying the GraphQL errors returned by the server, such as mutations
+/// being disabled or there being no such component.
+#[async_trait]
+pub trait ControlMutationExt {
+    /// Executes a pause source mutation.
+    async fn pause_source(
+        &self,
+        component_id: String,
+    ) -> anyhow::Result<pause_source_mutation::ResponseData>;
+
+    /// Executes a resume source mutation.
+    async fn resume_source(
+        &self,
+        component_id: String,
+    ) -> anyhow::Result<resume_source_mutation::ResponseData>;
+
+    /// Executes a drain sink mutation.
+    async fn drain_sink(
+        &self,
+        component_id: String,
+    ) -> anyhow::Result<drain_sink_mutation::ResponseData>;
+
+    /// Executes a resume sink mutation.
+    async fn resume_sink(
+        &self,
+        component_id: String,
+    ) -> anyhow::Result<resume_sink_mutation::ResponseData>;
+}
+
+#[async_trait]
+impl ControlMutationExt for crate::Client {
+    async fn pause_source(
+        &self,
+        component_id: String,
+    ) -> anyhow::Result<pause_source_mutation::ResponseData> {
+        let request_body =
+            PauseSourceMutation::build_query(pause_source_mutation::Variables { component_id });
+        response_data(self.query::<PauseSourceMutation>(&request_body).await?)
+    }
+
+    async fn resume_source(
+        &self,
+        component_id: String,
+    ) -> anyhow::Result<resume_source_mutation::ResponseData> {
+        let request_body =
+            ResumeSourceMutation::build_query(resume_source_mutation::Variables { component_id });
+        response_data(self.query::<ResumeSourceMutation>(&request_body).await?)
+    }
+
+    async fn drain_sink(
+        &self,
+        component_id: String,
+    ) -> anyhow::Result<drain_sink_mutation::ResponseData> {
+        let request_body =
+            DrainSinkMutation::build_query(drain_sink_mutation::Variables { component_id });
+        response_data(self.query::<DrainSinkMutation>(&request_body).await?)
+    }
+
+    async fn resume_sink(
+        &self,
+        component_id: String,
+    ) -> anyhow::Result<resume_sink_mutation::ResponseData> {
+        let request_body =
+            ResumeSinkMutation::build_query(resume_sink_mutation::Variables { component_id });
+        response_data(self.query::<ResumeSinkMutation>(&request_body).await?)
+    }
+}
+
+/// Returns the data of a response, or its errors.
+fn response_data<T>(response: graphql_client::Response<T>) -> anyhow::Result<T> {
+    if let Some(errors) = response.errors.filter(|errors| !errors.is_empty()) {
+        let messages = errors
+            .into_iter()
+            .map(|error| error.message)
+            .collect::<Vec<_>>();
+        anyhow::bail!("{}", messages.join(" "));
+    }
+    response
+        .data
+        .ok_or_else(|| anyhow::anyhow!("Vector API server returned no data"))
+}
+
+#[cfg(test)]
+mod tests {
+    use graphql_client::Error;
+
+    use super::*;
+
+    fn error(message: &str) -> Error {
+        Error {
+            message: message.to_string(),
+            locations: None,
+            path: None,
+            extensions: None,
+        }
+    }
+
+    #[test]
+    fn returns_response_data() {
+        let response = graphql_client::Response {
+            data: Some(pause_source_mutation::ResponseData { pause_source: true }),
+            errors: Some(Vec::new()),
+            extensions: None,
+        };
+
+        assert!(response_data(response).unwrap().pause_source);
+    }
+
+    #[test]
+    fn returns_response_errors() {
+        let response = graphql_client::Response::<drain_sink_mutation::ResponseData> {
+            data: None,
+            errors: Some(vec![
+                error("Mutations are disabled."),
+                error("No sink with component ID \"out\"."),
+            ]),
+            extensions: None,
+        };
+
+        assert_eq!(
+            response_data(response).unwrap_err().to_string(),
+            "Mutations are disabled. No sink with component ID \"out\"."
+        );
+    }
+
+    #[test]
+    fn requires_response_data() {
+        let response = graphql_client::Response::<resume_sink_mutation::ResponseData> {
+            data: None,
+            errors: None,
+            extensions: None,
+        };
+
+        assert_eq!(
+            response_data(response).unwrap_err().to_string(),
+            "Vector API server returned no data"
+        );
+    }
+}
@@ -13,10 +13,12 @@
 #![deny(missing_debug_implementations, missing_copy_implementations)]
 
 mod client;
+mod credentials;
 /// GraphQL queries
 pub mod gql;
 mod subscription;
 pub mod test;
 
 pub use client::*;
+pub use credentials::*;
 pub use subscription::*;
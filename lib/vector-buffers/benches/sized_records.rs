@@ -10,7 +10,7 @@ use criterion::{
     Criterion, SamplingMode, Throughput,
 };
 use tokio::runtime::{Handle, Runtime};
-use vector_buffers::{BufferType, WhenFull};
+use vector_buffers::{BufferType, DiskBufferCompression, WhenFull};
 
 use crate::common::{init_instrumentation, war_measurement, wtr_measurement};
 
@@ -76,6 +76,9 @@ fn create_disk_v2_variant(_max_events: usize, max_size: u64) -> BufferType {
     BufferType::DiskV2 {
         max_size: NonZeroU64::new(max_size).unwrap(),
         when_full: WhenFull::DropNewest,
+        max_age_secs: None,
+        compression: DiskBufferCompression::None,
+        encryption: None,
     }
 }
 
@@ -83,6 +86,7 @@ fn create_in_memory_variant(max_events: usize, _max_size: u64) -> BufferType {
     BufferType::Memory {
         max_events: NonZeroUsize::new(max_events).unwrap(),
         when_full: WhenFull::DropNewest,
+        max_age_secs: None,
     }
 }
 
@@ -21,7 +21,7 @@ use vector_buffers::{
         builder::TopologyBuilder,
         channel::{BufferReceiver, BufferSender},
     },
-    BufferType, Bufferable, EventCount, WhenFull,
+    BufferType, Bufferable, DiskBufferCompression, EventCount, WhenFull,
 };
 use vector_common::byte_size_of::ByteSizeOf;
 use vector_common::finalization::{
@@ -263,6 +263,7 @@ where
             BufferType::Memory {
                 max_events: max_size_events,
                 when_full,
+                max_age_secs: None,
             }
         }
         "disk-v2" => {
@@ -273,6 +274,9 @@ where
             BufferType::DiskV2 {
                 max_size: max_size_bytes,
                 when_full,
+                max_age_secs: None,
+                compression: DiskBufferCompression::None,
+                encryption: None,
             }
         }
         s => panic!(
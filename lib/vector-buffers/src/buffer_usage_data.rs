@@ -11,8 +11,11 @@ use tracing::{Instrument, Span};
 use vector_common::internal_event::emit;
 
 use crate::{
-    internal_events::{BufferCreated, BufferEventsDropped, BufferEventsReceived, BufferEventsSent},
-    spawn_named,
+    internal_events::{
+        BufferCreated, BufferEventsDropped, BufferEventsExpired, BufferEventsReceived,
+        BufferEventsSent,
+    },
+    spawn_named, WhenFull,
 };
 
 /// Snapshot of category metrics.
@@ -91,7 +94,7 @@ impl BufferUsageHandle {
     /// No usage data is written or stored.
     pub(crate) fn noop() -> Self {
         BufferUsageHandle {
-            state: Arc::new(BufferUsageData::new(0)),
+            state: Arc::new(BufferUsageData::new(0, WhenFull::default())),
         }
     }
 
@@ -140,22 +143,31 @@ impl BufferUsageHandle {
             self.state.dropped.increment(count, byte_size);
         }
     }
+
+    /// Increments the number of events (and their total size) discarded by this buffer component
+    /// for having been in the buffer for longer than its configured maximum age.
+    pub fn increment_expired_event_count_and_byte_size(&self, count: u64, byte_size: u64) {
+        self.state.expired.increment(count, byte_size);
+    }
 }
 
 #[derive(Debug, Default)]
 struct BufferUsageData {
     idx: usize,
+    when_full: WhenFull,
     received: CategoryMetrics,
     sent: CategoryMetrics,
     dropped: CategoryMetrics,
     dropped_intentional: CategoryMetrics,
+    expired: CategoryMetrics,
     max_size: CategoryMetrics,
 }
 
 impl BufferUsageData {
-    fn new(idx: usize) -> Self {
+    fn new(idx: usize, when_full: WhenFull) -> Self {
         Self {
             idx,
+            when_full,
             ..Default::default()
         }
     }
@@ -165,6 +177,7 @@ impl BufferUsageData {
         let sent = self.sent.get();
         let dropped = self.dropped.get();
         let dropped_intentional = self.dropped_intentional.get();
+        let expired = self.expired.get();
         let max_size = self.max_size.get();
 
         BufferUsageSnapshot {
@@ -176,6 +189,8 @@ impl BufferUsageData {
             dropped_event_byte_size: dropped.event_byte_size,
             dropped_event_count_intentional: dropped_intentional.event_count,
             dropped_event_byte_size_intentional: dropped_intentional.event_byte_size,
+            expired_event_count: expired.event_count,
+            expired_event_byte_size: expired.event_byte_size,
             max_size_bytes: max_size.event_byte_size,
             max_size_events: max_size
                 .event_count
@@ -196,6 +211,8 @@ pub struct BufferUsageSnapshot {
     pub dropped_event_byte_size: u64,
     pub dropped_event_count_intentional: u64,
     pub dropped_event_byte_size_intentional: u64,
+    pub expired_event_count: u64,
+    pub expired_event_byte_size: u64,
     pub max_size_bytes: u64,
     pub max_size_events: usize,
 }
@@ -225,8 +242,10 @@ impl BufferUsage {
     ///
     /// A [`BufferUsageHandle`] is returned that the caller can use to actually update the usage metrics with.  This
     /// handle will only update the usage metrics for the particular stage it was added for.
-    pub fn add_stage(&mut self, idx: usize) -> BufferUsageHandle {
-        let data = Arc::new(BufferUsageData::new(idx));
+    ///
+    /// The "when full" behavior of the stage is used to report why events were intentionally dropped.
+    pub fn add_stage(&mut self, idx: usize, when_full: WhenFull) -> BufferUsageHandle {
+        let data = Arc::new(BufferUsageData::new(idx, when_full));
         let handle = BufferUsageHandle {
             state: Arc::clone(&data),
         };
@@ -295,11 +314,23 @@ impl BufferUsage {
                         emit(BufferEventsDropped {
                             idx: stage.idx,
                             intentional: true,
-                            reason: "drop_newest",
+                            reason: match stage.when_full {
+                                WhenFull::DropOldest => "drop_oldest",
+                                _ => "drop_newest",
+                            },
                             count: dropped_intentional.event_count,
                             byte_size: dropped_intentional.event_byte_size,
                         });
                     }
+
+                    let expired = stage.expired.consume();
+                    if expired.has_updates() {
+                        emit(BufferEventsExpired {
+                            idx: stage.idx,
+                            count: expired.event_count,
+                            byte_size: expired.event_byte_size,
+                        });
+                    }
                 }
             }
         };
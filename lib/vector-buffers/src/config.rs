@@ -3,12 +3,15 @@ use std::{
     num::{NonZeroU64, NonZeroUsize},
     path::{Path, PathBuf},
     slice,
+    time::Duration,
 };
 
 use serde::{de, Deserialize, Deserializer, Serialize};
 use snafu::{ResultExt, Snafu};
 use tracing::Span;
-use vector_common::{config::ComponentKey, finalization::Finalizable};
+use vector_common::{
+    config::ComponentKey, finalization::Finalizable, sensitive_string::SensitiveString,
+};
 use vector_config::configurable_component;
 
 use crate::{
@@ -16,7 +19,7 @@ use crate::{
         builder::{TopologyBuilder, TopologyError},
         channel::{BufferReceiver, BufferSender},
     },
-    variants::{DiskV2Buffer, MemoryBuffer},
+    variants::{DiskV2Buffer, EncryptionKey, MemoryBuffer, PayloadCodec, ENCRYPTION_KEY_LEN},
     Bufferable, WhenFull,
 };
 
@@ -28,6 +31,11 @@ pub enum BufferBuildError {
     FailedToBuildTopology { source: TopologyError },
     #[snafu(display("`max_events` must be greater than zero"))]
     InvalidMaxEvents,
+    #[snafu(display(
+        "`encryption.key` must be {} bytes, encoded as base64",
+        ENCRYPTION_KEY_LEN
+    ))]
+    InvalidEncryptionKey,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -38,7 +46,26 @@ enum BufferTypeKind {
     DiskV2,
 }
 
-const ALL_FIELDS: [&str; 4] = ["type", "max_events", "max_size", "when_full"];
+const ALL_FIELDS: [&str; 7] = [
+    "type",
+    "max_events",
+    "max_size",
+    "when_full",
+    "max_age_secs",
+    "compression",
+    "encryption",
+];
+
+const MEMORY_FIELDS: [&str; 4] = ["type", "max_events", "when_full", "max_age_secs"];
+
+const DISK_FIELDS: [&str; 6] = [
+    "type",
+    "max_size",
+    "when_full",
+    "max_age_secs",
+    "compression",
+    "encryption",
+];
 
 struct BufferTypeVisitor;
 
@@ -51,6 +78,9 @@ impl BufferTypeVisitor {
         let mut max_events: Option<NonZeroUsize> = None;
         let mut max_size: Option<NonZeroU64> = None;
         let mut when_full: Option<WhenFull> = None;
+        let mut max_age_secs: Option<NonZeroU64> = None;
+        let mut compression: Option<DiskBufferCompression> = None;
+        let mut encryption: Option<DiskBufferEncryption> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_str() {
                 "type" => {
@@ -77,6 +107,24 @@ impl BufferTypeVisitor {
                     }
                     when_full = Some(map.next_value()?);
                 }
+                "max_age_secs" => {
+                    if max_age_secs.is_some() {
+                        return Err(de::Error::duplicate_field("max_age_secs"));
+                    }
+                    max_age_secs = Some(map.next_value()?);
+                }
+                "compression" => {
+                    if compression.is_some() {
+                        return Err(de::Error::duplicate_field("compression"));
+                    }
+                    compression = Some(map.next_value()?);
+                }
+                "encryption" => {
+                    if encryption.is_some() {
+                        return Err(de::Error::duplicate_field("encryption"));
+                    }
+                    encryption = Some(map.next_value()?);
+                }
                 other => {
                     return Err(de::Error::unknown_field(other, &ALL_FIELDS));
                 }
@@ -86,27 +134,32 @@ impl BufferTypeVisitor {
         let when_full = when_full.unwrap_or_default();
         match kind {
             BufferTypeKind::Memory => {
-                if max_size.is_some() {
-                    return Err(de::Error::unknown_field(
-                        "max_size",
-                        &["type", "max_events", "when_full"],
-                    ));
+                let disk_field = [
+                    ("max_size", max_size.is_some()),
+                    ("compression", compression.is_some()),
+                    ("encryption", encryption.is_some()),
+                ]
+                .into_iter()
+                .find_map(|(field, is_set)| is_set.then_some(field));
+                if let Some(field) = disk_field {
+                    return Err(de::Error::unknown_field(field, &MEMORY_FIELDS));
                 }
                 Ok(BufferType::Memory {
                     max_events: max_events.unwrap_or_else(memory_buffer_default_max_events),
                     when_full,
+                    max_age_secs,
                 })
             }
             BufferTypeKind::DiskV2 => {
                 if max_events.is_some() {
-                    return Err(de::Error::unknown_field(
-                        "max_events",
-                        &["type", "max_size", "when_full"],
-                    ));
+                    return Err(de::Error::unknown_field("max_events", &DISK_FIELDS));
                 }
                 Ok(BufferType::DiskV2 {
                     max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
                     when_full,
+                    max_age_secs,
+                    compression: compression.unwrap_or_default(),
+                    encryption,
                 })
             }
         }
@@ -141,6 +194,59 @@ pub const fn memory_buffer_default_max_events() -> NonZeroUsize {
     unsafe { NonZeroUsize::new_unchecked(500) }
 }
 
+fn max_age(max_age_secs: Option<NonZeroU64>) -> Option<Duration> {
+    max_age_secs.map(|secs| Duration::from_secs(secs.get()))
+}
+
+/// Compression applied to the events written to a disk buffer.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiskBufferCompression {
+    /// Events are stored uncompressed.
+    #[default]
+    None,
+
+    /// Events are compressed with [Zstandard][zstd].
+    ///
+    /// This trades some CPU usage for less disk usage and I/O, which is typically worthwhile
+    /// for repetitive data, such as JSON logs. Events that don't get any smaller when compressed
+    /// are stored uncompressed.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+}
+
+/// Encryption of the events written to a disk buffer.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DiskBufferEncryption {
+    /// The key to encrypt events with, using AES-256-GCM.
+    ///
+    /// Must be 32 bytes, encoded as base64, such as the output of `openssl rand -base64 32`. To
+    /// avoid storing the key in the configuration itself, load it with the [secrets][secrets]
+    /// functionality.
+    ///
+    /// Events already in the buffer can only be read with the key they were written with, so
+    /// changing the key discards them.
+    ///
+    /// [secrets]: https://vector.dev/docs/reference/configuration/global-options/#secret
+    #[configurable(metadata(docs::examples = "SECRET[backend.buffer_key]"))]
+    pub key: SensitiveString,
+}
+
+impl DiskBufferEncryption {
+    /// Gets the encryption key.
+    ///
+    /// # Errors
+    ///
+    /// If the key is not valid, an error variant will be returned describing the error.
+    pub fn key(&self) -> Result<EncryptionKey, BufferBuildError> {
+        EncryptionKey::from_base64(self.key.inner()).ok_or(BufferBuildError::InvalidEncryptionKey)
+    }
+}
+
 /// Disk usage configuration for disk-backed buffers.
 #[derive(Debug)]
 pub struct DiskUsage {
@@ -177,7 +283,7 @@ impl DiskUsage {
 
 /// A specific type of buffer stage.
 #[configurable_component(no_deser)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case", tag = "type")]
 #[configurable(metadata(docs::enum_tag_description = "The type of buffer to use."))]
 pub enum BufferType {
@@ -195,6 +301,16 @@ pub enum BufferType {
         #[configurable(derived)]
         #[serde(default)]
         when_full: WhenFull,
+
+        /// The maximum amount of time, in seconds, that events can spend in the buffer.
+        ///
+        /// Events that have been in the buffer for longer than this are discarded when they are
+        /// read from the buffer, instead of being sent on to the sink.
+        ///
+        /// By default, events do not expire.
+        #[configurable(metadata(docs::type_unit = "seconds"))]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_age_secs: Option<NonZeroU64>,
     },
 
     /// A buffer stage backed by disk.
@@ -218,6 +334,28 @@ pub enum BufferType {
         #[configurable(derived)]
         #[serde(default)]
         when_full: WhenFull,
+
+        /// The maximum amount of time, in seconds, that events can spend in the buffer.
+        ///
+        /// Events that have been in the buffer for longer than this are discarded when they are
+        /// read from the buffer, instead of being sent on to the sink.
+        ///
+        /// The age of an event is based on when the data file it was written to was last written
+        /// to, so events may be kept for up to the time it takes to fill a data file longer than
+        /// this.
+        ///
+        /// By default, events do not expire.
+        #[configurable(metadata(docs::type_unit = "seconds"))]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_age_secs: Option<NonZeroU64>,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        compression: DiskBufferCompression,
+
+        #[configurable(derived)]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        encryption: Option<DiskBufferEncryption>,
     },
 }
 
@@ -276,19 +414,37 @@ impl BufferType {
     where
         T: Bufferable + Clone + Finalizable,
     {
-        match *self {
+        match self {
             BufferType::Memory {
                 when_full,
                 max_events,
+                max_age_secs,
             } => {
-                builder.stage(MemoryBuffer::new(max_events), when_full);
+                builder.stage(
+                    MemoryBuffer::new(*max_events).with_max_age(max_age(*max_age_secs)),
+                    *when_full,
+                );
             }
             BufferType::DiskV2 {
                 when_full,
                 max_size,
+                max_age_secs,
+                compression,
+                encryption,
             } => {
                 let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
-                builder.stage(DiskV2Buffer::new(id, data_dir, max_size), when_full);
+                let encryption_key = encryption
+                    .as_ref()
+                    .map(DiskBufferEncryption::key)
+                    .transpose()?;
+                let payload_codec =
+                    PayloadCodec::new(*compression == DiskBufferCompression::Zstd, encryption_key);
+                builder.stage(
+                    DiskV2Buffer::new(id, data_dir, *max_size)
+                        .with_max_age(max_age(*max_age_secs))
+                        .with_payload_codec(payload_codec),
+                    *when_full,
+                );
             }
         };
 
@@ -337,6 +493,7 @@ impl Default for BufferConfig {
         Self::Single(BufferType::Memory {
             max_events: memory_buffer_default_max_events(),
             when_full: WhenFull::default(),
+            max_age_secs: None,
         })
     }
 }
@@ -357,8 +514,8 @@ impl BufferConfig {
     ///
     /// # Errors
     ///
-    /// If the buffer is configured with anything other than a single stage, an error variant will
-    /// be thrown.
+    /// If the stages of the buffer can't be chained together, such as when a stage is followed by
+    /// another stage but isn't in overflow mode, an error variant will be thrown.
     ///
     /// If a disk buffer stage is configured and the data directory provided is `None`, an error
     /// variant will be thrown.
@@ -389,6 +546,7 @@ impl BufferConfig {
 mod test {
     use std::num::{NonZeroU64, NonZeroUsize};
 
+    use super::{DiskBufferCompression, DiskBufferEncryption};
     use crate::{BufferConfig, BufferType, WhenFull};
 
     fn check_single_stage(source: &str, expected: BufferType) {
@@ -441,6 +599,7 @@ max_events: 42
             BufferType::Memory {
                 max_events: NonZeroUsize::new(100).unwrap(),
                 when_full: WhenFull::Block,
+                max_age_secs: None,
             },
         );
     }
@@ -457,10 +616,39 @@ max_events: 42
                 BufferType::Memory {
                     max_events: NonZeroUsize::new(42).unwrap(),
                     when_full: WhenFull::Block,
+                    max_age_secs: None,
                 },
                 BufferType::Memory {
                     max_events: NonZeroUsize::new(100).unwrap(),
                     when_full: WhenFull::DropNewest,
+                    max_age_secs: None,
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_memory_overflow_to_disk() {
+        check_multiple_stages(
+            r#"
+          - type: memory
+            max_events: 1000
+            when_full: overflow
+          - type: disk
+            max_size: 268435488
+          "#,
+            &[
+                BufferType::Memory {
+                    max_events: NonZeroUsize::new(1000).unwrap(),
+                    when_full: WhenFull::Overflow,
+                    max_age_secs: None,
+                },
+                BufferType::DiskV2 {
+                    max_size: NonZeroU64::new(268435488).unwrap(),
+                    when_full: WhenFull::Block,
+                    max_age_secs: None,
+                    compression: DiskBufferCompression::None,
+                    encryption: None,
                 },
             ],
         );
@@ -475,6 +663,7 @@ max_events: 42
             BufferType::Memory {
                 max_events: NonZeroUsize::new(500).unwrap(),
                 when_full: WhenFull::Block,
+                max_age_secs: None,
             },
         );
 
@@ -486,6 +675,7 @@ max_events: 42
             BufferType::Memory {
                 max_events: NonZeroUsize::new(100).unwrap(),
                 when_full: WhenFull::Block,
+                max_age_secs: None,
             },
         );
 
@@ -497,6 +687,7 @@ max_events: 42
             BufferType::Memory {
                 max_events: NonZeroUsize::new(500).unwrap(),
                 when_full: WhenFull::DropNewest,
+                max_age_secs: None,
             },
         );
 
@@ -508,6 +699,7 @@ max_events: 42
             BufferType::Memory {
                 max_events: NonZeroUsize::new(500).unwrap(),
                 when_full: WhenFull::Overflow,
+                max_age_secs: None,
             },
         );
 
@@ -519,7 +711,89 @@ max_events: 42
             BufferType::DiskV2 {
                 max_size: NonZeroU64::new(1024).unwrap(),
                 when_full: WhenFull::Block,
+                max_age_secs: None,
+                compression: DiskBufferCompression::None,
+                encryption: None,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_drop_oldest_with_max_age() {
+        check_multiple_stages(
+            r#"
+          - type: memory
+            max_events: 100
+            when_full: drop_oldest
+            max_age_secs: 60
+          - type: disk
+            max_size: 268435488
+            when_full: drop_oldest
+            max_age_secs: 3600
+          "#,
+            &[
+                BufferType::Memory {
+                    max_events: NonZeroUsize::new(100).unwrap(),
+                    when_full: WhenFull::DropOldest,
+                    max_age_secs: NonZeroU64::new(60),
+                },
+                BufferType::DiskV2 {
+                    max_size: NonZeroU64::new(268435488).unwrap(),
+                    when_full: WhenFull::DropOldest,
+                    max_age_secs: NonZeroU64::new(3600),
+                    compression: DiskBufferCompression::None,
+                    encryption: None,
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_disk_compression_and_encryption() {
+        check_single_stage(
+            r#"
+          type: disk
+          max_size: 268435488
+          compression: zstd
+          encryption:
+            key: BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc=
+          "#,
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(268435488).unwrap(),
+                when_full: WhenFull::Block,
+                max_age_secs: None,
+                compression: DiskBufferCompression::Zstd,
+                encryption: Some(DiskBufferEncryption {
+                    key: "BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc="
+                        .to_string()
+                        .into(),
+                }),
             },
         );
     }
+
+    #[test]
+    fn parse_memory_compression_is_invalid() {
+        let source = r#"
+          type: memory
+          compression: zstd
+          "#;
+        let error = serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+        assert_eq!(error.to_string(), BUFFER_CONFIG_NO_MATCH_ERR);
+    }
+
+    #[test]
+    fn validates_encryption_key() {
+        let encryption = DiskBufferEncryption {
+            key: "BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc="
+                .to_string()
+                .into(),
+        };
+        assert!(encryption.key().is_ok());
+
+        let encryption = DiskBufferEncryption {
+            key: "c2hvcnQ=".to_string().into(),
+        };
+        assert!(encryption.key().is_err());
+    }
 }
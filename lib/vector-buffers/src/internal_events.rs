@@ -89,6 +89,27 @@ impl InternalEvent for BufferEventsDropped {
     }
 }
 
+pub struct BufferEventsExpired {
+    pub idx: usize,
+    pub count: u64,
+    pub byte_size: u64,
+}
+
+impl InternalEvent for BufferEventsExpired {
+    #[allow(clippy::cast_precision_loss)]
+    fn emit(self) {
+        warn!(
+            message = "Events expired before being read from the buffer.",
+            count = %self.count,
+            stage = %self.idx,
+            internal_log_rate_limit = true,
+        );
+        counter!("buffer_expired_events_total", self.count, "stage" => self.idx.to_string());
+        decrement_gauge!("buffer_events", self.count as f64, "stage" => self.idx.to_string());
+        decrement_gauge!("buffer_byte_size", self.byte_size as f64, "stage" => self.idx.to_string());
+    }
+}
+
 pub struct BufferReadError {
     pub error_code: &'static str,
     pub error: String,
@@ -16,7 +16,7 @@ extern crate tracing;
 mod buffer_usage_data;
 
 pub mod config;
-pub use config::{BufferConfig, BufferType};
+pub use config::{BufferConfig, BufferType, DiskBufferCompression, DiskBufferEncryption};
 use encoding::Encodable;
 use vector_config::configurable_component;
 
@@ -31,6 +31,7 @@ pub mod test;
 pub mod topology;
 
 pub(crate) mod variants;
+pub use variants::{DiskBufferInspector, DiskBufferSummary, EncryptionKey, InspectError};
 
 use std::fmt::Debug;
 
@@ -58,6 +59,17 @@ pub enum WhenFull {
     /// slowdown in the acceptance/consumption of events.
     DropNewest,
 
+    /// Drops the oldest events in the buffer to make room for the event.
+    ///
+    /// Events that have been waiting in the buffer the longest are intentionally dropped, so that
+    /// the most recent events are always kept. This mode is typically used for real-time data, such
+    /// as metrics, where recent data is more valuable than data that has fallen behind.
+    ///
+    /// Disk buffers make room one data file at a time, starting with the oldest data file the
+    /// sink has not started reading yet. If there is no such data file, the event is dropped
+    /// instead, as with `drop_newest`.
+    DropOldest,
+
     /// Overflows to the next stage in the buffer topology.
     ///
     /// If the current buffer stage is full, attempt to send this event to the next buffer stage.
@@ -65,17 +77,23 @@ pub enum WhenFull {
     /// buffer topology must use one of the other handling behaviors. This means that next stage may
     /// potentially be able to buffer the event, but it may also block or drop the event.
     ///
+    /// Once an event has overflowed, subsequent events are sent to the next stage as well, until
+    /// all of the events that overflowed have been read back out of it. Events are read out of the
+    /// buffer in the order they were sent, so, for example, a memory stage overflowing to a disk
+    /// stage only spills to disk when the sink falls behind, and returns to memory once the sink
+    /// has caught up.
+    ///
     /// This mode can only be used when two or more buffer stages are configured.
-    #[configurable(metadata(docs::hidden))]
     Overflow,
 }
 
 #[cfg(test)]
 impl Arbitrary for WhenFull {
     fn arbitrary(g: &mut Gen) -> Self {
-        // TODO: We explicitly avoid generating "overflow" as a possible value because nothing yet
-        // supports handling it, and will be defaulted to to using "block" if they encounter
-        // "overflow".  Thus, there's no reason to emit it here... yet.
+        // We explicitly avoid generating "overflow" as a possible value because it's only valid
+        // for a stage that is followed by another stage, which callers of this impl don't build.
+        // "drop oldest" is also avoided, as the model used by those callers doesn't account for
+        // items being removed from the buffer before they're read.
         if bool::arbitrary(g) {
             WhenFull::Block
         } else {
@@ -1,4 +1,8 @@
-use std::{error::Error, num::NonZeroUsize};
+use std::{
+    error::Error,
+    num::NonZeroUsize,
+    sync::{atomic::AtomicUsize, Arc},
+};
 
 use async_trait::async_trait;
 use snafu::{ResultExt, Snafu};
@@ -40,7 +44,7 @@ pub enum TopologyError {
     #[snafu(display("buffer topology cannot be empty"))]
     EmptyTopology,
     #[snafu(display(
-        "stage {} configured with block/drop newest behavior in front of subsequent stage",
+        "stage {} configured with block/drop newest/drop oldest behavior in front of subsequent stage",
         stage_idx
     ))]
     NextStageNotUsed { stage_idx: usize },
@@ -76,15 +80,15 @@ impl<T: Bufferable> TopologyBuilder<T> {
     /// an overflow buffer is added to the topology after this, then the specified "when full"
     /// behavior will be ignored and will be set to "overflow" mode.
     ///
-    /// Callers can configure what to do when a buffer is full by setting `when_full`.  Three modes
-    /// are available -- block, drop newest, and overflow -- which are documented in more detail by
-    /// [`BufferSender`].
+    /// Callers can configure what to do when a buffer is full by setting `when_full`.  Four modes
+    /// are available -- block, drop newest, drop oldest, and overflow -- which are documented in
+    /// more detail by [`BufferSender`].
     ///
     /// Two notes about what modes are not valid in certain scenarios:
     /// - the innermost stage (the last stage given to the builder) cannot be set to "overflow" mode,
     ///   as there is no other stage to overflow to
-    /// - a stage cannot use the "block" or "drop newest" mode when there is a subsequent stage, and
-    ///   must user the "overflow" mode
+    /// - a stage cannot use the "block", "drop newest", or "drop oldest" mode when there is a
+    ///   subsequent stage, and must user the "overflow" mode
     ///
     /// Any occurrence of either of these scenarios will result in an error during build.
     pub fn stage<S>(&mut self, stage: S, when_full: WhenFull) -> &mut Self
@@ -122,9 +126,9 @@ impl<T: Bufferable> TopologyBuilder<T> {
                         return Err(TopologyError::OverflowWhenLast);
                     }
                 }
-                // If there's already an inner stage, then blocking or dropping the newest events
-                // doesn't no sense.  Overflowing is the only valid transition to another stage.
-                WhenFull::Block | WhenFull::DropNewest => {
+                // If there's already an inner stage, then blocking or dropping events doesn't no
+                // sense.  Overflowing is the only valid transition to another stage.
+                WhenFull::Block | WhenFull::DropNewest | WhenFull::DropOldest => {
                     if current_stage.is_some() {
                         return Err(TopologyError::NextStageNotUsed { stage_idx });
                     }
@@ -135,7 +139,7 @@ impl<T: Bufferable> TopologyBuilder<T> {
             // sender/receiver.  This is slightly awkward since we just end up actually giving
             // the handle to the `BufferSender`/`BufferReceiver` wrappers, but that's the price we
             // have to pay for letting each stage function in an opaque way when wrapped.
-            let usage_handle = buffer_usage.add_stage(stage_idx);
+            let usage_handle = buffer_usage.add_stage(stage_idx, stage.when_full);
             let provides_instrumentation = stage.untransformed.provides_instrumentation();
             let (sender, receiver) = stage
                 .untransformed
@@ -148,10 +152,20 @@ impl<T: Bufferable> TopologyBuilder<T> {
                     BufferSender::new(sender, stage.when_full),
                     BufferReceiver::new(receiver),
                 ),
-                Some((current_sender, current_receiver)) => (
-                    BufferSender::with_overflow(sender, current_sender),
-                    BufferReceiver::with_overflow(receiver, current_receiver),
-                ),
+                Some((current_sender, current_receiver)) => {
+                    // Events left in the overflow stage by a previous run are older than anything
+                    // sent from now on, so they count as having overflowed.
+                    let overflowed =
+                        Arc::new(AtomicUsize::new(current_receiver.initial_event_count()));
+                    (
+                        BufferSender::with_overflow(
+                            sender,
+                            current_sender,
+                            Arc::clone(&overflowed),
+                        ),
+                        BufferReceiver::with_overflow(receiver, current_receiver, overflowed),
+                    )
+                }
             };
 
             if !provides_instrumentation {
@@ -176,8 +190,8 @@ impl<T: Bufferable> TopologyBuilder<T> {
 impl<T: Bufferable> TopologyBuilder<T> {
     /// Creates a memory-only buffer topology.
     ///
-    /// The overflow mode (i.e. `WhenFull`) can be configured to either block or drop the newest
-    /// values, but cannot be configured to use overflow mode.  If overflow mode is selected, it
+    /// The overflow mode (i.e. `WhenFull`) can be configured to either block or drop the newest or
+    /// oldest values, but cannot be configured to use overflow mode.  If overflow mode is selected, it
     /// will be changed to blocking mode.
     ///
     /// This is a convenience method for `vector` as it is used for inter-transform channels, and we
@@ -211,8 +225,8 @@ impl<T: Bufferable> TopologyBuilder<T> {
     /// like channel capacity left, which cannot be done on in-memory v1 buffers as they use the
     /// more abstract `Sink`-based adapters.
     ///
-    /// The overflow mode (i.e. `WhenFull`) can be configured to either block or drop the newest
-    /// values, but cannot be configured to use overflow mode.  If overflow mode is selected, it
+    /// The overflow mode (i.e. `WhenFull`) can be configured to either block or drop the newest or
+    /// oldest values, but cannot be configured to use overflow mode.  If overflow mode is selected, it
     /// will be changed to blocking mode.
     ///
     /// This is a convenience method for `vector` as it is used for inter-transform channels, and we
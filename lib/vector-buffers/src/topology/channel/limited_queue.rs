@@ -5,6 +5,7 @@ use std::{
         atomic::{AtomicUsize, Ordering},
         Arc,
     },
+    time::{Duration, Instant},
 };
 
 use async_stream::stream;
@@ -12,7 +13,7 @@ use crossbeam_queue::ArrayQueue;
 use futures::Stream;
 use tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore, TryAcquireError};
 
-use crate::Bufferable;
+use crate::{buffer_usage_data::BufferUsageHandle, Bufferable};
 
 /// Error returned by `LimitedSender::send` when the receiver has disconnected.
 #[derive(Debug, PartialEq, Eq)]
@@ -54,12 +55,27 @@ impl<T> fmt::Display for TrySendError<T> {
 
 impl<T: fmt::Debug> std::error::Error for TrySendError<T> {}
 
+/// Called with each item that the receiver discards for having expired.
+type ExpiredHandler<T> = Arc<dyn Fn(&T) + Send + Sync>;
+
 #[derive(Debug)]
 struct Inner<T> {
-    data: Arc<ArrayQueue<(OwnedSemaphorePermit, T)>>,
+    // Items are stored alongside the time they were sent at, but only when they can expire.
+    data: Arc<ArrayQueue<(OwnedSemaphorePermit, Option<Instant>, T)>>,
     limit: usize,
     limiter: Arc<Semaphore>,
     read_waker: Arc<Notify>,
+    max_age: Option<Duration>,
+}
+
+impl<T> Inner<T> {
+    fn push(&self, permits: OwnedSemaphorePermit, item: T) {
+        let sent_at = self.max_age.map(|_| Instant::now());
+        self.data
+            .push((permits, sent_at, item))
+            .expect("acquired permits but channel reported being full");
+        self.read_waker.notify_one();
+    }
 }
 
 impl<T> Clone for Inner<T> {
@@ -69,6 +85,7 @@ impl<T> Clone for Inner<T> {
             limit: self.limit,
             limiter: self.limiter.clone(),
             read_waker: self.read_waker.clone(),
+            max_age: self.max_age,
         }
     }
 }
@@ -93,6 +110,11 @@ impl<T: Bufferable> LimitedSender<T> {
         self.inner.limiter.available_permits()
     }
 
+    /// Returns `true` if there are no items in the channel waiting to be received.
+    pub fn is_empty(&self) -> bool {
+        self.inner.data.is_empty()
+    }
+
     /// Sends an item into the channel.
     ///
     /// # Errors
@@ -111,11 +133,7 @@ impl<T: Bufferable> LimitedSender<T> {
             return Err(SendError(item))
         };
 
-        self.inner
-            .data
-            .push((permits, item))
-            .expect("acquired permits but channel reported being full");
-        self.inner.read_waker.notify_one();
+        self.inner.push(permits, item);
 
         trace!("Sent item.");
 
@@ -148,16 +166,42 @@ impl<T: Bufferable> LimitedSender<T> {
             }
         };
 
-        self.inner
-            .data
-            .push((permits, item))
-            .expect("acquired permits but channel reported being full");
-        self.inner.read_waker.notify_one();
+        self.inner.push(permits, item);
 
         trace!("Attempt to send item succeeded.");
 
         Ok(())
     }
+
+    /// Sends an item into the channel, dropping the oldest items in the channel to make room for it
+    /// if necessary.
+    ///
+    /// The items that were dropped are returned.
+    ///
+    /// # Errors
+    ///
+    /// If the receiver has disconnected (does not exist anymore), then `Err(SendError)` be returned
+    /// with the given `item`.
+    pub async fn send_dropping_oldest(&mut self, mut item: T) -> Result<Vec<T>, SendError<T>> {
+        let mut dropped = Vec::new();
+        loop {
+            match self.try_send(item) {
+                Ok(()) => return Ok(dropped),
+                Err(TrySendError::Disconnected(item)) => return Err(SendError(item)),
+                Err(TrySendError::InsufficientCapacity(old_item)) => {
+                    item = old_item;
+
+                    // Removing the oldest item releases the permits it holds. If there's nothing
+                    // left to remove, the receiver just took the last item, and so the permits are
+                    // about to be released anyways.
+                    match self.inner.data.pop() {
+                        Some((_permits, _, oldest)) => dropped.push(oldest),
+                        None => return self.send(item).await.map(|()| dropped),
+                    }
+                }
+            }
+        }
+    }
 }
 
 impl<T> Clone for LimitedSender<T> {
@@ -181,9 +225,17 @@ impl<T> Drop for LimitedSender<T> {
     }
 }
 
-#[derive(Debug)]
 pub struct LimitedReceiver<T> {
     inner: Inner<T>,
+    on_expired: Option<ExpiredHandler<T>>,
+}
+
+impl<T: fmt::Debug> fmt::Debug for LimitedReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LimitedReceiver")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
 }
 
 impl<T: Send + 'static> LimitedReceiver<T> {
@@ -194,7 +246,17 @@ impl<T: Send + 'static> LimitedReceiver<T> {
 
     pub async fn next(&mut self) -> Option<T> {
         loop {
-            if let Some((_permit, item)) = self.inner.data.pop() {
+            if let Some((_permit, sent_at, item)) = self.inner.data.pop() {
+                if let Some((max_age, sent_at)) = self.inner.max_age.zip(sent_at) {
+                    if sent_at.elapsed() > max_age {
+                        trace!("Discarding expired item.");
+                        if let Some(on_expired) = self.on_expired.as_ref() {
+                            on_expired(&item);
+                        }
+                        continue;
+                    }
+                }
+
                 return Some(item);
             }
 
@@ -231,30 +293,57 @@ impl<T> Drop for LimitedReceiver<T> {
 }
 
 pub fn limited<T>(limit: usize) -> (LimitedSender<T>, LimitedReceiver<T>) {
+    build_limited(limit, None, None)
+}
+
+/// Creates a channel whose receiver discards items that were sent more than `max_age` ago,
+/// tracking them as expired with the given usage handle.
+pub(crate) fn limited_with_max_age<T: Bufferable>(
+    limit: usize,
+    max_age: Duration,
+    usage_handle: BufferUsageHandle,
+) -> (LimitedSender<T>, LimitedReceiver<T>) {
+    let on_expired: ExpiredHandler<T> = Arc::new(move |item: &T| {
+        usage_handle.increment_expired_event_count_and_byte_size(
+            item.event_count() as u64,
+            item.size_of() as u64,
+        );
+    });
+    build_limited(limit, Some(max_age), Some(on_expired))
+}
+
+fn build_limited<T>(
+    limit: usize,
+    max_age: Option<Duration>,
+    on_expired: Option<ExpiredHandler<T>>,
+) -> (LimitedSender<T>, LimitedReceiver<T>) {
     let inner = Inner {
         data: Arc::new(ArrayQueue::new(limit)),
         limit,
         limiter: Arc::new(Semaphore::new(limit)),
         read_waker: Arc::new(Notify::new()),
+        max_age,
     };
 
     let sender = LimitedSender {
         inner: inner.clone(),
         sender_count: Arc::new(AtomicUsize::new(1)),
     };
-    let receiver = LimitedReceiver { inner };
+    let receiver = LimitedReceiver { inner, on_expired };
 
     (sender, receiver)
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use tokio_test::{assert_pending, assert_ready, task::spawn};
 
-    use super::limited;
+    use super::{limited, limited_with_max_age};
     use crate::{
-        test::MultiEventRecord, topology::channel::limited_queue::SendError,
-        topology::test_util::Sample,
+        buffer_usage_data::BufferUsageHandle, test::MultiEventRecord,
+        topology::channel::limited_queue::SendError, topology::test_util::Sample,
     };
 
     #[tokio::test]
@@ -590,4 +679,42 @@ mod tests {
 
         assert_eq!(2, tx.available_capacity());
     }
+
+    #[tokio::test]
+    async fn send_dropping_oldest_makes_room() {
+        let (mut tx, mut rx) = limited(2);
+
+        assert_eq!(Ok(vec![]), tx.send_dropping_oldest(Sample(1)).await);
+        assert_eq!(Ok(vec![]), tx.send_dropping_oldest(Sample(2)).await);
+        assert_eq!(0, tx.available_capacity());
+
+        // The channel is full, so the oldest item makes room for the new one.
+        assert_eq!(
+            Ok(vec![Sample(1)]),
+            tx.send_dropping_oldest(Sample(3)).await
+        );
+        assert_eq!(0, tx.available_capacity());
+
+        assert_eq!(Some(Sample(2)), rx.next().await);
+        assert_eq!(Some(Sample(3)), rx.next().await);
+        assert_eq!(2, tx.available_capacity());
+    }
+
+    #[tokio::test]
+    async fn receiver_discards_expired_items() {
+        let handle = BufferUsageHandle::noop();
+        let (mut tx, mut rx) = limited_with_max_age(2, Duration::from_millis(50), handle.clone());
+
+        tx.send(Sample(1)).await.expect("send should not fail");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        tx.send(Sample(2)).await.expect("send should not fail");
+
+        // The first item expired while waiting, so only the second one is received, but both of
+        // them give back their capacity.
+        assert_eq!(Some(Sample(2)), rx.next().await);
+        assert_eq!(2, tx.available_capacity());
+
+        let snapshot = handle.snapshot();
+        assert_eq!(1, snapshot.expired_event_count);
+    }
 }
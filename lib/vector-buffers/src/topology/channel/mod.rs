@@ -2,6 +2,7 @@ mod limited_queue;
 mod receiver;
 mod sender;
 
+pub(crate) use limited_queue::limited_with_max_age;
 pub use limited_queue::{limited, LimitedReceiver, LimitedSender, SendError};
 pub use receiver::*;
 pub use sender::*;
@@ -1,6 +1,10 @@
 use std::{
     mem,
     pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     task::{ready, Context, Poll},
 };
 
@@ -62,6 +66,19 @@ where
             },
         }
     }
+
+    /// Gets the number of events that are already in this channel.
+    ///
+    /// This is only meaningful right after the channel is created, as events written by a previous
+    /// run of a disk buffer are the only events that can be in the channel at that point.
+    fn initial_event_count(&self) -> usize {
+        match self {
+            ReceiverAdapter::InMemory(_) => 0,
+            ReceiverAdapter::DiskV2(reader) => {
+                usize::try_from(reader.get_total_records()).unwrap_or(usize::MAX)
+            }
+        }
+    }
 }
 
 /// A buffer receiver.
@@ -69,13 +86,15 @@ where
 /// The receiver handles retrieving events from the buffer, regardless of the overall buffer configuration.
 ///
 /// If a buffer was configured to operate in "overflow" mode, then the receiver will be responsible
-/// for querying the overflow buffer as well.  The ordering of events when operating in "overflow"
-/// is undefined, as the receiver will try to manage polling both its own buffer, as well as the
-/// overflow buffer, in order to fairly balance throughput.
+/// for querying the overflow buffer as well.  Events are received in the order they were sent: as
+/// the sender keeps sending to the overflow buffer until every event that overflowed has been
+/// received, events in the base buffer are always older than the events in the overflow buffer,
+/// and so the receiver always drains its own buffer first.
 #[derive(Debug)]
 pub struct BufferReceiver<T: Bufferable> {
     base: ReceiverAdapter<T>,
     overflow: Option<Box<BufferReceiver<T>>>,
+    overflowed: Arc<AtomicUsize>,
     instrumentation: Option<BufferUsageHandle>,
 }
 
@@ -85,26 +104,51 @@ impl<T: Bufferable> BufferReceiver<T> {
         Self {
             base,
             overflow: None,
+            overflowed: Arc::default(),
             instrumentation: None,
         }
     }
 
     /// Creates a new [`BufferReceiver`] wrapping the given channel receiver and overflow receiver.
-    pub fn with_overflow(base: ReceiverAdapter<T>, overflow: BufferReceiver<T>) -> Self {
+    ///
+    /// `overflowed` must be shared with the [`BufferSender`][super::BufferSender] of the same
+    /// stage, and start out as the [initial event count][Self::initial_event_count] of the
+    /// overflow receiver.
+    pub fn with_overflow(
+        base: ReceiverAdapter<T>,
+        overflow: BufferReceiver<T>,
+        overflowed: Arc<AtomicUsize>,
+    ) -> Self {
         Self {
             base,
             overflow: Some(Box::new(overflow)),
+            overflowed,
             instrumentation: None,
         }
     }
 
+    /// Gets the number of events that are already in this buffer when it's created, such as the
+    /// events left in a disk buffer by a previous run.
+    pub fn initial_event_count(&self) -> usize {
+        let overflow = self
+            .overflow
+            .as_ref()
+            .map_or(0, |overflow| overflow.initial_event_count());
+        self.base.initial_event_count().saturating_add(overflow)
+    }
+
     /// Converts this receiver into an overflowing receiver using the given `BufferSender<T>`.
     ///
     /// Note: this resets the internal state of this sender, and so this should not be called except
     /// when initially constructing `BufferSender<T>`.
     #[cfg(test)]
-    pub fn switch_to_overflow(&mut self, overflow: BufferReceiver<T>) {
+    pub fn switch_to_overflow(
+        &mut self,
+        overflow: BufferReceiver<T>,
+        overflowed: Arc<AtomicUsize>,
+    ) {
         self.overflow = Some(Box::new(overflow));
+        self.overflowed = overflowed;
     }
 
     /// Configures this receiver to instrument the items passing through it.
@@ -115,13 +159,9 @@ impl<T: Bufferable> BufferReceiver<T> {
     #[async_recursion]
     pub async fn next(&mut self) -> Option<T> {
         // We want to poll both our base and overflow receivers without waiting for one or the
-        // other to entirely drain before checking the other.  This ensures that we're fairly
-        // servicing both receivers, and avoiding stalls in one or the other.
-        //
-        // This is primarily important in situations where an overflow-triggering event has
-        // occurred, and is over, and items are flowing through the base receiver.  If we waited to
-        // entirely drain the overflow receiver, we might cause another small stall of the pipeline
-        // attached to the base receiver.
+        // other to entirely drain before checking the other, but the base receiver always takes
+        // precedence: while events are overflowing, the sender doesn't send anything to the base
+        // receiver, so whatever is in it was sent before anything in the overflow receiver.
         let overflow = self.overflow.as_mut().map(Pin::new);
 
         let (item, from_base) = match overflow {
@@ -131,13 +171,25 @@ impl<T: Bufferable> BufferReceiver<T> {
             },
             Some(mut overflow) => {
                 select! {
-                    Some(item) = overflow.next() => (item, false),
+                    biased;
                     Some(item) = self.base.next() => (item, true),
+                    Some(item) = overflow.next() => (item, false),
                     else => return None,
                 }
             }
         };
 
+        if !from_base {
+            // Once all of the events that overflowed have been received, the sender goes back to
+            // sending to the base receiver.
+            let event_count = item.event_count();
+            let _ =
+                self.overflowed
+                    .fetch_update(Ordering::AcqRel, Ordering::Acquire, |overflowed| {
+                        Some(overflowed.saturating_sub(event_count))
+                    });
+        }
+
         // If instrumentation is enabled, and we got the item from the base receiver, then and only
         // then do we track sending the event out.
         if let Some(handle) = self.instrumentation.as_ref() {
@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 
 use async_recursion::async_recursion;
 use tokio::sync::Mutex;
@@ -79,6 +82,42 @@ where
         }
     }
 
+    /// Sends an item, dropping the oldest items in the buffer to make room for it if necessary.
+    ///
+    /// The oldest items that were dropped are returned, along with the item being sent if no room
+    /// could be made for it.  Disk buffers drop entire data files at once, and account for the items
+    /// in them on their own, so they never return any of the oldest items.
+    pub(crate) async fn send_dropping_oldest(
+        &mut self,
+        item: T,
+    ) -> crate::Result<(Vec<T>, Option<T>)> {
+        match self {
+            Self::InMemory(tx) => tx
+                .send_dropping_oldest(item)
+                .await
+                .map(|dropped| (dropped, None))
+                .map_err(Into::into),
+            Self::DiskV2(writer) => {
+                let mut writer = writer.lock().await;
+
+                writer
+                    .write_record_dropping_oldest(item)
+                    .await
+                    .map(|item| (Vec::new(), item))
+                    .map_err(|e| {
+                        // TODO: Could some errors be handled and not be unrecoverable? Right now,
+                        // encoding should theoretically be recoverable -- encoded value was too big,
+                        // or error during encoding -- but the traits don't allow for recovering the
+                        // original event value because we have to consume it to do the encoding...
+                        // but that might not always be the case.
+                        error!("Disk buffer writer has encountered an unrecoverable error.");
+
+                        e.into()
+                    })
+            }
+        }
+    }
+
     pub(crate) async fn flush(&mut self) -> crate::Result<()> {
         match self {
             Self::InMemory(_) => Ok(()),
@@ -94,6 +133,14 @@ where
         }
     }
 
+    /// Returns `true` if there are no items in the buffer waiting to be received.
+    pub(crate) async fn is_empty(&self) -> bool {
+        match self {
+            Self::InMemory(tx) => tx.is_empty(),
+            Self::DiskV2(writer) => writer.lock().await.is_empty(),
+        }
+    }
+
     pub fn capacity(&self) -> Option<usize> {
         match self {
             Self::InMemory(tx) => Some(tx.available_capacity()),
@@ -108,16 +155,26 @@ where
 /// events when the internal channel is full.
 ///
 /// When creating a buffer sender/receiver pair, callers can specify the "when full" behavior of the
-/// sender.  This controls how events are handled when the internal channel is full.  Three modes
+/// sender.  This controls how events are handled when the internal channel is full.  Four modes
 /// are possible:
 /// - block
 /// - drop newest
+/// - drop oldest
 /// - overflow
 ///
 /// In "block" mode, callers are simply forced to wait until the channel has enough capacity to
 /// accept the event.  In "drop newest" mode, any event being sent when the channel is full will be
-/// dropped and proceed no further. In "overflow" mode, events will be sent to another buffer
-/// sender.  Callers can specify the overflow sender to use when constructing their buffers initially.
+/// dropped and proceed no further. In "drop oldest" mode, the oldest events in the channel are
+/// dropped to make room for the event being sent. In "overflow" mode, events will be sent to
+/// another buffer sender.  Callers can specify the overflow sender to use when constructing their
+/// buffers initially.
+///
+/// In "overflow" mode, the sender shares a count of the events that overflowed, and have yet to be
+/// received, with its receiver.  As long as that count isn't zero, events are sent directly to the
+/// overflow sender, even if the internal channel has capacity again, as they would otherwise be
+/// received ahead of the events that overflowed before them.  As events can also leave the overflow
+/// sender's buffer without being received, by being dropped or expiring, the count is reset once
+/// that buffer is empty.
 ///
 /// TODO: We should eventually rework `BufferSender`/`BufferReceiver` so that they contain a vector
 /// of the fields we already have here, but instead of cascading via calling into `overflow`, we'd
@@ -128,6 +185,7 @@ where
 pub struct BufferSender<T: Bufferable> {
     base: SenderAdapter<T>,
     overflow: Option<Box<BufferSender<T>>>,
+    overflowed: Arc<AtomicUsize>,
     when_full: WhenFull,
     instrumentation: Option<BufferUsageHandle>,
 }
@@ -138,16 +196,25 @@ impl<T: Bufferable> BufferSender<T> {
         Self {
             base,
             overflow: None,
+            overflowed: Arc::default(),
             when_full,
             instrumentation: None,
         }
     }
 
     /// Creates a new [`BufferSender`] wrapping the given channel sender and overflow sender.
-    pub fn with_overflow(base: SenderAdapter<T>, overflow: BufferSender<T>) -> Self {
+    ///
+    /// `overflowed` must be shared with the [`BufferReceiver`][super::BufferReceiver] of the same
+    /// stage, which decrements it as it receives the events that overflowed.
+    pub fn with_overflow(
+        base: SenderAdapter<T>,
+        overflow: BufferSender<T>,
+        overflowed: Arc<AtomicUsize>,
+    ) -> Self {
         Self {
             base,
             overflow: Some(Box::new(overflow)),
+            overflowed,
             when_full: WhenFull::Overflow,
             instrumentation: None,
         }
@@ -158,8 +225,9 @@ impl<T: Bufferable> BufferSender<T> {
     /// Note: this resets the internal state of this sender, and so this should not be called except
     /// when initially constructing `BufferSender<T>`.
     #[cfg(test)]
-    pub fn switch_to_overflow(&mut self, overflow: BufferSender<T>) {
+    pub fn switch_to_overflow(&mut self, overflow: BufferSender<T>, overflowed: Arc<AtomicUsize>) {
         self.overflow = Some(Box::new(overflow));
+        self.overflowed = overflowed;
         self.when_full = WhenFull::Overflow;
     }
 
@@ -180,8 +248,13 @@ impl<T: Bufferable> BufferSender<T> {
         self.overflow.as_ref().map(AsRef::as_ref)
     }
 
-    #[async_recursion]
     pub async fn send(&mut self, item: T) -> crate::Result<()> {
+        self.send_item(item).await.map(|_| ())
+    }
+
+    /// Sends an item, returning whether it was buffered rather than dropped.
+    #[async_recursion]
+    async fn send_item(&mut self, item: T) -> crate::Result<bool> {
         let item_sizing = self
             .instrumentation
             .as_ref()
@@ -189,6 +262,8 @@ impl<T: Bufferable> BufferSender<T> {
 
         let mut sent_to_base = true;
         let mut was_dropped = false;
+        let mut overflow_dropped = false;
+        let mut dropped_oldest = Vec::new();
         match self.when_full {
             WhenFull::Block => self.base.send(item).await?,
             WhenFull::DropNewest => {
@@ -196,14 +271,44 @@ impl<T: Bufferable> BufferSender<T> {
                     was_dropped = true;
                 }
             }
+            WhenFull::DropOldest => {
+                let (dropped, item) = self.base.send_dropping_oldest(item).await?;
+                dropped_oldest = dropped;
+                was_dropped = item.is_some();
+            }
             WhenFull::Overflow => {
-                if let Some(item) = self.base.try_send(item).await? {
+                // Events that overflowed must be received before anything sent after them, so we
+                // only go back to the base channel once they've all been received, or are otherwise
+                // gone from the overflow buffer.
+                if self.overflowed.load(Ordering::Acquire) > 0 && self.overflow_is_empty().await {
+                    self.overflowed.store(0, Ordering::Release);
+                }
+
+                let item = if self.overflowed.load(Ordering::Acquire) == 0 {
+                    self.base.try_send(item).await?
+                } else {
+                    Some(item)
+                };
+
+                if let Some(item) = item {
                     sent_to_base = false;
-                    self.overflow
+                    let event_count = item.event_count();
+                    self.overflowed.fetch_add(event_count, Ordering::AcqRel);
+                    let result = self
+                        .overflow
                         .as_mut()
                         .expect("overflow must exist")
-                        .send(item)
-                        .await?;
+                        .send_item(item)
+                        .await;
+                    if !matches!(result, Ok(true)) {
+                        let _ = self.overflowed.fetch_update(
+                            Ordering::AcqRel,
+                            Ordering::Acquire,
+                            |overflowed| Some(overflowed.saturating_sub(event_count)),
+                        );
+                    }
+                    // The overflow sender accounts for the item itself if it drops it.
+                    overflow_dropped = !result?;
                 }
             }
         };
@@ -224,10 +329,33 @@ impl<T: Bufferable> BufferSender<T> {
                         true,
                     );
                 }
+
+                for dropped in &dropped_oldest {
+                    instrumentation.increment_dropped_event_count_and_byte_size(
+                        dropped.event_count() as u64,
+                        dropped.size_of() as u64,
+                        true,
+                    );
+                }
             }
         }
 
-        Ok(())
+        Ok(!was_dropped && !overflow_dropped)
+    }
+
+    /// Returns `true` if there are no items waiting to be received in the overflow sender's buffer.
+    async fn overflow_is_empty(&self) -> bool {
+        match self.overflow.as_ref() {
+            Some(overflow) => overflow.is_empty().await,
+            None => true,
+        }
+    }
+
+    /// Returns `true` if there are no items waiting to be received in this sender's buffer, or in
+    /// the buffers of its overflow senders.
+    #[async_recursion]
+    async fn is_empty(&self) -> bool {
+        self.base.is_empty().await && self.overflow_is_empty().await
     }
 
     #[async_recursion]
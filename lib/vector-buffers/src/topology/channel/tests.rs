@@ -134,6 +134,28 @@ async fn test_sender_drop_newest() {
     assert_eq!(results, vec![1, 2, 3]);
 }
 
+#[tokio::test]
+async fn test_sender_drop_oldest() {
+    // Get a non-overflow buffer in "drop oldest" mode with a capacity of 3.
+    let (mut tx, rx, _) = build_buffer(3, WhenFull::DropOldest, None).await;
+
+    // We should be able to send three messages through unimpeded.
+    assert_current_send_capacity(&mut tx, Some(3), None);
+    assert_send_ok_with_capacities(&mut tx, 1, Some(2), None).await;
+    assert_send_ok_with_capacities(&mut tx, 2, Some(1), None).await;
+    assert_send_ok_with_capacities(&mut tx, 3, Some(0), None).await;
+
+    // Then, since we're in "drop oldest" mode, we could continue to send without issue or being
+    // blocked, but the oldest items in the buffer would be dropped to make room.
+    assert_send_ok_with_capacities(&mut tx, 7, Some(0), None).await;
+    assert_send_ok_with_capacities(&mut tx, 8, Some(0), None).await;
+
+    // Then, when we collect all of the messages from the receiver, we should only get back the
+    // last three of them, in order.
+    let results: Vec<u64> = drain_receiver(tx, rx).await;
+    assert_eq!(results, vec![3, 7, 8]);
+}
+
 #[tokio::test]
 async fn test_sender_overflow_block() {
     // Get an overflow buffer, where the overflow buffer is in blocking mode, and both the base
@@ -187,6 +209,66 @@ async fn test_sender_overflow_drop_newest() {
     assert_eq!(results, vec![1, 2, 7, 8]);
 }
 
+#[tokio::test]
+async fn test_sender_overflow_preserves_order() {
+    // Get an overflow buffer, where the overflow buffer is in blocking mode, and both the base
+    // and overflow buffers have a capacity of 3.
+    let (mut tx, mut rx, _) = build_buffer(3, WhenFull::Overflow, Some(WhenFull::Block)).await;
+
+    // Fill up the base buffer, and overflow a single message.
+    assert_send_ok_with_capacities(&mut tx, 1, Some(2), Some(3)).await;
+    assert_send_ok_with_capacities(&mut tx, 2, Some(1), Some(3)).await;
+    assert_send_ok_with_capacities(&mut tx, 3, Some(0), Some(3)).await;
+    assert_send_ok_with_capacities(&mut tx, 4, Some(0), Some(2)).await;
+
+    // Even though the base buffer has capacity again once we receive from it, the next message
+    // should still be sent to the overflow buffer, as it would otherwise be received ahead of the
+    // message that overflowed before it.
+    assert_eq!(rx.next().await.map(u64::from), Some(1));
+    assert_send_ok_with_capacities(&mut tx, 5, Some(1), Some(1)).await;
+
+    let mut results = Vec::new();
+    for _ in 0..4 {
+        results.extend(rx.next().await.map(u64::from));
+    }
+    assert_eq!(results, vec![2, 3, 4, 5]);
+
+    // Now that everything that overflowed has been received, we should be back to sending to the
+    // base buffer.
+    assert_send_ok_with_capacities(&mut tx, 6, Some(2), Some(3)).await;
+
+    let results: Vec<u64> = drain_receiver(tx, rx).await;
+    assert_eq!(results, vec![6]);
+}
+
+#[tokio::test]
+async fn test_sender_overflow_drop_oldest() {
+    // Get an overflow buffer, where the overflow buffer is in "drop oldest" mode, and both the
+    // base and overflow buffers have a capacity of 2.
+    let (mut tx, mut rx, _) = build_buffer(2, WhenFull::Overflow, Some(WhenFull::DropOldest)).await;
+
+    // Fill up both buffers, and then send one more message, which drops the oldest message in the
+    // overflow buffer.
+    assert_send_ok_with_capacities(&mut tx, 1, Some(1), Some(2)).await;
+    assert_send_ok_with_capacities(&mut tx, 2, Some(0), Some(2)).await;
+    assert_send_ok_with_capacities(&mut tx, 3, Some(0), Some(1)).await;
+    assert_send_ok_with_capacities(&mut tx, 4, Some(0), Some(0)).await;
+    assert_send_ok_with_capacities(&mut tx, 5, Some(0), Some(0)).await;
+
+    let mut results = Vec::new();
+    for _ in 0..4 {
+        results.extend(rx.next().await.map(u64::from));
+    }
+    assert_eq!(results, vec![1, 2, 4, 5]);
+
+    // The dropped message was never received, but now that the overflow buffer is empty, we
+    // should be back to sending to the base buffer.
+    assert_send_ok_with_capacities(&mut tx, 6, Some(1), Some(2)).await;
+
+    let results: Vec<u64> = drain_receiver(tx, rx).await;
+    assert_eq!(results, vec![6]);
+}
+
 #[tokio::test]
 async fn test_buffer_metrics_normal() {
     // Get a regular blocking buffer.
@@ -240,3 +322,29 @@ async fn test_buffer_metrics_drop_newest() {
     assert_eq!(2, snapshot.sent_event_count);
     assert_eq!(1, snapshot.dropped_event_count_intentional);
 }
+
+#[tokio::test]
+async fn test_buffer_metrics_drop_oldest() {
+    // Get a buffer that drops the oldest items when full.
+    let (mut tx, rx, handle) = build_buffer(2, WhenFull::DropOldest, None).await;
+
+    // Send three items through, and make sure the buffer usage stats reflect that.
+    assert_current_send_capacity(&mut tx, Some(2), None);
+    assert_send_ok_with_capacities(&mut tx, 7, Some(1), None).await;
+    assert_send_ok_with_capacities(&mut tx, 8, Some(0), None).await;
+    assert_send_ok_with_capacities(&mut tx, 2, Some(0), None).await;
+
+    let snapshot = handle.snapshot();
+    assert_eq!(3, snapshot.received_event_count);
+    assert_eq!(0, snapshot.sent_event_count);
+    assert_eq!(1, snapshot.dropped_event_count_intentional);
+
+    // Then, when we collect all of the messages from the receiver, the metrics should also reflect that.
+    let results: Vec<u64> = drain_receiver(tx, rx).await;
+    assert_eq!(results, vec![8, 2]);
+
+    let snapshot = handle.snapshot();
+    assert_eq!(3, snapshot.received_event_count);
+    assert_eq!(2, snapshot.sent_event_count);
+    assert_eq!(1, snapshot.dropped_event_count_intentional);
+}
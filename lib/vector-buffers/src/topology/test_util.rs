@@ -1,4 +1,4 @@
-use std::{error, fmt, num::NonZeroUsize};
+use std::{error, fmt, num::NonZeroUsize, sync::Arc};
 
 use bytes::{Buf, BufMut};
 use vector_common::byte_size_of::ByteSizeOf;
@@ -112,8 +112,9 @@ pub(crate) async fn build_buffer(
                 handle.clone(),
             )
             .await;
-            base_sender.switch_to_overflow(overflow_sender);
-            base_receiver.switch_to_overflow(overflow_receiver);
+            let overflowed = Arc::default();
+            base_sender.switch_to_overflow(overflow_sender, Arc::clone(&overflowed));
+            base_receiver.switch_to_overflow(overflow_receiver, overflowed);
 
             (base_sender, base_receiver)
         }
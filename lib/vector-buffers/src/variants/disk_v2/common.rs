@@ -9,6 +9,7 @@ use snafu::Snafu;
 use super::{
     io::{Filesystem, ProductionFilesystem},
     ledger::LEDGER_LEN,
+    payload::PayloadCodec,
     record::RECORD_HEADER_LEN,
 };
 
@@ -153,6 +154,19 @@ pub struct DiskBufferConfig<FS> {
     /// amount of data written since the last flush would be lost.
     pub(crate) flush_interval: Duration,
 
+    /// Maximum age of records that can be read from the buffer.
+    ///
+    /// The age of a record is based on when its data file was last written to, as records do not
+    /// carry the time they were written at.  Records in data files that were last written to more
+    /// than this long ago are discarded when read, instead of being returned to the caller.
+    pub(crate) max_age: Option<Duration>,
+
+    /// Codec for the payloads of records.
+    ///
+    /// This controls whether or not record payloads are compressed and/or encrypted when written
+    /// to disk, and holds the encryption key needed to read back encrypted records.
+    pub(crate) payload_codec: PayloadCodec,
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -173,6 +187,8 @@ where
     pub(crate) max_record_size: Option<usize>,
     pub(crate) write_buffer_size: Option<usize>,
     pub(crate) flush_interval: Option<Duration>,
+    pub(crate) max_age: Option<Duration>,
+    pub(crate) payload_codec: PayloadCodec,
     pub(crate) filesystem: FS,
 }
 
@@ -188,6 +204,8 @@ impl DiskBufferConfigBuilder {
             max_record_size: None,
             write_buffer_size: None,
             flush_interval: None,
+            max_age: None,
+            payload_codec: PayloadCodec::default(),
             filesystem: ProductionFilesystem,
         }
     }
@@ -268,6 +286,29 @@ where
         self
     }
 
+    /// Sets the maximum age of records that can be read from the buffer.
+    ///
+    /// The age of a record is based on when its data file was last written to, as records do not
+    /// carry the time they were written at.  Records in data files that were last written to more
+    /// than this long ago are discarded when read, instead of being returned to the caller.
+    ///
+    /// Defaults to no maximum age.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Sets the codec for the payloads of records.
+    ///
+    /// This controls whether or not record payloads are compressed and/or encrypted when written
+    /// to disk, and holds the encryption key needed to read back encrypted records.
+    ///
+    /// Defaults to storing record payloads as-is.
+    pub fn payload_codec(mut self, payload_codec: PayloadCodec) -> Self {
+        self.payload_codec = payload_codec;
+        self
+    }
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -287,6 +328,8 @@ where
             max_record_size: self.max_record_size,
             write_buffer_size: self.write_buffer_size,
             flush_interval: self.flush_interval,
+            max_age: self.max_age,
+            payload_codec: self.payload_codec,
             filesystem,
         }
     }
@@ -412,6 +455,8 @@ where
             max_record_size,
             write_buffer_size,
             flush_interval,
+            max_age: self.max_age,
+            payload_codec: self.payload_codec,
             filesystem,
         })
     }
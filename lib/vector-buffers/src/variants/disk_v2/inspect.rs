@@ -0,0 +1,409 @@
+//! Offline inspection of disk buffers.
+//!
+//! When a sink is unable to make progress for a long time, its disk buffer can hold a large amount
+//! of data that isn't otherwise visible.  [`DiskBufferInspector`] opens a disk buffer the same way
+//! Vector does, including taking the advisory lock on it, which means it can only be used while
+//! the Vector process that owns the buffer is stopped, and then allows looking at the state of the
+//! buffer, reading the records it holds without acknowledging them, or getting rid of them
+//! entirely.
+
+use std::{
+    io::{self, ErrorKind},
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use snafu::{ResultExt, Snafu};
+use tokio::fs;
+
+use super::{
+    common::MAX_FILE_ID,
+    get_disk_v2_data_dir_path,
+    io::{Filesystem, ProductionFilesystem},
+    ledger::{Ledger, LedgerLoadCreateError},
+    payload::{EncryptionKey, PayloadCodec},
+    reader::RecordReader,
+    DiskBufferConfigBuilder,
+};
+use crate::{buffer_usage_data::BufferUsageHandle, Bufferable};
+
+/// Error that occurred when inspecting a disk buffer.
+#[derive(Debug, Snafu)]
+pub enum InspectError {
+    /// There is no disk buffer at the given path.
+    #[snafu(display("no disk buffer found at {}", path.display()))]
+    NotFound { path: PathBuf },
+
+    /// The ledger of the disk buffer could not be loaded.
+    ///
+    /// Most notably, this happens when the buffer is in use by a running Vector process.
+    #[snafu(display("failed to open disk buffer: {}", source))]
+    Open { source: LedgerLoadCreateError },
+
+    /// A general I/O error occurred.
+    #[snafu(display("disk buffer I/O error: {}", source))]
+    Io { source: io::Error },
+
+    /// A record could not be read from a data file.
+    #[snafu(display("failed to read record from {}: {}", path.display(), reason))]
+    ReadRecord { path: PathBuf, reason: String },
+
+    /// The destination a disk buffer was to be moved to already exists.
+    #[snafu(display("destination {} already exists", path.display()))]
+    DestinationExists { path: PathBuf },
+}
+
+/// The state of a disk buffer.
+#[derive(Clone, Debug)]
+pub struct DiskBufferSummary {
+    /// The number of events that are yet to be acknowledged.
+    pub events: u64,
+
+    /// The ID of the oldest record that is yet to be acknowledged, if any.
+    pub oldest_record_id: Option<u64>,
+
+    /// The ID of the newest record that was written, if any record is yet to be acknowledged.
+    ///
+    /// As record IDs are assigned based on the number of events in each record, this is the ID of
+    /// the last event that was written rather than the ID the newest record starts at.
+    pub newest_record_id: Option<u64>,
+
+    /// The number of data files on disk.
+    pub data_files: usize,
+
+    /// The total size of the data files on disk, in bytes.
+    ///
+    /// Data files are only deleted once all of their records are acknowledged, so this may
+    /// include records that have already been acknowledged.
+    pub data_file_bytes: u64,
+
+    /// The time the oldest data file was last written to, if there is any data file.
+    pub oldest_write: Option<SystemTime>,
+
+    /// The time the newest data file was last written to, if there is any data file.
+    pub newest_write: Option<SystemTime>,
+}
+
+/// Inspects a disk buffer while it isn't in use.
+pub struct DiskBufferInspector {
+    path: PathBuf,
+    ledger: Ledger<ProductionFilesystem>,
+    payload_codec: PayloadCodec,
+}
+
+impl DiskBufferInspector {
+    /// Gets the path of the disk buffer of the given component, within the given data directory.
+    pub fn buffer_path(data_dir: &Path, id: &str) -> PathBuf {
+        get_disk_v2_data_dir_path(data_dir, id)
+    }
+
+    /// Opens the disk buffer at the given path.
+    ///
+    /// The buffer stays locked for as long as the inspector exists.
+    ///
+    /// # Errors
+    ///
+    /// If there is no disk buffer at the given path, or if its ledger cannot be loaded, such as
+    /// when another Vector process is using the buffer, an error variant will be returned
+    /// describing the error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the default disk buffer configuration is invalid, which would be a bug.
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self, InspectError> {
+        let path = path.into();
+
+        // Loading the ledger creates the buffer if it doesn't exist, which is never what we want
+        // when inspecting one.
+        match fs::metadata(path.join("buffer.db")).await {
+            Ok(_) => {}
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Err(InspectError::NotFound { path })
+            }
+            Err(source) => return Err(InspectError::Io { source }),
+        }
+
+        let config = DiskBufferConfigBuilder::from_path(path.clone())
+            .build()
+            .expect("default disk buffer configuration should be valid");
+        let ledger = Ledger::load_or_create(config, BufferUsageHandle::noop())
+            .await
+            .context(OpenSnafu)?;
+
+        Ok(Self {
+            path,
+            ledger,
+            payload_codec: PayloadCodec::default(),
+        })
+    }
+
+    /// Uses the given key to decrypt records when reading them.
+    ///
+    /// This is required to read the records of a disk buffer that was configured with encryption.
+    #[must_use]
+    pub fn with_encryption_key(mut self, encryption_key: EncryptionKey) -> Self {
+        self.payload_codec = PayloadCodec::new(false, Some(encryption_key));
+        self
+    }
+
+    /// Gets the path of the disk buffer.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets the state of the disk buffer.
+    ///
+    /// # Errors
+    ///
+    /// If an I/O error occurs while looking at the data files, an error variant will be returned
+    /// describing the error.
+    pub async fn summary(&self) -> Result<DiskBufferSummary, InspectError> {
+        let events = self.ledger.get_total_records();
+        let (oldest_record_id, newest_record_id) = if events == 0 {
+            (None, None)
+        } else {
+            let state = self.ledger.state();
+            (
+                Some(state.get_last_reader_record_id().wrapping_add(1)),
+                Some(state.get_next_writer_record_id().wrapping_sub(1)),
+            )
+        };
+
+        let mut summary = DiskBufferSummary {
+            events,
+            oldest_record_id,
+            newest_record_id,
+            data_files: 0,
+            data_file_bytes: 0,
+            oldest_write: None,
+            newest_write: None,
+        };
+
+        let mut entries = fs::read_dir(&self.path).await.context(IoSnafu)?;
+        while let Some(entry) = entries.next_entry().await.context(IoSnafu)? {
+            #[allow(clippy::case_sensitive_file_extension_comparisons)]
+            if !entry.file_name().to_string_lossy().ends_with(".dat") {
+                continue;
+            }
+
+            let metadata = entry.metadata().await.context(IoSnafu)?;
+            summary.data_files += 1;
+            summary.data_file_bytes += metadata.len();
+            if let Ok(modified) = metadata.modified() {
+                summary.oldest_write = Some(
+                    summary
+                        .oldest_write
+                        .map_or(modified, |oldest| oldest.min(modified)),
+                );
+                summary.newest_write = Some(
+                    summary
+                        .newest_write
+                        .map_or(modified, |newest| newest.max(modified)),
+                );
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Reads the records that are yet to be acknowledged, from oldest to newest, without
+    /// acknowledging them.
+    ///
+    /// Each record is passed to `f` along with its ID, until there are no more records or `f`
+    /// returns [`ControlFlow::Break`].
+    ///
+    /// # Errors
+    ///
+    /// If an I/O error occurs, or if a record cannot be read or decoded, an error variant will be
+    /// returned describing the error.
+    pub async fn read_records<T, F>(&self, mut f: F) -> Result<(), InspectError>
+    where
+        T: Bufferable,
+        F: FnMut(u64, T) -> ControlFlow<()>,
+    {
+        let last_reader_record_id = self.ledger.state().get_last_reader_record_id();
+        let (mut file_id, writer_file_id) = self.ledger.get_current_reader_writer_file_id();
+
+        loop {
+            let path = self.ledger.get_data_file_path(file_id);
+            match self.ledger.filesystem().open_file_readable(&path).await {
+                Ok(file) => {
+                    // Only the data file that the writer was on can end with a partial write,
+                    // which simply means there's nothing more to read.
+                    let is_finalized = file_id != writer_file_id;
+                    let mut reader = RecordReader::<_, T>::new(file, self.payload_codec.clone());
+                    loop {
+                        let token = match reader.try_next_record(is_finalized).await {
+                            Ok(Some(token)) => token,
+                            Ok(None) => break,
+                            Err(e) => {
+                                return Err(InspectError::ReadRecord {
+                                    path,
+                                    reason: e.to_string(),
+                                })
+                            }
+                        };
+
+                        // Records up to where the reader left off have been acknowledged already,
+                        // and only remain because the rest of their data file hasn't been.
+                        let record_id = token.record_id();
+                        if record_id <= last_reader_record_id {
+                            continue;
+                        }
+
+                        let record =
+                            reader
+                                .read_record(token)
+                                .map_err(|e| InspectError::ReadRecord {
+                                    path: path.clone(),
+                                    reason: e.to_string(),
+                                })?;
+                        if f(record_id, record).is_break() {
+                            return Ok(());
+                        }
+                    }
+                }
+                // The writer only creates its current data file once it has something to write.
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(source) => return Err(InspectError::Io { source }),
+            }
+
+            if file_id == writer_file_id {
+                return Ok(());
+            }
+            file_id = (file_id + 1) % MAX_FILE_ID;
+        }
+    }
+
+    /// Deletes the disk buffer, and all of the records it holds.
+    ///
+    /// Vector creates a new, empty, buffer the next time it starts.
+    ///
+    /// # Errors
+    ///
+    /// If an I/O error occurs while deleting the buffer, an error variant will be returned
+    /// describing the error.
+    pub async fn truncate(self) -> Result<(), InspectError> {
+        let path = self.release();
+        fs::remove_dir_all(&path).await.context(IoSnafu)
+    }
+
+    /// Moves the disk buffer, and all of the records it holds, to the given path.
+    ///
+    /// A buffer that was moved aside can be moved back in place later on, to replay its records
+    /// through the sink.
+    ///
+    /// # Errors
+    ///
+    /// If the destination already exists, or if an I/O error occurs while moving the buffer, an
+    /// error variant will be returned describing the error.
+    pub async fn move_to(self, destination: impl Into<PathBuf>) -> Result<(), InspectError> {
+        let destination = destination.into();
+        match fs::metadata(&destination).await {
+            Ok(_) => return Err(InspectError::DestinationExists { path: destination }),
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(source) => return Err(InspectError::Io { source }),
+        }
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent).await.context(IoSnafu)?;
+        }
+
+        let path = self.release();
+        fs::rename(&path, &destination).await.context(IoSnafu)
+    }
+
+    /// Releases the lock on the buffer, returning its path.
+    fn release(self) -> PathBuf {
+        let Self { path, ledger, .. } = self;
+        drop(ledger);
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::ControlFlow;
+
+    use super::{DiskBufferInspector, InspectError};
+    use crate::{
+        test::{with_temp_dir, SizedRecord},
+        variants::disk_v2::tests::create_default_buffer_v2,
+    };
+
+    #[tokio::test]
+    async fn inspects_and_moves_buffer() {
+        with_temp_dir(|dir| {
+            let buffer_path = dir.join("buffer");
+            let moved_path = dir.join("moved");
+
+            async move {
+                let (mut writer, reader, ledger) =
+                    create_default_buffer_v2::<_, SizedRecord>(buffer_path.clone()).await;
+                for size in [64, 128, 256] {
+                    writer
+                        .write_record(SizedRecord::new(size))
+                        .await
+                        .expect("write should not fail");
+                }
+                writer.flush().await.expect("flush should not fail");
+                writer.close();
+
+                // Pretend the first record was acknowledged, without its data file being deleted.
+                unsafe { ledger.state().unsafe_set_reader_last_record_id(1) };
+                ledger.flush().expect("should not fail to flush ledger");
+                drop(reader);
+                drop(writer);
+                drop(ledger);
+
+                let inspector = DiskBufferInspector::open(buffer_path.clone())
+                    .await
+                    .expect("open should not fail");
+                let summary = inspector.summary().await.expect("summary should not fail");
+                assert_eq!(summary.events, 2);
+                assert_eq!(summary.oldest_record_id, Some(2));
+                assert_eq!(summary.newest_record_id, Some(3));
+                assert_eq!(summary.data_files, 1);
+
+                let mut records = Vec::new();
+                inspector
+                    .read_records(|id, record: SizedRecord| {
+                        records.push((id, record));
+                        ControlFlow::Continue(())
+                    })
+                    .await
+                    .expect("read should not fail");
+                assert_eq!(
+                    records,
+                    vec![(2, SizedRecord::new(128)), (3, SizedRecord::new(256))]
+                );
+
+                // The buffer stays locked for as long as it's being inspected.
+                assert!(matches!(
+                    DiskBufferInspector::open(buffer_path.clone()).await,
+                    Err(InspectError::Open { .. })
+                ));
+
+                inspector
+                    .move_to(moved_path.clone())
+                    .await
+                    .expect("move should not fail");
+                assert!(matches!(
+                    DiskBufferInspector::open(buffer_path).await,
+                    Err(InspectError::NotFound { .. })
+                ));
+
+                let inspector = DiskBufferInspector::open(moved_path.clone())
+                    .await
+                    .expect("open should not fail");
+                assert_eq!(inspector.summary().await.unwrap().events, 2);
+                inspector
+                    .truncate()
+                    .await
+                    .expect("truncate should not fail");
+                assert!(!moved_path.exists());
+            }
+        })
+        .await;
+    }
+}
@@ -1,7 +1,7 @@
 use std::{
     fmt, io, mem,
     path::PathBuf,
-    sync::atomic::{AtomicBool, AtomicU16, AtomicU64, Ordering},
+    sync::atomic::{fence, AtomicBool, AtomicU16, AtomicU32, AtomicU64, Ordering},
     sync::Arc,
     time::Instant,
 };
@@ -27,6 +27,9 @@ use crate::buffer_usage_data::BufferUsageHandle;
 
 pub const LEDGER_LEN: usize = align16(mem::size_of::<ArchivedLedgerState>());
 
+/// Sentinel value for when no data file is being dropped.
+const NO_DROPPING_DATA_FILE: u32 = u32::MAX;
+
 /// Error that occurred during calls to [`Ledger`].
 #[derive(Debug, Snafu)]
 pub enum LedgerLoadCreateError {
@@ -228,6 +231,11 @@ where
     pending_acks: AtomicU64,
     // The file ID offset of the reader past the acknowledged reader file ID.
     unacked_reader_file_id_offset: AtomicU16,
+    // The file ID of the data file currently being dropped by the writer, if any.
+    dropping_data_file_id: AtomicU32,
+    // Number of events dropped by the writer, as part of dropping the oldest data file, that the
+    // reader has yet to skip over.
+    dropped_oldest_events: AtomicU64,
     // Last flush of all unflushed files: ledger, data file, etc.
     last_flush: AtomicCell<Instant>,
     // Tracks usage data about the buffer.
@@ -308,6 +316,14 @@ where
         self.state().get_offset_reader_file_id(unacked_offset)
     }
 
+    /// Returns `true` if the reader has moved past the acknowledged reader file ID.
+    ///
+    /// This is the case when the reader has finished reading one or more data files that are still
+    /// waiting on acknowledgements before they can be deleted.
+    pub fn has_unacked_reader_data_files(&self) -> bool {
+        self.unacked_reader_file_id_offset.load(Ordering::Acquire) > 0
+    }
+
     /// Gets the current writer file ID.
     pub fn get_current_writer_file_id(&self) -> u16 {
         self.state().get_current_writer_file_id()
@@ -398,6 +414,64 @@ where
             .increment_sent_event_count_and_byte_size(event_count, total_record_size);
     }
 
+    /// Tracks the statistics of multiple records that expired before being read.
+    pub fn track_expired(&self, event_count: u64, total_record_size: u64) {
+        self.decrement_total_buffer_size(total_record_size);
+        self.usage_handle
+            .increment_expired_event_count_and_byte_size(event_count, total_record_size);
+    }
+
+    /// Tracks the statistics of a data file dropped by the writer to make room for newer records.
+    ///
+    /// The events are also remembered so that, when the reader skips over the record IDs that were
+    /// dropped, it does not report them as lost a second time.
+    pub fn track_dropped_oldest(&self, event_count: u64, data_file_size: u64) {
+        self.decrement_total_buffer_size(data_file_size);
+        self.dropped_oldest_events
+            .fetch_add(event_count, Ordering::AcqRel);
+        self.usage_handle
+            .increment_dropped_event_count_and_byte_size(event_count, data_file_size, true);
+    }
+
+    /// Consumes up to `event_count` events from those dropped by the writer to make room for newer
+    /// records, returning the number of events consumed.
+    pub fn consume_dropped_oldest_events(&self, event_count: u64) -> u64 {
+        let previous = self
+            .dropped_oldest_events
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                Some(n.saturating_sub(event_count))
+            })
+            .expect("update closure always returns a value");
+        previous.min(event_count)
+    }
+
+    /// Marks the given data file as being dropped by the writer.
+    ///
+    /// The reader will not open this data file until [`finish_dropping_data_file`] is called.
+    /// Callers must check that the reader has not moved on to the data file _after_ calling this
+    /// method, as the reader may have already opened it.
+    pub fn begin_dropping_data_file(&self, file_id: u16) {
+        self.dropping_data_file_id
+            .store(u32::from(file_id), Ordering::SeqCst);
+
+        // Pairs with the fence in `is_dropping_data_file`, ensuring that either the writer sees the
+        // reader moving on to the data file, or the reader sees the data file being dropped.
+        fence(Ordering::SeqCst);
+    }
+
+    /// Marks the data file being dropped by the writer, if any, as no longer being dropped.
+    pub fn finish_dropping_data_file(&self) {
+        self.dropping_data_file_id
+            .store(NO_DROPPING_DATA_FILE, Ordering::SeqCst);
+        self.notify_writer_waiters();
+    }
+
+    /// Returns `true` if the given data file is currently being dropped by the writer.
+    pub fn is_dropping_data_file(&self, file_id: u16) -> bool {
+        fence(Ordering::SeqCst);
+        self.dropping_data_file_id.load(Ordering::SeqCst) == u32::from(file_id)
+    }
+
     /// Marks the writer as finished.
     ///
     /// If the writer was not yet marked done, `false` is returned.  Otherwise, `true` is returned,
@@ -648,6 +722,8 @@ where
             writer_done: AtomicBool::new(false),
             pending_acks: AtomicU64::new(0),
             unacked_reader_file_id_offset: AtomicU16::new(0),
+            dropping_data_file_id: AtomicU32::new(NO_DROPPING_DATA_FILE),
+            dropped_oldest_events: AtomicU64::new(0),
             last_flush: AtomicCell::new(Instant::now()),
             usage_handle,
         };
@@ -42,6 +42,11 @@
 //!   payload:    uint8[record_len]
 //! ```
 //!
+//! The payload can optionally be compressed (zstd) and/or encrypted (AES-256-GCM) before being
+//! stored. Whether or not it was is tracked per record, using bits of the record metadata that are
+//! reserved for this purpose, so the configuration can be changed without affecting records
+//! already in the buffer.
+//!
 //! We say "pseudo-structure" as a helper serialization library, [`rkyv`][rkyv], is used to handle
 //! serialization, and zero-copy deserialization, of records. This effectively adds some amount of
 //! padding to record fields, due to the need to structure record field data in a way that makes it
@@ -176,6 +181,7 @@ use std::{
     num::NonZeroU64,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use async_trait::async_trait;
@@ -184,8 +190,10 @@ use vector_common::finalization::Finalizable;
 
 mod backed_archive;
 mod common;
+mod inspect;
 mod io;
 mod ledger;
+mod payload;
 mod reader;
 mod record;
 mod ser;
@@ -197,8 +205,10 @@ mod tests;
 use self::ledger::Ledger;
 pub use self::{
     common::{DiskBufferConfig, DiskBufferConfigBuilder},
+    inspect::{DiskBufferInspector, DiskBufferSummary, InspectError},
     io::{Filesystem, ProductionFilesystem},
     ledger::LedgerLoadCreateError,
+    payload::{EncryptionKey, PayloadCodec, PayloadError, ENCRYPTION_KEY_LEN},
     reader::{Reader, ReaderError},
     writer::{Writer, WriterError},
 };
@@ -301,6 +311,8 @@ pub struct DiskV2Buffer {
     id: String,
     data_dir: PathBuf,
     max_size: NonZeroU64,
+    max_age: Option<Duration>,
+    payload_codec: PayloadCodec,
 }
 
 impl DiskV2Buffer {
@@ -309,8 +321,24 @@ impl DiskV2Buffer {
             id,
             data_dir,
             max_size,
+            max_age: None,
+            payload_codec: PayloadCodec::default(),
         }
     }
+
+    /// Discards records that have been in the buffer for longer than `max_age` when they're read.
+    #[must_use]
+    pub fn with_max_age(mut self, max_age: Option<Duration>) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Compresses and/or encrypts records when they're written, based on the given codec.
+    #[must_use]
+    pub fn with_payload_codec(mut self, payload_codec: PayloadCodec) -> Self {
+        self.payload_codec = payload_codec;
+        self
+    }
 }
 
 #[async_trait]
@@ -331,6 +359,8 @@ where
             &self.data_dir,
             self.id.as_str(),
             self.max_size,
+            self.max_age,
+            self.payload_codec,
         )
         .await?;
 
@@ -343,6 +373,8 @@ async fn build_disk_v2_buffer<T>(
     data_dir: &Path,
     id: &str,
     max_size: NonZeroU64,
+    max_age: Option<Duration>,
+    payload_codec: PayloadCodec,
 ) -> Result<
     (
         Writer<T, ProductionFilesystem>,
@@ -356,9 +388,13 @@ where
     usage_handle.set_buffer_limits(Some(max_size.get()), None);
 
     let buffer_path = get_disk_v2_data_dir_path(data_dir, id);
-    let config = DiskBufferConfigBuilder::from_path(buffer_path)
+    let mut builder = DiskBufferConfigBuilder::from_path(buffer_path)
         .max_buffer_size(max_size.get())
-        .build()?;
+        .payload_codec(payload_codec);
+    if let Some(max_age) = max_age {
+        builder = builder.max_age(max_age);
+    }
+    let config = builder.build()?;
     Buffer::from_config(config, usage_handle)
         .await
         .map_err(Into::into)
@@ -0,0 +1,321 @@
+use std::{borrow::Cow, fmt, io};
+
+use openssl::{
+    error::ErrorStack,
+    rand::rand_bytes,
+    symm::{decrypt_aead, encrypt_aead, Cipher},
+};
+use snafu::{OptionExt, ResultExt, Snafu};
+
+/// Record metadata flag signalling that the record payload is compressed with zstd.
+const PAYLOAD_COMPRESSED: u32 = 1 << 31;
+
+/// Record metadata flag signalling that the record payload is encrypted with AES-256-GCM.
+const PAYLOAD_ENCRYPTED: u32 = 1 << 30;
+
+/// Record metadata bits that are reserved for describing how the record payload is stored.
+///
+/// The remaining bits belong to the metadata of the record type itself, which means a version of
+/// Vector that doesn't know about these flags treats records using them as incompatible, rather
+/// than trying to decode a compressed or encrypted payload as-is.
+pub(super) const PAYLOAD_FLAGS_MASK: u32 = PAYLOAD_COMPRESSED | PAYLOAD_ENCRYPTED;
+
+/// Length, in bytes, of an encryption key.
+pub const ENCRYPTION_KEY_LEN: usize = 32;
+
+/// Length, in bytes, of the nonce that precedes every encrypted payload.
+const NONCE_LEN: usize = 12;
+
+/// Length, in bytes, of the authentication tag that follows every encrypted payload.
+const TAG_LEN: usize = 16;
+
+/// Compression level used for record payloads.
+///
+/// This is the default level of zstd, which is a good trade-off between speed and compression
+/// ratio for the kind of repetitive, structured data we tend to buffer.
+const ZSTD_LEVEL: i32 = 3;
+
+/// Error that occurred when encoding or decoding a record payload.
+#[derive(Debug, Snafu)]
+pub enum PayloadError {
+    /// The payload could not be compressed.
+    #[snafu(display("failed to compress payload: {}", source))]
+    Compression { source: io::Error },
+
+    /// The payload could not be decompressed.
+    #[snafu(display("failed to decompress payload: {}", source))]
+    Decompression { source: io::Error },
+
+    /// The payload could not be encrypted.
+    #[snafu(display("failed to encrypt payload: {}", source))]
+    Encryption { source: ErrorStack },
+
+    /// The payload could not be decrypted.
+    ///
+    /// As the record checksum is validated before the payload is decrypted, this generally means
+    /// the payload was encrypted with a different key than the one that is configured.
+    #[snafu(display(
+        "failed to decrypt payload, which may have been encrypted with a different key"
+    ))]
+    Decryption,
+
+    /// The payload is encrypted, but there is no encryption key configured.
+    #[snafu(display("payload is encrypted, but no encryption key is configured"))]
+    MissingEncryptionKey,
+}
+
+/// Key used to encrypt record payloads with AES-256-GCM.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EncryptionKey([u8; ENCRYPTION_KEY_LEN]);
+
+impl EncryptionKey {
+    /// Creates an [`EncryptionKey`] from its base64 representation.
+    ///
+    /// Leading and trailing whitespace is ignored, as keys are commonly read from files that end
+    /// with a newline.  Returns `None` if the value is not valid base64, or does not decode to
+    /// exactly [`ENCRYPTION_KEY_LEN`] bytes.
+    pub fn from_base64(value: &str) -> Option<Self> {
+        let decoded = openssl::base64::decode_block(value.trim()).ok()?;
+        decoded.try_into().ok().map(Self)
+    }
+}
+
+impl From<[u8; ENCRYPTION_KEY_LEN]> for EncryptionKey {
+    fn from(key: [u8; ENCRYPTION_KEY_LEN]) -> Self {
+        Self(key)
+    }
+}
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EncryptionKey(**REDACTED**)")
+    }
+}
+
+/// Transforms record payloads when they're written to, and read from, disk.
+///
+/// Payloads are compressed first, and then encrypted, as encrypted data does not compress.  How a
+/// given payload was stored is tracked with flags in the record metadata, so records can always be
+/// read back regardless of whether or not compression or encryption were enabled when they were
+/// written, as long as the encryption key is available for encrypted records.
+#[derive(Clone, Debug, Default)]
+pub struct PayloadCodec {
+    compression: bool,
+    encryption_key: Option<EncryptionKey>,
+}
+
+impl PayloadCodec {
+    /// Creates a new [`PayloadCodec`].
+    ///
+    /// When `compression` is `true`, payloads are compressed with zstd, unless doing so would not
+    /// make them any smaller.  When an encryption key is given, payloads are encrypted with it.
+    pub fn new(compression: bool, encryption_key: Option<EncryptionKey>) -> Self {
+        Self {
+            compression,
+            encryption_key,
+        }
+    }
+
+    /// Encodes the given payload for storage.
+    ///
+    /// If the payload needs to be transformed, the transformed payload is written to `buf`, and the
+    /// record metadata, updated to describe the transformation, is returned.  Otherwise, `None` is
+    /// returned and the payload should be stored as-is.
+    ///
+    /// # Errors
+    ///
+    /// If the payload cannot be compressed or encrypted, an error variant will be returned
+    /// describing the error.
+    pub(super) fn encode(
+        &self,
+        record_id: u64,
+        mut metadata: u32,
+        payload: &[u8],
+        buf: &mut Vec<u8>,
+    ) -> Result<Option<u32>, PayloadError> {
+        let mut payload = Cow::Borrowed(payload);
+
+        if self.compression {
+            let compressed =
+                zstd::bulk::compress(&payload, ZSTD_LEVEL).context(CompressionSnafu)?;
+
+            // Payloads that don't compress well are stored as-is, which also saves us from having
+            // to decompress them when reading them back.
+            if compressed.len() < payload.len() {
+                payload = Cow::Owned(compressed);
+                metadata |= PAYLOAD_COMPRESSED;
+            }
+        }
+
+        if let Some(key) = &self.encryption_key {
+            metadata |= PAYLOAD_ENCRYPTED;
+
+            let mut nonce = [0; NONCE_LEN];
+            rand_bytes(&mut nonce).context(EncryptionSnafu)?;
+
+            let mut tag = [0; TAG_LEN];
+            let ciphertext = encrypt_aead(
+                Cipher::aes_256_gcm(),
+                &key.0,
+                Some(&nonce),
+                &associated_data(record_id, metadata),
+                &payload,
+                &mut tag,
+            )
+            .context(EncryptionSnafu)?;
+
+            buf.clear();
+            buf.extend_from_slice(&nonce);
+            buf.extend_from_slice(&ciphertext);
+            buf.extend_from_slice(&tag);
+            return Ok(Some(metadata));
+        }
+
+        match payload {
+            Cow::Borrowed(_) => Ok(None),
+            Cow::Owned(compressed) => {
+                *buf = compressed;
+                Ok(Some(metadata))
+            }
+        }
+    }
+
+    /// Decodes the given stored payload.
+    ///
+    /// # Errors
+    ///
+    /// If the payload is encrypted and cannot be decrypted, or is compressed and cannot be
+    /// decompressed, an error variant will be returned describing the error.
+    pub(super) fn decode<'a>(
+        &self,
+        record_id: u64,
+        metadata: u32,
+        payload: &'a [u8],
+    ) -> Result<Cow<'a, [u8]>, PayloadError> {
+        let mut payload = Cow::Borrowed(payload);
+
+        if metadata & PAYLOAD_ENCRYPTED != 0 {
+            let key = self
+                .encryption_key
+                .as_ref()
+                .context(MissingEncryptionKeySnafu)?;
+            if payload.len() < NONCE_LEN + TAG_LEN {
+                return Err(PayloadError::Decryption);
+            }
+
+            let (nonce, rest) = payload.split_at(NONCE_LEN);
+            let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LEN);
+            let plaintext = decrypt_aead(
+                Cipher::aes_256_gcm(),
+                &key.0,
+                Some(nonce),
+                &associated_data(record_id, metadata),
+                ciphertext,
+                tag,
+            )
+            .map_err(|_| PayloadError::Decryption)?;
+            payload = Cow::Owned(plaintext);
+        }
+
+        if metadata & PAYLOAD_COMPRESSED != 0 {
+            let decompressed =
+                zstd::stream::decode_all(&payload[..]).context(DecompressionSnafu)?;
+            payload = Cow::Owned(decompressed);
+        }
+
+        Ok(payload)
+    }
+}
+
+/// Gets the data that is authenticated, but not encrypted, alongside an encrypted payload.
+///
+/// Binding the record ID and metadata to the payload ensures that an encrypted payload can't be
+/// swapped with the payload of another record, or have its flags tampered with, without the
+/// decryption failing.
+fn associated_data(record_id: u64, metadata: u32) -> [u8; 12] {
+    let mut data = [0; 12];
+    data[..8].copy_from_slice(&record_id.to_be_bytes());
+    data[8..].copy_from_slice(&metadata.to_be_bytes());
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EncryptionKey, PayloadCodec, PayloadError, PAYLOAD_FLAGS_MASK};
+
+    fn payload() -> Vec<u8> {
+        br#"{"message":"hello world","host":"localhost"}"#.repeat(32)
+    }
+
+    #[test]
+    fn passthrough_leaves_payload_as_is() {
+        let codec = PayloadCodec::default();
+        let mut buf = Vec::new();
+        assert_eq!(codec.encode(1, 0b1, &payload(), &mut buf).unwrap(), None);
+
+        let decoded = codec.decode(1, 0b1, &payload()).unwrap();
+        assert_eq!(decoded.as_ref(), payload().as_slice());
+    }
+
+    #[test]
+    fn compression_roundtrip() {
+        let codec = PayloadCodec::new(true, None);
+        let mut buf = Vec::new();
+        let metadata = codec.encode(1, 0b1, &payload(), &mut buf).unwrap().unwrap();
+        assert_eq!(metadata & !PAYLOAD_FLAGS_MASK, 0b1);
+        assert!(buf.len() < payload().len());
+
+        let decoded = codec.decode(1, metadata, &buf).unwrap();
+        assert_eq!(decoded.as_ref(), payload().as_slice());
+    }
+
+    #[test]
+    fn incompressible_payload_is_stored_as_is() {
+        let codec = PayloadCodec::new(true, None);
+        let mut buf = Vec::new();
+        assert_eq!(codec.encode(1, 0b1, b"abc", &mut buf).unwrap(), None);
+    }
+
+    #[test]
+    fn encryption_roundtrip() {
+        let key = EncryptionKey::from([7; 32]);
+        let codec = PayloadCodec::new(true, Some(key));
+        let mut buf = Vec::new();
+        let metadata = codec
+            .encode(42, 0b1, &payload(), &mut buf)
+            .unwrap()
+            .unwrap();
+        assert!(!buf
+            .windows(b"hello world".len())
+            .any(|w| w == b"hello world"));
+
+        let decoded = codec.decode(42, metadata, &buf).unwrap();
+        assert_eq!(decoded.as_ref(), payload().as_slice());
+
+        // The payload is bound to the record it was written for.
+        assert!(matches!(
+            codec.decode(43, metadata, &buf),
+            Err(PayloadError::Decryption)
+        ));
+
+        // Decrypting requires the same key.
+        let other_codec = PayloadCodec::new(true, Some(EncryptionKey::from([8; 32])));
+        assert!(matches!(
+            other_codec.decode(42, metadata, &buf),
+            Err(PayloadError::Decryption)
+        ));
+        assert!(matches!(
+            PayloadCodec::default().decode(42, metadata, &buf),
+            Err(PayloadError::MissingEncryptionKey)
+        ));
+    }
+
+    #[test]
+    fn parses_base64_keys() {
+        let key = EncryptionKey::from_base64("BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc=\n");
+        assert_eq!(key, Some(EncryptionKey::from([7; 32])));
+
+        assert_eq!(EncryptionKey::from_base64("BwcHBwcHBwc="), None);
+        assert_eq!(EncryptionKey::from_base64("not base64!"), None);
+    }
+}
@@ -3,8 +3,9 @@ use std::{
     io::{self, ErrorKind},
     marker::PhantomData,
     num::NonZeroU64,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
+    time::SystemTime,
 };
 
 use crc32fast::Hasher;
@@ -16,6 +17,7 @@ use vector_common::{finalization::BatchNotifier, finalizer::OrderedFinalizer};
 use super::{
     common::create_crc32c_hasher,
     ledger::Ledger,
+    payload::{PayloadCodec, PayloadError, PAYLOAD_FLAGS_MASK},
     record::{validate_record_archive, ArchivedRecord, Record, RecordStatus},
     Filesystem,
 };
@@ -100,6 +102,14 @@ where
         source: <T as Encodable>::DecodeError,
     },
 
+    /// The record payload could not be decompressed or decrypted.
+    ///
+    /// At this stage, the record can be assumed to have been written correctly, and read correctly
+    /// from disk, as the checksum was also validated.  Most commonly, this means the record was
+    /// encrypted with a different key than the one that is configured.
+    #[snafu(display("failed to decode record payload: {}", source))]
+    Payload { source: PayloadError },
+
     /// The record is not compatible with this version of Vector.
     ///
     /// This can occur when records written to a buffer in previous versions of Vector are read by
@@ -144,6 +154,7 @@ where
             ReaderError::Deserialization { .. } => "deser_failed",
             ReaderError::Checksum { .. } => "checksum_mismatch",
             ReaderError::Decode { .. } => "decode_failed",
+            ReaderError::Payload { .. } => "payload_decode_failed",
             ReaderError::Incompatible { .. } => "incompatible_record_version",
             ReaderError::PartialWrite => "partial_write",
             ReaderError::EmptyRecord => "empty_record",
@@ -159,6 +170,7 @@ where
             ReaderError::Deserialization { .. }
             | ReaderError::Checksum { .. }
             | ReaderError::Decode { .. }
+            | ReaderError::Payload { .. }
             | ReaderError::Incompatible { .. }
             | ReaderError::PartialWrite => Some(BufferReadError { error_code, error }),
         }
@@ -199,6 +211,7 @@ pub(super) struct RecordReader<R, T> {
     reader: BufReader<R>,
     aligned_buf: AlignedVec,
     checksummer: Hasher,
+    payload_codec: PayloadCodec,
     current_record_id: u64,
     _t: PhantomData<T>,
 }
@@ -212,11 +225,14 @@ where
     ///
     /// Internally, the reader is wrapped in a [`BufReader`], so callers should not pass in an
     /// already buffered reader.
-    pub fn new(reader: R) -> Self {
+    ///
+    /// Record payloads are decoded with the given [`PayloadCodec`] before being decoded into `T`.
+    pub fn new(reader: R, payload_codec: PayloadCodec) -> Self {
         Self {
             reader: BufReader::with_capacity(256 * 1024, reader),
             aligned_buf: AlignedVec::new(),
             checksummer: create_crc32c_hasher(),
+            payload_codec,
             current_record_id: 0,
             _t: PhantomData,
         }
@@ -374,7 +390,7 @@ where
         // - `try_next_record` does all the archive checks, checksum validation, etc
         let record = unsafe { archived_root::<Record<'_>>(&self.aligned_buf) };
 
-        decode_record_payload(record)
+        decode_record_payload(record, &self.payload_codec)
     }
 }
 
@@ -387,6 +403,7 @@ where
             .field("reader", &self.reader)
             .field("aligned_buf", &self.aligned_buf)
             .field("checksummer", &self.checksummer)
+            .field("payload_codec", &self.payload_codec)
             .field("current_record_id", &self.current_record_id)
             .finish()
     }
@@ -405,8 +422,9 @@ where
     data_file_start_record_id: Option<u64>,
     data_file_record_count: u64,
     data_file_marked_record_count: u64,
+    data_file_modified: Option<SystemTime>,
     ready_to_read: bool,
-    record_acks: OrderedAcknowledgements<u64, u64>,
+    record_acks: OrderedAcknowledgements<u64, (u64, bool)>,
     data_file_acks: OrderedAcknowledgements<u64, (PathBuf, u64)>,
     finalizer: OrderedFinalizer<u64>,
     _t: PhantomData<T>,
@@ -431,6 +449,7 @@ where
             data_file_start_record_id: None,
             data_file_record_count: 0,
             data_file_marked_record_count: 0,
+            data_file_modified: None,
             ready_to_read: false,
             record_acks: OrderedAcknowledgements::from_acked(next_expected_record_id),
             data_file_acks: OrderedAcknowledgements::from_acked(0),
@@ -439,13 +458,27 @@ where
         }
     }
 
+    /// Gets the total number of unacknowledged events in the buffer.
+    ///
+    /// See [`Ledger::get_total_records`] for more details.
+    pub(crate) fn get_total_records(&self) -> u64 {
+        self.ledger.get_total_records()
+    }
+
     fn reset(&mut self) {
         self.reader = None;
         self.bytes_read = 0;
         self.data_file_start_record_id = None;
+        self.data_file_modified = None;
     }
 
-    fn track_read(&mut self, record_id: u64, record_bytes: u64, event_count: NonZeroU64) {
+    fn track_read(
+        &mut self,
+        record_id: u64,
+        record_bytes: u64,
+        event_count: NonZeroU64,
+        expired: bool,
+    ) {
         // We explicitly reduce the event count by one here in order to correctly calculate the
         // "last" record ID, which you can visualize as follows...
         //
@@ -473,10 +506,11 @@ where
         // We've done a "real" record read, so we need to track it for acknowledgement.  Check our
         // acknowledge state first to see if this is the next record ID we expected.
         self.data_file_record_count += 1;
-        if let Err(me) =
-            self.record_acks
-                .add_marker(record_id, Some(event_count.get()), Some(record_bytes))
-        {
+        if let Err(me) = self.record_acks.add_marker(
+            record_id,
+            Some(event_count.get()),
+            Some((record_bytes, expired)),
+        ) {
             match me {
                 MarkerError::MonotonicityViolation => {
                     panic!("record ID monotonicity violation detected; this is a serious bug")
@@ -511,11 +545,43 @@ where
         // occur at all, so we're relying on this method to correct the buffer size for us.  This is
         // why `bytes_read` is optional: when it's specified, we calculate a delta for handling
         // partial-read scenarios, otherwise, we just use the entire data file size as is.
-        let data_file = self
+        //
+        // The data file may also already be gone if the writer dropped it to make room for newer
+        // records, in which case the writer has already adjusted the buffer size.
+        let data_file = match self
             .ledger
             .filesystem()
             .open_file_readable(&data_file_path)
-            .await?;
+            .await
+        {
+            Ok(data_file) => Some(data_file),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+
+        if let Some(data_file) = data_file {
+            self.delete_data_file(&data_file_path, data_file, bytes_read)
+                .await?;
+        }
+
+        self.ledger.increment_acked_reader_file_id();
+        self.ledger.flush()?;
+
+        debug!("Flushed after deleting data file, notifying writers and continuing.");
+
+        // Notify any waiting writers that we've deleted a data file, which they may be waiting on
+        // because they're looking to reuse the file ID of the file we just finished reading.
+        self.ledger.notify_reader_waiters();
+
+        Ok(())
+    }
+
+    async fn delete_data_file(
+        &self,
+        data_file_path: &Path,
+        data_file: FS::File,
+        bytes_read: Option<u64>,
+    ) -> io::Result<()> {
         let metadata = data_file.metadata().await?;
 
         let decrease_amount = bytes_read.map_or_else(
@@ -540,21 +606,8 @@ where
 
         drop(data_file);
 
-        // Delete the current data file, and increment our actual reader file ID.
-        self.ledger
-            .filesystem()
-            .delete_file(&data_file_path)
-            .await?;
-        self.ledger.increment_acked_reader_file_id();
-        self.ledger.flush()?;
-
-        debug!("Flushed after deleting data file, notifying writers and continuing.");
-
-        // Notify any waiting writers that we've deleted a data file, which they may be waiting on
-        // because they're looking to reuse the file ID of the file we just finished reading.
-        self.ledger.notify_reader_waiters();
-
-        Ok(())
+        // Delete the current data file. Our caller increments our actual reader file ID.
+        self.ledger.filesystem().delete_file(data_file_path).await
     }
 
     #[cfg_attr(test, instrument(skip(self), level = "debug"))]
@@ -578,6 +631,8 @@ where
         let mut events_acknowledged: u64 = 0;
         let mut events_skipped: u64 = 0;
         let mut bytes_acknowledged: u64 = 0;
+        let mut events_expired: u64 = 0;
+        let mut bytes_expired: u64 = 0;
 
         let consumed_acks = self.ledger.consume_pending_acks();
         if consumed_acks > 0 {
@@ -602,16 +657,25 @@ where
                     }
                     // We got a valid marker representing a known number of events.
                     EligibleMarkerLength::Known(len) => {
-                        // We specifically pass the size of the record, in bytes, as the marker data.
-                        let record_bytes = data.expect("record bytes should always be known");
+                        // We specifically pass the size of the record, in bytes, as the marker data,
+                        // along with whether or not the record expired before being read.
+                        let (record_bytes, expired) =
+                            data.expect("record bytes should always be known");
 
                         records_acknowledged = records_acknowledged.checked_add(1).expect(
                             "acknowledging more than 2^64 records at a time is obviously a bug",
                         );
-                        events_acknowledged = events_acknowledged.checked_add(len).expect(
+                        // Expired records are tallied separately, as they were never actually
+                        // processed by the caller.
+                        let (events, bytes) = if expired {
+                            (&mut events_expired, &mut bytes_expired)
+                        } else {
+                            (&mut events_acknowledged, &mut bytes_acknowledged)
+                        };
+                        *events = events.checked_add(len).expect(
                             "acknowledging more than 2^64 events at a time is obviously a bug",
                         );
-                        bytes_acknowledged = bytes_acknowledged.checked_add(record_bytes).expect(
+                        *bytes = bytes.checked_add(record_bytes).expect(
                             "acknowledging more than 2^64 bytes at a time is obviously a bug",
                         );
                     }
@@ -622,10 +686,13 @@ where
             if had_eligible_records {
                 self.ledger
                     .track_reads(events_acknowledged, bytes_acknowledged);
+                if events_expired > 0 {
+                    self.ledger.track_expired(events_expired, bytes_expired);
+                }
 
                 // We need to account for skipped events, too, so that our "last reader record ID"
                 // value stays correct as we process these gap markers.
-                let last_increment_amount = events_acknowledged + events_skipped;
+                let last_increment_amount = events_acknowledged + events_expired + events_skipped;
                 self.ledger
                     .state()
                     .increment_last_reader_record_id(last_increment_amount);
@@ -634,9 +701,12 @@ where
                     .add_acknowledgements(records_acknowledged);
             }
 
-            // If any events were skipped, do our logging/metrics for that.
-            if events_skipped > 0 {
-                self.ledger.track_dropped_events(events_skipped);
+            // If any events were skipped, do our logging/metrics for that.  Events that the writer
+            // dropped, to make room for newer records, were already reported by the writer.
+            let events_lost =
+                events_skipped - self.ledger.consume_dropped_oldest_events(events_skipped);
+            if events_lost > 0 {
+                self.ledger.track_dropped_events(events_lost);
             }
         }
 
@@ -679,6 +749,7 @@ where
                     records_acknowledged,
                     events_acknowledged,
                     events_skipped,
+                    events_expired,
                     bytes_acknowledged,
                     data_files_deleted,
                     "Finished handling acknowledgements."
@@ -754,6 +825,14 @@ where
         // implies a data file existing.
         loop {
             let (reader_file_id, writer_file_id) = self.ledger.get_current_reader_writer_file_id();
+
+            // The writer may be in the middle of dropping this data file to make room for newer
+            // records, so wait for it to finish before trying to open it.
+            if self.ledger.is_dropping_data_file(reader_file_id) {
+                self.ledger.wait_for_writer().await;
+                continue;
+            }
+
             let data_file_path = self.ledger.get_current_reader_data_file_path();
             let data_file = match self
                 .ledger
@@ -770,6 +849,12 @@ where
                                 "Data file does not yet exist. Waiting for writer to create."
                             );
                             self.ledger.wait_for_writer().await;
+                        } else if self.ledger.has_unacked_reader_data_files() {
+                            // Data files we've already read are still waiting to be deleted, so we
+                            // roll over this one as if it were empty, which keeps data file deletion
+                            // in order.  This happens when the writer drops a data file to make room
+                            // for newer records.
+                            self.roll_to_next_data_file();
                         } else {
                             self.ledger.increment_acked_reader_file_id();
                         }
@@ -785,7 +870,10 @@ where
                 "Opened data file for reading."
             );
 
-            self.reader = Some(RecordReader::new(data_file));
+            self.reader = Some(RecordReader::new(
+                data_file,
+                self.ledger.config().payload_codec.clone(),
+            ));
             return Ok(());
         }
     }
@@ -851,7 +939,8 @@ where
                     let record = try_as_record_archive(data_file_mmap.as_ref())
                         .expect("record was already validated");
 
-                    let Ok(item) = decode_record_payload::<T>(record) else {
+                    let payload_codec = &self.ledger.config().payload_codec;
+                    let Ok(item) = decode_record_payload::<T>(record, payload_codec) else {
                         // If there's an error decoding the item, just fall back to the slow path,
                         // because this file might actually be where we left off, so we don't want
                         // to incorrectly skip ahead or anything.
@@ -1011,8 +1100,20 @@ where
                 // Not even enough data to read a length delimiter, so we need to wait for the
                 // writer to signal us that there's some actual data to read.
                 Ok(None) => {}
-                // We got a valid record, so keep the token.
-                Ok(Some(token)) => break token,
+                // We got a valid record, so keep the token, unless the record has expired, in which
+                // case we read it and immediately discard it.
+                Ok(Some(token)) => {
+                    if !self.ready_to_read
+                        || !self
+                            .is_data_file_expired(is_finalized)
+                            .await
+                            .context(IoSnafu)?
+                    {
+                        break token;
+                    }
+
+                    drop(self.read_record(token, true)?);
+                }
                 // A length-delimited payload was read, but we failed to deserialize it as a valid
                 // record, or we deserialized it and the checksum was invalid.  Either way, we're not
                 // sure the rest of the data file is even valid, so roll to the next file.
@@ -1097,6 +1198,11 @@ where
 
         // We got a read token, so our record is present in the reader, and now we can actually read
         // it out and return it.
+        self.read_record(token, false).map(Some)
+    }
+
+    /// Reads the record associated with the given [`ReadToken`], and tracks it for acknowledgement.
+    fn read_record(&mut self, token: ReadToken, expired: bool) -> Result<T, ReaderError<T>> {
         let record_id = token.record_id();
         let record_bytes = token.record_bytes() as u64;
 
@@ -1113,8 +1219,9 @@ where
         let record_events = record_events
             .try_into()
             .map_err(|_| ReaderError::EmptyRecord)?;
-        self.track_read(record_id, record_bytes, record_events);
+        self.track_read(record_id, record_bytes, record_events, expired);
 
+        // Expired records are dropped right away, which acknowledges them just like any other record.
         let (batch, receiver) = BatchNotifier::new_with_receiver();
         record.add_batch_notifier(batch);
         self.finalizer.add(record_events.get(), receiver);
@@ -1124,33 +1231,71 @@ where
                 record_id,
                 record_events,
                 record_bytes,
+                expired,
                 data_file_id = self.ledger.get_current_reader_file_id(),
                 "Read record."
             );
         }
 
-        Ok(Some(record))
+        Ok(record)
+    }
+
+    /// Checks whether or not the records in the current data file have expired.
+    ///
+    /// The age of a data file is based on when it was last written to, so only data files that the
+    /// writer has moved on from can expire.
+    async fn is_data_file_expired(&mut self, is_finalized: bool) -> io::Result<bool> {
+        let Some(max_age) = self.ledger.config().max_age else {
+            return Ok(false);
+        };
+        if !is_finalized {
+            return Ok(false);
+        }
+
+        let modified = match self.data_file_modified {
+            Some(modified) => modified,
+            None => {
+                let data_file_path = self.ledger.get_current_reader_data_file_path();
+                let data_file = self
+                    .ledger
+                    .filesystem()
+                    .open_file_readable(&data_file_path)
+                    .await?;
+                let Some(modified) = data_file.metadata().await?.modified() else {
+                    return Ok(false);
+                };
+
+                self.data_file_modified = Some(modified);
+                modified
+            }
+        };
+
+        Ok(modified.elapsed().map_or(false, |age| age > max_age))
     }
 }
 
 pub(crate) fn decode_record_payload<T: Bufferable>(
     record: &ArchivedRecord<'_>,
+    payload_codec: &PayloadCodec,
 ) -> Result<T, ReaderError<T>> {
     // Try and convert the raw record metadata into the true metadata type used by `T`, and then
     // also verify that `T` is able to decode records with the metadata used for this record in particular.
-    let metadata = T::Metadata::from_u32(record.metadata()).ok_or(ReaderError::Incompatible {
+    //
+    // The payload flags are ours, not part of the metadata of `T`, so we mask them off first.
+    let raw_metadata = record.metadata() & !PAYLOAD_FLAGS_MASK;
+    let metadata = T::Metadata::from_u32(raw_metadata).ok_or(ReaderError::Incompatible {
         reason: format!("invalid metadata for {}", std::any::type_name::<T>()),
     })?;
 
     if !T::can_decode(metadata) {
         return Err(ReaderError::Incompatible {
-            reason: format!(
-                "record metadata not supported (metadata: {:#036b})",
-                record.metadata()
-            ),
+            reason: format!("record metadata not supported (metadata: {raw_metadata:#036b})"),
         });
     }
 
-    // Now we can finally try decoding.
-    T::decode(metadata, record.payload()).context(DecodeSnafu)
+    // Undo any compression or encryption of the payload, and then we can finally try decoding.
+    let payload = payload_codec
+        .decode(record.id(), record.metadata(), record.payload())
+        .context(PayloadSnafu)?;
+    T::decode(metadata, &payload[..]).context(DecodeSnafu)
 }
@@ -62,7 +62,8 @@ pub struct Record<'a> {
 
     /// The record metadata.
     ///
-    /// Based on `Encodable::Metadata`.
+    /// Based on `Encodable::Metadata`, except for the bits in `PAYLOAD_FLAGS_MASK`, which describe
+    /// whether the payload is compressed and/or encrypted.
     pub(super) metadata: u32,
 
     /// The record payload.
@@ -128,6 +129,11 @@ impl<'a> Record<'a> {
 }
 
 impl<'a> ArchivedRecord<'a> {
+    /// Gets the ID of this record.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
     /// Gets the metadata of this record.
     pub fn metadata(&self) -> u32 {
         self.metadata
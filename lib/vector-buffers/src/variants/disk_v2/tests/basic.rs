@@ -1,15 +1,21 @@
-use std::io::Cursor;
+use std::{io::Cursor, time::Duration};
 
 use futures::{stream, StreamExt};
 use tokio_test::{assert_pending, assert_ready, task::spawn};
 use tracing::Instrument;
 use vector_common::finalization::Finalizable;
 
-use super::{create_default_buffer_v2, read_next, read_next_some};
+use super::{create_buffer_v2_with_max_age, create_default_buffer_v2, read_next, read_next_some};
 use crate::{
     assert_buffer_is_empty, assert_buffer_records,
     test::{acknowledge, install_tracing_helpers, with_temp_dir, MultiEventRecord, SizedRecord},
-    variants::disk_v2::{tests::create_default_buffer_v2_with_usage, writer::RecordWriter},
+    variants::disk_v2::{
+        payload::PayloadCodec,
+        tests::{
+            create_default_buffer_v2_with_usage, get_minimum_data_file_size_for_record_payload,
+        },
+        writer::RecordWriter,
+    },
     EventCount,
 };
 
@@ -169,8 +175,14 @@ async fn initial_size_correct_with_multievents() {
             // are identical:
             let expected_bytes = stream::iter(input_items.iter().cloned())
                 .filter_map(|record| async move {
-                    let mut record_writer =
-                        RecordWriter::new(Cursor::new(Vec::new()), 0, 16_384, u64::MAX, usize::MAX);
+                    let mut record_writer = RecordWriter::new(
+                        Cursor::new(Vec::new()),
+                        0,
+                        16_384,
+                        u64::MAX,
+                        usize::MAX,
+                        PayloadCodec::default(),
+                    );
                     let (bytes_written, flush_result) = record_writer
                         .write_record(0, record)
                         .await
@@ -230,3 +242,56 @@ async fn initial_size_correct_with_multievents() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn reader_discards_expired_records() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Create our buffer such that the first record fills an entire data file, which lets
+            // the writer move on from it, and so it can expire.
+            let first_record = SizedRecord::new(64);
+            let second_record = SizedRecord::new(32);
+            let max_data_file_size = get_minimum_data_file_size_for_record_payload(&first_record);
+            let (mut writer, mut reader, ledger, usage) = create_buffer_v2_with_max_age(
+                data_dir,
+                Duration::from_millis(100),
+                max_data_file_size,
+            )
+            .await;
+
+            let first_bytes_written = writer
+                .write_record(first_record)
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+
+            tokio::time::sleep(Duration::from_millis(200)).await;
+
+            // The second record is written to the data file the writer is currently on, which is
+            // never considered expired, no matter how old it is.
+            writer
+                .write_record(second_record.clone())
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            writer.close();
+
+            // The first record has expired, so we should only get the second record.
+            let record_read = read_next_some(&mut reader).await;
+            assert_eq!(record_read, second_record);
+            acknowledge(record_read).await;
+
+            let final_record_read = read_next(&mut reader).await;
+            assert_eq!(final_record_read, None);
+            assert_buffer_is_empty!(ledger);
+
+            let snapshot = usage.snapshot();
+            assert_eq!(snapshot.expired_event_count, 1);
+            assert_eq!(snapshot.expired_event_byte_size, first_bytes_written as u64);
+            assert_eq!(snapshot.dropped_event_count, 0);
+        }
+    })
+    .await;
+}
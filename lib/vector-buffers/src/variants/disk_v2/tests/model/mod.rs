@@ -542,6 +542,7 @@ impl WriterModel {
             ledger.config().write_buffer_size,
             ledger.config().max_data_file_size,
             ledger.config().max_record_size,
+            ledger.config().payload_codec.clone(),
         );
 
         let mut writer = Self {
@@ -481,3 +481,95 @@ async fn writer_can_validate_last_write_when_buffer_is_full() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn writer_drops_oldest_data_file_when_buffer_is_full() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Create our buffer such that each record fills an entire data file, and the buffer can
+            // only hold two of them at a time.
+            let record = SizedRecord::new(64);
+            let max_data_file_size = get_minimum_data_file_size_for_record_payload(&record);
+            let (mut writer, mut reader, ledger) =
+                create_buffer_v2_with_data_file_count_limit(data_dir, max_data_file_size, 3).await;
+
+            assert_buffer_is_empty!(ledger);
+
+            // Fill up the buffer, which puts each record in its own data file.
+            let mut bytes_written = 0;
+            for _ in 0..2 {
+                bytes_written = writer
+                    .write_record(record.clone())
+                    .await
+                    .expect("write should not fail");
+                writer.flush().await.expect("flush should not fail");
+            }
+            assert_buffer_size!(ledger, 2, bytes_written * 2);
+            assert_reader_writer_v2_file_positions!(ledger, 0, 1);
+
+            // Our next write can't fit, so the writer should drop the second data file -- the oldest
+            // one that the reader is not positioned at -- to make room for it.
+            let result = writer
+                .write_record_dropping_oldest(record.clone())
+                .await
+                .expect("write should not fail");
+            assert_eq!(result, None);
+            writer.flush().await.expect("flush should not fail");
+            writer.close();
+
+            assert_eq!(ledger.get_total_buffer_size(), (bytes_written * 2) as u64);
+            assert_reader_writer_v2_file_positions!(ledger, 0, 2);
+
+            // The reader should read the first record and then the third record, skipping over the
+            // dropped data file, and the dropped record should not be considered lost.
+            let first_record_read = read_next_some(&mut reader).await;
+            assert_eq!(first_record_read, record);
+            acknowledge(first_record_read).await;
+
+            let second_record_read = read_next_some(&mut reader).await;
+            assert_eq!(second_record_read, record);
+            acknowledge(second_record_read).await;
+
+            let final_record_read = read_next(&mut reader).await;
+            assert_eq!(final_record_read, None);
+            assert_buffer_is_empty!(ledger);
+            assert_eq!(ledger.consume_dropped_oldest_events(u64::MAX), 0);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn writer_does_not_drop_data_file_being_read() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Create our buffer such that each record fills an entire data file, and the buffer can
+            // only hold one of them at a time.
+            let record = SizedRecord::new(64);
+            let max_data_file_size = get_minimum_data_file_size_for_record_payload(&record);
+            let (mut writer, _reader, ledger) =
+                create_buffer_v2_with_data_file_count_limit(data_dir, max_data_file_size, 2).await;
+
+            let bytes_written = writer
+                .write_record(record.clone())
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            assert_buffer_size!(ledger, 1, bytes_written);
+
+            // The only data file with records in it is the one the reader is positioned at, so
+            // there's nothing we can drop, and we should get our record back.
+            let result = writer
+                .write_record_dropping_oldest(record.clone())
+                .await
+                .expect("write should not fail");
+            assert_eq!(result, Some(record));
+            assert_buffer_size!(ledger, 1, bytes_written);
+        }
+    })
+    .await;
+}
@@ -5,6 +5,7 @@ use std::{
     io::{self, ErrorKind},
     marker::PhantomData,
     num::NonZeroUsize,
+    path::Path,
     sync::Arc,
 };
 
@@ -24,9 +25,11 @@ use snafu::{ResultExt, Snafu};
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use super::{
-    common::{create_crc32c_hasher, DiskBufferConfig},
+    common::{create_crc32c_hasher, DiskBufferConfig, MAX_FILE_ID},
     io::Filesystem,
     ledger::Ledger,
+    payload::{PayloadCodec, PayloadError, PAYLOAD_FLAGS_MASK},
+    reader::{ReaderError, RecordReader},
     record::{validate_record_archive, Record, RecordStatus},
 };
 use crate::{
@@ -95,6 +98,10 @@ where
         source: <T as Encodable>::EncodeError,
     },
 
+    /// The encoded record could not be compressed or encrypted.
+    #[snafu(display("failed to encode record payload: {}", source))]
+    FailedToEncodePayload { source: PayloadError },
+
     /// The writer failed to serialize the record.
     ///
     /// As records are encoded and then wrapped in a container which carries metadata about the size
@@ -360,9 +367,11 @@ impl<W: fmt::Debug> fmt::Debug for TrackingBufWriter<W> {
 pub(super) struct RecordWriter<W, T> {
     writer: TrackingBufWriter<W>,
     encode_buf: Vec<u8>,
+    payload_buf: Vec<u8>,
     ser_buf: AlignedVec,
     ser_scratch: AlignedVec,
     checksummer: Hasher,
+    payload_codec: PayloadCodec,
     max_record_size: usize,
     current_data_file_size: u64,
     max_data_file_size: u64,
@@ -378,12 +387,15 @@ where
     ///
     /// Internally, the writer is wrapped in a [`BufWriter`], so callers should not pass in an
     /// already buffered writer.
+    ///
+    /// Record payloads are encoded with the given [`PayloadCodec`] before being written.
     pub fn new(
         writer: W,
         current_data_file_size: u64,
         write_buffer_size: usize,
         max_data_file_size: u64,
         max_record_size: usize,
+        payload_codec: PayloadCodec,
     ) -> Self {
         // These should also be getting checked at a higher level, but we're double-checking them here to be absolutely sure.
         let max_record_size_converted = u64::try_from(max_record_size)
@@ -410,9 +422,11 @@ where
         Self {
             writer: TrackingBufWriter::with_capacity(write_buffer_size, writer),
             encode_buf: Vec::with_capacity(16_384),
+            payload_buf: Vec::new(),
             ser_buf: AlignedVec::with_capacity(16_384),
             ser_scratch: AlignedVec::with_capacity(16_384),
             checksummer: create_crc32c_hasher(),
+            payload_codec,
             max_record_size,
             current_data_file_size,
             max_data_file_size,
@@ -480,9 +494,29 @@ where
             });
         }
 
+        // Compress and/or encrypt the encoded record, if configured to.  As this can make the
+        // payload slightly larger, such as when encrypting it, we have to check the size again.
         let metadata = T::get_metadata().into_u32();
-        let wrapped_record =
-            Record::with_checksum(id, metadata, &self.encode_buf, &self.checksummer);
+        debug_assert_eq!(
+            metadata & PAYLOAD_FLAGS_MASK,
+            0,
+            "record metadata must not use the bits reserved for payload flags"
+        );
+        let (metadata, payload) = match self
+            .payload_codec
+            .encode(id, metadata, &self.encode_buf, &mut self.payload_buf)
+            .context(FailedToEncodePayloadSnafu)?
+        {
+            Some(metadata) => (metadata, &self.payload_buf[..]),
+            None => (metadata, &self.encode_buf[..]),
+        };
+        if payload.len() > self.max_record_size {
+            return Err(WriterError::RecordTooLarge {
+                limit: self.max_record_size,
+            });
+        }
+
+        let wrapped_record = Record::with_checksum(id, metadata, payload, &self.checksummer);
 
         // Push 8 dummy bytes where our length delimiter will sit.  We'll fix this up after
         // serialization.  Notably, `AlignedSerializer` will report the serializer position as
@@ -882,63 +916,78 @@ where
                 // next writer record ID should be.
                 let record = try_as_record_archive(data_file_mmap.as_ref())
                     .expect("record was already validated");
-                let item = decode_record_payload::<T>(record).map_err(|e| {
-                    WriterError::FailedToValidate {
-                        reason: e.to_string(),
-                    }
-                })?;
-
-                // Since we have a valid record, checksum and all, see if the writer record ID
-                // in the ledger lines up with the record ID we have here.  Specifically, the record
-                // ID plus the number of events in the record should be the next record ID that gets used.
-                let ledger_next = self.ledger.state().get_next_writer_record_id();
-                let record_events =
-                    u64::try_from(item.event_count()).expect("event count should never exceed u64");
-                let record_next = last_record_id.wrapping_add(record_events);
-
-                match ledger_next.cmp(&record_next) {
-                    Ordering::Equal => {
-                        // We're exactly where the ledger thinks we should be, so nothing to do.
-                        debug!(
-                            ledger_next,
-                            last_record_id,
-                            record_events,
-                            "Synchronized with ledger. Writer ready."
-                        );
-                        false
-                    }
-                    Ordering::Greater => {
-                        // Our last write is behind where the ledger thinks we should be, so we
-                        // likely missed flushing some records, or partially flushed the data file.
-                        // Better roll over to be safe.
+                let payload_codec = &self.config.payload_codec;
+                match decode_record_payload::<T>(record, payload_codec) {
+                    // The record was written with a different encryption key, or with compression
+                    // or encryption that we can't undo, so the reader won't be able to read any of
+                    // the records in this data file either.  Like a corrupted record, all we can
+                    // do is skip to the next data file.
+                    Err(ReaderError::Payload { source }) => {
                         error!(
-                            ledger_next, last_record_id, record_events,
-                            "Last record written to data file is behind expected position. Events have likely been lost.");
+                            %source,
+                            "Last written record could not be decoded. Events in the current data file will be lost."
+                        );
                         true
                     }
-                    Ordering::Less => {
-                        // We're actually _ahead_ of the ledger, which is to say we wrote a valid
-                        // record to the data file, but never incremented our "writer next record
-                        // ID" field.  Given that record IDs are monotonic, it's safe to forward
-                        // ourselves to make the "writer next record ID" in the ledger match the
-                        // reality of the data file.  If there were somehow gaps in the data file,
-                        // the reader will detect it, and this way, we avoid duplicate record IDs.
-                        debug!(
-                            ledger_next,
-                            last_record_id,
-                            record_events,
-                            new_ledger_next = record_next,
-                            "Ledger desynchronized from data files. Fast forwarding ledger state."
-                        );
-                        let ledger_record_delta = record_next - ledger_next;
-                        let next_record_id = self
-                            .ledger
-                            .state()
-                            .increment_next_writer_record_id(ledger_record_delta);
-                        self.next_record_id = next_record_id;
-                        self.unflushed_events = 0;
-
-                        false
+                    Err(e) => {
+                        return Err(WriterError::FailedToValidate {
+                            reason: e.to_string(),
+                        })
+                    }
+                    Ok(item) => {
+                        // Since we have a valid record, checksum and all, see if the writer record ID
+                        // in the ledger lines up with the record ID we have here.  Specifically, the record
+                        // ID plus the number of events in the record should be the next record ID that gets used.
+                        let ledger_next = self.ledger.state().get_next_writer_record_id();
+                        let record_events = u64::try_from(item.event_count())
+                            .expect("event count should never exceed u64");
+                        let record_next = last_record_id.wrapping_add(record_events);
+
+                        match ledger_next.cmp(&record_next) {
+                            Ordering::Equal => {
+                                // We're exactly where the ledger thinks we should be, so nothing to do.
+                                debug!(
+                                    ledger_next,
+                                    last_record_id,
+                                    record_events,
+                                    "Synchronized with ledger. Writer ready."
+                                );
+                                false
+                            }
+                            Ordering::Greater => {
+                                // Our last write is behind where the ledger thinks we should be, so we
+                                // likely missed flushing some records, or partially flushed the data file.
+                                // Better roll over to be safe.
+                                error!(
+                                    ledger_next, last_record_id, record_events,
+                                    "Last record written to data file is behind expected position. Events have likely been lost.");
+                                true
+                            }
+                            Ordering::Less => {
+                                // We're actually _ahead_ of the ledger, which is to say we wrote a valid
+                                // record to the data file, but never incremented our "writer next record
+                                // ID" field.  Given that record IDs are monotonic, it's safe to forward
+                                // ourselves to make the "writer next record ID" in the ledger match the
+                                // reality of the data file.  If there were somehow gaps in the data file,
+                                // the reader will detect it, and this way, we avoid duplicate record IDs.
+                                debug!(
+                                    ledger_next,
+                                    last_record_id,
+                                    record_events,
+                                    new_ledger_next = record_next,
+                                    "Ledger desynchronized from data files. Fast forwarding ledger state."
+                                );
+                                let ledger_record_delta = record_next - ledger_next;
+                                let next_record_id = self
+                                    .ledger
+                                    .state()
+                                    .increment_next_writer_record_id(ledger_record_delta);
+                                self.next_record_id = next_record_id;
+                                self.unflushed_events = 0;
+
+                                false
+                            }
+                        }
                     }
                 }
             }
@@ -1126,6 +1175,7 @@ where
                     self.config.write_buffer_size,
                     self.config.max_data_file_size,
                     self.config.max_record_size,
+                    self.config.payload_codec.clone(),
                 ));
                 self.data_file_size = data_file_size;
 
@@ -1300,6 +1350,129 @@ where
         }
     }
 
+    /// Writes a record, dropping the oldest data files in the buffer to make room for it if necessary.
+    ///
+    /// Only data files that the reader has not yet started reading can be dropped.  If the buffer is
+    /// full, and there are no such data files left to drop, the original record will be returned.
+    /// Otherwise, `None` will be returned.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurred while writing the record, or while dropping a data file, an error
+    /// variant will be returned describing the error.
+    #[instrument(skip_all, level = "debug")]
+    pub async fn write_record_dropping_oldest(
+        &mut self,
+        mut record: T,
+    ) -> Result<Option<T>, WriterError<T>> {
+        loop {
+            match self.try_write_record_inner(record).await? {
+                Ok(_) => return Ok(None),
+                Err(old_record) => {
+                    record = old_record;
+                    if !self.drop_oldest_data_file().await.context(IoSnafu)? {
+                        return Ok(Some(record));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Drops the oldest data file that the reader has not yet started reading.
+    ///
+    /// Returns `true` if a data file was dropped, and `false` if there was no data file to drop.
+    #[instrument(skip(self), level = "debug")]
+    async fn drop_oldest_data_file(&mut self) -> io::Result<bool> {
+        // Flush first, so that all of our writes so far are accounted for in the buffer size, and
+        // the writer file ID reflects any data file we just rolled over from.
+        self.flush().await?;
+
+        loop {
+            let (reader_file_id, writer_file_id) = self.ledger.get_current_reader_writer_file_id();
+            if reader_file_id == writer_file_id {
+                return Ok(false);
+            }
+
+            // Find the oldest data file between the reader and the writer. Some of them may already
+            // be gone if we've previously dropped them but the reader hasn't caught up yet.
+            let mut file_id = reader_file_id;
+            let (data_file_path, data_file) = loop {
+                file_id = (file_id + 1) % MAX_FILE_ID;
+                if file_id == writer_file_id {
+                    return Ok(false);
+                }
+
+                let data_file_path = self.ledger.get_data_file_path(file_id);
+                match self
+                    .ledger
+                    .filesystem()
+                    .open_file_readable(&data_file_path)
+                    .await
+                {
+                    Ok(data_file) => break (data_file_path, data_file),
+                    Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                    Err(e) => return Err(e),
+                }
+            };
+
+            // Claim the data file, and then make sure the reader didn't move on while we were
+            // looking for it, as it might have opened the data file already. If it did, we just
+            // start over.
+            self.ledger.begin_dropping_data_file(file_id);
+            if self.ledger.get_current_reader_file_id() != reader_file_id {
+                self.ledger.finish_dropping_data_file();
+                continue;
+            }
+
+            let result = self
+                .delete_dropped_data_file(&data_file_path, data_file)
+                .await;
+            self.ledger.finish_dropping_data_file();
+            result?;
+
+            return Ok(true);
+        }
+    }
+
+    async fn delete_dropped_data_file(
+        &self,
+        data_file_path: &Path,
+        data_file: FS::File,
+    ) -> io::Result<()> {
+        // Count the events in the data file so that we can report them as dropped. We can only
+        // count the events in records we're able to read, so events in records after any invalid
+        // record are left for the reader to report as lost, which is what would have happened if it
+        // had read the data file itself.
+        let data_file_size = data_file.metadata().await?.len();
+        let mut record_reader =
+            RecordReader::<_, T>::new(data_file, self.config.payload_codec.clone());
+        let mut event_count = 0;
+        while let Ok(Some(token)) = record_reader.try_next_record(true).await {
+            match record_reader.read_record(token) {
+                Ok(record) => event_count += record.event_count() as u64,
+                Err(_) => break,
+            }
+        }
+        drop(record_reader);
+
+        self.ledger.filesystem().delete_file(data_file_path).await?;
+        self.ledger
+            .track_dropped_oldest(event_count, data_file_size);
+        self.ledger.notify_reader_waiters();
+
+        debug!(
+            data_file_path = data_file_path.to_string_lossy().as_ref(),
+            event_count, data_file_size, "Dropped oldest data file to make room for newer records."
+        );
+
+        Ok(())
+    }
+
+    /// Returns `true` if there are no records in the buffer that have yet to be acknowledged.
+    pub fn is_empty(&self) -> bool {
+        self.ledger.get_total_buffer_size() + self.unflushed_bytes == 0
+    }
+
     #[instrument(skip(self), level = "debug")]
     async fn flush_inner(&mut self, force_full_flush: bool) -> io::Result<()> {
         // We always flush the `BufWriter` when this is called, but we don't always flush to disk or
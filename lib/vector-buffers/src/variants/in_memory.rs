@@ -1,4 +1,4 @@
-use std::{error::Error, num::NonZeroUsize};
+use std::{error::Error, num::NonZeroUsize, time::Duration};
 
 use async_trait::async_trait;
 
@@ -6,18 +6,29 @@ use crate::{
     buffer_usage_data::BufferUsageHandle,
     topology::{
         builder::IntoBuffer,
-        channel::{limited, ReceiverAdapter, SenderAdapter},
+        channel::{limited, limited_with_max_age, ReceiverAdapter, SenderAdapter},
     },
     Bufferable,
 };
 
 pub struct MemoryBuffer {
     capacity: NonZeroUsize,
+    max_age: Option<Duration>,
 }
 
 impl MemoryBuffer {
     pub fn new(capacity: NonZeroUsize) -> Self {
-        MemoryBuffer { capacity }
+        MemoryBuffer {
+            capacity,
+            max_age: None,
+        }
+    }
+
+    /// Discards items that have been in the buffer for longer than `max_age` when they're read.
+    #[must_use]
+    pub fn with_max_age(mut self, max_age: Option<Duration>) -> Self {
+        self.max_age = max_age;
+        self
     }
 }
 
@@ -32,7 +43,10 @@ where
     ) -> Result<(SenderAdapter<T>, ReceiverAdapter<T>), Box<dyn Error + Send + Sync>> {
         usage_handle.set_buffer_limits(None, Some(self.capacity.get()));
 
-        let (tx, rx) = limited(self.capacity.get());
+        let (tx, rx) = match self.max_age {
+            None => limited(self.capacity.get()),
+            Some(max_age) => limited_with_max_age(self.capacity.get(), max_age, usage_handle),
+        };
         Ok((tx.into(), rx.into()))
     }
 }
@@ -1,5 +1,8 @@
 pub(crate) mod disk_v2;
-pub use disk_v2::DiskV2Buffer;
+pub use disk_v2::{
+    DiskBufferInspector, DiskBufferSummary, DiskV2Buffer, EncryptionKey, InspectError,
+    PayloadCodec, ENCRYPTION_KEY_LEN,
+};
 
 pub(crate) mod in_memory;
 pub use in_memory::MemoryBuffer;
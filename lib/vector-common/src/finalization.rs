@@ -3,7 +3,14 @@
 //! as it flows through transforms, being duplicated and merged, and
 //! then report its status when the last copy is delivered or dropped.
 
-use std::{cmp, future::Future, mem, pin::Pin, sync::Arc, task::Poll};
+use std::{
+    cmp,
+    future::Future,
+    mem,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::Poll,
+};
 
 use crossbeam_utils::atomic::AtomicCell;
 use futures::future::FutureExt;
@@ -86,6 +93,17 @@ impl EventFinalizers {
         }
     }
 
+    /// Updates the status of all event finalizers in the collection, recording the details of the
+    /// failure on their underlying batches.
+    ///
+    /// Only the first failure recorded for a batch is kept.
+    pub fn update_status_with_failure(&self, status: EventStatus, failure: &DeliveryFailure) {
+        for finalizer in &self.0 {
+            finalizer.update_status(status);
+            finalizer.batch.record_failure(failure);
+        }
+    }
+
     /// Consumes all event finalizers and updates their underlying batches immediately.
     pub fn update_sources(&mut self) {
         let finalizers = mem::take(&mut self.0);
@@ -157,10 +175,25 @@ impl Drop for EventFinalizer {
     }
 }
 
+/// The details of a failed attempt at delivering a batch of events.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DeliveryFailure {
+    /// The number of times that delivery was attempted, including the initial attempt.
+    pub attempts: usize,
+
+    /// The status code returned by the destination, for protocols that have one, such as HTTP.
+    pub status_code: Option<u16>,
+
+    /// The error that caused the last attempt to fail, if any.
+    pub error: Option<String>,
+}
+
+type FailureSlot = Arc<Mutex<Option<DeliveryFailure>>>;
+
 /// A convenience newtype wrapper for the one-shot receiver for an
 /// individual batch status.
 #[pin_project::pin_project]
-pub struct BatchStatusReceiver(oneshot::Receiver<BatchStatus>);
+pub struct BatchStatusReceiver(oneshot::Receiver<BatchStatus>, FailureSlot);
 
 impl Future for BatchStatusReceiver {
     type Output = BatchStatus;
@@ -186,6 +219,14 @@ impl BatchStatusReceiver {
     pub fn try_recv(&mut self) -> Result<BatchStatus, oneshot::error::TryRecvError> {
         self.0.try_recv()
     }
+
+    /// Returns the details of the first delivery failure recorded for the batch, if any.
+    ///
+    /// Failures may be recorded until the batch status has been received.
+    #[must_use]
+    pub fn failure(&self) -> Option<DeliveryFailure> {
+        self.1.lock().expect("poisoned lock").clone()
+    }
 }
 
 /// A batch notifier contains the status of the current batch along with
@@ -199,11 +240,16 @@ impl BatchNotifier {
     #[must_use]
     pub fn new_with_receiver() -> (Self, BatchStatusReceiver) {
         let (sender, receiver) = oneshot::channel();
+        let failure = FailureSlot::default();
         let notifier = OwnedBatchNotifier {
             status: AtomicCell::new(BatchStatus::Delivered),
+            failure: Arc::clone(&failure),
             notifier: Some(sender),
         };
-        (Self(Arc::new(notifier)), BatchStatusReceiver(receiver))
+        (
+            Self(Arc::new(notifier)),
+            BatchStatusReceiver(receiver, failure),
+        )
     }
 
     /// Optionally creates a new `BatchNotifier` along with the receiver used to await its finalization status.
@@ -250,12 +296,21 @@ impl BatchNotifier {
                 .unwrap_or_else(|_| unreachable!());
         }
     }
+
+    /// Records the details of a delivery failure, unless one was already recorded.
+    fn record_failure(&self, failure: &DeliveryFailure) {
+        let mut slot = self.0.failure.lock().expect("poisoned lock");
+        if slot.is_none() {
+            *slot = Some(failure.clone());
+        }
+    }
 }
 
 /// The non-shared data underlying the shared `BatchNotifier`
 #[derive(Debug)]
 pub struct OwnedBatchNotifier {
     status: AtomicCell<BatchStatus>,
+    failure: FailureSlot,
     notifier: Option<oneshot::Sender<BatchStatus>>,
 }
 
@@ -507,6 +562,36 @@ mod tests {
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
     }
 
+    #[test]
+    fn records_first_failure() {
+        let (fin, mut receiver) = make_finalizer();
+        let failure = DeliveryFailure {
+            attempts: 3,
+            status_code: Some(503),
+            error: None,
+        };
+        fin.update_status_with_failure(EventStatus::Errored, &failure);
+        fin.update_status_with_failure(
+            EventStatus::Rejected,
+            &DeliveryFailure {
+                attempts: 1,
+                ..Default::default()
+            },
+        );
+        drop(fin);
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+        assert_eq!(receiver.failure(), Some(failure));
+    }
+
+    #[test]
+    fn no_failure_when_delivered() {
+        let (fin, mut receiver) = make_finalizer();
+        fin.update_status(EventStatus::Delivered);
+        drop(fin);
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+        assert_eq!(receiver.failure(), None);
+    }
+
     fn make_finalizer() -> (EventFinalizers, BatchStatusReceiver) {
         let (batch, receiver) = BatchNotifier::new_with_receiver();
         let finalizer = EventFinalizers::new(EventFinalizer::new(batch));
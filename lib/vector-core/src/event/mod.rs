@@ -9,8 +9,8 @@ use crate::{config::OutputId, ByteSizeOf};
 pub use array::{into_event_stream, EventArray, EventContainer, LogArray, MetricArray, TraceArray};
 pub use estimated_json_encoded_size_of::EstimatedJsonEncodedSizeOf;
 pub use finalization::{
-    BatchNotifier, BatchStatus, BatchStatusReceiver, EventFinalizer, EventFinalizers, EventStatus,
-    Finalizable,
+    BatchNotifier, BatchStatus, BatchStatusReceiver, DeliveryFailure, EventFinalizer,
+    EventFinalizers, EventStatus, Finalizable,
 };
 pub use log_event::LogEvent;
 pub use metadata::{EventMetadata, WithMetadata};
@@ -127,6 +127,14 @@ impl Controller {
         self.recorder.with_registry(Registry::clear);
     }
 
+    /// Reset the counters whose key matches the given predicate back to zero.
+    ///
+    /// Returns the number of counters that were reset.
+    pub fn reset_counters(&self, matches: impl Fn(&Key) -> bool) -> usize {
+        self.recorder
+            .with_registry(|registry| registry.reset_counters(&matches))
+    }
+
     /// Get a handle to the globally registered controller, if it's initialized.
     ///
     /// # Errors
@@ -335,4 +343,38 @@ mod tests {
             value => panic!("Invalid metric value {value:?}"),
         }
     }
+
+    #[test]
+    fn resets_matching_counters() {
+        let controller = init_metrics();
+
+        metrics::counter!("test9", 3, "component_id" => "a");
+        metrics::counter!("test9", 5, "component_id" => "b");
+        let reset = controller.reset_counters(|key| {
+            key.labels()
+                .any(|label| label.key() == "component_id" && label.value() == "a")
+        });
+        assert_eq!(reset, 1);
+
+        for metric in controller.capture_metrics() {
+            if metric.name() != "test9" {
+                continue;
+            }
+            let expected = match metric.tag_value("component_id").as_deref() {
+                Some("a") => 0.0,
+                Some("b") => 5.0,
+                tag => panic!("Unexpected component_id tag {tag:?}"),
+            };
+            assert_eq!(metric.value(), &MetricValue::Counter { value: expected });
+        }
+
+        // Counters keep counting from zero after being reset.
+        metrics::counter!("test9", 2, "component_id" => "a");
+        let metric = controller
+            .capture_metrics()
+            .into_iter()
+            .find(|metric| metric.tag_matches("component_id", "a"))
+            .expect("Test metric is not present");
+        assert_eq!(metric.value(), &MetricValue::Counter { value: 2.0 });
+    }
 }
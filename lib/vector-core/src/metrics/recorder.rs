@@ -31,6 +31,17 @@ impl Registry {
         self.registry.clear();
     }
 
+    pub(super) fn reset_counters(&self, matches: &dyn Fn(&Key) -> bool) -> usize {
+        let mut reset = 0;
+        for (key, counter) in self.registry.get_counter_handles() {
+            if matches(&key) {
+                counter.get_inner().store(0, Ordering::Relaxed);
+                reset += 1;
+            }
+        }
+        reset
+    }
+
     pub(super) fn set_expiry(&self, timeout: Option<Duration>) {
         let recency = timeout.map(|_| Recency::new(Clock::new(), MetricKindMask::ALL, timeout));
         *(self.recency.write()).expect("Failed to acquire write lock on recency map") = recency;
@@ -1,4 +1,10 @@
-use std::{collections::VecDeque, fmt, future::poll_fn, task::Poll};
+use std::{
+    cell::Cell,
+    collections::VecDeque,
+    fmt,
+    future::{poll_fn, Future},
+    task::Poll,
+};
 
 use futures::{poll, FutureExt, Stream, StreamExt, TryFutureExt};
 use tokio::{pin, select};
@@ -12,10 +18,45 @@ use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
 
 use super::FuturesUnorderedCount;
 use crate::{
-    event::{EventFinalizers, EventStatus, Finalizable},
+    event::{DeliveryFailure, EventFinalizers, EventStatus, Finalizable},
     internal_event::emit,
 };
 
+tokio::task_local! {
+    /// The number of times that the request of the current service call has been attempted.
+    static ATTEMPTS: Cell<usize>;
+}
+
+/// Records that the request of the current service call is about to be retried.
+///
+/// Retry policies call this so that the number of attempts can be reported when a request
+/// ultimately fails. Calls made outside of a service call driven by [`Driver`] are ignored.
+pub fn record_retry() {
+    _ = ATTEMPTS.try_with(|attempts| attempts.set(attempts.get() + 1));
+}
+
+/// Returns the number of times that the request of the current service call has been attempted,
+/// including the attempt in progress.
+///
+/// Calls made outside of a service call driven by [`Driver`] always return 1.
+pub fn current_attempt() -> usize {
+    ATTEMPTS.try_with(Cell::get).unwrap_or(1)
+}
+
+/// Runs the given service call, keeping track of the number of times its request is attempted,
+/// and returns its output along with that number.
+///
+/// [`Driver`] does this for the calls that it makes, this is for sinks that call their service
+/// by other means.
+pub async fn track_attempts<F: Future>(call: F) -> (F::Output, usize) {
+    ATTEMPTS
+        .scope(Cell::new(1), async move {
+            let output = call.await;
+            (output, ATTEMPTS.with(Cell::get))
+        })
+        .await
+}
+
 pub trait DriverResponse {
     fn event_status(&self) -> EventStatus;
     fn events_sent(&self) -> CountByteSize;
@@ -26,6 +67,14 @@ pub trait DriverResponse {
     fn bytes_sent(&self) -> Option<usize> {
         None
     }
+
+    /// Return the status code sent by the destination in this response, for protocols that have
+    /// one.
+    ///
+    /// This is reported along with the events of requests that failed to be delivered.
+    fn status_code(&self) -> Option<u16> {
+        None
+    }
 }
 
 /// Drives the interaction between a stream of items and a service which processes them
@@ -72,7 +121,7 @@ where
     St: Stream,
     St::Item: Finalizable + MetaDescriptive,
     Svc: Service<St::Item>,
-    Svc::Error: fmt::Debug + 'static,
+    Svc::Error: fmt::Debug + fmt::Display + 'static,
     Svc::Future: Send + 'static,
     Svc::Response: DriverResponse,
 {
@@ -175,11 +224,13 @@ where
                             .map(move |result| Self::handle_response(
                                 result,
                                 request_id,
+                                ATTEMPTS.with(Cell::get),
                                 finalizers,
                                 &metadata,
                                 &bytes_sent,
                                 &events_sent,
-                            ))
+                            ));
+                        let fut = ATTEMPTS.scope(Cell::new(1), fut)
                             .instrument(info_span!("request", request_id).or_current());
 
                         in_flight.push(fut);
@@ -201,6 +252,7 @@ where
     fn handle_response(
         result: Result<Svc::Response, Svc::Error>,
         request_id: usize,
+        attempts: usize,
         finalizers: EventFinalizers,
         metadata: &RequestMetadata,
         bytes_sent: &Option<Registered<BytesSent>>,
@@ -208,13 +260,28 @@ where
     ) {
         match result {
             Err(error) => {
+                let failure = DeliveryFailure {
+                    attempts,
+                    status_code: None,
+                    error: Some(error.to_string()),
+                };
                 Self::emit_call_error(Some(error), request_id, metadata.event_count());
-                finalizers.update_status(EventStatus::Rejected);
+                finalizers.update_status_with_failure(EventStatus::Rejected, &failure);
             }
             Ok(response) => {
                 trace!(message = "Service call succeeded.", request_id);
-                finalizers.update_status(response.event_status());
-                if response.event_status() == EventStatus::Delivered {
+                let status = response.event_status();
+                if matches!(status, EventStatus::Errored | EventStatus::Rejected) {
+                    let failure = DeliveryFailure {
+                        attempts,
+                        status_code: response.status_code(),
+                        error: None,
+                    };
+                    finalizers.update_status_with_failure(status, &failure);
+                } else {
+                    finalizers.update_status(status);
+                }
+                if status == EventStatus::Delivered {
                     if let Some(bytes_sent) = bytes_sent {
                         if let Some(byte_size) = response.bytes_sent() {
                             bytes_sent.emit(ByteSize(byte_size));
@@ -222,9 +289,8 @@ where
                     }
                     events_sent.emit(response.events_sent());
                 // This condition occurs specifically when the `HttpBatchService::call()` is called *within* the `Service::call()`
-                } else if response.event_status() == EventStatus::Rejected {
+                } else if status == EventStatus::Rejected {
                     Self::emit_call_error(None, request_id, metadata.event_count());
-                    finalizers.update_status(EventStatus::Rejected);
                 }
             }
         };
@@ -245,6 +311,7 @@ where
 #[cfg(test)]
 mod tests {
     use std::{
+        convert::Infallible,
         future::Future,
         pin::Pin,
         sync::{atomic::AtomicUsize, atomic::Ordering, Arc},
@@ -262,13 +329,15 @@ mod tests {
     use tokio_util::sync::PollSemaphore;
     use tower::Service;
     use vector_common::{
-        finalization::{BatchNotifier, EventFinalizer, EventFinalizers, EventStatus, Finalizable},
+        finalization::{
+            BatchNotifier, BatchStatus, EventFinalizer, EventFinalizers, EventStatus, Finalizable,
+        },
         json_size::JsonSize,
         request_metadata::RequestMetadata,
     };
     use vector_common::{internal_event::CountByteSize, request_metadata::MetaDescriptive};
 
-    use super::{Driver, DriverResponse};
+    use super::{record_retry, Driver, DriverResponse};
 
     type Counter = Arc<AtomicUsize>;
 
@@ -363,7 +432,7 @@ mod tests {
 
     impl Service<DelayRequest> for DelayService {
         type Response = DelayResponse;
-        type Error = ();
+        type Error = Infallible;
         type Future =
             Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + Sync>>;
 
@@ -439,4 +508,28 @@ mod tests {
         tokio::task::yield_now().await;
         assert_eq!(input_total, counter.load(Ordering::SeqCst));
     }
+
+    #[tokio::test]
+    async fn driver_records_failures() {
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let request = DelayRequest(
+            1,
+            EventFinalizers::new(EventFinalizer::new(batch)),
+            RequestMetadata::default(),
+        );
+        let service = tower::service_fn(|_: DelayRequest| async {
+            record_retry();
+            record_retry();
+            Err::<DelayResponse, _>("connection refused")
+        });
+        let driver = Driver::new(stream::iter([request]), service);
+
+        assert_eq!(driver.run().await, Ok(()));
+        assert_eq!((&mut receiver).await, BatchStatus::Rejected);
+
+        let failure = receiver.failure().expect("failure should be recorded");
+        assert_eq!(failure.attempts, 3);
+        assert_eq!(failure.status_code, None);
+        assert_eq!(failure.error.as_deref(), Some("connection refused"));
+    }
 }
@@ -5,6 +5,6 @@ mod futures_unordered_count;
 mod partitioned_batcher;
 
 pub use concurrent_map::ConcurrentMap;
-pub use driver::{Driver, DriverResponse};
+pub use driver::{current_attempt, record_retry, track_attempts, Driver, DriverResponse};
 pub(self) use futures_unordered_count::FuturesUnorderedCount;
 pub use partitioned_batcher::{BatcherSettings, ExpirationQueue, PartitionedBatcher};
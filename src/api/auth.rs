@@ -0,0 +1,121 @@
+use vector_common::sensitive_string::SensitiveString;
+use warp::{http::StatusCode, reject::Reject, Filter, Rejection, Reply};
+
+use crate::config;
+
+/// Checks the `Authorization` header of API requests against the bearer token set in
+/// `api.auth.token`, if any.
+#[derive(Clone, Debug, Default)]
+pub struct Authenticator {
+    token: Option<SensitiveString>,
+}
+
+impl Authenticator {
+    pub fn new(auth: Option<&config::api::Auth>) -> Self {
+        Self {
+            token: auth.map(|auth| auth.token.clone()),
+        }
+    }
+
+    /// Checks whether or not the given `Authorization` header value carries the configured bearer
+    /// token. Always passes if no token is configured.
+    fn is_authorized(&self, authorization: Option<&str>) -> bool {
+        let Some(token) = &self.token else {
+            return true;
+        };
+
+        let token = token.inner().as_bytes();
+        authorization
+            .and_then(|value| {
+                let (scheme, presented) = value.split_once(' ')?;
+                scheme
+                    .eq_ignore_ascii_case("bearer")
+                    .then(|| presented.trim().as_bytes())
+            })
+            .map_or(false, |presented| {
+                presented.len() == token.len() && openssl::memcmp::eq(presented, token)
+            })
+    }
+}
+
+#[derive(Debug)]
+struct Unauthorized;
+
+impl Reject for Unauthorized {}
+
+/// Rejects requests that don't carry the configured bearer token. This applies to WebSocket
+/// upgrades as well, so subscriptions are covered by the same check as queries.
+pub fn authenticate(
+    authenticator: Authenticator,
+) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::header::optional::<String>("authorization")
+        .and_then(move |authorization: Option<String>| {
+            let authorized = authenticator.is_authorized(authorization.as_deref());
+            async move {
+                if authorized {
+                    Ok(())
+                } else {
+                    Err(warp::reject::custom(Unauthorized))
+                }
+            }
+        })
+        .untuple_one()
+}
+
+/// Replies with `401 Unauthorized` to requests rejected by [`authenticate`].
+pub async fn handle_rejection(rejection: Rejection) -> Result<impl Reply, Rejection> {
+    if rejection.find::<Unauthorized>().is_some() {
+        Ok(warp::reply::with_header(
+            StatusCode::UNAUTHORIZED,
+            "WWW-Authenticate",
+            "Bearer",
+        ))
+    } else {
+        Err(rejection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn authenticator(token: Option<&str>) -> Authenticator {
+        let auth = token.map(|token| config::api::Auth {
+            token: token.to_string().into(),
+        });
+        Authenticator::new(auth.as_ref())
+    }
+
+    #[test]
+    fn authorizes_without_token() {
+        assert!(authenticator(None).is_authorized(None));
+        assert!(authenticator(None).is_authorized(Some("Bearer anything")));
+    }
+
+    #[test]
+    fn authorizes_bearer_token() {
+        let authenticator = authenticator(Some("secret"));
+        assert!(authenticator.is_authorized(Some("Bearer secret")));
+        assert!(authenticator.is_authorized(Some("bearer secret")));
+        assert!(!authenticator.is_authorized(None));
+        assert!(!authenticator.is_authorized(Some("Bearer other")));
+        assert!(!authenticator.is_authorized(Some("Basic secret")));
+        assert!(!authenticator.is_authorized(Some("secret")));
+    }
+
+    #[tokio::test]
+    async fn rejects_requests_without_token() {
+        let route = authenticate(authenticator(Some("secret")))
+            .map(warp::reply)
+            .recover(handle_rejection);
+
+        let response = warp::test::request().reply(&route).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = warp::test::request()
+            .header("authorization", "Bearer secret")
+            .reply(&route)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
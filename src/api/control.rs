@@ -0,0 +1,115 @@
+use async_graphql::{Context, Error, Result};
+
+use super::schema::events::TapSampling;
+use crate::{
+    config,
+    signal::SignalTx,
+    topology::{SinkDrains, SourcePauses},
+};
+
+/// Handles used by GraphQL mutations to control the running instance.
+#[derive(Clone, Debug)]
+pub struct RuntimeControl {
+    allow_mutations: bool,
+    pub source_pauses: SourcePauses,
+    pub sink_drains: SinkDrains,
+    pub tap_sampling: TapSampling,
+    pub signal_tx: SignalTx,
+}
+
+impl RuntimeControl {
+    pub fn new(
+        api: &config::api::Options,
+        source_pauses: SourcePauses,
+        sink_drains: SinkDrains,
+        signal_tx: SignalTx,
+    ) -> Self {
+        Self {
+            allow_mutations: api.allow_mutations,
+            source_pauses,
+            sink_drains,
+            tap_sampling: TapSampling::default(),
+            signal_tx,
+        }
+    }
+}
+
+/// Checks that mutations are allowed, returning the handles used to control the running instance.
+///
+/// Requests are authenticated before reaching the schema, so this doesn't check credentials.
+pub fn authorize<'a>(ctx: &'a Context<'_>) -> Result<&'a RuntimeControl> {
+    ctx.data_opt::<RuntimeControl>()
+        .filter(|control| control.allow_mutations)
+        .ok_or_else(|| {
+            Error::new(
+                "Mutations are disabled. Set `api.allow_mutations` to `true` to enable them.",
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::{EmptySubscription, Object, Schema};
+    use tokio::sync::broadcast;
+
+    use super::*;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn noop(&self) -> bool {
+            true
+        }
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        async fn control(&self, ctx: &Context<'_>) -> Result<bool> {
+            authorize(ctx).map(|_| true)
+        }
+    }
+
+    async fn run_mutation(allow_mutations: bool) -> async_graphql::Response {
+        let api = config::api::Options {
+            allow_mutations,
+            ..Default::default()
+        };
+        let (signal_tx, _) = broadcast::channel(1);
+        let control = RuntimeControl::new(
+            &api,
+            SourcePauses::default(),
+            SinkDrains::default(),
+            signal_tx,
+        );
+
+        Schema::build(Query, Mutation, EmptySubscription)
+            .data(control)
+            .finish()
+            .execute("mutation { control }")
+            .await
+    }
+
+    #[tokio::test]
+    async fn rejects_mutations_when_disabled() {
+        let response = run_mutation(false).await;
+
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0]
+            .message
+            .starts_with("Mutations are disabled."));
+    }
+
+    #[tokio::test]
+    async fn allows_mutations_when_enabled() {
+        let response = run_mutation(true).await;
+
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({ "control": true })
+        );
+    }
+}
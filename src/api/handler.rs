@@ -6,6 +6,10 @@ use std::sync::{
 use serde_json::json;
 use warp::{reply::json, Rejection, Reply};
 
+use super::readiness::Readiness;
+#[cfg(feature = "sinks-prometheus")]
+use crate::{metrics::Controller, sinks::prometheus::encode_text};
+
 // Health handler, responds with '{ ok: true }' when running and '{ ok: false}'
 // when shutting down
 pub(super) async fn health(running: Arc<AtomicBool>) -> Result<impl Reply, Rejection> {
@@ -21,3 +25,47 @@ pub(super) async fn health(running: Arc<AtomicBool>) -> Result<impl Reply, Rejec
         ))
     }
 }
+
+// Readiness handler, responds with a report of the starting and unhealthy components and of the
+// buffers above the threshold, with a '503 Service Unavailable' status unless there are none.
+pub(super) async fn ready(
+    running: Arc<AtomicBool>,
+    readiness: Readiness,
+) -> Result<impl Reply, Rejection> {
+    let report = readiness.report(running.load(atomic::Ordering::Relaxed));
+    let status = if report.ok {
+        warp::http::StatusCode::OK
+    } else {
+        warp::http::StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    Ok(warp::reply::with_status(json(&report), status))
+}
+
+// Metrics handler, responds with the internal metrics in the Prometheus text exposition format.
+// Serving them requires the encoder of the `prometheus_exporter` sink, so the endpoint isn't
+// found in builds without it.
+pub(super) async fn metrics() -> Result<warp::reply::Response, Rejection> {
+    #[cfg(feature = "sinks-prometheus")]
+    {
+        // Prometheus records the time of the scrape, which is when the metrics were captured.
+        let metrics = Controller::get()
+            .map(Controller::capture_metrics)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|metric| metric.with_timestamp(None))
+            .collect::<Vec<_>>();
+
+        Ok(warp::reply::with_header(
+            encode_text(&metrics),
+            "Content-Type",
+            "text/plain; version=0.0.4",
+        )
+        .into_response())
+    }
+
+    #[cfg(not(feature = "sinks-prometheus"))]
+    {
+        Err(warp::reject::not_found())
+    }
+}
@@ -1,11 +1,15 @@
 #![allow(missing_docs)]
+mod auth;
+mod control;
 mod handler;
+mod readiness;
 mod schema;
 mod server;
 pub mod tap;
 #[cfg(all(test, feature = "vector-api-tests"))]
 mod tests;
 
+pub use control::RuntimeControl;
 pub use schema::build_schema;
 pub use server::Server;
 use tokio::sync::oneshot;
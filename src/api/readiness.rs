@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::{
+    config,
+    event::{Metric, MetricValue},
+    metrics::Controller,
+    topology::{ComponentHealth, HealthRegistry},
+};
+
+/// Determines whether or not the running instance is ready to receive traffic, from the health of
+/// its components and the utilization of its sinks' buffers.
+#[derive(Clone, Debug)]
+pub struct Readiness {
+    health: HealthRegistry,
+    buffer_threshold: f64,
+}
+
+/// Readiness report, served as the body of `/health/ready`.
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    pub ok: bool,
+    /// Components whose healthcheck hasn't completed yet.
+    pub starting: Vec<String>,
+    /// Components whose healthcheck failed.
+    pub unhealthy: Vec<String>,
+    /// Utilization of the buffers above the threshold, by component.
+    pub buffers: BTreeMap<String, f64>,
+}
+
+impl Readiness {
+    pub fn new(api: &config::api::Options, health: HealthRegistry) -> Self {
+        Self {
+            health,
+            buffer_threshold: api.readiness_buffer_threshold,
+        }
+    }
+
+    /// Reports on the readiness of the running instance. It isn't ready while shutting down.
+    pub fn report(&self, running: bool) -> Report {
+        let mut report = Report::default();
+
+        for (key, health) in self.health.snapshot() {
+            match health {
+                ComponentHealth::Starting => report.starting.push(key.to_string()),
+                ComponentHealth::Unhealthy => report.unhealthy.push(key.to_string()),
+                ComponentHealth::Healthy => {}
+            }
+        }
+        report.starting.sort();
+        report.unhealthy.sort();
+
+        let metrics = Controller::get()
+            .map(Controller::capture_metrics)
+            .unwrap_or_default();
+        report.buffers = buffer_utilization(&metrics)
+            .into_iter()
+            .filter(|(_, utilization)| *utilization > self.buffer_threshold)
+            .collect();
+
+        report.ok = running
+            && report.starting.is_empty()
+            && report.unhealthy.is_empty()
+            && report.buffers.is_empty();
+        report
+    }
+}
+
+#[derive(Default)]
+struct BufferUsage {
+    events: f64,
+    max_events: Option<f64>,
+    bytes: f64,
+    max_bytes: Option<f64>,
+}
+
+impl BufferUsage {
+    fn utilization(&self) -> f64 {
+        let events = self.max_events.map_or(0.0, |max| ratio(self.events, max));
+        let bytes = self.max_bytes.map_or(0.0, |max| ratio(self.bytes, max));
+        events.max(bytes)
+    }
+}
+
+fn ratio(used: f64, max: f64) -> f64 {
+    if max > 0.0 {
+        used / max
+    } else {
+        0.0
+    }
+}
+
+/// Computes the utilization of each component's buffer, from the `buffer_*` gauges. Buffers with
+/// several stages report the utilization of their fullest stage.
+fn buffer_utilization(metrics: &[Metric]) -> BTreeMap<String, f64> {
+    let mut stages = BTreeMap::<(String, String), BufferUsage>::new();
+
+    for metric in metrics {
+        let MetricValue::Gauge { value } = metric.value() else {
+            continue;
+        };
+        let (Some(component_id), Some(stage)) =
+            (metric.tag_value("component_id"), metric.tag_value("stage"))
+        else {
+            continue;
+        };
+
+        let usage = || stages.entry((component_id, stage)).or_default();
+        match metric.name() {
+            "buffer_events" => usage().events = *value,
+            "buffer_max_event_size" => usage().max_events = Some(*value),
+            "buffer_byte_size" => usage().bytes = *value,
+            "buffer_max_byte_size" => usage().max_bytes = Some(*value),
+            _ => {}
+        }
+    }
+
+    let mut components = BTreeMap::<String, f64>::new();
+    for ((component_id, _), usage) in stages {
+        let utilization = components.entry(component_id).or_default();
+        *utilization = utilization.max(usage.utilization());
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::metric_tags;
+
+    use super::*;
+    use crate::event::MetricKind;
+
+    fn gauge(name: &str, component_id: &str, stage: &str, value: f64) -> Metric {
+        Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value }).with_tags(Some(
+            metric_tags!("component_id" => component_id, "stage" => stage),
+        ))
+    }
+
+    #[test]
+    fn computes_buffer_utilization() {
+        let metrics = vec![
+            gauge("buffer_events", "memory", "0", 50.0),
+            gauge("buffer_max_event_size", "memory", "0", 100.0),
+            gauge("buffer_byte_size", "disk", "0", 900.0),
+            gauge("buffer_max_byte_size", "disk", "0", 1000.0),
+            gauge("buffer_events", "disk", "1", 10.0),
+            gauge("buffer_max_event_size", "disk", "1", 100.0),
+            gauge("buffer_events", "unbounded", "0", 1000.0),
+        ];
+
+        let utilization = buffer_utilization(&metrics);
+        assert_eq!(utilization.get("memory"), Some(&0.5));
+        assert_eq!(utilization.get("disk"), Some(&0.9));
+        assert_eq!(utilization.get("unbounded"), Some(&0.0));
+    }
+}
@@ -8,20 +8,23 @@ use std::{
     collections::{HashMap, HashSet},
 };
 
-use async_graphql::{Enum, InputObject, Interface, Object, Subscription};
+use async_graphql::{Context, Enum, Error, InputObject, Interface, Object, Result, Subscription};
 use once_cell::sync::Lazy;
 use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
 use vector_config::NamedComponent;
 use vector_core::internal_event::DEFAULT_OUTPUT;
 
 use crate::{
-    api::schema::{
-        components::state::component_by_component_key,
-        filter::{self, filter_items},
-        relay, sort,
+    api::{
+        control,
+        schema::{
+            components::state::component_by_component_key,
+            filter::{self, filter_items},
+            relay, sort,
+        },
     },
     config::{get_transform_output_ids, ComponentKey, Config},
-    filter_check,
+    filter_check, topology,
 };
 
 #[derive(Debug, Clone, Interface)]
@@ -42,6 +45,37 @@ pub enum ComponentKind {
     Sink,
 }
 
+/// Health of a source or sink
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum ComponentHealth {
+    /// The healthcheck hasn't completed yet
+    Starting,
+    /// The healthcheck passed, or is disabled
+    Healthy,
+    /// The healthcheck failed, or timed out
+    Unhealthy,
+}
+
+impl From<topology::ComponentHealth> for ComponentHealth {
+    fn from(health: topology::ComponentHealth) -> Self {
+        match health {
+            topology::ComponentHealth::Starting => Self::Starting,
+            topology::ComponentHealth::Healthy => Self::Healthy,
+            topology::ComponentHealth::Unhealthy => Self::Unhealthy,
+        }
+    }
+}
+
+/// Returns the health of the given component, as recorded by the running topology.
+pub(super) fn health_by_component_key(
+    ctx: &Context<'_>,
+    component_key: &ComponentKey,
+) -> Option<ComponentHealth> {
+    ctx.data_opt::<topology::HealthRegistry>()?
+        .get(component_key)
+        .map(Into::into)
+}
+
 impl Component {
     const fn get_component_key(&self) -> &ComponentKey {
         match self {
@@ -217,6 +251,65 @@ impl ComponentsQuery {
     }
 }
 
+#[derive(Default)]
+pub struct ComponentsMutation;
+
+#[Object]
+impl ComponentsMutation {
+    /// Pauses a source, which stops it from sending events downstream until it's resumed. Returns
+    /// whether or not the source was running beforehand
+    async fn pause_source(&self, ctx: &Context<'_>, component_id: String) -> Result<bool> {
+        set_source_paused(ctx, component_id, true).map(|was_paused| !was_paused)
+    }
+
+    /// Resumes a paused source. Returns whether or not the source was paused beforehand
+    async fn resume_source(&self, ctx: &Context<'_>, component_id: String) -> Result<bool> {
+        set_source_paused(ctx, component_id, false)
+    }
+
+    /// Drains a sink, which stops it from pulling events from its buffer, and stops it once it
+    /// has sent the events it already pulled. Returns the state of the sink beforehand
+    async fn drain_sink(&self, ctx: &Context<'_>, component_id: String) -> Result<sink::SinkState> {
+        let control = control::authorize(ctx)?;
+        let key = ComponentKey::from(component_id);
+        control
+            .sink_drains
+            .drain(&key)
+            .map(Into::into)
+            .ok_or_else(|| no_such_sink(&key))
+    }
+
+    /// Resumes a draining or stopped sink, which carries on from where it stopped in its buffer.
+    /// Returns the state of the sink beforehand
+    async fn resume_sink(
+        &self,
+        ctx: &Context<'_>,
+        component_id: String,
+    ) -> Result<sink::SinkState> {
+        let control = control::authorize(ctx)?;
+        let key = ComponentKey::from(component_id);
+        control
+            .sink_drains
+            .resume(&key)
+            .map(Into::into)
+            .ok_or_else(|| no_such_sink(&key))
+    }
+}
+
+/// Pauses, or resumes, a source, returning whether or not it was paused beforehand.
+fn set_source_paused(ctx: &Context<'_>, component_id: String, paused: bool) -> Result<bool> {
+    let control = control::authorize(ctx)?;
+    let key = ComponentKey::from(component_id);
+    control
+        .source_pauses
+        .set_paused(&key, paused)
+        .ok_or_else(|| Error::new(format!("No source with component ID \"{key}\".")))
+}
+
+fn no_such_sink(key: &ComponentKey) -> Error {
+    Error::new(format!("No sink with component ID \"{key}\"."))
+}
+
 #[derive(Clone, Debug)]
 enum ComponentChanged {
     Added(Component),
@@ -1,16 +1,17 @@
 use std::cmp;
 
-use async_graphql::{Enum, InputObject, Object};
+use async_graphql::{Context, Enum, InputObject, Object};
 
-use super::{source, state, transform, Component};
+use super::{health_by_component_key, source, state, transform, Component, ComponentHealth};
 use crate::{
+    api::control::RuntimeControl,
     api::schema::{
         filter,
         metrics::{self, IntoSinkMetrics},
         sort,
     },
     config::{ComponentKey, Inputs, OutputId},
-    filter_check,
+    filter_check, topology,
 };
 
 #[derive(Debug, Clone)]
@@ -20,6 +21,27 @@ pub struct Data {
     pub inputs: Inputs<OutputId>,
 }
 
+/// State of a sink, as controlled by the `drainSink` and `resumeSink` mutations
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum SinkState {
+    /// The sink is sending the events from its buffer
+    Running,
+    /// The sink is finishing sending the events it pulled from its buffer
+    Draining,
+    /// The sink is stopped until it's resumed, its inputs writing to its buffer in the meantime
+    Stopped,
+}
+
+impl From<topology::SinkState> for SinkState {
+    fn from(state: topology::SinkState) -> Self {
+        match state {
+            topology::SinkState::Running => Self::Running,
+            topology::SinkState::Draining => Self::Draining,
+            topology::SinkState::Stopped => Self::Stopped,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Sink(pub Data);
 
@@ -118,6 +140,19 @@ impl Sink {
         metrics::by_component_key(self.get_component_key())
             .into_sink_metrics(self.get_component_type())
     }
+
+    /// Sink health, or null if the sink isn't running
+    pub async fn health(&self, ctx: &Context<'_>) -> Option<ComponentHealth> {
+        health_by_component_key(ctx, self.get_component_key())
+    }
+
+    /// Sink state, or null if the sink isn't running
+    pub async fn state(&self, ctx: &Context<'_>) -> Option<SinkState> {
+        ctx.data_opt::<RuntimeControl>()?
+            .sink_drains
+            .state(self.get_component_key())
+            .map(Into::into)
+    }
 }
 
 #[cfg(test)]
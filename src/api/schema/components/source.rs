@@ -1,9 +1,10 @@
 use std::cmp;
 
-use async_graphql::{Enum, InputObject, Object};
+use async_graphql::{Context, Enum, InputObject, Object};
 
-use super::{sink, state, transform, Component};
+use super::{health_by_component_key, sink, state, transform, Component, ComponentHealth};
 use crate::{
+    api::control::RuntimeControl,
     api::schema::{
         filter,
         metrics::{self, outputs_by_component_key, IntoSourceMetrics, Output},
@@ -138,6 +139,18 @@ impl Source {
         metrics::by_component_key(&self.0.component_key)
             .into_source_metrics(self.get_component_type())
     }
+
+    /// Source health, or null if the source isn't running
+    pub async fn health(&self, ctx: &Context<'_>) -> Option<ComponentHealth> {
+        health_by_component_key(ctx, &self.0.component_key)
+    }
+
+    /// Whether or not the source is paused, or null if the source isn't running
+    pub async fn paused(&self, ctx: &Context<'_>) -> Option<bool> {
+        ctx.data_opt::<RuntimeControl>()?
+            .source_pauses
+            .is_paused(&self.0.component_key)
+    }
 }
 
 #[derive(Default, InputObject)]
@@ -5,9 +5,15 @@ pub mod notification;
 pub mod output;
 pub mod trace;
 
-use std::collections::HashSet;
-
-use async_graphql::{Context, Subscription};
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use async_graphql::{Context, Object, Result, Subscription};
 use encoding::EventEncodingType;
 use futures::{stream, Stream, StreamExt};
 use output::OutputEventsPayload;
@@ -15,7 +21,10 @@ use rand::{rngs::SmallRng, Rng, SeedableRng};
 use tokio::{select, sync::mpsc, time};
 use tokio_stream::wrappers::ReceiverStream;
 
-use crate::{api::tap::TapController, topology::WatchRx};
+use crate::{
+    api::{control, tap::TapController},
+    topology::WatchRx,
+};
 
 /// Patterns (glob) used by tap to match against components and access events
 /// flowing into (for_inputs) or out of (for_outputs) specified components
@@ -43,6 +52,31 @@ impl TapPatterns {
     }
 }
 
+/// Cap on the number of events each tap subscription samples per interval, which can be adjusted
+/// at runtime to reduce the overhead of tapping.
+#[derive(Clone, Debug, Default)]
+pub struct TapSampling(Arc<AtomicUsize>);
+
+impl TapSampling {
+    /// Gets the cap, if any.
+    pub fn limit(&self) -> Option<usize> {
+        match self.0.load(Ordering::Relaxed) {
+            0 => None,
+            limit => Some(limit),
+        }
+    }
+
+    /// Sets the cap, or removes it if `None`.
+    pub fn set_limit(&self, limit: Option<usize>) {
+        self.0.store(limit.unwrap_or(0), Ordering::Relaxed);
+    }
+
+    /// Applies the cap to the limit requested by a subscription.
+    fn apply(&self, limit: usize) -> usize {
+        self.limit().map_or(limit, |cap| limit.min(cap))
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct EventsSubscription;
 
@@ -58,24 +92,56 @@ impl EventsSubscription {
         #[graphql(default = 100, validator(minimum = 1, maximum = 10_000))] limit: u32,
     ) -> impl Stream<Item = Vec<OutputEventsPayload>> + 'a {
         let watch_rx = ctx.data_unchecked::<WatchRx>().clone();
+        let sampling = ctx
+            .data_opt::<control::RuntimeControl>()
+            .map(|control| control.tap_sampling.clone())
+            .unwrap_or_default();
 
         let patterns = TapPatterns {
             for_outputs: outputs_patterns.into_iter().collect(),
             for_inputs: inputs_patterns.unwrap_or_default().into_iter().collect(),
         };
         // Client input is confined to `u32` to provide sensible bounds.
-        create_events_stream(watch_rx, patterns, interval as u64, limit as usize)
+        create_events_stream(
+            watch_rx,
+            patterns,
+            interval as u64,
+            limit as usize,
+            sampling,
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct EventsMutation;
+
+#[Object]
+impl EventsMutation {
+    /// Caps the number of events each tap subscription samples per interval, including
+    /// subscriptions that are already active. Passing `null` removes the cap. Returns the new cap.
+    async fn set_tap_sampling_limit(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(validator(minimum = 1, maximum = 10_000))] limit: Option<u32>,
+    ) -> Result<Option<u32>> {
+        let control = control::authorize(ctx)?;
+        control
+            .tap_sampling
+            .set_limit(limit.map(|limit| limit as usize));
+        Ok(limit)
     }
 }
 
 /// Creates an events stream based on component ids, and a provided interval. Will emit
 /// control messages that bubble up the application if the sink goes away. The stream contains
-/// all matching events; filtering should be done at the caller level.
+/// all matching events; filtering should be done at the caller level. The number of events
+/// sampled per interval is capped by `limit`, as well as the current `sampling` limit.
 pub(crate) fn create_events_stream(
     watch_rx: WatchRx,
     patterns: TapPatterns,
     interval: u64,
     limit: usize,
+    sampling: TapSampling,
 ) -> impl Stream<Item = Vec<OutputEventsPayload>> {
     // Channel for receiving individual tap payloads. Since we can process at most `limit` per
     // interval, this is capped to the same value.
@@ -137,7 +203,7 @@ pub(crate) fn create_events_stream(
                         // https://en.wikipedia.org/wiki/Reservoir_sampling. As we're unable to
                         // pluck the nth result, this is chosen over the more optimal "Algorithm L"
                         // since discarding results isn't an option.
-                        if limit > results.len() {
+                        if sampling.apply(limit) > results.len() {
                             results.push(payload);
                         } else {
                             let random_number = rng.gen_range(0..batch);
@@ -155,6 +221,10 @@ pub(crate) fn create_events_stream(
                         // Reset the batch count, to adjust sampling probability for the next round.
                         batch = 0;
 
+                        // The sampling cap may have been lowered since we started collecting
+                        // results. As they're in random order, truncating keeps a random sample.
+                        results.truncate(sampling.apply(limit));
+
                         // Since events will appear out of order per the random sampling
                         // strategy, drain the existing results and sort by timestamp.
                         results.sort_by_key(|r| r.batch);
@@ -1,4 +1,6 @@
-use async_graphql::Object;
+use async_graphql::{Context, Error, Object, Result};
+
+use crate::{api::control, signal::SignalTo};
 
 #[derive(Default)]
 pub struct Meta;
@@ -25,3 +27,20 @@ impl MetaQuery {
         Meta
     }
 }
+
+#[derive(Default)]
+pub struct MetaMutation;
+
+#[Object]
+impl MetaMutation {
+    /// Reloads the configuration from disk, as on receiving a SIGHUP. The reload happens in the
+    /// background; its outcome is reported in the logs
+    async fn reload_config(&self, ctx: &Context<'_>) -> Result<bool> {
+        let control = control::authorize(ctx)?;
+        control
+            .signal_tx
+            .send(SignalTo::ReloadFromDisk)
+            .map(|_| true)
+            .map_err(|_| Error::new("Vector is shutting down."))
+    }
+}
@@ -159,6 +159,14 @@ pub fn get_all_metrics(interval: i32) -> impl Stream<Item = Vec<Metric>> {
     }
 }
 
+/// Resets the counters tagged with the given component id, returning how many were reset.
+pub fn reset_component_counters(component_key: &ComponentKey) -> usize {
+    get_controller().reset_counters(|key| {
+        key.labels()
+            .any(|label| label.key() == "component_id" && label.value() == component_key.id())
+    })
+}
+
 /// Return [`Vec<Metric>`] based on a component id tag.
 pub fn by_component_key(component_key: &ComponentKey) -> Vec<Metric> {
     get_controller()
@@ -240,8 +248,8 @@ pub fn counter_throughput(
     get_metrics(interval)
         .filter(filter_fn)
         .filter_map(move |m| match m.value() {
-            MetricValue::Counter { value } if *value > last => {
-                let throughput = value - last;
+            MetricValue::Counter { value } if *value != last => {
+                let throughput = counter_delta(*value, last);
                 last = *value;
                 Some((m, throughput))
             }
@@ -267,8 +275,7 @@ pub fn component_counter_throughputs(
                     match m.value() {
                         MetricValue::Counter { value } => {
                             let last = cache.insert(id, *value).unwrap_or(0.00);
-                            let throughput = value - last;
-                            Some((m, throughput))
+                            Some((m, counter_delta(*value, last)))
                         }
                         _ => None,
                     }
@@ -391,9 +398,18 @@ fn throughput(metric: &Metric, id: String, cache: &mut BTreeMap<String, f64>) ->
     match metric.value() {
         MetricValue::Counter { value } => {
             let last = cache.insert(id, *value).unwrap_or(0.00);
-            let throughput = value - last;
-            Some(throughput)
+            Some(counter_delta(*value, last))
         }
         _ => None,
     }
 }
+
+/// Returns the increase of a counter since its `last` value. A counter that went down was reset
+/// through the API, in which case it counts up from zero again.
+fn counter_delta(value: f64, last: f64) -> f64 {
+    if value < last {
+        value
+    } else {
+        value - last
+    }
+}
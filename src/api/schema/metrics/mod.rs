@@ -15,7 +15,7 @@ mod uptime;
 mod host;
 
 pub use allocated_bytes::{AllocatedBytes, ComponentAllocatedBytes};
-use async_graphql::{Interface, Object, Subscription};
+use async_graphql::{Context, Error, Interface, Object, Result, Subscription};
 use chrono::{DateTime, Utc};
 pub use errors::{ComponentErrorsTotal, ErrorsTotal};
 pub use filter::*;
@@ -34,7 +34,10 @@ use tokio_stream::{Stream, StreamExt};
 pub use transform::{IntoTransformMetrics, TransformMetrics};
 pub use uptime::Uptime;
 
-use crate::config::ComponentKey;
+use crate::{
+    api::{control, schema::components::state},
+    config::ComponentKey,
+};
 
 #[derive(Interface)]
 #[graphql(field(name = "timestamp", type = "Option<DateTime<Utc>>"))]
@@ -54,6 +57,27 @@ impl MetricsQuery {
     }
 }
 
+#[derive(Default)]
+pub struct MetricsMutation;
+
+#[Object]
+impl MetricsMutation {
+    /// Resets the counters of a component, such as its received and sent events totals, back to
+    /// zero. Returns the number of counters that were reset
+    async fn reset_component_counters(
+        &self,
+        ctx: &Context<'_>,
+        component_id: String,
+    ) -> Result<i32> {
+        control::authorize(ctx)?;
+        let key = ComponentKey::from(component_id);
+        if state::component_by_component_key(&key).is_none() {
+            return Err(Error::new(format!("No component with ID \"{key}\".")));
+        }
+        Ok(reset_component_counters(&key) as i32)
+    }
+}
+
 #[derive(Default)]
 pub struct MetricsSubscription;
 
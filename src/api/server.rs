@@ -12,10 +12,17 @@ use async_graphql_warp::{graphql_protocol, GraphQLResponse, GraphQLWebSocket};
 use tokio::sync::oneshot;
 use warp::{filters::BoxedFilter, http::Response, ws::Ws, Filter, Reply};
 
-use super::{handler, schema, ShutdownTx};
+use super::{
+    auth::{self, Authenticator},
+    control::RuntimeControl,
+    handler,
+    readiness::Readiness,
+    schema, ShutdownTx,
+};
 use crate::{
     config,
     internal_events::{SocketBindError, SocketMode},
+    tls::MaybeTlsSettings,
     topology,
 };
 
@@ -31,27 +38,46 @@ impl Server {
         config: &config::Config,
         watch_rx: topology::WatchRx,
         running: Arc<AtomicBool>,
+        health: topology::HealthRegistry,
+        control: RuntimeControl,
         runtime: &tokio::runtime::Runtime,
     ) -> crate::Result<Self> {
-        let routes = make_routes(config.api.playground, watch_rx, running);
+        let authenticator = Authenticator::new(config.api.auth.as_ref());
+        let routes = make_routes(
+            &config.api,
+            watch_rx,
+            running,
+            health,
+            control,
+            authenticator,
+        );
+
+        let address = config.api.address.expect("No socket address");
+        let tls = MaybeTlsSettings::from_config(&config.api.tls, true)?;
+        if config.api.auth.is_some() && tls.is_raw() && !address.ip().is_loopback() {
+            warn!(
+                message = "The API is served without TLS, so its bearer token is sent in plain text.",
+                address = %address,
+            );
+        }
+        let listener = runtime.block_on(tls.bind(&address)).map_err(|error| {
+            emit!(SocketBindError {
+                mode: SocketMode::Tcp,
+                error: &error,
+            });
+            error
+        })?;
+        let addr = listener.local_addr()?;
 
         let (_shutdown, rx) = oneshot::channel();
         // warp uses `tokio::spawn` and so needs us to enter the runtime context.
         let _guard = runtime.enter();
-        let (addr, server) = warp::serve(routes)
-            .try_bind_with_graceful_shutdown(
-                config.api.address.expect("No socket address"),
-                async {
-                    rx.await.ok();
-                },
-            )
-            .map_err(|error| {
-                emit!(SocketBindError {
-                    mode: SocketMode::Tcp,
-                    error: &error,
-                });
-                error
-            })?;
+        let server = warp::serve(routes).serve_incoming_with_graceful_shutdown(
+            listener.accept_stream(),
+            async {
+                rx.await.ok();
+            },
+        );
 
         // Update component schema with the config before starting the server.
         schema::components::update_config(config);
@@ -76,17 +102,39 @@ impl Server {
 }
 
 fn make_routes(
-    playground: bool,
+    api: &config::api::Options,
     watch_tx: topology::WatchRx,
     running: Arc<AtomicBool>,
+    health: topology::HealthRegistry,
+    control: RuntimeControl,
+    authenticator: Authenticator,
 ) -> BoxedFilter<(impl Reply,)> {
     // Routes...
 
+    // Liveness, which is the same as health, and readiness, which also takes the healthchecks of
+    // the components and the utilization of the buffers into account.
+    let liveness = warp::path!("health" / "live")
+        .and(with_shared(Arc::clone(&running)))
+        .and_then(handler::health);
+    let readiness = Readiness::new(api, health.clone());
+    let readiness = warp::path!("health" / "ready")
+        .and(with_shared(Arc::clone(&running)))
+        .and(warp::any().map(move || readiness.clone()))
+        .and_then(handler::ready);
+
     // Health.
-    let health = warp::path("health")
+    let health_route = warp::path("health")
         .and(with_shared(running))
         .and_then(handler::health);
 
+    // Internal metrics, in the Prometheus text exposition format. Behind the bearer token, if one
+    // is configured.
+    let internal_metrics = warp::path("metrics")
+        .and(warp::get())
+        .and(enabled(api.metrics))
+        .and(auth::authenticate(authenticator.clone()))
+        .and_then(handler::metrics);
+
     // 404.
     let not_found = warp::any().and_then(|| async { Err(warp::reject::not_found()) });
 
@@ -94,11 +142,16 @@ fn make_routes(
     // parses the required headers for GraphQL and builds per-connection context based on the
     // provided `WatchTx` channel sender. This allows GraphQL resolvers to subscribe to
     // topology changes.
+    let subscription_control = control.clone();
+    let subscription_health = health.clone();
     let graphql_subscription_handler =
         warp::ws()
             .and(graphql_protocol())
             .map(move |ws: Ws, protocol: WebSocketProtocols| {
-                let schema = schema::build_schema().finish();
+                let schema = schema::build_schema()
+                    .data(subscription_control.clone())
+                    .data(subscription_health.clone())
+                    .finish();
                 let watch_tx = watch_tx.clone();
 
                 let reply = ws.on_upgrade(move |socket| {
@@ -119,17 +172,21 @@ fn make_routes(
 
     // Handle GraphQL queries. Headers will first be parsed to determine whether the query is
     // a subscription and if so, an attempt will be made to upgrade the connection to WebSockets.
-    // All other queries will fall back to the default HTTP handler.
-    let graphql_handler = warp::path("graphql").and(graphql_subscription_handler.or(
-        async_graphql_warp::graphql(schema::build_schema().finish()).and_then(
-            |(schema, request): (Schema<_, _, _>, Request)| async move {
-                Ok::<_, Infallible>(GraphQLResponse::from(schema.execute(request).await))
-            },
-        ),
-    ));
+    // All other queries will fall back to the default HTTP handler. Both are behind the bearer
+    // token, if one is configured.
+    let graphql_schema = schema::build_schema().data(control).data(health).finish();
+    let graphql_handler = warp::path("graphql")
+        .and(auth::authenticate(authenticator))
+        .and(graphql_subscription_handler.or(
+            async_graphql_warp::graphql(graphql_schema).and_then(
+                |(schema, request): (Schema<_, _, _>, Request)| async move {
+                    Ok::<_, Infallible>(GraphQLResponse::from(schema.execute(request).await))
+                },
+            ),
+        ));
 
     // Provide a playground for executing GraphQL queries/mutations/subscriptions.
-    let graphql_playground = if playground {
+    let graphql_playground = if api.playground {
         warp::path("playground")
             .map(move || {
                 Response::builder()
@@ -145,10 +202,14 @@ fn make_routes(
 
     // Wire up the health + GraphQL endpoints. Provides a permissive CORS policy to allow for
     // cross-origin interaction with the Vector API.
-    health
+    liveness
+        .or(readiness)
+        .or(health_route)
+        .or(internal_metrics)
         .or(graphql_handler)
         .or(graphql_playground)
         .or(not_found)
+        .recover(auth::handle_rejection)
         .with(
             warp::cors()
                 .allow_any_origin()
@@ -161,6 +222,7 @@ fn make_routes(
                     "Access-Control-Allow-Origin",
                     "Access-Control-Request-Headers",
                     "Content-Type",
+                    "Authorization",
                     "X-Apollo-Tracing", // for Apollo GraphQL clients
                     "Pragma",
                     "Host",
@@ -177,3 +239,85 @@ fn with_shared(
 ) -> impl Filter<Extract = (Arc<AtomicBool>,), Error = Infallible> + Clone {
     warp::any().map(move || Arc::<AtomicBool>::clone(&shared))
 }
+
+/// Rejects requests as not found, unless the route is `enabled`.
+fn enabled(enabled: bool) -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    warp::any()
+        .and_then(move || async move {
+            if enabled {
+                Ok(())
+            } else {
+                Err(warp::reject::not_found())
+            }
+        })
+        .untuple_one()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::{json, Value};
+    use tokio::sync::{broadcast, watch};
+
+    use super::*;
+    use crate::{
+        config::Config,
+        sinks::blackhole::BlackholeConfig,
+        sources::demo_logs::DemoLogsConfig,
+        test_util::start_topology,
+        topology::{SinkDrains, SourcePauses, TapResource},
+    };
+
+    #[tokio::test]
+    async fn graphql_reports_component_health() {
+        let mut config = Config::builder();
+        config.add_source("health_in", DemoLogsConfig::default());
+        config.add_sink("health_out", &["health_in"], BlackholeConfig::default());
+        let config = config.build().unwrap();
+
+        let (topology, _crash) = start_topology(config.clone(), false).await;
+        schema::components::update_config(&config);
+
+        let (signal_tx, _) = broadcast::channel(1);
+        let control = RuntimeControl::new(
+            &config.api,
+            SourcePauses::default(),
+            SinkDrains::default(),
+            signal_tx,
+        );
+        let (_watch_tx, watch_rx) = watch::channel(TapResource::default());
+        let routes = make_routes(
+            &config.api,
+            watch_rx,
+            Arc::new(AtomicBool::new(true)),
+            topology.component_health.clone(),
+            control,
+            Authenticator::new(None),
+        );
+
+        // Sources are recorded as healthy once they're running, which may take a moment.
+        let mut health = Value::Null;
+        for _ in 0..50 {
+            let response = warp::test::request()
+                .method("POST")
+                .path("/graphql")
+                .json(&json!({
+                    "query": r#"{ componentByComponentKey(componentId: "health_in") {
+                        ... on Source { health }
+                    } }"#
+                }))
+                .reply(&routes)
+                .await;
+            let body = serde_json::from_slice::<Value>(response.body()).unwrap();
+            health = body["data"]["componentByComponentKey"]["health"].clone();
+            if !health.is_null() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        assert_eq!(health, json!("HEALTHY"));
+        topology.stop().await;
+    }
+}
@@ -425,7 +425,7 @@ mod tests {
 
     use super::*;
     use crate::api::schema::events::output::OutputEventsPayload;
-    use crate::api::schema::events::{create_events_stream, log, metric};
+    use crate::api::schema::events::{create_events_stream, log, metric, TapSampling};
     use crate::config::{Config, OutputId};
     use crate::event::{LogEvent, Metric, MetricKind, MetricValue};
     use crate::sinks::blackhole::BlackholeConfig;
@@ -598,6 +598,7 @@ mod tests {
             TapPatterns::new(HashSet::from(["in".to_string()]), HashSet::new()),
             500,
             100,
+            TapSampling::default(),
         );
 
         let source_tap_events: Vec<_> = source_tap_stream.take(2).collect().await;
@@ -609,6 +610,56 @@ mod tests {
         let _log = assert_log(source_tap_events[1][0].clone());
     }
 
+    #[tokio::test]
+    async fn integration_test_tap_sampling_limit() {
+        trace_init();
+
+        let mut config = Config::builder();
+        config.add_source(
+            "in",
+            DemoLogsConfig {
+                interval: Duration::from_secs_f64(0.01),
+                count: 200,
+                format: OutputFormat::Json,
+                ..Default::default()
+            },
+        );
+        config.add_sink(
+            "out",
+            &["in"],
+            BlackholeConfig {
+                print_interval_secs: Duration::from_secs(1),
+                rate: None,
+                acknowledgements: Default::default(),
+            },
+        );
+
+        let (topology, _) = start_topology(config.build().unwrap(), false).await;
+
+        // The source emits ~50 events per interval, so the cap, rather than the limit of the
+        // subscription, determines how many of them are sampled.
+        let sampling = TapSampling::default();
+        sampling.set_limit(Some(2));
+        let source_tap_stream = create_events_stream(
+            topology.watch(),
+            TapPatterns::new(HashSet::from(["in".to_string()]), HashSet::new()),
+            500,
+            100,
+            sampling,
+        );
+
+        let source_tap_events: Vec<_> = source_tap_stream.take(3).collect().await;
+
+        assert_eq!(
+            assert_notification(source_tap_events[0][0].clone()),
+            Notification::Matched(Matched::new("in".to_string()))
+        );
+        for events in &source_tap_events[1..] {
+            assert!(!events.is_empty() && events.len() <= 2);
+            let _log = assert_log(events[0].clone());
+        }
+    }
+
     #[tokio::test]
     async fn integration_test_source_metric() {
         trace_init();
@@ -656,6 +707,7 @@ mod tests {
             TapPatterns::new(HashSet::from(["to_metric".to_string()]), HashSet::new()),
             500,
             100,
+            TapSampling::default(),
         );
 
         let source_tap_events: Vec<_> = source_tap_stream.take(2).collect().await;
@@ -706,6 +758,7 @@ mod tests {
             TapPatterns::new(HashSet::from(["transform".to_string()]), HashSet::new()),
             500,
             100,
+            TapSampling::default(),
         );
 
         let transform_tap_events: Vec<_> = transform_tap_stream.take(2).collect().await;
@@ -762,6 +815,7 @@ mod tests {
             ),
             500,
             100,
+            TapSampling::default(),
         );
 
         let tap_events: Vec<_> = tap_stream.take(4).collect().await;
@@ -833,6 +887,7 @@ mod tests {
             TapPatterns::new(HashSet::new(), HashSet::from(["out".to_string()])),
             500,
             100,
+            TapSampling::default(),
         );
 
         let tap_events: Vec<_> = tap_stream.take(2).collect().await;
@@ -896,6 +951,7 @@ mod tests {
             ),
             500,
             100,
+            TapSampling::default(),
         );
 
         let transform_tap_events: Vec<_> =
@@ -969,6 +1025,7 @@ mod tests {
             TapPatterns::new(HashSet::from(["transform*".to_string()]), HashSet::new()),
             500,
             100,
+            TapSampling::default(),
         );
 
         let transform_tap_notifications = transform_tap_all_outputs_stream.next().await.unwrap();
@@ -95,7 +95,7 @@ impl ApplicationConfig {
             .ok_or(exitcode::CONFIG)?;
 
         #[cfg(feature = "api")]
-        let api = config.api;
+        let api = config.api.clone();
 
         let result = topology::start_validated(config, diff, pieces).await;
         let (topology, (graceful_crash_sender, graceful_crash_receiver)) =
@@ -115,18 +115,31 @@ impl ApplicationConfig {
 
     /// Configure the API server, if applicable
     #[cfg(feature = "api")]
-    pub fn setup_api(&self, runtime: &Runtime) -> Option<api::Server> {
+    pub fn setup_api(
+        &self,
+        runtime: &Runtime,
+        signal_tx: crate::signal::SignalTx,
+    ) -> Option<api::Server> {
         if self.api.enabled {
+            let control = api::RuntimeControl::new(
+                &self.api,
+                self.topology.source_pauses.clone(),
+                self.topology.sink_drains.clone(),
+                signal_tx,
+            );
             match api::Server::start(
                 self.topology.config(),
                 self.topology.watch(),
                 std::sync::Arc::clone(&self.topology.running),
+                self.topology.component_health.clone(),
+                control,
                 runtime,
             ) {
                 Ok(api_server) => {
                     emit!(ApiStarted {
                         addr: self.api.address.unwrap(),
-                        playground: self.api.playground
+                        playground: self.api.playground,
+                        tls: self.api.tls.as_ref().and_then(|tls| tls.enabled) == Some(true),
                     });
 
                     Some(api_server)
@@ -217,7 +230,7 @@ impl Application {
 
         let topology_controller = SharedTopologyController::new(TopologyController {
             #[cfg(feature = "api")]
-            api_server: config.setup_api(runtime),
+            api_server: config.setup_api(runtime, signals.handler.clone_tx()),
             topology: config.topology,
             config_paths: config.config_paths.clone(),
             require_healthy,
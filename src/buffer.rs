@@ -0,0 +1,342 @@
+#![allow(missing_docs)]
+use std::{
+    io::{self, Write},
+    ops::ControlFlow,
+    path::PathBuf,
+    time::SystemTime,
+};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use clap::Parser;
+use serde_json::json;
+use vector_buffers::{DiskBufferInspector, DiskBufferSummary, EncryptionKey, InspectError};
+use vector_core::event::{EventArray, EventContainer};
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+enum Command {
+    /// Show the number of events in a disk buffer, and how old they are.
+    Status {
+        #[command(flatten)]
+        buffer: BufferOpts,
+
+        /// Format of the output.
+        #[arg(long, default_value = "text")]
+        format: Format,
+    },
+
+    /// Write the events in a disk buffer to stdout, as newline-delimited JSON.
+    ///
+    /// Events are not removed from the buffer.
+    Dump {
+        #[command(flatten)]
+        buffer: BufferOpts,
+
+        /// Maximum number of records to write. Records can hold more than one event.
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// The key the buffer is encrypted with, as set by the `buffer.encryption.key` option of
+        /// the sink.
+        ///
+        /// Only required when the buffer holds encrypted events.
+        #[arg(
+            long,
+            env = "VECTOR_BUFFER_ENCRYPTION_KEY",
+            hide_env_values = true,
+            value_parser = parse_encryption_key
+        )]
+        encryption_key: Option<EncryptionKey>,
+    },
+
+    /// Delete a disk buffer, along with all of the events it holds.
+    Truncate {
+        #[command(flatten)]
+        buffer: BufferOpts,
+    },
+
+    /// Move a disk buffer aside, along with all of the events it holds.
+    ///
+    /// The sink starts with an empty buffer the next time Vector starts. The events can be sent
+    /// later on by restoring the buffer.
+    Move {
+        #[command(flatten)]
+        buffer: BufferOpts,
+
+        /// Path to move the buffer to.
+        destination: PathBuf,
+    },
+
+    /// Move a disk buffer that was moved aside back in place, so that the sink sends its events the
+    /// next time Vector starts.
+    ///
+    /// If the sink has a buffer already, it must be empty.
+    Restore {
+        #[command(flatten)]
+        buffer: BufferOpts,
+
+        /// Path of the buffer that was moved aside.
+        source: PathBuf,
+    },
+}
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+struct BufferOpts {
+    /// The data directory of Vector, as set by the global `data_dir` option.
+    #[arg(long, env = "VECTOR_DATA_DIR", default_value = "/var/lib/vector/")]
+    data_dir: PathBuf,
+
+    /// The ID of the sink the disk buffer belongs to.
+    ///
+    /// Vector must not be running while the buffer is operated on.
+    component_id: String,
+}
+
+impl BufferOpts {
+    fn path(&self) -> PathBuf {
+        DiskBufferInspector::buffer_path(&self.data_dir, &self.component_id)
+    }
+}
+
+fn parse_encryption_key(value: &str) -> Result<EncryptionKey, String> {
+    EncryptionKey::from_base64(value)
+        .ok_or_else(|| "must be 32 bytes, encoded as base64".to_string())
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+pub(crate) async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let result = match &opts.command {
+        Command::Status { buffer, format } => status(buffer, *format).await,
+        Command::Dump {
+            buffer,
+            limit,
+            encryption_key,
+        } => dump(buffer, *limit, encryption_key.clone()).await,
+        Command::Truncate { buffer } => truncate(buffer).await,
+        Command::Move {
+            buffer,
+            destination,
+        } => move_aside(buffer, destination.clone()).await,
+        Command::Restore { buffer, source } => restore(buffer, source.clone()).await,
+    };
+
+    match result {
+        Ok(()) => exitcode::OK,
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("{}", error);
+            }
+            match error {
+                InspectError::NotFound { .. } => exitcode::NOINPUT,
+                InspectError::Open { .. } => exitcode::TEMPFAIL,
+                InspectError::DestinationExists { .. } => exitcode::CANTCREAT,
+                InspectError::Io { .. } | InspectError::ReadRecord { .. } => exitcode::IOERR,
+            }
+        }
+    }
+}
+
+async fn status(opts: &BufferOpts, format: Format) -> Result<(), InspectError> {
+    let inspector = DiskBufferInspector::open(opts.path()).await?;
+    let summary = inspector.summary().await?;
+
+    #[allow(clippy::print_stdout)]
+    match format {
+        Format::Text => print!("{}", format_summary(&inspector, &summary)),
+        Format::Json => println!("{}", summary_json(&inspector, &summary)),
+    }
+
+    Ok(())
+}
+
+fn format_summary(inspector: &DiskBufferInspector, summary: &DiskBufferSummary) -> String {
+    let or_none = |value: Option<String>| value.unwrap_or_else(|| "none".to_owned());
+    format!(
+        "Buffer:            {}\n\
+         Events:            {}\n\
+         Oldest record ID:  {}\n\
+         Newest record ID:  {}\n\
+         Data files:        {} ({} bytes)\n\
+         Oldest write:      {}\n\
+         Newest write:      {}\n",
+        inspector.path().display(),
+        summary.events,
+        or_none(summary.oldest_record_id.map(|id| id.to_string())),
+        or_none(summary.newest_record_id.map(|id| id.to_string())),
+        summary.data_files,
+        summary.data_file_bytes,
+        or_none(summary.oldest_write.map(format_time)),
+        or_none(summary.newest_write.map(format_time)),
+    )
+}
+
+fn summary_json(inspector: &DiskBufferInspector, summary: &DiskBufferSummary) -> serde_json::Value {
+    json!({
+        "path": inspector.path(),
+        "events": summary.events,
+        "oldest_record_id": summary.oldest_record_id,
+        "newest_record_id": summary.newest_record_id,
+        "data_files": summary.data_files,
+        "data_file_bytes": summary.data_file_bytes,
+        "oldest_write": summary.oldest_write.map(format_time),
+        "newest_write": summary.newest_write.map(format_time),
+    })
+}
+
+fn format_time(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+async fn dump(
+    opts: &BufferOpts,
+    limit: Option<usize>,
+    encryption_key: Option<EncryptionKey>,
+) -> Result<(), InspectError> {
+    let mut inspector = DiskBufferInspector::open(opts.path()).await?;
+    if let Some(encryption_key) = encryption_key {
+        inspector = inspector.with_encryption_key(encryption_key);
+    }
+
+    let mut stdout = io::stdout();
+    let mut records = 0;
+    let mut write_error = None;
+    inspector
+        .read_records(|record_id, events: EventArray| {
+            if limit.map_or(false, |limit| records >= limit) {
+                return ControlFlow::Break(());
+            }
+            records += 1;
+
+            for event in events.into_events() {
+                let line = json!({ "record_id": record_id, "event": event });
+                if let Err(error) = writeln!(stdout, "{}", line) {
+                    write_error = Some(error);
+                    return ControlFlow::Break(());
+                }
+            }
+            ControlFlow::Continue(())
+        })
+        .await?;
+
+    match write_error {
+        // The reader of our output going away, such as when piping to `head`, isn't an error.
+        Some(error) if error.kind() != io::ErrorKind::BrokenPipe => {
+            Err(InspectError::Io { source: error })
+        }
+        _ => Ok(()),
+    }
+}
+
+async fn truncate(opts: &BufferOpts) -> Result<(), InspectError> {
+    let inspector = DiskBufferInspector::open(opts.path()).await?;
+    let events = inspector.summary().await?.events;
+    inspector.truncate().await?;
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!(
+            "Deleted buffer of {} with {} events.",
+            opts.component_id, events
+        );
+    }
+    Ok(())
+}
+
+async fn move_aside(opts: &BufferOpts, destination: PathBuf) -> Result<(), InspectError> {
+    let inspector = DiskBufferInspector::open(opts.path()).await?;
+    let events = inspector.summary().await?.events;
+    inspector.move_to(destination.clone()).await?;
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!(
+            "Moved buffer of {} with {} events to {}.",
+            opts.component_id,
+            events,
+            destination.display()
+        );
+    }
+    Ok(())
+}
+
+async fn restore(opts: &BufferOpts, source: PathBuf) -> Result<(), InspectError> {
+    let path = opts.path();
+
+    // Open the buffer to restore first, so that we don't touch the current buffer if it turns out
+    // there's nothing to restore.
+    let inspector = DiskBufferInspector::open(source).await?;
+    let events = inspector.summary().await?.events;
+
+    match DiskBufferInspector::open(path.clone()).await {
+        Ok(current) => {
+            if current.summary().await?.events > 0 {
+                return Err(InspectError::DestinationExists { path });
+            }
+            current.truncate().await?;
+        }
+        Err(InspectError::NotFound { .. }) => {}
+        Err(error) => return Err(error),
+    }
+    inspector.move_to(path).await?;
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!(
+            "Restored buffer of {} with {} events.",
+            opts.component_id, events
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::*;
+
+    #[test]
+    fn formats_times_as_rfc3339() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        assert_eq!(format_time(time), "2023-11-14T22:13:20Z");
+    }
+
+    #[test]
+    fn parses_component_and_data_dir() {
+        let opts = Opts::try_parse_from([
+            "buffer",
+            "move",
+            "--data-dir",
+            "/tmp/vector",
+            "my_sink",
+            "/tmp/my_sink.aside",
+        ])
+        .unwrap();
+        let Command::Move {
+            buffer,
+            destination,
+        } = opts.command
+        else {
+            panic!("expected the move command");
+        };
+        assert_eq!(
+            buffer.path(),
+            PathBuf::from("/tmp/vector/buffer/v2/my_sink")
+        );
+        assert_eq!(destination, PathBuf::from("/tmp/my_sink.aside"));
+    }
+}
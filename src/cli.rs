@@ -3,13 +3,15 @@ use std::{num::NonZeroU64, path::PathBuf};
 
 use clap::{ArgAction, CommandFactory, FromArgMatches, Parser};
 
+#[cfg(feature = "api-client")]
+use crate::control;
 #[cfg(windows)]
 use crate::service;
 #[cfg(feature = "api-client")]
 use crate::tap;
 #[cfg(feature = "api-client")]
 use crate::top;
-use crate::{config, generate, get_version, graph, list, unit_test, validate};
+use crate::{buffer, config, generate, get_version, graph, list, unit_test, validate};
 use crate::{generate_schema, signal};
 
 #[derive(Parser, Debug)]
@@ -32,6 +34,7 @@ impl Opts {
     pub const fn log_level(&self) -> &'static str {
         let (quiet_level, verbose_level) = match self.sub_command {
             Some(SubCommand::Validate(_))
+            | Some(SubCommand::Buffer(_))
             | Some(SubCommand::Graph(_))
             | Some(SubCommand::Generate(_))
             | Some(SubCommand::List(_))
@@ -246,6 +249,9 @@ pub enum SubCommand {
     /// Output the topology as visual representation using the DOT language which can be rendered by GraphViz
     Graph(graph::Opts),
 
+    /// Inspect, export, or move aside the disk buffer of a sink, while Vector is stopped.
+    Buffer(buffer::Opts),
+
     /// Display topology and metrics in the console, for a local or remote Vector instance
     #[cfg(feature = "api-client")]
     Top(top::Opts),
@@ -254,6 +260,10 @@ pub enum SubCommand {
     #[cfg(feature = "api-client")]
     Tap(tap::Opts),
 
+    /// Pause and resume sources, or drain and resume sinks, of a local or remote Vector instance
+    #[cfg(feature = "api-client")]
+    Control(control::Opts),
+
     /// Manage the vector service.
     #[cfg(windows)]
     Service(service::Opts),
@@ -269,7 +279,10 @@ impl SubCommand {
         color: bool,
     ) -> exitcode::ExitCode {
         match self {
+            Self::Buffer(b) => buffer::cmd(b).await,
             Self::Config(c) => config::cmd(c),
+            #[cfg(feature = "api-client")]
+            Self::Control(c) => control::cmd(c).await,
             Self::Generate(g) => generate::cmd(g),
             Self::GenerateSchema => generate_schema::cmd(),
             Self::Graph(g) => graph::cmd(g),
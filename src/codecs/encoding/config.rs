@@ -2,6 +2,7 @@ use crate::codecs::Transformer;
 use codecs::{
     encoding::{Framer, FramingConfig, Serializer, SerializerConfig},
     CharacterDelimitedEncoder, LengthDelimitedEncoder, NewlineDelimitedEncoder,
+    OctetCountingEncoder,
 };
 use vector_config::configurable_component;
 
@@ -107,6 +108,10 @@ impl EncodingConfigWithFraming {
             (None, Serializer::Avro(_) | Serializer::Native(_)) => {
                 LengthDelimitedEncoder::new().into()
             }
+            (None, Serializer::Syslog(_)) => match sink_type {
+                SinkType::StreamBased => OctetCountingEncoder::new().into(),
+                SinkType::MessageBased => NewlineDelimitedEncoder::new().into(),
+            },
             (
                 None,
                 Serializer::Csv(_)
@@ -0,0 +1,561 @@
+//! HMAC-SHA256 signatures of HTTP requests, as commonly used to authenticate webhooks.
+//!
+//! The signature is computed over a canonical string made of the configured parts of the request,
+//! and is sent in a header along with the timestamp at which the request was signed. The receiving
+//! end computes the signature again to authenticate the request, and relies on the timestamp to
+//! reject requests that are replayed, which is why it has to be signed for requests to be verified.
+
+#[cfg(feature = "sources-http_server")]
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::{Arc, Mutex},
+};
+use std::{fmt::Write as _, str::FromStr};
+
+use bytes::Bytes;
+use chrono::Utc;
+#[cfg(feature = "sources-http_server")]
+use http::HeaderMap;
+use http::{
+    header::{HeaderName, HeaderValue, InvalidHeaderName, InvalidHeaderValue},
+    Request,
+};
+use openssl::{
+    base64,
+    error::ErrorStack,
+    hash::{hash, MessageDigest},
+    pkey::{PKey, Private},
+    sign::Signer,
+};
+use snafu::{ResultExt, Snafu};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+#[derive(Debug, Snafu)]
+#[allow(unused)] // triggered by check-component-features
+pub enum HttpSignatureError {
+    #[snafu(display("Invalid header name {:?}: {}", name, source))]
+    InvalidHeaderName {
+        name: String,
+        source: InvalidHeaderName,
+    },
+    #[snafu(display("Invalid signature prefix: {}", source))]
+    InvalidSignaturePrefix { source: InvalidHeaderValue },
+    #[snafu(display("Failed to compute signature: {}", source))]
+    Sign { source: ErrorStack },
+    #[snafu(display("Missing signature header"))]
+    MissingSignature,
+    #[snafu(display("Missing or invalid timestamp header"))]
+    InvalidTimestamp,
+    #[snafu(display("Request timestamp is outside of the allowed window"))]
+    Expired,
+    #[snafu(display("Invalid signature"))]
+    InvalidSignature,
+    #[snafu(display("Request was already received"))]
+    Replayed,
+    #[snafu(display("The timestamp must be signed for replayed requests to be rejected"))]
+    UnsignedTimestamp,
+}
+
+/// A part of the request covered by its signature.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignedComponent {
+    /// The HTTP method of the request, such as `POST`.
+    Method,
+
+    /// The path of the request URI, including the query string if there is one.
+    Path,
+
+    /// The Unix timestamp, in seconds, at which the request was signed.
+    Timestamp,
+
+    /// The hex-encoded SHA-256 digest of the request body, as sent over the wire.
+    BodyDigest,
+}
+
+/// The encoding of the signature.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureEncoding {
+    /// Lowercase hexadecimal encoding.
+    #[derivative(Default)]
+    Hex,
+
+    /// Standard Base64 encoding, with padding.
+    Base64,
+}
+
+/// Configuration for signing HTTP requests with HMAC-SHA256.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct HttpSignatureConfig {
+    /// The secret key that requests are signed with, shared by the sender and the receiver.
+    #[configurable(metadata(docs::examples = "${WEBHOOK_SECRET}"))]
+    pub secret: SensitiveString,
+
+    /// The parts of the request that are signed.
+    ///
+    /// The signature is computed over a canonical string made of these parts, in the given order,
+    /// separated by newlines.
+    #[serde(default = "default_components")]
+    pub components: Vec<SignedComponent>,
+
+    /// The name of the header that holds the signature.
+    #[serde(default = "default_signature_header")]
+    #[configurable(metadata(docs::examples = "X-Hub-Signature-256"))]
+    pub signature_header: String,
+
+    /// The name of the header that holds the timestamp at which the request was signed.
+    ///
+    /// The timestamp is sent even if it isn't part of the signed `components`. It must be signed
+    /// for requests to be verified, as it is used to reject replayed requests.
+    #[serde(default = "default_timestamp_header")]
+    pub timestamp_header: String,
+
+    /// A prefix for the value of the signature header, such as `sha256=`.
+    #[serde(default = "default_signature_prefix")]
+    pub signature_prefix: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub encoding: SignatureEncoding,
+}
+
+fn default_components() -> Vec<SignedComponent> {
+    vec![
+        SignedComponent::Method,
+        SignedComponent::Path,
+        SignedComponent::Timestamp,
+        SignedComponent::BodyDigest,
+    ]
+}
+
+fn default_signature_header() -> String {
+    "X-Signature".to_owned()
+}
+
+fn default_timestamp_header() -> String {
+    "X-Signature-Timestamp".to_owned()
+}
+
+fn default_signature_prefix() -> String {
+    "sha256=".to_owned()
+}
+
+impl HttpSignatureConfig {
+    pub fn build(&self) -> Result<HttpSigner, HttpSignatureError> {
+        let header = |name: &str| {
+            HeaderName::from_str(name).context(InvalidHeaderNameSnafu {
+                name: name.to_owned(),
+            })
+        };
+        HeaderValue::from_str(&self.signature_prefix).context(InvalidSignaturePrefixSnafu)?;
+
+        Ok(HttpSigner {
+            key: PKey::hmac(self.secret.inner().as_bytes()).context(SignSnafu)?,
+            components: self.components.clone(),
+            signature_header: header(&self.signature_header)?,
+            timestamp_header: header(&self.timestamp_header)?,
+            signature_prefix: self.signature_prefix.clone(),
+            encoding: self.encoding,
+        })
+    }
+}
+
+/// Computes the signatures of HTTP requests.
+#[derive(Clone)]
+pub struct HttpSigner {
+    key: PKey<Private>,
+    components: Vec<SignedComponent>,
+    signature_header: HeaderName,
+    timestamp_header: HeaderName,
+    signature_prefix: String,
+    encoding: SignatureEncoding,
+}
+
+impl HttpSigner {
+    /// Computes the value of the signature header for a request.
+    fn signature(
+        &self,
+        method: &str,
+        path: &str,
+        timestamp: i64,
+        body: &[u8],
+    ) -> Result<String, ErrorStack> {
+        let mut canonical = String::new();
+        for (i, component) in self.components.iter().enumerate() {
+            if i > 0 {
+                canonical.push('\n');
+            }
+            match component {
+                SignedComponent::Method => canonical.push_str(method),
+                SignedComponent::Path => canonical.push_str(path),
+                SignedComponent::Timestamp => {
+                    _ = write!(canonical, "{}", timestamp);
+                }
+                SignedComponent::BodyDigest => {
+                    let digest = hash(MessageDigest::sha256(), body)?;
+                    canonical.push_str(&hex_encode(&digest));
+                }
+            }
+        }
+
+        let mut signer = Signer::new(MessageDigest::sha256(), &self.key)?;
+        signer.update(canonical.as_bytes())?;
+        let signature = signer.sign_to_vec()?;
+
+        let encoded = match self.encoding {
+            SignatureEncoding::Hex => hex_encode(&signature),
+            SignatureEncoding::Base64 => base64::encode_block(&signature),
+        };
+        Ok(format!("{}{}", self.signature_prefix, encoded))
+    }
+
+    /// Signs a request, as it is about to be sent.
+    pub fn sign(&self, request: &mut Request<Bytes>) -> Result<(), HttpSignatureError> {
+        let path = request
+            .uri()
+            .path_and_query()
+            .map_or("/", |path| path.as_str());
+        let timestamp = Utc::now().timestamp();
+        let signature = self
+            .signature(request.method().as_str(), path, timestamp, request.body())
+            .context(SignSnafu)?;
+
+        let headers = request.headers_mut();
+        headers.insert(
+            self.signature_header.clone(),
+            HeaderValue::from_str(&signature).expect("signature is a valid header value"),
+        );
+        headers.insert(self.timestamp_header.clone(), HeaderValue::from(timestamp));
+        Ok(())
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        _ = write!(hex, "{:02x}", byte);
+        hex
+    })
+}
+
+/// Configuration for verifying the HMAC-SHA256 signatures of HTTP requests.
+#[cfg(feature = "sources-http_server")]
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct HttpSignatureVerificationConfig {
+    #[serde(flatten)]
+    pub signature: HttpSignatureConfig,
+
+    /// The maximum age, in seconds, of the timestamp of signed requests.
+    ///
+    /// Requests that were signed longer ago than this, or as far in the future, are rejected.
+    /// Signatures are remembered for as long, so that requests replayed within this window are
+    /// rejected as well. The signature of a request that isn't handled successfully is forgotten,
+    /// so that the request can be retried.
+    ///
+    /// The `timestamp` component must be part of the signed `components`.
+    #[serde(default = "default_max_age_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub max_age_secs: u64,
+}
+
+#[cfg(feature = "sources-http_server")]
+const fn default_max_age_secs() -> u64 {
+    300
+}
+
+#[cfg(feature = "sources-http_server")]
+impl HttpSignatureVerificationConfig {
+    pub fn build(&self) -> Result<HttpSignatureVerifier, HttpSignatureError> {
+        if !self
+            .signature
+            .components
+            .contains(&SignedComponent::Timestamp)
+        {
+            return Err(HttpSignatureError::UnsignedTimestamp);
+        }
+
+        Ok(HttpSignatureVerifier {
+            signer: self.signature.build()?,
+            max_age: i64::try_from(self.max_age_secs).unwrap_or(i64::MAX),
+            seen: Default::default(),
+        })
+    }
+}
+
+/// The signatures of the requests received recently, with the time after which they expire.
+#[cfg(feature = "sources-http_server")]
+#[derive(Default)]
+struct SeenSignatures {
+    signatures: HashSet<String>,
+    expirations: VecDeque<(i64, String)>,
+}
+
+/// Verifies the signatures of HTTP requests, rejecting requests that are replayed.
+#[cfg(feature = "sources-http_server")]
+#[derive(Clone)]
+pub struct HttpSignatureVerifier {
+    signer: HttpSigner,
+    max_age: i64,
+    seen: Arc<Mutex<SeenSignatures>>,
+}
+
+#[cfg(feature = "sources-http_server")]
+impl HttpSignatureVerifier {
+    /// Verifies the signature of a request, given the path and query of its URI and its body as
+    /// received.
+    ///
+    /// The signature is recorded so that the request is rejected if it is received again, and
+    /// returned so that it can be forgotten if the request isn't handled successfully.
+    pub fn verify(
+        &self,
+        method: &str,
+        path: &str,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<String, HttpSignatureError> {
+        let signature = headers
+            .get(&self.signer.signature_header)
+            .and_then(|value| value.to_str().ok())
+            .ok_or(HttpSignatureError::MissingSignature)?;
+        let timestamp = headers
+            .get(&self.signer.timestamp_header)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<i64>().ok())
+            .ok_or(HttpSignatureError::InvalidTimestamp)?;
+
+        let now = Utc::now().timestamp();
+        if now.abs_diff(timestamp) > self.max_age.unsigned_abs() {
+            return Err(HttpSignatureError::Expired);
+        }
+
+        let expected = self
+            .signer
+            .signature(method, path, timestamp, body)
+            .context(SignSnafu)?;
+        if expected.len() != signature.len()
+            || !openssl::memcmp::eq(expected.as_bytes(), signature.as_bytes())
+        {
+            return Err(HttpSignatureError::InvalidSignature);
+        }
+
+        let mut seen = self.seen.lock().expect("poisoned lock");
+        while let Some((expiration, _)) = seen.expirations.front() {
+            if *expiration >= now {
+                break;
+            }
+            if let Some((_, signature)) = seen.expirations.pop_front() {
+                seen.signatures.remove(&signature);
+            }
+        }
+        if !seen.signatures.insert(expected.clone()) {
+            return Err(HttpSignatureError::Replayed);
+        }
+        seen.expirations
+            .push_back((timestamp.saturating_add(self.max_age), expected.clone()));
+
+        Ok(expected)
+    }
+
+    /// Forgets a signature returned by [`HttpSignatureVerifier::verify`], so that the request it
+    /// belongs to is accepted if it is received again.
+    pub fn forget(&self, signature: &str) {
+        let mut seen = self.seen.lock().expect("poisoned lock");
+        if seen.signatures.remove(signature) {
+            seen.expirations.retain(|(_, seen)| seen != signature);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(extra: &str) -> HttpSignatureConfig {
+        toml::from_str(&format!("secret = \"hunter2\"\n{}", extra)).unwrap()
+    }
+
+    fn signed_request(signer: &HttpSigner, body: &'static str) -> Request<Bytes> {
+        let mut request = Request::post("http://localhost:8080/webhooks?tenant=a")
+            .body(Bytes::from(body))
+            .unwrap();
+        signer.sign(&mut request).unwrap();
+        request
+    }
+
+    #[test]
+    fn signs_canonical_string() {
+        let signer = config("").build().unwrap();
+        let signature = signer
+            .signature("POST", "/webhooks", 1_700_000_000, b"hello")
+            .unwrap();
+
+        let canonical = format!(
+            "POST\n/webhooks\n1700000000\n{}",
+            hex_encode(&hash(MessageDigest::sha256(), b"hello").unwrap())
+        );
+        let key = PKey::hmac(b"hunter2").unwrap();
+        let mut expected = Signer::new(MessageDigest::sha256(), &key).unwrap();
+        expected.update(canonical.as_bytes()).unwrap();
+        let expected = hex_encode(&expected.sign_to_vec().unwrap());
+
+        assert_eq!(signature, format!("sha256={}", expected));
+    }
+
+    #[test]
+    fn places_signature_in_configured_headers() {
+        let signer = config(
+            r#"
+            components = ["timestamp", "body_digest"]
+            signature_header = "X-Hub-Signature-256"
+            timestamp_header = "X-Hub-Timestamp"
+            signature_prefix = ""
+            encoding = "base64"
+            "#,
+        )
+        .build()
+        .unwrap();
+        let request = signed_request(&signer, "hello");
+
+        let timestamp: i64 = request.headers()["X-Hub-Timestamp"]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let expected = signer.signature("", "", timestamp, b"hello").unwrap();
+        assert_eq!(request.headers()["X-Hub-Signature-256"], expected.as_str());
+        assert!(!expected.starts_with("sha256="));
+    }
+
+    #[test]
+    fn rejects_invalid_header_names() {
+        let error = config("signature_header = \"X Signature\"")
+            .build()
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error,
+            HttpSignatureError::InvalidHeaderName { .. }
+        ));
+    }
+
+    #[cfg(feature = "sources-http_server")]
+    mod verification {
+        use super::*;
+
+        fn verifier(max_age_secs: u64) -> HttpSignatureVerifier {
+            HttpSignatureVerificationConfig {
+                signature: config(""),
+                max_age_secs,
+            }
+            .build()
+            .unwrap()
+        }
+
+        fn verify(
+            verifier: &HttpSignatureVerifier,
+            request: &Request<Bytes>,
+        ) -> Result<String, HttpSignatureError> {
+            verifier.verify(
+                request.method().as_str(),
+                request.uri().path_and_query().unwrap().as_str(),
+                request.headers(),
+                request.body(),
+            )
+        }
+
+        #[test]
+        fn accepts_signed_requests_once() {
+            let verifier = verifier(300);
+            let request = signed_request(&verifier.signer, "hello");
+
+            verify(&verifier, &request).unwrap();
+            assert!(matches!(
+                verify(&verifier, &request),
+                Err(HttpSignatureError::Replayed)
+            ));
+        }
+
+        #[test]
+        fn accepts_forgotten_requests_again() {
+            let verifier = verifier(300);
+            let request = signed_request(&verifier.signer, "hello");
+
+            let signature = verify(&verifier, &request).unwrap();
+            verifier.forget(&signature);
+            verify(&verifier, &request).unwrap();
+            assert!(matches!(
+                verify(&verifier, &request),
+                Err(HttpSignatureError::Replayed)
+            ));
+        }
+
+        #[test]
+        fn requires_signed_timestamp() {
+            let error = HttpSignatureVerificationConfig {
+                signature: config("components = [\"method\", \"path\", \"body_digest\"]"),
+                max_age_secs: 300,
+            }
+            .build()
+            .err()
+            .unwrap();
+            assert!(matches!(error, HttpSignatureError::UnsignedTimestamp));
+        }
+
+        #[test]
+        fn rejects_tampered_requests() {
+            let verifier = verifier(300);
+            let mut request = signed_request(&verifier.signer, "hello");
+            *request.body_mut() = Bytes::from("goodbye");
+
+            assert!(matches!(
+                verify(&verifier, &request),
+                Err(HttpSignatureError::InvalidSignature)
+            ));
+        }
+
+        #[test]
+        fn rejects_unsigned_requests() {
+            let verifier = verifier(300);
+            let mut request = signed_request(&verifier.signer, "hello");
+            request.headers_mut().remove("X-Signature");
+            assert!(matches!(
+                verify(&verifier, &request),
+                Err(HttpSignatureError::MissingSignature)
+            ));
+
+            request.headers_mut().remove("X-Signature-Timestamp");
+            request
+                .headers_mut()
+                .insert("X-Signature", HeaderValue::from_static("sha256=00"));
+            assert!(matches!(
+                verify(&verifier, &request),
+                Err(HttpSignatureError::InvalidTimestamp)
+            ));
+        }
+
+        #[test]
+        fn rejects_expired_requests() {
+            let verifier = verifier(300);
+            let mut request = signed_request(&verifier.signer, "hello");
+            let timestamp = Utc::now().timestamp() - 600;
+            let signature = verifier
+                .signer
+                .signature("POST", "/webhooks?tenant=a", timestamp, b"hello")
+                .unwrap();
+            let headers = request.headers_mut();
+            headers.insert("X-Signature", HeaderValue::from_str(&signature).unwrap());
+            headers.insert("X-Signature-Timestamp", HeaderValue::from(timestamp));
+
+            assert!(matches!(
+                verify(&verifier, &request),
+                Err(HttpSignatureError::Expired)
+            ));
+        }
+    }
+}
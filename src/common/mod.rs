@@ -9,6 +9,9 @@
 ))]
 pub(crate) mod datadog;
 
+#[cfg(any(feature = "sinks-http", feature = "sources-http_server"))]
+pub(crate) mod http_signature;
+
 #[cfg(any(
     feature = "sources-aws_sqs",
     feature = "sinks-aws_sqs",
@@ -18,3 +21,6 @@ pub(crate) mod sqs;
 
 #[cfg(any(feature = "sources-aws_s3", feature = "sinks-aws_s3"))]
 pub(crate) mod s3;
+
+#[cfg(any(feature = "sources-websocket", feature = "sinks-websocket"))]
+pub(crate) mod websocket;
@@ -0,0 +1,321 @@
+use std::{
+    net::SocketAddr,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use snafu::{ResultExt, Snafu};
+use tokio::{net::TcpStream, time};
+use tokio_tungstenite::{
+    client_async_with_config,
+    tungstenite::{
+        client::{uri_mode, IntoClientRequest},
+        error::{Error as WsError, ProtocolError, UrlError},
+        handshake::client::Request as WsRequest,
+        protocol::WebSocketConfig,
+        stream::Mode as UriMode,
+    },
+    WebSocketStream as WsStream,
+};
+
+use crate::{
+    dns, emit,
+    http::Auth,
+    internal_events::{WsConnectionEstablished, WsConnectionFailedError},
+    sinks::util::retries::ExponentialBackoff,
+    tls::{MaybeTlsSettings, MaybeTlsStream, TlsError},
+};
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum WebSocketError {
+    #[snafu(display("Creating WebSocket client failed: {}", source))]
+    CreateFailed { source: WsError },
+    #[snafu(display("Connect error: {}", source))]
+    ConnectError { source: TlsError },
+    #[snafu(display("Unable to resolve DNS: {}", source))]
+    DnsError { source: dns::DnsError },
+    #[snafu(display("No addresses returned."))]
+    NoAddresses,
+}
+
+#[derive(Clone)]
+pub struct WebSocketConnector {
+    uri: String,
+    host: String,
+    port: u16,
+    tls: MaybeTlsSettings,
+    auth: Option<Auth>,
+}
+
+impl WebSocketConnector {
+    pub fn new(
+        uri: String,
+        tls: MaybeTlsSettings,
+        auth: Option<Auth>,
+    ) -> Result<Self, WebSocketError> {
+        let request = (&uri).into_client_request().context(CreateFailedSnafu)?;
+        let (host, port) = Self::extract_host_and_port(&request).context(CreateFailedSnafu)?;
+
+        Ok(Self {
+            uri,
+            host,
+            port,
+            tls,
+            auth,
+        })
+    }
+
+    fn extract_host_and_port(request: &WsRequest) -> Result<(String, u16), WsError> {
+        let host = request
+            .uri()
+            .host()
+            .ok_or(WsError::Url(UrlError::NoHostName))?
+            .to_string();
+        let mode = uri_mode(request.uri())?;
+        let port = request.uri().port_u16().unwrap_or(match mode {
+            UriMode::Tls => 443,
+            UriMode::Plain => 80,
+        });
+
+        Ok((host, port))
+    }
+
+    /// The host of the remote peer, as found in the URI.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    const fn fresh_backoff() -> ExponentialBackoff {
+        ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(Duration::from_secs(60))
+    }
+
+    async fn tls_connect(&self) -> Result<MaybeTlsStream<TcpStream>, WebSocketError> {
+        let ip = dns::Resolver
+            .lookup_ip(self.host.clone())
+            .await
+            .context(DnsSnafu)?
+            .next()
+            .ok_or(WebSocketError::NoAddresses)?;
+
+        let addr = SocketAddr::new(ip, self.port);
+        self.tls
+            .connect(&self.host, &addr)
+            .await
+            .context(ConnectSnafu)
+    }
+
+    async fn connect(&self) -> Result<WsStream<MaybeTlsStream<TcpStream>>, WebSocketError> {
+        let mut request = (&self.uri)
+            .into_client_request()
+            .context(CreateFailedSnafu)?;
+
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        let maybe_tls = self.tls_connect().await?;
+
+        let ws_config = WebSocketConfig {
+            max_send_queue: None, // don't buffer messages
+            ..Default::default()
+        };
+
+        let (ws_stream, _response) = client_async_with_config(request, maybe_tls, Some(ws_config))
+            .await
+            .context(CreateFailedSnafu)?;
+
+        Ok(ws_stream)
+    }
+
+    /// Connects to the remote peer, retrying with an exponential backoff until it succeeds.
+    ///
+    /// Failed attempts are reported as errors of the given stage.
+    pub async fn connect_backoff(
+        &self,
+        stage: &'static str,
+    ) -> WsStream<MaybeTlsStream<TcpStream>> {
+        let mut backoff = Self::fresh_backoff();
+        loop {
+            match self.connect().await {
+                Ok(ws_stream) => {
+                    emit!(WsConnectionEstablished {});
+                    return ws_stream;
+                }
+                Err(error) => {
+                    emit!(WsConnectionFailedError {
+                        error: Box::new(error),
+                        stage,
+                    });
+                    time::sleep(backoff.next().unwrap()).await;
+                }
+            }
+        }
+    }
+
+    pub async fn healthcheck(&self) -> crate::Result<()> {
+        self.connect().await.map(|_| ()).map_err(Into::into)
+    }
+}
+
+/// An optional interval at which pings are sent to the remote peer.
+///
+/// Ticking never completes if no period is set.
+pub struct PingInterval {
+    interval: Option<time::Interval>,
+}
+
+impl PingInterval {
+    pub fn new(period: Option<u64>) -> Self {
+        Self {
+            interval: period.map(|period| time::interval(Duration::from_secs(period))),
+        }
+    }
+
+    fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<time::Instant> {
+        match self.interval.as_mut() {
+            Some(interval) => interval.poll_tick(cx),
+            None => Poll::Pending,
+        }
+    }
+
+    pub async fn tick(&mut self) -> time::Instant {
+        std::future::poll_fn(|cx| self.poll_tick(cx)).await
+    }
+}
+
+/// Returns whether the error means that the connection was closed by the remote peer.
+pub const fn is_closed(error: &WsError) -> bool {
+    matches!(
+        error,
+        WsError::ConnectionClosed
+            | WsError::AlreadyClosed
+            | WsError::Protocol(ProtocolError::ResetWithoutClosingHandshake)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::accept_async;
+    use vector_common::internal_event::error_stage;
+
+    use super::*;
+    use crate::test_util::next_addr;
+
+    fn connector(uri: &str) -> Result<WebSocketConnector, WebSocketError> {
+        WebSocketConnector::new(uri.to_string(), MaybeTlsSettings::Raw(()), None)
+    }
+
+    #[test]
+    fn extracts_host_and_port() {
+        let plain = connector("ws://example.com/events").unwrap();
+        assert_eq!((plain.host(), plain.port), ("example.com", 80));
+
+        let tls = connector("wss://example.com").unwrap();
+        assert_eq!((tls.host(), tls.port), ("example.com", 443));
+
+        let explicit = connector("ws://127.0.0.1:8080").unwrap();
+        assert_eq!((explicit.host(), explicit.port), ("127.0.0.1", 8080));
+    }
+
+    #[test]
+    fn rejects_non_websocket_uri() {
+        assert!(matches!(
+            connector("http://example.com"),
+            Err(WebSocketError::CreateFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn backoff_grows_up_to_a_minute() {
+        let delays = WebSocketConnector::fresh_backoff()
+            .take(10)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            delays[..3],
+            [
+                Duration::from_millis(500),
+                Duration::from_secs(1),
+                Duration::from_secs(2)
+            ]
+        );
+        assert_eq!(delays[9], Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn connect_backoff_retries_until_peer_is_up() {
+        let addr = next_addr();
+        let connector = connector(&format!("ws://{}", addr)).unwrap();
+        let client = tokio::spawn(async move {
+            connector.connect_backoff(error_stage::RECEIVING).await;
+        });
+
+        // Nothing is listening yet, so the first attempt fails.
+        time::sleep(Duration::from_millis(100)).await;
+        assert!(!client.is_finished());
+
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let (socket, _) = listener.accept().await.unwrap();
+        let _ws_stream = accept_async(socket).await.unwrap();
+
+        time::timeout(Duration::from_secs(5), client)
+            .await
+            .expect("client should have connected")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn connect_fails_without_peer() {
+        let connector = connector(&format!("ws://{}", next_addr())).unwrap();
+
+        assert!(connector.healthcheck().await.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn ping_interval_ticks_every_period() {
+        let start = time::Instant::now();
+        let mut interval = PingInterval::new(Some(5));
+
+        assert_eq!(interval.tick().await, start);
+        assert_eq!(interval.tick().await, start + Duration::from_secs(5));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn ping_interval_without_period_never_ticks() {
+        let mut interval = PingInterval::new(None);
+
+        assert!(time::timeout(Duration::from_secs(3600), interval.tick())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn detects_closed_connections() {
+        assert!(is_closed(&WsError::ConnectionClosed));
+        assert!(is_closed(&WsError::AlreadyClosed));
+        assert!(is_closed(&WsError::Protocol(
+            ProtocolError::ResetWithoutClosingHandshake
+        )));
+        assert!(!is_closed(&WsError::Io(
+            std::io::ErrorKind::TimedOut.into()
+        )));
+
+        // A peer that goes away without a closing handshake ends the stream with one of these.
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let connector = connector(&format!("ws://{}", addr)).unwrap();
+        let (client, server) = tokio::join!(connector.connect(), async {
+            let (socket, _) = listener.accept().await.unwrap();
+            accept_async(socket).await.unwrap()
+        });
+        drop(server);
+
+        let error = client.unwrap().next().await.unwrap().unwrap_err();
+        assert!(is_closed(&error), "{:?}", error);
+    }
+}
@@ -146,7 +146,7 @@ fn deserializer_config_to_serializer(config: &DeserializerConfig) -> encoding::S
         // the data as Avro, we can't possibly send anything else without the source just
         // immediately barfing.
         #[cfg(feature = "sources-syslog")]
-        DeserializerConfig::Syslog => SerializerConfig::Logfmt,
+        DeserializerConfig::Syslog => SerializerConfig::Syslog(Default::default()),
         DeserializerConfig::Native => SerializerConfig::Native,
         DeserializerConfig::NativeJson => SerializerConfig::NativeJson,
         DeserializerConfig::Gelf => SerializerConfig::Gelf,
@@ -171,9 +171,7 @@ fn decoder_framing_to_encoding_framer(framing: &decoding::FramingConfig) -> enco
         decoding::FramingConfig::NewlineDelimited { .. } => {
             encoding::FramingConfig::NewlineDelimited
         }
-        // TODO: There's no equivalent octet counting framer for encoding... although
-        // there's no particular reason that would make it hard to write.
-        decoding::FramingConfig::OctetCounting { .. } => todo!(),
+        decoding::FramingConfig::OctetCounting { .. } => encoding::FramingConfig::OctetCounting,
     };
 
     framing_config.build()
@@ -191,6 +189,11 @@ fn serializer_config_to_deserializer(config: &SerializerConfig) -> decoding::Des
         SerializerConfig::Native => DeserializerConfig::Native,
         SerializerConfig::NativeJson => DeserializerConfig::NativeJson,
         SerializerConfig::RawMessage | SerializerConfig::Text(_) => DeserializerConfig::Bytes,
+        #[cfg(feature = "sources-syslog")]
+        SerializerConfig::Syslog(_) => DeserializerConfig::Syslog,
+        // Without the syslog decoder, the best we can do is to take each message as is.
+        #[cfg(not(feature = "sources-syslog"))]
+        SerializerConfig::Syslog(_) => DeserializerConfig::Bytes,
     };
 
     deserializer_config.build()
@@ -211,6 +214,9 @@ fn encoder_framing_to_decoding_framer(framing: encoding::FramingConfig) -> decod
         encoding::FramingConfig::NewlineDelimited => decoding::FramingConfig::NewlineDelimited {
             newline_delimited: NewlineDelimitedDecoderOptions::default(),
         },
+        encoding::FramingConfig::OctetCounting => decoding::FramingConfig::OctetCounting {
+            octet_counting: Default::default(),
+        },
     };
 
     framing_config.build()
@@ -1,10 +1,13 @@
 use std::net::{Ipv4Addr, SocketAddr};
 
+use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 
+use crate::tls::TlsEnableableConfig;
+
 /// API options.
 #[configurable_component]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[serde(default, deny_unknown_fields)]
 pub struct Options {
     /// Whether or not the API endpoint is available.
@@ -18,6 +21,52 @@ pub struct Options {
     /// Whether or not to expose the GraphQL playground on the API endpoint.
     #[serde(default = "default_playground")]
     pub playground: bool,
+
+    /// Whether or not to expose internal metrics on the API endpoint.
+    ///
+    /// The metrics are served at `/metrics`, in the Prometheus text exposition format, so they can
+    /// be scraped without configuring an `internal_metrics` source and a `prometheus_exporter`
+    /// sink.
+    #[serde(default = "default_metrics")]
+    pub metrics: bool,
+
+    /// Whether or not to allow GraphQL mutations.
+    ///
+    /// Mutations control the running instance, such as pausing and resuming sources, or reloading
+    /// the configuration, so they are disabled by default.
+    #[serde(default)]
+    pub allow_mutations: bool,
+
+    /// The buffer utilization above which a sink makes the instance not ready.
+    ///
+    /// Utilization is the fraction of a sink's buffer that is in use, by events or by bytes,
+    /// whichever is greater. Once a sink's buffer is fuller than this, `/health/ready` reports the
+    /// instance as not ready, so orchestrators can stop routing traffic to it.
+    #[configurable(validation(range(min = 0.0, max = 1.0)))]
+    #[serde(default = "default_readiness_buffer_threshold")]
+    pub readiness_buffer_threshold: f64,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsEnableableConfig>,
+}
+
+/// API authentication options.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Auth {
+    /// The bearer token that requests must present in the `Authorization` header.
+    ///
+    /// This applies to GraphQL queries, mutations, and the WebSocket connections used for
+    /// subscriptions, such as those of `vector top` and `vector tap`, as well as to the metrics
+    /// endpoint. The health endpoints remain available without it.
+    #[configurable(metadata(docs::examples = "${VECTOR_API_TOKEN}"))]
+    pub token: SensitiveString,
 }
 
 impl Default for Options {
@@ -25,7 +74,12 @@ impl Default for Options {
         Self {
             enabled: default_enabled(),
             playground: default_playground(),
+            metrics: default_metrics(),
             address: default_address(),
+            allow_mutations: false,
+            readiness_buffer_threshold: default_readiness_buffer_threshold(),
+            auth: None,
+            tls: None,
         }
     }
 }
@@ -45,6 +99,14 @@ const fn default_playground() -> bool {
     true
 }
 
+const fn default_metrics() -> bool {
+    true
+}
+
+const fn default_readiness_buffer_threshold() -> f64 {
+    0.9
+}
+
 impl Options {
     pub fn merge(&mut self, other: Self) -> Result<(), String> {
         // Merge options
@@ -66,10 +128,45 @@ impl Options {
             }
         };
 
+        // Prefer non default threshold
+        let readiness_buffer_threshold = match (
+            self.readiness_buffer_threshold,
+            other.readiness_buffer_threshold,
+        ) {
+            (a, b) if a == b => a,
+            (a, b) if a == default_readiness_buffer_threshold() => b,
+            (a, b) if b == default_readiness_buffer_threshold() => a,
+            (a, b) => {
+                return Err(format!(
+                    "Conflicting `api` readiness buffer threshold: {}, {} .",
+                    a, b
+                ))
+            }
+        };
+
+        let auth = match (self.auth.clone(), other.auth) {
+            (Some(a), Some(b)) if a != b => {
+                return Err("Conflicting `api` auth options.".to_string())
+            }
+            (a, b) => a.or(b),
+        };
+
+        let tls = match (self.tls.clone(), other.tls) {
+            (Some(a), Some(b)) if a != b => {
+                return Err("Conflicting `api` TLS options.".to_string())
+            }
+            (a, b) => a.or(b),
+        };
+
         let options = Options {
             address,
             enabled: self.enabled | other.enabled,
             playground: self.playground & other.playground,
+            metrics: self.metrics & other.metrics,
+            allow_mutations: self.allow_mutations | other.allow_mutations,
+            readiness_buffer_threshold,
+            auth,
+            tls,
         };
 
         *self = options;
@@ -83,6 +180,11 @@ fn bool_merge() {
         enabled: true,
         address: None,
         playground: false,
+        metrics: true,
+        allow_mutations: true,
+        readiness_buffer_threshold: 0.9,
+        auth: None,
+        tls: None,
     };
 
     a.merge(Options::default()).unwrap();
@@ -93,6 +195,11 @@ fn bool_merge() {
             enabled: true,
             address: default_address(),
             playground: false,
+            metrics: true,
+            allow_mutations: true,
+            readiness_buffer_threshold: 0.9,
+            auth: None,
+            tls: None,
         }
     );
 }
@@ -104,6 +211,11 @@ fn bind_merge() {
         enabled: true,
         address: Some(address),
         playground: true,
+        metrics: false,
+        allow_mutations: false,
+        readiness_buffer_threshold: 0.5,
+        auth: None,
+        tls: None,
     };
 
     a.merge(Options::default()).unwrap();
@@ -114,6 +226,11 @@ fn bind_merge() {
             enabled: true,
             address: Some(address),
             playground: true,
+            metrics: false,
+            allow_mutations: false,
+            readiness_buffer_threshold: 0.5,
+            auth: None,
+            tls: None,
         }
     );
 }
@@ -132,3 +249,52 @@ fn bind_conflict() {
 
     assert!(a.merge(b).is_err());
 }
+
+#[test]
+fn auth_conflict() {
+    let mut a = Options {
+        auth: Some(Auth {
+            token: "a".to_string().into(),
+        }),
+        ..Options::default()
+    };
+
+    let b = Options {
+        auth: Some(Auth {
+            token: "b".to_string().into(),
+        }),
+        ..Options::default()
+    };
+
+    assert!(a.merge(b).is_err());
+}
+
+#[test]
+fn tls_conflict() {
+    let mut a = Options {
+        tls: Some(TlsEnableableConfig::enabled()),
+        ..Options::default()
+    };
+
+    let b = Options {
+        tls: Some(TlsEnableableConfig::test_config()),
+        ..Options::default()
+    };
+
+    assert!(a.merge(b).is_err());
+}
+
+#[test]
+fn readiness_buffer_threshold_conflict() {
+    let mut a = Options {
+        readiness_buffer_threshold: 0.5,
+        ..Options::default()
+    };
+
+    let b = Options {
+        readiness_buffer_threshold: 0.8,
+        ..Options::default()
+    };
+
+    assert!(a.merge(b).is_err());
+}
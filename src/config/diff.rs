@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use indexmap::IndexMap;
 
-use super::{ComponentKey, Config};
+use super::{get_transform_output_ids, ComponentKey, Config};
 
 #[derive(Debug)]
 pub struct ConfigDiff {
@@ -26,11 +26,42 @@ impl ConfigDiff {
         }
     }
 
+    /// Moves the changed transforms that can be swapped in place from `to_change` to `to_swap`, so
+    /// that they keep running with their input buffer and outputs, and only the transform itself is
+    /// replaced.
+    ///
+    /// Only transforms whose type, inputs and outputs are unchanged are considered, as swapping
+    /// them leaves the shape of the topology as it is. `can_swap` decides whether or not the
+    /// running transform supports being swapped, and whether or not the schema definitions of its
+    /// outputs are unchanged, which requires the enrichment tables to compute.
+    ///
+    /// Changes to global options, such as `log_schema`, still can't be reloaded at all, and
+    /// enrichment tables are reloaded in place as before, so neither is handled here.
+    pub fn with_swapped_transforms(
+        mut self,
+        old: &Config,
+        new: &Config,
+        can_swap: impl Fn(&ComponentKey) -> bool,
+    ) -> Self {
+        let (to_swap, to_change): (HashSet<_>, HashSet<_>) = self
+            .transforms
+            .to_change
+            .drain()
+            .partition(|key| can_swap(key) && transform_is_swappable(old, new, key));
+        self.transforms.to_swap = to_swap;
+        self.transforms.to_change = to_change;
+        self
+    }
+
     /// Swaps removed with added in Differences.
+    ///
+    /// Transforms to swap are left as they are until the diff is spawned, so there's nothing to
+    /// swap back.
     pub fn flip(mut self) -> Self {
         self.sources.flip();
         self.transforms.flip();
         self.sinks.flip();
+        self.transforms.to_swap.clear();
         self
     }
 
@@ -54,11 +85,41 @@ impl ConfigDiff {
     }
 }
 
+/// Checks whether or not the given transform is changed in a way that it can be swapped in place,
+/// rather than rebuilt.
+fn transform_is_swappable(old: &Config, new: &Config, key: &ComponentKey) -> bool {
+    let (Some(old_transform), Some(new_transform)) = (old.transform(key), new.transform(key)) else {
+        return false;
+    };
+
+    let old_outputs = get_transform_output_ids(
+        old_transform.inner.as_ref(),
+        key.clone(),
+        old.schema.log_namespace(),
+    )
+    .collect::<HashSet<_>>();
+    let new_outputs = get_transform_output_ids(
+        new_transform.inner.as_ref(),
+        key.clone(),
+        new.schema.log_namespace(),
+    )
+    .collect::<HashSet<_>>();
+
+    old_transform.inner.get_component_name() == new_transform.inner.get_component_name()
+        && old_transform.inputs == &new_transform.inputs[..]
+        && old_transform.inner.input() == new_transform.inner.input()
+        && old_transform.inner.enable_concurrency() == new_transform.inner.enable_concurrency()
+        && old_outputs == new_outputs
+}
+
 #[derive(Debug)]
 pub struct Difference {
     pub to_remove: HashSet<ComponentKey>,
     pub to_change: HashSet<ComponentKey>,
     pub to_add: HashSet<ComponentKey>,
+    /// Changed components that are swapped in place rather than rebuilt. These are left running,
+    /// so they aren't part of the other sets.
+    pub to_swap: HashSet<ComponentKey>,
 }
 
 impl Difference {
@@ -92,6 +153,7 @@ impl Difference {
             to_remove,
             to_change,
             to_add,
+            to_swap: HashSet::new(),
         }
     }
 
@@ -120,6 +182,11 @@ impl Difference {
         self.to_change.contains(key)
     }
 
+    /// Checks whether or not the given component is swapped in place.
+    pub fn is_swapped(&self, key: &ComponentKey) -> bool {
+        self.to_swap.contains(key)
+    }
+
     /// Checks whether the given component is present as an addition.
     pub fn is_added(&self, id: &ComponentKey) -> bool {
         self.to_add.contains(id)
@@ -142,3 +209,45 @@ impl Difference {
         self.to_change.iter().chain(self.to_remove.iter())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::mock::{basic_sink, basic_source, basic_transform};
+
+    fn config(transform_inputs: &[&str], suffix: &str) -> Config {
+        let mut config = Config::builder();
+        config.add_source("in1", basic_source().1);
+        config.add_source("in2", basic_source().1);
+        config.add_transform("t1", transform_inputs, basic_transform(suffix, 0.0));
+        config.add_sink("out1", &["t1"], basic_sink(10).1);
+        config.build().unwrap()
+    }
+
+    #[test]
+    fn swaps_transforms_with_unchanged_inputs_and_outputs() {
+        let old = config(&["in1"], " v1");
+        let new = config(&["in1"], " v2");
+        let key = ComponentKey::from("t1");
+
+        let diff = ConfigDiff::new(&old, &new).with_swapped_transforms(&old, &new, |_| true);
+        assert!(diff.transforms.is_swapped(&key));
+        assert!(!diff.contains(&key));
+        assert!(!diff.flip().transforms.is_swapped(&key));
+
+        let diff = ConfigDiff::new(&old, &new).with_swapped_transforms(&old, &new, |_| false);
+        assert!(!diff.transforms.is_swapped(&key));
+        assert!(diff.transforms.is_changed(&key));
+    }
+
+    #[test]
+    fn rebuilds_transforms_with_changed_inputs() {
+        let old = config(&["in1"], " v1");
+        let new = config(&["in1", "in2"], " v2");
+        let key = ComponentKey::from("t1");
+
+        let diff = ConfigDiff::new(&old, &new).with_swapped_transforms(&old, &new, |_| true);
+        assert!(!diff.transforms.is_swapped(&key));
+        assert!(diff.transforms.is_changed(&key));
+    }
+}
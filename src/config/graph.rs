@@ -3,7 +3,7 @@ use std::collections::{HashMap, HashSet, VecDeque};
 
 use super::{
     schema, ComponentKey, DataType, OutputId, SinkConfig, SinkOuter, SourceOuter, SourceOutput,
-    TransformOuter, TransformOutput,
+    TransformOuter, TransformOutput, DEAD_LETTER_OUTPUT,
 };
 
 #[derive(Debug, Clone)]
@@ -17,6 +17,7 @@ pub enum Node {
     },
     Sink {
         ty: DataType,
+        dead_letter: bool,
     },
 }
 
@@ -90,6 +91,7 @@ impl Graph {
                 id.clone(),
                 Node::Sink {
                     ty: config.inner.input().data_type(),
+                    dead_letter: config.dead_letter.enabled,
                 },
             );
         }
@@ -156,7 +158,7 @@ impl Graph {
         match self.nodes[key] {
             Node::Source { .. } => panic!("no inputs on sources"),
             Node::Transform { in_ty, .. } => in_ty,
-            Node::Sink { ty } => ty,
+            Node::Sink { ty, .. } => ty,
         }
     }
 
@@ -164,8 +166,8 @@ impl Graph {
     ///
     /// # Panics
     ///
-    /// Will panic if the given id is not present in the graph or identifies a sink, which can't
-    /// have inputs.
+    /// Will panic if the given id is not present in the graph or identifies a sink without a
+    /// dead-letter output.
     fn get_output_type(&self, id: &OutputId) -> DataType {
         match &self.nodes[&id.component] {
             Node::Source { outputs } => outputs
@@ -178,6 +180,11 @@ impl Graph {
                 .find(|output| output.port == id.port)
                 .map(|output| output.ty)
                 .expect("output didn't exist"),
+            // Events sent to the dead-letter output are the ones the sink received.
+            Node::Sink {
+                ty,
+                dead_letter: true,
+            } if id.port.as_deref() == Some(DEAD_LETTER_OUTPUT) => *ty,
             Node::Sink { .. } => panic!("no outputs on sinks"),
         }
     }
@@ -263,7 +270,15 @@ impl Graph {
         self.nodes
             .iter()
             .flat_map(|(key, node)| match node {
-                Node::Sink { .. } => vec![],
+                Node::Sink {
+                    dead_letter: false, ..
+                } => vec![],
+                Node::Sink {
+                    dead_letter: true, ..
+                } => vec![OutputId {
+                    component: key.clone(),
+                    port: Some(DEAD_LETTER_OUTPUT.to_owned()),
+                }],
                 Node::Source { outputs } => outputs
                     .iter()
                     .map(|output| OutputId {
@@ -355,7 +370,7 @@ impl Graph {
             .into_iter()
             .filter(|path| {
                 if let Some(key) = path.last() {
-                    matches!(self.nodes.get(key), Some(Node::Sink { ty: _ }))
+                    matches!(self.nodes.get(key), Some(Node::Sink { .. }))
                 } else {
                     false
                 }
@@ -429,7 +444,13 @@ mod test {
         fn add_sink(&mut self, id: &str, ty: DataType, inputs: Vec<&str>) {
             let id = ComponentKey::from(id);
             let inputs = clean_inputs(inputs);
-            self.nodes.insert(id.clone(), Node::Sink { ty });
+            self.nodes.insert(
+                id.clone(),
+                Node::Sink {
+                    ty,
+                    dead_letter: false,
+                },
+            );
             for from in inputs {
                 self.edges.push(Edge {
                     from,
@@ -438,6 +459,13 @@ mod test {
             }
         }
 
+        fn enable_dead_letter(&mut self, id: &str) {
+            match self.nodes.get_mut(&id.into()) {
+                Some(Node::Sink { dead_letter, .. }) => *dead_letter = true,
+                _ => panic!("invalid sink"),
+            }
+        }
+
         fn test_add_input(&mut self, node: &str, input: &str) -> Result<(), String> {
             let available_inputs = self.input_map().unwrap();
             self.add_input(input, &node.into(), &available_inputs)
@@ -632,6 +660,41 @@ mod test {
         );
     }
 
+    #[test]
+    fn allows_sink_dead_letter_outputs() {
+        let mut graph = Graph::default();
+        graph.add_source("log_source", DataType::Log);
+        graph.add_sink("primary", DataType::Log, vec!["log_source"]);
+        graph.add_sink("fallback", DataType::Log, vec![]);
+
+        let expected =
+            "Input \"primary.dead_letter\" for sink \"fallback\" doesn't match any components."
+                .to_string();
+        assert_eq!(
+            Err(expected),
+            graph.test_add_input("fallback", "primary.dead_letter")
+        );
+
+        graph.enable_dead_letter("primary");
+        assert_eq!(
+            Ok(()),
+            graph.test_add_input("fallback", "primary.dead_letter")
+        );
+        assert_eq!(Ok(()), graph.typecheck());
+        assert_eq!(Ok(()), graph.check_for_cycles());
+
+        // A sink can't receive its own failed events, even through other components.
+        graph.enable_dead_letter("fallback");
+        graph.add_transform(
+            "annotate",
+            DataType::Log,
+            DataType::Log,
+            vec!["fallback.dead_letter"],
+        );
+        assert_eq!(Ok(()), graph.test_add_input("primary", "annotate"));
+        assert!(graph.check_for_cycles().is_err());
+    }
+
     #[test]
     fn disallows_ambiguous_inputs() {
         let mut graph = Graph::default();
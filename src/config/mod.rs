@@ -54,7 +54,10 @@ pub use loading::{
 };
 pub use provider::ProviderConfig;
 pub use secret::SecretBackend;
-pub use sink::{SinkConfig, SinkContext, SinkHealthcheckOptions, SinkOuter};
+pub use sink::{
+    SinkConfig, SinkContext, SinkDeadLetterOptions, SinkHealthcheckOptions, SinkOuter,
+    DEAD_LETTER_OUTPUT,
+};
 pub use source::{BoxedSource, SourceConfig, SourceContext, SourceOuter};
 pub use transform::{
     get_transform_output_ids, BoxedTransform, TransformConfig, TransformContext, TransformOuter,
@@ -751,6 +754,62 @@ mod tests {
         )
     }
 
+    #[tokio::test]
+    async fn dead_letter_outputs() {
+        let warnings = load(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.primary]
+            type = "test_basic"
+            inputs = ["in"]
+            dead_letter.enabled = true
+
+            [sinks.fallback]
+            type = "test_basic"
+            inputs = ["primary.dead_letter"]
+            dead_letter = true
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            warnings,
+            vec![
+                "Sink \"fallback.dead_letter\" has no consumers, so the events the sink fails to \
+                 deliver are dropped and acknowledged as delivered"
+            ]
+        );
+
+        let errors = load(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.primary]
+            type = "test_basic"
+            inputs = ["in"]
+
+            [sinks.fallback]
+            type = "test_basic"
+            inputs = ["primary.dead_letter"]
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                "Input \"primary.dead_letter\" for sink \"fallback\" doesn't match any components."
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn cycle() {
         let errors = load(
@@ -11,6 +11,9 @@ use vector_core::{
 use super::{id::Inputs, schema, ComponentKey, ProxyConfig, Resource};
 use crate::sinks::{util::UriSerde, Healthcheck, Sinks};
 
+/// The name of the output that sinks send the events they failed to deliver to.
+pub const DEAD_LETTER_OUTPUT: &str = "dead_letter";
+
 /// Fully resolved sink component.
 #[configurable_component]
 #[configurable(metadata(docs::component_base_type = "sink"))]
@@ -33,6 +36,14 @@ where
     #[serde(default, deserialize_with = "crate::serde::bool_or_struct")]
     healthcheck: SinkHealthcheckOptions,
 
+    #[configurable(derived, metadata(docs::advanced))]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub dead_letter: SinkDeadLetterOptions,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -66,6 +77,7 @@ where
             buffer: Default::default(),
             healthcheck: SinkHealthcheckOptions::default(),
             healthcheck_uri: None,
+            dead_letter: SinkDeadLetterOptions::default(),
             inner: inner.into(),
             proxy: Default::default(),
         }
@@ -123,6 +135,7 @@ where
             buffer: self.buffer,
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
+            dead_letter: self.dead_letter,
             proxy: self.proxy,
         }
     }
@@ -168,6 +181,41 @@ impl From<UriSerde> for SinkHealthcheckOptions {
     }
 }
 
+/// Dead-letter configuration.
+///
+/// Events that the sink fails to deliver, either because they were rejected by the destination
+/// or because retries were exhausted, are sent to the `<sink_id>.dead_letter` output instead of
+/// being dropped. Other components can use that output as an input, to store the events for later
+/// replay, for example.
+///
+/// The events are annotated with the details of the failure, in the same way as events sent to the
+/// `dropped` output of the `remap` transform: under `metadata.dead_letter` for logs and traces, or
+/// `%vector.dead_letter` when using the Vector namespace, and as `metadata.dead_letter.*` tags for
+/// metrics. The details include the `status` of the delivery (`errored` or `rejected`), the sink
+/// that failed to deliver the events and, when known, the number of `attempts` made, the
+/// `status_code` returned by the destination, and the last `error` encountered.
+///
+/// The events are acknowledged once the components consuming the `dead_letter` output have
+/// delivered them. If no component consumes it, the events the sink fails to deliver are dropped
+/// and acknowledged as delivered, as they would be by a sink without a dead-letter output.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(default)]
+pub struct SinkDeadLetterOptions {
+    /// Whether or not to send the events that the sink fails to deliver to the `dead_letter`
+    /// output.
+    ///
+    /// A copy of each event is kept in memory until its delivery completes, which increases the
+    /// memory usage of the sink.
+    pub enabled: bool,
+}
+
+impl From<bool> for SinkDeadLetterOptions {
+    fn from(enabled: bool) -> Self {
+        Self { enabled }
+    }
+}
+
 /// Generalized interface for describing and building sink components.
 #[async_trait]
 #[enum_dispatch]
@@ -7,7 +7,7 @@ use vector_core::internal_event::DEFAULT_OUTPUT;
 
 use super::{
     builder::ConfigBuilder, transform::get_transform_output_ids, ComponentKey, Config, OutputId,
-    Resource,
+    Resource, DEAD_LETTER_OUTPUT,
 };
 
 /// Check that provide + topology config aren't present in the same builder, which is an error.
@@ -349,8 +349,13 @@ pub fn warnings(config: &Config) -> Vec<String> {
         .map(|output| ("transform", output))
         .collect::<Vec<_>>()
     });
+    let sink_ids = config
+        .sinks
+        .iter()
+        .filter(|(_, sink)| sink.dead_letter.enabled)
+        .map(|(key, _)| ("sink", OutputId::from((key, DEAD_LETTER_OUTPUT.to_owned()))));
 
-    for (input_type, id) in transform_ids.chain(source_ids) {
+    for (input_type, id) in transform_ids.chain(source_ids).chain(sink_ids) {
         if !config
             .transforms
             .iter()
@@ -360,11 +365,15 @@ pub fn warnings(config: &Config) -> Vec<String> {
                 .iter()
                 .any(|(_, sink)| sink.inputs.contains(&id))
         {
-            warnings.push(format!(
-                "{} \"{}\" has no consumers",
-                capitalize(input_type),
-                id
-            ));
+            let mut warning = format!("{} \"{}\" has no consumers", capitalize(input_type), id);
+            if input_type == "sink" {
+                // The events routed to a dead-letter output nothing consumes are dropped, which
+                // acknowledges them.
+                warning.push_str(
+                    ", so the events the sink fails to deliver are dropped and acknowledged as delivered",
+                );
+            }
+            warnings.push(warning);
         }
     }
 
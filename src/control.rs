@@ -0,0 +1,331 @@
+#![allow(missing_docs)]
+use std::time::Duration;
+
+use clap::Parser;
+use url::Url;
+use vector_api_client::{
+    gql::{drain_sink_mutation, resume_sink_mutation, ControlMutationExt},
+    Client,
+};
+
+use crate::config;
+
+/// Interval at which a draining sink is checked for having stopped, when waiting for it.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Parser, Debug, Clone)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Vector GraphQL API server endpoint
+    #[arg(short, long)]
+    url: Option<Url>,
+
+    #[command(flatten)]
+    credentials: vector_api_client::Credentials,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(rename_all = "kebab-case")]
+enum Command {
+    /// Pause a source, which stops it from sending events downstream until it's resumed.
+    ///
+    /// The source stops pulling in new events once its channel is full, but keeps its
+    /// connections open.
+    PauseSource {
+        /// ID of the source.
+        component_id: String,
+    },
+
+    /// Resume a paused source.
+    ResumeSource {
+        /// ID of the source.
+        component_id: String,
+    },
+
+    /// Drain a sink, which stops it from pulling events from its buffer, and stops it once it has
+    /// sent the events it already pulled.
+    ///
+    /// The inputs of the sink keep writing to its buffer until it's resumed, or until the buffer
+    /// is full.
+    DrainSink {
+        /// ID of the sink.
+        component_id: String,
+
+        /// Wait until the sink has stopped.
+        #[arg(long)]
+        wait: bool,
+    },
+
+    /// Resume a draining or stopped sink, which carries on from where it stopped in its buffer.
+    ResumeSink {
+        /// ID of the sink.
+        component_id: String,
+    },
+}
+
+/// CLI command func for controlling the components of a running Vector instance, by way of its
+/// GraphQL API rather than signals.
+pub(crate) async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    // Use the provided URL as the Vector GraphQL API server, or default to the local port
+    // provided by the API config.
+    let url = opts.url.clone().unwrap_or_else(|| {
+        let addr = config::api::default_address().unwrap();
+        Url::parse(&format!("http://{}/graphql", addr))
+            .expect("Couldn't parse default API URL. Please report this.")
+    });
+
+    let Some(client) = Client::new_with_healthcheck(url, opts.credentials.clone()).await else {
+        return exitcode::UNAVAILABLE;
+    };
+
+    match run(&client, &opts.command).await {
+        Ok(message) => {
+            #[allow(clippy::print_stdout)]
+            {
+                println!("{}", message);
+            }
+            exitcode::OK
+        }
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("{}", error);
+            }
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+async fn run(client: &Client, command: &Command) -> crate::Result<String> {
+    match command {
+        Command::PauseSource { component_id } => {
+            let was_running = client
+                .pause_source(component_id.clone())
+                .await?
+                .pause_source;
+            Ok(if was_running {
+                format!("Paused source \"{}\".", component_id)
+            } else {
+                format!("Source \"{}\" was already paused.", component_id)
+            })
+        }
+        Command::ResumeSource { component_id } => {
+            let was_paused = client
+                .resume_source(component_id.clone())
+                .await?
+                .resume_source;
+            Ok(if was_paused {
+                format!("Resumed source \"{}\".", component_id)
+            } else {
+                format!("Source \"{}\" wasn't paused.", component_id)
+            })
+        }
+        Command::DrainSink { component_id, wait } => {
+            let state = client.drain_sink(component_id.clone()).await?.drain_sink;
+            if !wait {
+                return Ok(match state {
+                    drain_sink_mutation::SinkState::RUNNING => {
+                        format!("Draining sink \"{}\".", component_id)
+                    }
+                    drain_sink_mutation::SinkState::STOPPED => {
+                        format!("Sink \"{}\" was already stopped.", component_id)
+                    }
+                    _ => format!("Sink \"{}\" was already draining.", component_id),
+                });
+            }
+
+            // Draining an already draining, or stopped, sink leaves it as is, so the mutation
+            // reports the progress of the drain.
+            let mut state = state;
+            while state != drain_sink_mutation::SinkState::STOPPED {
+                tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+                state = client.drain_sink(component_id.clone()).await?.drain_sink;
+            }
+            Ok(format!("Sink \"{}\" is stopped.", component_id))
+        }
+        Command::ResumeSink { component_id } => {
+            let state = client.resume_sink(component_id.clone()).await?.resume_sink;
+            Ok(if state == resume_sink_mutation::SinkState::RUNNING {
+                format!("Sink \"{}\" wasn't drained.", component_id)
+            } else {
+                format!("Resumed sink \"{}\".", component_id)
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::{json, Value};
+    use warp::Filter;
+
+    use super::*;
+
+    type Requests = Arc<Mutex<Vec<Value>>>;
+
+    /// Starts a GraphQL API server that records the requests it receives, and replies to each with
+    /// the next of the given responses, or the last one once they run out.
+    fn mock_api(responses: Vec<Value>) -> (Client, Requests) {
+        let requests = Requests::default();
+        let route = {
+            let requests = Arc::clone(&requests);
+            warp::post()
+                .and(warp::path("graphql"))
+                .and(warp::body::json())
+                .map(move |request: Value| {
+                    let mut requests = requests.lock().unwrap();
+                    requests.push(request);
+                    let index = (requests.len() - 1).min(responses.len() - 1);
+                    warp::reply::json(&responses[index])
+                })
+        };
+        let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+
+        let url = Url::parse(&format!("http://{}/graphql", addr)).unwrap();
+        (Client::new(url), requests)
+    }
+
+    fn parse(args: &[&str]) -> Command {
+        Opts::try_parse_from(std::iter::once("control").chain(args.iter().copied()))
+            .unwrap()
+            .command
+    }
+
+    #[test]
+    fn parses_commands() {
+        assert!(matches!(
+            parse(&["pause-source", "in"]),
+            Command::PauseSource { component_id } if component_id == "in"
+        ));
+        assert!(matches!(
+            parse(&["resume-source", "in"]),
+            Command::ResumeSource { component_id } if component_id == "in"
+        ));
+        assert!(matches!(
+            parse(&["drain-sink", "out"]),
+            Command::DrainSink { component_id, wait: false } if component_id == "out"
+        ));
+        assert!(matches!(
+            parse(&["drain-sink", "out", "--wait"]),
+            Command::DrainSink { component_id, wait: true } if component_id == "out"
+        ));
+        assert!(matches!(
+            parse(&["resume-sink", "out"]),
+            Command::ResumeSink { component_id } if component_id == "out"
+        ));
+    }
+
+    #[test]
+    fn parses_url() {
+        let opts = Opts::try_parse_from([
+            "control",
+            "--url",
+            "http://localhost:9999/graphql",
+            "pause-source",
+            "in",
+        ])
+        .unwrap();
+        assert_eq!(
+            opts.url.map(String::from).as_deref(),
+            Some("http://localhost:9999/graphql")
+        );
+    }
+
+    #[test]
+    fn rejects_missing_component_id() {
+        assert!(Opts::try_parse_from(["control", "pause-source"]).is_err());
+        assert!(Opts::try_parse_from(["control", "resume-sink"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn sends_mutation_for_each_command() {
+        let cases = [
+            (
+                ["pause-source", "in"],
+                "PauseSourceMutation",
+                json!({ "pauseSource": true }),
+                "Paused source \"in\".",
+            ),
+            (
+                ["resume-source", "in"],
+                "ResumeSourceMutation",
+                json!({ "resumeSource": false }),
+                "Source \"in\" wasn't paused.",
+            ),
+            (
+                ["drain-sink", "out"],
+                "DrainSinkMutation",
+                json!({ "drainSink": "RUNNING" }),
+                "Draining sink \"out\".",
+            ),
+            (
+                ["resume-sink", "out"],
+                "ResumeSinkMutation",
+                json!({ "resumeSink": "STOPPED" }),
+                "Resumed sink \"out\".",
+            ),
+        ];
+
+        for (args, operation, data, message) in cases {
+            let (client, requests) = mock_api(vec![json!({ "data": data })]);
+
+            assert_eq!(run(&client, &parse(&args)).await.unwrap(), message);
+
+            let requests = requests.lock().unwrap();
+            assert_eq!(requests.len(), 1);
+            assert_eq!(requests[0]["operationName"], operation);
+            assert_eq!(requests[0]["variables"]["componentId"], args[1]);
+        }
+    }
+
+    #[tokio::test]
+    async fn waits_for_drained_sink_to_stop() {
+        let (client, requests) = mock_api(vec![
+            json!({ "data": { "drainSink": "RUNNING" } }),
+            json!({ "data": { "drainSink": "DRAINING" } }),
+            json!({ "data": { "drainSink": "STOPPED" } }),
+        ]);
+
+        let message = run(&client, &parse(&["drain-sink", "out", "--wait"]))
+            .await
+            .unwrap();
+
+        assert_eq!(message, "Sink \"out\" is stopped.");
+        assert_eq!(requests.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn reports_unknown_component() {
+        let (client, _) = mock_api(vec![json!({
+            "data": null,
+            "errors": [{ "message": "No sink with component ID \"nope\"." }],
+        })]);
+
+        let error = run(&client, &parse(&["drain-sink", "nope"]))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), "No sink with component ID \"nope\".");
+    }
+
+    #[tokio::test]
+    async fn reports_disabled_mutations() {
+        let message = "Mutations are disabled. Set `api.allow_mutations` to `true` to enable them.";
+        let (client, _) = mock_api(vec![json!({
+            "data": null,
+            "errors": [{ "message": message }],
+        })]);
+
+        let error = run(&client, &parse(&["pause-source", "in"]))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), message);
+    }
+}
@@ -1,12 +1,20 @@
 #![allow(missing_docs)]
 use std::{
     fmt,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 
+use bytes::Bytes;
 use futures::future::BoxFuture;
 use headers::{Authorization, HeaderMapExt};
-use http::{header::HeaderValue, request::Builder, uri::InvalidUri, HeaderMap, Request, Uri};
+use http::{
+    header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE},
+    request::Builder,
+    uri::InvalidUri,
+    HeaderMap, Request, StatusCode, Uri,
+};
 use hyper::{
     body::{Body, HttpBody},
     client,
@@ -14,6 +22,7 @@ use hyper::{
 };
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
+use serde::Deserialize;
 use snafu::{ResultExt, Snafu};
 use tower::Service;
 use tracing::Instrument;
@@ -39,23 +48,63 @@ pub enum HttpError {
     CallRequest { source: hyper::Error },
     #[snafu(display("Failed to build HTTP request: {}", source))]
     BuildRequest { source: http::Error },
+    #[snafu(display("Failed to read HTTP request body: {}", source))]
+    ReadRequestBody { source: crate::Error },
+    #[snafu(display("Failed to fetch OAuth2 access token: {}", source))]
+    FetchOAuth2Token { source: OAuth2Error },
 }
 
 impl HttpError {
-    pub const fn is_retriable(&self) -> bool {
+    pub fn is_retriable(&self) -> bool {
         match self {
-            HttpError::BuildRequest { .. } | HttpError::MakeProxyConnector { .. } => false,
+            HttpError::BuildRequest { .. }
+            | HttpError::MakeProxyConnector { .. }
+            | HttpError::ReadRequestBody { .. } => false,
             HttpError::CallRequest { .. }
             | HttpError::BuildTlsConnector { .. }
             | HttpError::MakeHttpsConnector { .. } => true,
+            HttpError::FetchOAuth2Token { source } => source.is_retriable(),
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum OAuth2Error {
+    #[snafu(display("Invalid token endpoint: {}", source))]
+    InvalidTokenEndpoint { source: InvalidUri },
+    #[snafu(display("Failed to call token endpoint: {}", source))]
+    CallTokenEndpoint { source: hyper::Error },
+    #[snafu(display("Token endpoint responded with {}: {}", status, body))]
+    UnexpectedTokenStatus { status: StatusCode, body: String },
+    #[snafu(display("Failed to parse token response: {}", source))]
+    ParseTokenResponse { source: serde_json::Error },
+    #[snafu(display("Invalid access token: {}", source))]
+    InvalidAccessToken {
+        source: http::header::InvalidHeaderValue,
+    },
+}
+
+impl OAuth2Error {
+    fn is_retriable(&self) -> bool {
+        match self {
+            OAuth2Error::CallTokenEndpoint { .. } => true,
+            OAuth2Error::UnexpectedTokenStatus { status, .. } => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            OAuth2Error::InvalidTokenEndpoint { .. }
+            | OAuth2Error::ParseTokenResponse { .. }
+            | OAuth2Error::InvalidAccessToken { .. } => false,
         }
     }
 }
 
 pub type HttpClientFuture = <HttpClient as Service<http::Request<Body>>>::Future;
 
+type HttpsProxyConnector = ProxyConnector<HttpsConnector<HttpConnector>>;
+
 pub struct HttpClient<B = Body> {
-    client: Client<ProxyConnector<HttpsConnector<HttpConnector>>, B>,
+    client: Client<HttpsProxyConnector, B>,
+    oauth2: OAuth2Client,
     user_agent: HeaderValue,
 }
 
@@ -78,13 +127,23 @@ where
         client_builder: &mut client::Builder,
     ) -> Result<HttpClient<B>, HttpError> {
         let proxy = build_proxy_connector(tls_settings.into(), proxy_config)?;
-        let client = client_builder.build(proxy);
+        let client = client_builder.build(proxy.clone());
 
         let version = crate::get_version();
         let user_agent = HeaderValue::from_str(&format!("Vector/{}", version))
             .expect("Invalid header value for version!");
 
-        Ok(HttpClient { client, user_agent })
+        let oauth2 = OAuth2Client {
+            client: client_builder.build(proxy),
+            user_agent: user_agent.clone(),
+            tokens: Default::default(),
+        };
+
+        Ok(HttpClient {
+            client,
+            oauth2,
+            user_agent,
+        })
     }
 
     pub fn send(
@@ -96,45 +155,241 @@ where
 
         default_request_headers(&mut request, &self.user_agent);
 
+        // Requests authenticated with OAuth2 get their access token here, as fetching it is
+        // asynchronous.
+        if let Some(config) = request.extensions_mut().remove::<OAuth2Config>() {
+            let fut = self
+                .oauth2
+                .clone()
+                .send(request, config)
+                .instrument(span.clone().or_current());
+            return Box::pin(fut);
+        }
+
         emit!(http_client::AboutToSendHttpRequest { request: &request });
 
         let response = self.client.request(request);
 
-        let fut = async move {
-            // Capture the time right before we issue the request.
-            // Request doesn't start the processing until we start polling it.
-            let before = std::time::Instant::now();
-
-            // Send request and wait for the result.
-            let response_result = response.await;
-
-            // Compute the roundtrip time it took to send the request and get
-            // the response or error.
-            let roundtrip = before.elapsed();
-
-            // Handle the errors and extract the response.
-            let response = response_result
-                .map_err(|error| {
-                    // Emit the error into the internal events system.
-                    emit!(http_client::GotHttpWarning {
-                        error: &error,
-                        roundtrip
-                    });
-                    error
-                })
-                .context(CallRequestSnafu)?;
-
-            // Emit the response into the internal events system.
-            emit!(http_client::GotHttpResponse {
-                response: &response,
+        Box::pin(track_response(response).instrument(span.clone().or_current()))
+    }
+}
+
+async fn track_response(
+    response: client::ResponseFuture,
+) -> Result<http::Response<Body>, HttpError> {
+    // Capture the time right before we issue the request.
+    // Request doesn't start the processing until we start polling it.
+    let before = std::time::Instant::now();
+
+    // Send request and wait for the result.
+    let response_result = response.await;
+
+    // Compute the roundtrip time it took to send the request and get
+    // the response or error.
+    let roundtrip = before.elapsed();
+
+    // Handle the errors and extract the response.
+    let response = response_result
+        .map_err(|error| {
+            // Emit the error into the internal events system.
+            emit!(http_client::GotHttpWarning {
+                error: &error,
                 roundtrip
             });
-            Ok(response)
+            error
+        })
+        .context(CallRequestSnafu)?;
+
+    // Emit the response into the internal events system.
+    emit!(http_client::GotHttpResponse {
+        response: &response,
+        roundtrip
+    });
+    Ok(response)
+}
+
+/// An access token obtained from a token endpoint.
+struct AccessToken {
+    header: HeaderValue,
+    refresh_at: Option<Instant>,
+}
+
+impl AccessToken {
+    fn is_fresh(&self) -> bool {
+        self.refresh_at
+            .map_or(true, |refresh_at| Instant::now() < refresh_at)
+    }
+}
+
+/// The response of a token endpoint, as described in [RFC 6749, section 5.1][rfc6749].
+///
+/// [rfc6749]: https://datatracker.ietf.org/doc/html/rfc6749#section-5.1
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+type TokenSlot = Arc<tokio::sync::Mutex<Option<AccessToken>>>;
+
+/// Sends requests authenticated with OAuth2 access tokens, which are cached for each set of
+/// client credentials.
+#[derive(Clone)]
+struct OAuth2Client {
+    client: Client<HttpsProxyConnector, Body>,
+    user_agent: HeaderValue,
+    tokens: Arc<Mutex<Vec<(OAuth2Config, TokenSlot)>>>,
+}
+
+impl OAuth2Client {
+    async fn send<B>(
+        self,
+        request: Request<B>,
+        config: OAuth2Config,
+    ) -> Result<http::Response<Body>, HttpError>
+    where
+        B: HttpBody,
+        B::Error: Into<crate::Error>,
+    {
+        // The body is buffered, so that the request can be sent again with a new token.
+        let (parts, body) = request.into_parts();
+        let body = hyper::body::to_bytes(body)
+            .await
+            .map_err(Into::into)
+            .context(ReadRequestBodySnafu)?;
+
+        let slot = self.token_slot(&config);
+        let mut retried = false;
+        loop {
+            let token = self
+                .token(&config, &slot)
+                .await
+                .context(FetchOAuth2TokenSnafu)?;
+
+            let mut request = Request::new(Body::from(body.clone()));
+            *request.method_mut() = parts.method.clone();
+            *request.uri_mut() = parts.uri.clone();
+            *request.version_mut() = parts.version;
+            *request.headers_mut() = parts.headers.clone();
+            request.headers_mut().insert(AUTHORIZATION, token.clone());
+
+            emit!(http_client::AboutToSendHttpRequest { request: &request });
+
+            let response = track_response(self.client.request(request)).await?;
+
+            // The token may have been revoked before it expired, in which case a new one is
+            // fetched, once.
+            if response.status() == StatusCode::UNAUTHORIZED && !retried {
+                retried = true;
+                let mut cached = slot.lock().await;
+                if cached
+                    .as_ref()
+                    .map_or(false, |cached| cached.header == token)
+                {
+                    *cached = None;
+                }
+                continue;
+            }
+
+            return Ok(response);
+        }
+    }
+
+    fn token_slot(&self, config: &OAuth2Config) -> TokenSlot {
+        let mut tokens = self.tokens.lock().unwrap();
+        match tokens.iter().find(|(other, _)| other == config) {
+            Some((_, slot)) => Arc::clone(slot),
+            None => {
+                let slot = TokenSlot::default();
+                tokens.push((config.clone(), Arc::clone(&slot)));
+                slot
+            }
+        }
+    }
+
+    /// Returns the cached access token, fetching a new one if there is none or it is about to
+    /// expire.
+    async fn token(
+        &self,
+        config: &OAuth2Config,
+        slot: &TokenSlot,
+    ) -> Result<HeaderValue, OAuth2Error> {
+        // The slot stays locked while fetching, so concurrent requests wait for the same token.
+        let mut cached = slot.lock().await;
+        if let Some(token) = cached.as_ref().filter(|token| token.is_fresh()) {
+            return Ok(token.header.clone());
         }
-        .instrument(span.clone().or_current());
 
-        Box::pin(fut)
+        let token = self.fetch_token(config).await?;
+        let header = token.header.clone();
+        *cached = Some(token);
+        Ok(header)
     }
+
+    /// Fetches an access token with the client credentials grant, as described in
+    /// [RFC 6749, section 4.4][rfc6749].
+    ///
+    /// [rfc6749]: https://datatracker.ietf.org/doc/html/rfc6749#section-4.4
+    async fn fetch_token(&self, config: &OAuth2Config) -> Result<AccessToken, OAuth2Error> {
+        let uri = config
+            .token_endpoint
+            .parse::<Uri>()
+            .context(InvalidTokenEndpointSnafu)?;
+
+        let mut form = url::form_urlencoded::Serializer::new(String::new());
+        form.append_pair("grant_type", "client_credentials");
+        if !config.scopes.is_empty() {
+            form.append_pair("scope", &config.scopes.join(" "));
+        }
+
+        let mut request = Request::post(uri)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(form.finish()))
+            .expect("Token request should be valid.");
+        request.headers_mut().typed_insert(Authorization::basic(
+            config.client_id.as_str(),
+            config.client_secret.inner(),
+        ));
+        default_request_headers(&mut request, &self.user_agent);
+
+        let requested_at = Instant::now();
+        let response = self
+            .client
+            .request(request)
+            .await
+            .context(CallTokenEndpointSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .context(CallTokenEndpointSnafu)?;
+        if !status.is_success() {
+            return Err(OAuth2Error::UnexpectedTokenStatus {
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            });
+        }
+
+        parse_token_response(&body, requested_at, config.refresh_before_expiry())
+    }
+}
+
+fn parse_token_response(
+    body: &Bytes,
+    requested_at: Instant,
+    refresh_before_expiry: Duration,
+) -> Result<AccessToken, OAuth2Error> {
+    let response: TokenResponse = serde_json::from_slice(body).context(ParseTokenResponseSnafu)?;
+
+    let mut header = HeaderValue::from_str(&format!("Bearer {}", response.access_token))
+        .context(InvalidAccessTokenSnafu)?;
+    header.set_sensitive(true);
+
+    // Tokens without an expiry are used until they are rejected.
+    let refresh_at = response.expires_in.map(|expires_in| {
+        requested_at + Duration::from_secs(expires_in).saturating_sub(refresh_before_expiry)
+    });
+
+    Ok(AccessToken { header, refresh_at })
 }
 
 pub fn build_proxy_connector(
@@ -215,6 +470,7 @@ impl<B> Clone for HttpClient<B> {
     fn clone(&self) -> Self {
         Self {
             client: self.client.clone(),
+            oauth2: self.oauth2.clone(),
             user_agent: self.user_agent.clone(),
         }
     }
@@ -262,6 +518,57 @@ pub enum Auth {
         /// The bearer authentication token.
         token: SensitiveString,
     },
+
+    /// OAuth2 authentication.
+    ///
+    /// Access tokens are fetched from the token endpoint with the
+    /// [client credentials grant][grant], and passed as bearer tokens. They are cached until
+    /// shortly before they expire, and fetched again if a request is rejected with a
+    /// `401 Unauthorized` status.
+    ///
+    /// [grant]: https://datatracker.ietf.org/doc/html/rfc6749#section-4.4
+    #[serde(rename = "oauth2")]
+    OAuth2(OAuth2Config),
+}
+
+/// OAuth2 client credentials configuration.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct OAuth2Config {
+    /// The URL of the token endpoint of the authorization server.
+    #[configurable(metadata(docs::examples = "https://auth.example.com/oauth2/token"))]
+    pub token_endpoint: String,
+
+    /// The client identifier.
+    #[configurable(metadata(docs::examples = "${CLIENT_ID}"))]
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub client_id: String,
+
+    /// The client secret.
+    #[configurable(metadata(docs::examples = "${CLIENT_SECRET}"))]
+    pub client_secret: SensitiveString,
+
+    /// The scopes to request access to.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "logs:write"))]
+    pub scopes: Vec<String>,
+
+    /// How long before it expires an access token is replaced by a new one.
+    #[serde(default = "default_refresh_before_expiry_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Refresh Before Expiry"))]
+    pub refresh_before_expiry_secs: u64,
+}
+
+const fn default_refresh_before_expiry_secs() -> u64 {
+    60
+}
+
+impl OAuth2Config {
+    const fn refresh_before_expiry(&self) -> Duration {
+        Duration::from_secs(self.refresh_before_expiry_secs)
+    }
 }
 
 pub trait MaybeAuth: Sized {
@@ -279,15 +586,29 @@ impl MaybeAuth for Option<Auth> {
 }
 
 impl Auth {
+    /// Applies the credentials to the request.
+    ///
+    /// For OAuth2, the configuration is attached to the request instead, and the access token is
+    /// added by `HttpClient` when the request is sent.
     pub fn apply<B>(&self, req: &mut Request<B>) {
-        self.apply_headers_map(req.headers_mut())
+        match self {
+            Auth::OAuth2(config) => {
+                req.extensions_mut().insert(config.clone());
+            }
+            _ => self.apply_headers_map(req.headers_mut()),
+        }
     }
 
     pub fn apply_builder(&self, mut builder: Builder) -> Builder {
-        if let Some(map) = builder.headers_mut() {
-            self.apply_headers_map(map)
+        match self {
+            Auth::OAuth2(config) => builder.extension(config.clone()),
+            _ => {
+                if let Some(map) = builder.headers_mut() {
+                    self.apply_headers_map(map)
+                }
+                builder
+            }
         }
-        builder
     }
 
     pub fn apply_headers_map(&self, map: &mut HeaderMap) {
@@ -300,6 +621,8 @@ impl Auth {
                 Ok(auth) => map.typed_insert(auth),
                 Err(error) => error!(message = "Invalid bearer token.", token = %token, %error),
             },
+            // Access tokens can only be added once fetched, see `apply`.
+            Auth::OAuth2(_) => {}
         }
     }
 }
@@ -320,7 +643,16 @@ pub fn get_http_scheme_from_uri(uri: &Uri) -> &'static str {
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        convert::Infallible,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use headers::authorization::Basic;
+    use hyper::service::{make_service_fn, service_fn};
+
     use super::*;
+    use crate::test_util::next_addr;
 
     #[test]
     fn test_default_request_headers_defaults() {
@@ -351,4 +683,146 @@ mod tests {
             Some(&HeaderValue::from_static("foo"))
         );
     }
+
+    /// Starts a server issuing numbered access tokens at `/token`, and accepting any of them but
+    /// the first one on other paths, as if it had been revoked.
+    fn start_oauth2_server(expires_in: u64) -> (String, Arc<AtomicUsize>) {
+        let addr = next_addr();
+        let issued = Arc::new(AtomicUsize::new(0));
+
+        let server_issued = Arc::clone(&issued);
+        let make_service = make_service_fn(move |_| {
+            let issued = Arc::clone(&server_issued);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                    let issued = Arc::clone(&issued);
+                    async move {
+                        let response = if request.uri().path() == "/token" {
+                            let credentials = request.headers().typed_get::<Authorization<Basic>>();
+                            let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                            if credentials.map_or(false, |credentials| {
+                                credentials.username() == "vector"
+                                    && credentials.password() == "secret"
+                            }) && body == "grant_type=client_credentials&scope=read+write"
+                            {
+                                let token = issued.fetch_add(1, Ordering::SeqCst) + 1;
+                                let body = serde_json::json!({
+                                    "access_token": format!("token-{}", token),
+                                    "token_type": "bearer",
+                                    "expires_in": expires_in,
+                                });
+                                http::Response::new(Body::from(body.to_string()))
+                            } else {
+                                status_response(StatusCode::BAD_REQUEST)
+                            }
+                        } else {
+                            match request.headers().get(AUTHORIZATION) {
+                                Some(header) if header != "Bearer token-1" => {
+                                    status_response(StatusCode::OK)
+                                }
+                                _ => status_response(StatusCode::UNAUTHORIZED),
+                            }
+                        };
+                        Ok::<_, Infallible>(response)
+                    }
+                }))
+            }
+        });
+        tokio::spawn(hyper::Server::bind(&addr).serve(make_service));
+
+        (format!("http://{}", addr), issued)
+    }
+
+    fn status_response(status: StatusCode) -> http::Response<Body> {
+        http::Response::builder()
+            .status(status)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    fn oauth2_auth(address: &str, refresh_before_expiry_secs: u64) -> Auth {
+        toml::from_str(&format!(
+            r#"
+            strategy = "oauth2"
+            token_endpoint = "{}/token"
+            client_id = "vector"
+            client_secret = "secret"
+            scopes = ["read", "write"]
+            refresh_before_expiry_secs = {}
+            "#,
+            address, refresh_before_expiry_secs
+        ))
+        .unwrap()
+    }
+
+    async fn send_authenticated(client: &HttpClient, address: &str, auth: &Auth) -> StatusCode {
+        let mut request = Request::get(format!("{}/resource", address))
+            .body(Body::empty())
+            .unwrap();
+        auth.apply(&mut request);
+        client.send(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn oauth2_caches_token_and_refetches_on_unauthorized() {
+        let (address, issued) = start_oauth2_server(3600);
+        let auth = oauth2_auth(&address, 60);
+        let client = HttpClient::new(None, &Default::default()).unwrap();
+
+        // The first token is rejected, so a second one is fetched and then reused.
+        assert_eq!(
+            send_authenticated(&client, &address, &auth).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            send_authenticated(&client, &address, &auth).await,
+            StatusCode::OK
+        );
+        assert_eq!(issued.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn oauth2_refreshes_token_before_expiry() {
+        let (address, issued) = start_oauth2_server(30);
+        let auth = oauth2_auth(&address, 60);
+        let client = HttpClient::new(None, &Default::default()).unwrap();
+
+        assert_eq!(
+            send_authenticated(&client, &address, &auth).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            send_authenticated(&client, &address, &auth).await,
+            StatusCode::OK
+        );
+        assert_eq!(issued.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn oauth2_token_endpoint_rejection_is_not_retriable() {
+        let (address, issued) = start_oauth2_server(3600);
+        let auth = match oauth2_auth(&address, 60) {
+            Auth::OAuth2(config) => Auth::OAuth2(OAuth2Config {
+                client_secret: "wrong".to_owned().into(),
+                ..config
+            }),
+            _ => unreachable!(),
+        };
+        let client = HttpClient::new(None, &Default::default()).unwrap();
+
+        let mut request = Request::get(format!("{}/resource", address))
+            .body(Body::empty())
+            .unwrap();
+        auth.apply(&mut request);
+        let error = client.send(request).await.unwrap_err();
+
+        assert!(matches!(
+            error,
+            HttpError::FetchOAuth2Token {
+                source: OAuth2Error::UnexpectedTokenStatus { status, .. }
+            } if status == StatusCode::BAD_REQUEST
+        ));
+        assert!(!error.is_retriable());
+        assert_eq!(issued.load(Ordering::SeqCst), 0);
+    }
 }
@@ -0,0 +1,65 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+use crate::sources::auditd::AuditdParseError;
+
+#[derive(Debug)]
+pub struct AuditdReadError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for AuditdReadError {
+    fn emit(self) {
+        error!(
+            message = "Could not read audit records.",
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::READER_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AuditdInvalidRecordError {
+    pub error: AuditdParseError,
+    pub text: String,
+}
+
+impl InternalEvent for AuditdInvalidRecordError {
+    fn emit(self) {
+        error!(
+            message = "Invalid audit record, discarding.",
+            error = %self.error,
+            text = %self.text,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::PARSER_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AuditdRecordsLost;
+
+impl InternalEvent for AuditdRecordsLost {
+    fn emit(self) {
+        warn!(
+            message =
+                "The kernel discarded audit records because the netlink socket buffer was full.",
+            internal_log_rate_limit = true,
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use crate::emit;
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, UNINTENTIONAL};
+
+#[derive(Debug)]
+pub struct HttpSinkInvalidRenderedValueError<'a> {
+    pub field: &'a str,
+    pub error: crate::Error,
+}
+
+impl<'a> InternalEvent for HttpSinkInvalidRenderedValueError<'a> {
+    fn emit(self) {
+        let reason = "Template rendered an invalid request value.";
+
+        error!(
+            message = reason,
+            field = %self.field,
+            error = %self.error,
+            error_code = "invalid_rendered_value",
+            error_type = error_type::TEMPLATE_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_rendered_value",
+            "error_type" => error_type::TEMPLATE_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
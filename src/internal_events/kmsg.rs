@@ -0,0 +1,65 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+use crate::sources::kmsg::KmsgParseError;
+
+#[derive(Debug)]
+pub struct KmsgReadError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for KmsgReadError {
+    fn emit(self) {
+        error!(
+            message = "Could not read from the kernel ring buffer.",
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::READER_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KmsgInvalidRecordError {
+    pub error: KmsgParseError,
+    pub text: String,
+}
+
+impl InternalEvent for KmsgInvalidRecordError {
+    fn emit(self) {
+        error!(
+            message = "Invalid record from the kernel ring buffer, discarding.",
+            error = %self.error,
+            text = %self.text,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::PARSER_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KmsgRecordsOverwritten;
+
+impl InternalEvent for KmsgRecordsOverwritten {
+    fn emit(self) {
+        warn!(
+            message =
+                "Records in the kernel ring buffer were overwritten before they could be read.",
+            internal_log_rate_limit = true,
+        );
+    }
+}
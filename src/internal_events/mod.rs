@@ -9,6 +9,8 @@ mod amqp;
 mod apache_metrics;
 #[cfg(feature = "api")]
 mod api;
+#[cfg(all(target_os = "linux", feature = "sources-auditd"))]
+mod auditd;
 #[cfg(feature = "aws-core")]
 mod aws;
 #[cfg(feature = "sinks-aws_cloudwatch_logs")]
@@ -64,6 +66,8 @@ mod http;
 pub mod http_client;
 #[cfg(feature = "sources-utils-http-client")]
 mod http_client_source;
+#[cfg(feature = "sinks-http")]
+mod http_sink;
 #[cfg(feature = "sinks-influxdb")]
 mod influxdb;
 #[cfg(feature = "sources-internal_logs")]
@@ -74,6 +78,8 @@ mod internal_metrics;
 mod journald;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
 mod kafka;
+#[cfg(all(target_os = "linux", feature = "sources-kmsg"))]
+mod kmsg;
 #[cfg(feature = "sources-kubernetes_logs")]
 mod kubernetes_logs;
 mod log_to_metric;
@@ -86,18 +92,22 @@ mod lua;
 mod metric_to_log;
 #[cfg(feature = "sources-mongodb_metrics")]
 mod mongodb_metrics;
-#[cfg(feature = "sinks-nats")]
+#[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
 mod open;
 mod parser;
+#[cfg(feature = "sinks-postgres")]
+mod postgres_sink;
 #[cfg(feature = "sources-postgresql_metrics")]
 mod postgresql_metrics;
+#[cfg(feature = "sources-postgresql_query")]
+mod postgresql_query;
 mod process;
 #[cfg(any(feature = "sources-prometheus", feature = "sinks-prometheus"))]
 mod prometheus;
-#[cfg(feature = "sinks-pulsar")]
+#[cfg(any(feature = "sources-pulsar", feature = "sinks-pulsar"))]
 mod pulsar;
 #[cfg(feature = "sources-redis")]
 mod redis;
@@ -120,7 +130,7 @@ mod template;
 mod throttle;
 mod udp;
 mod unix;
-#[cfg(feature = "sinks-websocket")]
+#[cfg(any(feature = "sources-websocket", feature = "sinks-websocket"))]
 mod websocket;
 
 #[cfg(any(
@@ -142,6 +152,8 @@ pub(crate) use self::amqp::*;
 pub(crate) use self::apache_metrics::*;
 #[cfg(feature = "api")]
 pub(crate) use self::api::*;
+#[cfg(all(target_os = "linux", feature = "sources-auditd"))]
+pub(crate) use self::auditd::*;
 #[cfg(feature = "aws-core")]
 pub(crate) use self::aws::*;
 #[cfg(feature = "sinks-aws_cloudwatch_logs")]
@@ -203,6 +215,8 @@ pub(crate) use self::host_metrics::*;
 pub(crate) use self::http::*;
 #[cfg(feature = "sources-utils-http-client")]
 pub(crate) use self::http_client_source::*;
+#[cfg(feature = "sinks-http")]
+pub(crate) use self::http_sink::*;
 #[cfg(feature = "sinks-influxdb")]
 pub(crate) use self::influxdb::*;
 #[cfg(feature = "sources-internal_logs")]
@@ -213,6 +227,8 @@ pub(crate) use self::internal_metrics::*;
 pub(crate) use self::journald::*;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
 pub(crate) use self::kafka::*;
+#[cfg(all(target_os = "linux", feature = "sources-kmsg"))]
+pub(crate) use self::kmsg::*;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub(crate) use self::kubernetes_logs::*;
 pub(crate) use self::log_to_metric::*;
@@ -224,16 +240,20 @@ pub(crate) use self::loki::*;
 pub(crate) use self::lua::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
-#[cfg(feature = "sinks-nats")]
+#[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 pub(crate) use self::nats::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
 pub(crate) use self::parser::*;
+#[cfg(feature = "sinks-postgres")]
+pub(crate) use self::postgres_sink::*;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub(crate) use self::postgresql_metrics::*;
+#[cfg(feature = "sources-postgresql_query")]
+pub(crate) use self::postgresql_query::*;
 #[cfg(any(feature = "sources-prometheus", feature = "sinks-prometheus"))]
 pub(crate) use self::prometheus::*;
-#[cfg(feature = "sinks-pulsar")]
+#[cfg(any(feature = "sources-pulsar", feature = "sinks-pulsar"))]
 pub(crate) use self::pulsar::*;
 #[cfg(feature = "sources-redis")]
 pub(crate) use self::redis::*;
@@ -255,7 +275,7 @@ pub(crate) use self::tag_cardinality_limit::*;
 pub(crate) use self::throttle::*;
 #[cfg(unix)]
 pub(crate) use self::unix::*;
-#[cfg(feature = "sinks-websocket")]
+#[cfg(any(feature = "sources-websocket", feature = "sinks-websocket"))]
 pub(crate) use self::websocket::*;
 #[cfg(windows)]
 pub(crate) use self::windows::*;
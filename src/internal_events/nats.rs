@@ -37,3 +37,51 @@ impl InternalEvent for NatsEventSendError {
         counter!("send_errors_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct NatsJetStreamPublishError {
+    pub error: crate::nats::JetStreamError,
+}
+
+impl InternalEvent for NatsJetStreamPublishError {
+    fn emit(self) {
+        let reason = "Failed to publish message to JetStream.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct NatsAcknowledgementError {
+    pub error: Error,
+}
+
+impl InternalEvent for NatsAcknowledgementError {
+    fn emit(self) {
+        error!(
+            message = "Unable to acknowledge JetStream message.",
+            error = %self.error,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            error_code = io_error_code(&self.error),
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "error_code" => io_error_code(&self.error),
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use crate::emit;
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, UNINTENTIONAL};
+
+#[derive(Debug)]
+pub struct PostgresSinkConversionError<'a> {
+    pub column: &'a str,
+    pub error: &'a dyn std::error::Error,
+}
+
+impl<'a> InternalEvent for PostgresSinkConversionError<'a> {
+    fn emit(self) {
+        let reason = "Event field could not be converted to the column type.";
+
+        error!(
+            message = reason,
+            column = %self.column,
+            error = %self.error,
+            error_code = "column_conversion_failed",
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "column_conversion_failed",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct PostgresSinkDescribeTableError<'a> {
+    pub table: &'a str,
+    pub error: &'a dyn std::error::Error,
+}
+
+impl<'a> InternalEvent for PostgresSinkDescribeTableError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read the column types of the table, retrying.",
+            table = %self.table,
+            error = %self.error,
+            error_code = "describe_table_failed",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "describe_table_failed",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
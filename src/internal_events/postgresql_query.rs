@@ -0,0 +1,85 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
+
+#[derive(Debug)]
+pub struct PostgresqlQueryError<'a> {
+    pub error: String,
+    pub endpoint: &'a str,
+}
+
+impl<'a> InternalEvent for PostgresqlQueryError<'a> {
+    fn emit(self) {
+        error!(
+            message = "PostgreSQL query error.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            endpoint = %self.endpoint,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct PostgresqlQueryConversionError<'a> {
+    pub error: String,
+    pub column: &'a str,
+    /// Whether the row is dropped, or read again by the next query.
+    pub drop_row: bool,
+}
+
+impl<'a> InternalEvent for PostgresqlQueryConversionError<'a> {
+    fn emit(self) {
+        let reason = "Failed to convert PostgreSQL column.";
+        error!(
+            message = reason,
+            error = %self.error,
+            column = %self.column,
+            drop_row = %self.drop_row,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        if self.drop_row {
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PostgresqlQueryCheckpointError {
+    pub error: std::io::Error,
+    pub filename: String,
+}
+
+impl InternalEvent for PostgresqlQueryCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Could not write PostgreSQL query checkpoint.",
+            filename = ?self.filename,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::IO_FAILED,
+        );
+    }
+}
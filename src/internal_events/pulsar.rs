@@ -56,3 +56,69 @@ impl<F: std::fmt::Display> InternalEvent for PulsarPropertyExtractionError<F> {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct PulsarReadError {
+    pub error: pulsar::Error,
+}
+
+impl InternalEvent for PulsarReadError {
+    fn emit(self) {
+        error!(
+            message = "Failed to read message.",
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct PulsarAcknowledgmentError {
+    pub error: pulsar::Error,
+}
+
+impl InternalEvent for PulsarAcknowledgmentError {
+    fn emit(self) {
+        error!(
+            message = "Unable to acknowledge message.",
+            error = %self.error,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct PulsarNegativeAcknowledgmentError {
+    pub error: pulsar::Error,
+}
+
+impl InternalEvent for PulsarNegativeAcknowledgmentError {
+    fn emit(self) {
+        error!(
+            message = "Unable to negatively acknowledge message.",
+            error = %self.error,
+            error_type = error_type::COMMAND_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::COMMAND_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
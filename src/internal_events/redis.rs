@@ -34,3 +34,52 @@ impl InternalEvent for RedisReceiveEventError {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct RedisStreamMissingFieldError<'a> {
+    pub field: &'a str,
+    pub id: &'a str,
+}
+
+impl<'a> InternalEvent for RedisStreamMissingFieldError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Stream entry is missing the message field, skipping it.",
+            field = %self.field,
+            id = %self.id,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct RedisAcknowledgementError {
+    pub error: redis::RedisError,
+}
+
+impl InternalEvent for RedisAcknowledgementError {
+    fn emit(self) {
+        let error_code = self.error.code().unwrap_or("UNKNOWN").to_string();
+        error!(
+            message = "Unable to acknowledge stream entries.",
+            error = %self.error,
+            error_code = %error_code,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => error_code,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
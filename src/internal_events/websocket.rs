@@ -4,7 +4,7 @@ use std::fmt::Debug;
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
-use vector_common::internal_event::{error_stage, error_type};
+use vector_common::internal_event::error_type;
 
 #[derive(Debug)]
 pub struct WsConnectionEstablished;
@@ -23,6 +23,7 @@ impl InternalEvent for WsConnectionEstablished {
 #[derive(Debug)]
 pub struct WsConnectionFailedError {
     pub error: Box<dyn Error>,
+    pub stage: &'static str,
 }
 
 impl InternalEvent for WsConnectionFailedError {
@@ -32,14 +33,14 @@ impl InternalEvent for WsConnectionFailedError {
             error = %self.error,
             error_code = "ws_connection_error",
             error_type = error_type::CONNECTION_FAILED,
-            stage = error_stage::SENDING,
+            stage = self.stage,
             internal_log_rate_limit = true,
         );
         counter!(
             "component_errors_total", 1,
             "error_code" => "ws_connection_failed",
             "error_type" => error_type::CONNECTION_FAILED,
-            "stage" => error_stage::SENDING,
+            "stage" => self.stage,
         );
     }
 
@@ -65,6 +66,7 @@ impl InternalEvent for WsConnectionShutdown {
 #[derive(Debug)]
 pub struct WsConnectionError {
     pub error: tokio_tungstenite::tungstenite::Error,
+    pub stage: &'static str,
 }
 
 impl InternalEvent for WsConnectionError {
@@ -74,14 +76,14 @@ impl InternalEvent for WsConnectionError {
             error = %self.error,
             error_code = "ws_connection_error",
             error_type = error_type::WRITER_FAILED,
-            stage = error_stage::SENDING,
+            stage = self.stage,
             internal_log_rate_limit = true,
         );
         counter!(
             "component_errors_total", 1,
             "error_code" => "ws_connection_error",
             "error_type" => error_type::WRITER_FAILED,
-            "stage" => error_stage::SENDING,
+            "stage" => self.stage,
         );
     }
 
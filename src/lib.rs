@@ -46,6 +46,9 @@ pub mod cli;
 #[allow(unreachable_pub)]
 pub mod components;
 pub mod conditions;
+#[cfg(feature = "api-client")]
+#[allow(unreachable_pub)]
+pub(crate) mod control;
 pub mod dns;
 #[cfg(feature = "docker")]
 pub mod docker;
@@ -64,6 +67,7 @@ pub mod app;
 pub mod async_read;
 #[cfg(feature = "aws-config")]
 pub mod aws;
+pub(crate) mod buffer;
 #[allow(unreachable_pub)]
 pub mod codecs;
 pub(crate) mod common;
@@ -84,9 +88,17 @@ pub mod list;
 #[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 pub(crate) mod nats;
 pub mod net;
+#[cfg(any(
+    feature = "sources-postgresql_metrics",
+    feature = "sources-postgresql_query",
+    feature = "sinks-postgres"
+))]
+pub(crate) mod postgresql;
 #[allow(unreachable_pub)]
 pub(crate) mod proto;
 pub mod providers;
+#[cfg(any(feature = "sources-pulsar", feature = "sinks-pulsar"))]
+pub(crate) mod pulsar;
 pub mod secrets;
 pub mod serde;
 #[cfg(windows)]
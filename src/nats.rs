@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use nkeys::error::Error as NKeysError;
+use serde::{de::DeserializeOwned, Deserialize};
 use snafu::{ResultExt, Snafu};
 use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
@@ -184,6 +187,73 @@ pub(crate) fn from_tls_auth_config(
     }
 }
 
+/// How long to wait for the JetStream API to respond to a request.
+const JETSTREAM_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Snafu)]
+pub enum JetStreamError {
+    #[snafu(display("JetStream request failed: {}", source))]
+    Request { source: std::io::Error },
+    #[snafu(display("JetStream request timed out"))]
+    Timeout,
+    #[snafu(display("invalid JetStream response: {}", source))]
+    InvalidResponse { source: serde_json::Error },
+    #[snafu(display("JetStream API error {}: {}", code, description))]
+    Api { code: u16, description: String },
+}
+
+#[derive(Debug, Deserialize)]
+struct JetStreamApiError {
+    code: u16,
+    #[serde(default)]
+    description: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JetStreamErrorResponse {
+    error: Option<JetStreamApiError>,
+}
+
+/// The acknowledgement returned by a stream for a published message.
+#[derive(Debug, Deserialize)]
+pub(crate) struct JetStreamPublishAck {
+    pub(crate) stream: String,
+    pub(crate) seq: u64,
+    #[serde(default)]
+    pub(crate) duplicate: bool,
+}
+
+/// Sends a request to a JetStream subject, and decodes the response.
+///
+/// Both publishing to a stream and calling the JetStream API are plain requests, the server replying
+/// with either the expected JSON body or an `error` object.
+pub(crate) async fn jetstream_request<T: DeserializeOwned>(
+    connection: &nats::asynk::Connection,
+    subject: &str,
+    payload: impl AsRef<[u8]>,
+) -> Result<T, JetStreamError> {
+    let response = tokio::time::timeout(
+        JETSTREAM_REQUEST_TIMEOUT,
+        connection.request(subject, payload),
+    )
+    .await
+    .map_err(|_| JetStreamError::Timeout)?
+    .context(RequestSnafu)?;
+
+    parse_jetstream_response(&response.data)
+}
+
+fn parse_jetstream_response<T: DeserializeOwned>(data: &[u8]) -> Result<T, JetStreamError> {
+    let response: JetStreamErrorResponse =
+        serde_json::from_slice(data).context(InvalidResponseSnafu)?;
+    match response.error {
+        Some(JetStreamApiError { code, description }) => {
+            Err(JetStreamError::Api { code, description })
+        }
+        None => serde_json::from_slice(data).context(InvalidResponseSnafu),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -327,4 +397,22 @@ mod tests {
         )
         .unwrap_err();
     }
+
+    #[test]
+    fn jetstream_publish_ack() {
+        let ack: JetStreamPublishAck =
+            parse_jetstream_response(br#"{"stream":"events","seq":42}"#).unwrap();
+        assert_eq!(ack.stream, "events");
+        assert_eq!(ack.seq, 42);
+        assert!(!ack.duplicate);
+    }
+
+    #[test]
+    fn jetstream_api_error() {
+        let error = parse_jetstream_response::<JetStreamPublishAck>(
+            br#"{"error":{"code":503,"description":"no responders"}}"#,
+        )
+        .unwrap_err();
+        assert!(matches!(error, JetStreamError::Api { code: 503, .. }));
+    }
 }
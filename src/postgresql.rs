@@ -0,0 +1,162 @@
+use std::path::PathBuf;
+
+use openssl::{
+    error::ErrorStack,
+    ssl::{SslConnector, SslMethod},
+};
+use postgres_openssl::MakeTlsConnector;
+use snafu::{ResultExt, Snafu};
+use tokio_postgres::{
+    config::{ChannelBinding, Host, SslMode, TargetSessionAttrs},
+    Client, Config, Error as PgError, NoTls,
+};
+use vector_config::configurable_component;
+
+#[derive(Debug, Snafu)]
+pub enum PostgresqlConnectError {
+    #[snafu(display("failed to create tls connector: {}", source))]
+    TlsFailed { source: ErrorStack },
+    #[snafu(display("failed to connect ({}): {}", endpoint, source))]
+    ConnectionFailed { source: PgError, endpoint: String },
+}
+
+/// Configuration of TLS when connecting to PostgreSQL.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PostgresqlTlsConfig {
+    /// Absolute path to an additional CA certificate file.
+    ///
+    /// The certificate must be in the DER or PEM (X.509) format.
+    #[configurable(metadata(docs::examples = "certs/ca.pem"))]
+    pub ca_file: PathBuf,
+}
+
+/// Opens a new connection, spawning the connection task in the background.
+pub async fn connect(
+    config: &Config,
+    tls: Option<&PostgresqlTlsConfig>,
+) -> Result<Client, PostgresqlConnectError> {
+    let endpoint = || ConnectionFailedSnafu {
+        endpoint: config_to_endpoint(config),
+    };
+    match tls {
+        Some(tls) => {
+            let mut builder =
+                SslConnector::builder(SslMethod::tls_client()).context(TlsFailedSnafu)?;
+            builder
+                .set_ca_file(tls.ca_file.clone())
+                .context(TlsFailedSnafu)?;
+            let connector = MakeTlsConnector::new(builder.build());
+
+            let (client, connection) = config
+                .connect(connector)
+                .await
+                .with_context(|_| endpoint())?;
+            tokio::spawn(connection);
+            Ok(client)
+        }
+        None => {
+            let (client, connection) = config.connect(NoTls).await.with_context(|_| endpoint())?;
+            tokio::spawn(connection);
+            Ok(client)
+        }
+    }
+}
+
+/// Renders the connection configuration as a URL, leaving out the user and password.
+pub fn config_to_endpoint(config: &Config) -> String {
+    let mut params: Vec<(&'static str, String)> = vec![];
+
+    // options
+    if let Some(options) = config.get_options() {
+        params.push(("options", options.to_string()));
+    }
+
+    // application_name
+    if let Some(name) = config.get_application_name() {
+        params.push(("application_name", name.to_string()));
+    }
+
+    // ssl_mode, ignore default value (SslMode::Prefer)
+    match config.get_ssl_mode() {
+        SslMode::Disable => params.push(("sslmode", "disable".to_string())),
+        SslMode::Prefer => {} // default, ignore
+        SslMode::Require => params.push(("sslmode", "require".to_string())),
+        // non_exhaustive enum
+        _ => {
+            warn!("Unknown variant of \"SslMode\".");
+        }
+    };
+
+    // host
+    for host in config.get_hosts() {
+        match host {
+            Host::Tcp(host) => params.push(("host", host.to_string())),
+            #[cfg(unix)]
+            Host::Unix(path) => params.push(("host", path.to_string_lossy().to_string())),
+        }
+    }
+
+    // port
+    for port in config.get_ports() {
+        params.push(("port", port.to_string()));
+    }
+
+    // connect_timeout
+    if let Some(connect_timeout) = config.get_connect_timeout() {
+        params.push(("connect_timeout", connect_timeout.as_secs().to_string()));
+    }
+
+    // keepalives, ignore default value (true)
+    if !config.get_keepalives() {
+        params.push(("keepalives", "1".to_owned()));
+    }
+
+    // keepalives_idle, ignore default value (2 * 60 * 60)
+    let keepalives_idle = config.get_keepalives_idle().as_secs();
+    if keepalives_idle != 2 * 60 * 60 {
+        params.push(("keepalives_idle", keepalives_idle.to_string()));
+    }
+
+    // target_session_attrs, ignore default value (TargetSessionAttrs::Any)
+    match config.get_target_session_attrs() {
+        TargetSessionAttrs::Any => {} // default, ignore
+        TargetSessionAttrs::ReadWrite => {
+            params.push(("target_session_attrs", "read-write".to_owned()))
+        }
+        // non_exhaustive enum
+        _ => {
+            warn!("Unknown variant of \"TargetSessionAttr\".");
+        }
+    }
+
+    // channel_binding, ignore default value (ChannelBinding::Prefer)
+    match config.get_channel_binding() {
+        ChannelBinding::Disable => params.push(("channel_binding", "disable".to_owned())),
+        ChannelBinding::Prefer => {} // default, ignore
+        ChannelBinding::Require => params.push(("channel_binding", "require".to_owned())),
+        // non_exhaustive enum
+        _ => {
+            warn!("Unknown variant of \"ChannelBinding\".");
+        }
+    }
+
+    format!(
+        "postgresql:///{}?{}",
+        config.get_dbname().unwrap_or(""),
+        params
+            .into_iter()
+            .map(|(k, v)| format!(
+                "{}={}",
+                percent_encoding(k.as_bytes()),
+                percent_encoding(v.as_bytes())
+            ))
+            .collect::<Vec<String>>()
+            .join("&")
+    )
+}
+
+fn percent_encoding(input: &'_ [u8]) -> String {
+    percent_encoding::percent_encode(input, percent_encoding::NON_ALPHANUMERIC).to_string()
+}
@@ -1,4 +1,9 @@
-use std::{borrow::Cow, fmt::Debug, marker::PhantomData, num::NonZeroUsize};
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Display},
+    marker::PhantomData,
+    num::NonZeroUsize,
+};
 
 use rand::random;
 
@@ -36,7 +41,7 @@ where
     S: Service<BatchKinesisRequest<R>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: Debug + Into<crate::Error> + Send,
+    S::Error: Debug + Display + Into<crate::Error> + Send,
     R: Record + Send + Sync + Unpin + Clone + 'static,
 {
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
@@ -89,7 +94,7 @@ where
     S: Service<BatchKinesisRequest<R>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: Debug + Into<crate::Error> + Send,
+    S::Error: Debug + Display + Into<crate::Error> + Send,
     R: Record + Send + Sync + Unpin + Clone + 'static,
 {
     async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
@@ -64,7 +64,7 @@ pub(crate) struct DatadogMetricsSink<S> {
 impl<S> DatadogMetricsSink<S>
 where
     S: Service<DatadogMetricsRequest> + Send,
-    S::Error: fmt::Debug + Send + 'static,
+    S::Error: fmt::Debug + fmt::Display + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse,
 {
@@ -147,7 +147,7 @@ where
 impl<S> StreamSink<Event> for DatadogMetricsSink<S>
 where
     S: Service<DatadogMetricsRequest> + Send,
-    S::Error: fmt::Debug + Send + 'static,
+    S::Error: fmt::Debug + fmt::Display + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse,
 {
@@ -1,4 +1,7 @@
-use std::{fmt::Debug, sync::Arc};
+use std::{
+    fmt::{Debug, Display},
+    sync::Arc,
+};
 
 use async_trait::async_trait;
 use futures_util::{
@@ -79,7 +82,7 @@ pub struct TracesSink<S> {
 impl<S> TracesSink<S>
 where
     S: Service<TraceApiRequest> + Send,
-    S::Error: Debug + Send + 'static,
+    S::Error: Debug + Display + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse,
 {
@@ -142,7 +145,7 @@ where
 impl<S> StreamSink<Event> for TracesSink<S>
 where
     S: Service<TraceApiRequest> + Send,
-    S::Error: Debug + Send + 'static,
+    S::Error: Debug + Display + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse,
 {
@@ -66,6 +66,7 @@ impl ElasticsearchCommon {
                 user: user.clone(),
                 password: password.clone(),
             }),
+            Some(ElasticsearchAuth::OAuth2(oauth2)) => Some(Auth::OAuth2(oauth2.clone())),
             _ => None,
         };
         let uri = endpoint.parse::<UriSerde>()?;
@@ -73,7 +74,7 @@ impl ElasticsearchCommon {
         let base_url = uri.uri.to_string().trim_end_matches('/').to_owned();
 
         let aws_auth = match &config.auth {
-            Some(ElasticsearchAuth::Basic { .. }) | None => None,
+            Some(ElasticsearchAuth::Basic { .. } | ElasticsearchAuth::OAuth2(_)) | None => None,
             Some(ElasticsearchAuth::Aws(aws)) => {
                 let region = config
                     .aws
@@ -92,6 +92,9 @@ pub struct ElasticsearchConfig {
 
     /// Whether or not to retry successful requests containing partial failures.
     ///
+    /// Only the documents that failed with a retriable error, such as backpressure, are sent again.
+    /// Otherwise, these documents are rejected like the ones that failed with any other error.
+    ///
     /// To avoid duplicates in Elasticsearch, please use option `id_key`.
     #[serde(default)]
     #[configurable(metadata(docs::advanced))]
@@ -487,7 +490,12 @@ impl SinkConfig for ElasticsearchConfig {
                 let endpoint = common.base_url.clone();
 
                 let http_request_builder = HttpRequestBuilder::new(&common, self);
-                let service = ElasticsearchService::new(client.clone(), http_request_builder);
+                let service = ElasticsearchService::new(
+                    client.clone(),
+                    http_request_builder,
+                    common.request_builder.clone(),
+                    self.request_retry_partial,
+                );
 
                 (endpoint, service)
             })
@@ -27,6 +27,7 @@ use vector_config::configurable_component;
 use crate::aws::AwsAuthentication;
 use crate::{
     event::{EventRef, LogEvent},
+    http::OAuth2Config,
     internal_events::TemplateRenderingError,
     template::{Template, TemplateParseError},
 };
@@ -52,6 +53,10 @@ pub enum ElasticsearchAuth {
 
     /// Amazon OpenSearch Service-specific authentication.
     Aws(AwsAuthentication),
+
+    /// OAuth2 authentication, with access tokens obtained with the client credentials grant.
+    #[serde(rename = "oauth2")]
+    OAuth2(OAuth2Config),
 }
 
 /// Elasticsearch Indexing mode.
@@ -3,15 +3,15 @@ use vector_common::{json_size::JsonSize, request_metadata::RequestMetadata};
 use vector_core::EstimatedJsonEncodedSizeOf;
 
 use crate::{
-    event::{EventFinalizers, Finalizable},
+    event::Finalizable,
     sinks::{
         elasticsearch::{
             encoder::{ElasticsearchEncoder, ProcessedEvent},
             service::ElasticsearchRequest,
         },
         util::{
-            metadata::RequestMetadataBuilder, request_builder::EncodeResult, Compression,
-            RequestBuilder,
+            metadata::RequestMetadataBuilder, partial_retry::PartialRetryItems,
+            request_builder::EncodeResult, Compression, RequestBuilder,
         },
     },
 };
@@ -23,7 +23,7 @@ pub struct ElasticsearchRequestBuilder {
 }
 
 pub struct Metadata {
-    finalizers: EventFinalizers,
+    items: PartialRetryItems<ProcessedEvent>,
     batch_size: usize,
     events_byte_size: JsonSize,
 }
@@ -56,8 +56,18 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
 
         let metadata_builder = RequestMetadataBuilder::from_events(&events);
 
+        // Each document keeps its own finalizers, so that its outcome in the bulk response can be
+        // reported on its own, and a copy of it in case it has to be sent again.
+        let items = events
+            .iter_mut()
+            .map(|event| {
+                let finalizers = event.take_finalizers();
+                (event.clone(), finalizers)
+            })
+            .collect::<Vec<_>>();
+
         let es_metadata = Metadata {
-            finalizers: events.take_finalizers(),
+            items: PartialRetryItems::new(items),
             batch_size: events.len(),
             events_byte_size,
         };
@@ -72,7 +82,7 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
     ) -> Self::Request {
         ElasticsearchRequest {
             payload: payload.into_payload(),
-            finalizers: es_metadata.finalizers,
+            items: es_metadata.items,
             batch_size: es_metadata.batch_size,
             events_byte_size: es_metadata.events_byte_size,
             metadata,
@@ -5,17 +5,20 @@ use crate::{
     http::HttpError,
     sinks::{
         elasticsearch::service::ElasticsearchResponse,
-        util::retries::{RetryAction, RetryLogic},
+        util::{
+            partial_retry::ItemOutcome,
+            retries::{RetryAction, RetryLogic},
+        },
     },
 };
 
 #[derive(Deserialize, Debug)]
-struct EsResultResponse {
+pub(super) struct EsResultResponse {
     items: Vec<EsResultItem>,
 }
 
 impl EsResultResponse {
-    fn parse(body: &str) -> Result<Self, String> {
+    pub(super) fn parse(body: &str) -> Result<Self, String> {
         serde_json::from_str::<EsResultResponse>(body).map_err(|json_error| {
             format!(
                 "some messages failed, could not parse response, error: {}",
@@ -24,6 +27,43 @@ impl EsResultResponse {
         })
     }
 
+    /// Returns the number of items in the response, which matches the number of documents in the
+    /// request.
+    pub(super) fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns the outcome of each item, in the order of the documents in the request.
+    ///
+    /// Items that failed with backpressure or a server error are only retried if `retry_partial`
+    /// is set, and rejected otherwise.
+    pub(super) fn outcomes(&self, retry_partial: bool) -> impl Iterator<Item = ItemOutcome> + '_ {
+        self.items.iter().map(move |item| {
+            let result = item.result();
+            let status = result
+                .status
+                .and_then(|status| StatusCode::from_u16(status).ok());
+            let status_code = status.map(|status| status.as_u16());
+            let error = result
+                .error
+                .as_ref()
+                .map(|error| format!("error type: {}, reason: {}", error.err_type, error.reason));
+
+            match status {
+                Some(status) if status.is_success() => ItemOutcome::Delivered,
+                None if error.is_none() => ItemOutcome::Delivered,
+                Some(status)
+                    if retry_partial
+                        && (status == StatusCode::TOO_MANY_REQUESTS
+                            || status.is_server_error()) =>
+                {
+                    ItemOutcome::Retry { status_code, error }
+                }
+                _ => ItemOutcome::Rejected { status_code, error },
+            }
+        })
+    }
+
     /// Returns iterator over status codes for items and optional error details.
     fn iter_status(&self) -> impl Iterator<Item = (StatusCode, Option<&EsErrorDetails>)> {
         self.items.iter().filter_map(|item| {
@@ -38,7 +78,9 @@ impl EsResultResponse {
     /// If partial retry is enabled and we don't retry, this is because there is no retriable error in the
     /// response, thus all errors are equally interesting so logging the first is sufficient.
     /// When partial retry is disabled, we don't retry on any error.
-    fn get_error_reason(&self, body: &str) -> String {
+    ///
+    /// The details of each failed item are also reported on its own event.
+    pub(super) fn get_error_reason(&self, body: &str) -> String {
         match self
             .items
             .iter()
@@ -124,6 +166,8 @@ impl RetryLogic for ElasticsearchRetryLogic {
                                 // We will retry if there exists at least one item that
                                 // failed with a retriable error.
                                 // Those are backpressure and server errors.
+                                // Only those items are sent again, as the service already
+                                // finalized the other items of the request.
                                 if let Some((status, error)) =
                                     resp.iter_status().find(|(status, _)| {
                                         *status == StatusCode::TOO_MANY_REQUESTS
@@ -207,6 +251,43 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn reports_outcome_of_each_item() {
+        let json = r#"{"took":34,"errors":true,"items":[{"index":{"_index":"test","_id":"1","status":201}},{"index":{"_index":"test","_id":"2","status":429,"error":{"type":"es_rejected_execution_exception","reason":"rejected execution"}}},{"create":{"_index":"test","_id":"3","status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse"}}}]}"#;
+        let resp = EsResultResponse::parse(json).unwrap();
+        assert_eq!(resp.len(), 3);
+
+        let throttled = Some(
+            "error type: es_rejected_execution_exception, reason: rejected execution".to_owned(),
+        );
+        let rejected = ItemOutcome::Rejected {
+            status_code: Some(400),
+            error: Some("error type: mapper_parsing_exception, reason: failed to parse".to_owned()),
+        };
+        assert_eq!(
+            resp.outcomes(true).collect::<Vec<_>>(),
+            vec![
+                ItemOutcome::Delivered,
+                ItemOutcome::Retry {
+                    status_code: Some(429),
+                    error: throttled.clone(),
+                },
+                rejected.clone(),
+            ]
+        );
+        assert_eq!(
+            resp.outcomes(false).collect::<Vec<_>>(),
+            vec![
+                ItemOutcome::Delivered,
+                ItemOutcome::Rejected {
+                    status_code: Some(429),
+                    error: throttled,
+                },
+                rejected,
+            ]
+        );
+    }
+
     #[test]
     fn get_index_error_reason() {
         let json = "{\"took\":185,\"errors\":true,\"items\":[{\"index\":{\"_index\":\"test-hgw28jv10u\",\"_type\":\"log_lines\",\"_id\":\"3GhQLXEBE62DvOOUKdFH\",\"status\":400,\"error\":{\"type\":\"illegal_argument_exception\",\"reason\":\"mapper [message] of different type, current_type [long], merged_type [text]\"}}}]}";
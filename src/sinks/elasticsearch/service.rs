@@ -1,5 +1,6 @@
 use std::{
     collections::HashMap,
+    iter,
     sync::Arc,
     task::{Context, Poll},
 };
@@ -15,7 +16,11 @@ use vector_common::{
     json_size::JsonSize,
     request_metadata::{MetaDescriptive, RequestMetadata},
 };
-use vector_core::{internal_event::CountByteSize, stream::DriverResponse, ByteSizeOf};
+use vector_core::{
+    internal_event::{CountByteSize, EventsSent, InternalEventHandle as _, Output},
+    stream::DriverResponse,
+    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+};
 
 use crate::sinks::elasticsearch::sign_request;
 use crate::{
@@ -23,16 +28,21 @@ use crate::{
     http::{Auth, HttpClient},
     sinks::util::{
         http::{HttpBatchService, RequestConfig},
-        Compression, ElementCount,
+        partial_retry::{ItemOutcome, PartialRetryItems},
+        Compression, ElementCount, RequestBuilder,
     },
 };
 
-use super::{ElasticsearchCommon, ElasticsearchConfig};
+use super::{
+    encoder::ProcessedEvent, request_builder::ElasticsearchRequestBuilder, retry::EsResultResponse,
+    ElasticsearchCommon, ElasticsearchConfig,
+};
 
 #[derive(Clone, Debug)]
 pub struct ElasticsearchRequest {
     pub payload: Bytes,
-    pub finalizers: EventFinalizers,
+    /// The documents of the request, which are finalized as the bulk responses report them.
+    pub items: PartialRetryItems<ProcessedEvent>,
     pub batch_size: usize,
     pub events_byte_size: JsonSize,
     pub metadata: RequestMetadata,
@@ -40,7 +50,7 @@ pub struct ElasticsearchRequest {
 
 impl ByteSizeOf for ElasticsearchRequest {
     fn allocated_bytes(&self) -> usize {
-        self.payload.allocated_bytes() + self.finalizers.allocated_bytes()
+        self.payload.allocated_bytes()
     }
 }
 
@@ -52,7 +62,8 @@ impl ElementCount for ElasticsearchRequest {
 
 impl Finalizable for ElasticsearchRequest {
     fn take_finalizers(&mut self) -> EventFinalizers {
-        std::mem::take(&mut self.finalizers)
+        // The documents are finalized individually by the service, see `complete_items`.
+        EventFinalizers::default()
     }
 }
 
@@ -68,12 +79,16 @@ pub struct ElasticsearchService {
         BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>>,
         ElasticsearchRequest,
     >,
+    request_builder: ElasticsearchRequestBuilder,
+    retry_partial: bool,
 }
 
 impl ElasticsearchService {
     pub fn new(
         http_client: HttpClient<Body>,
         http_request_builder: HttpRequestBuilder,
+        request_builder: ElasticsearchRequestBuilder,
+        retry_partial: bool,
     ) -> ElasticsearchService {
         let http_request_builder = Arc::new(http_request_builder);
         let batch_service = HttpBatchService::new(http_client, move |req| {
@@ -82,7 +97,11 @@ impl ElasticsearchService {
                 Box::pin(async move { request_builder.build_request(req).await });
             future
         });
-        ElasticsearchService { batch_service }
+        ElasticsearchService {
+            batch_service,
+            request_builder,
+            retry_partial,
+        }
     }
 }
 
@@ -160,6 +179,10 @@ impl DriverResponse for ElasticsearchResponse {
     fn events_sent(&self) -> CountByteSize {
         CountByteSize(self.batch_size, self.events_byte_size)
     }
+
+    fn status_code(&self) -> Option<u16> {
+        Some(self.http_response.status().as_u16())
+    }
 }
 
 impl Service<ElasticsearchRequest> for ElasticsearchService {
@@ -173,15 +196,40 @@ impl Service<ElasticsearchRequest> for ElasticsearchService {
     }
 
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
-    fn call(&mut self, req: ElasticsearchRequest) -> Self::Future {
+    fn call(&mut self, mut req: ElasticsearchRequest) -> Self::Future {
         let mut http_service = self.batch_service.clone();
+        let request_builder = self.request_builder.clone();
+        let retry_partial = self.retry_partial;
         Box::pin(async move {
-            http_service.ready().await?;
-            let batch_size = req.batch_size;
-            let events_byte_size = req.events_byte_size;
-            let http_response = http_service.call(req).await?;
+            let items = req.items.clone();
+            let result = async {
+                http_service.ready().await?;
+                if items.is_partial() {
+                    // A previous attempt already delivered or rejected some of the documents, so
+                    // only the ones that are still pending are sent again.
+                    req.payload = request_builder
+                        .encode_events(items.pending())?
+                        .into_payload();
+                }
+                http_service.call(req).await
+            };
+
+            let http_response = match result.await {
+                Ok(http_response) => http_response,
+                Err(error) => {
+                    items.record_failure(EventStatus::Rejected, None, Some(error.to_string()));
+                    return Err(error);
+                }
+            };
 
-            let event_status = get_event_status(&http_response);
+            let (event_status, delivered) = complete_items(&items, &http_response, retry_partial);
+            // The driver only counts the documents sent by a request that ends up delivered, so
+            // the ones delivered by an attempt that leaves others to retry, or to be rejected, are
+            // counted here.
+            if event_status != EventStatus::Delivered && delivered.0 > 0 {
+                register!(EventsSent::from(Output(None))).emit(delivered);
+            }
+            let CountByteSize(batch_size, events_byte_size) = delivered;
             Ok(ElasticsearchResponse {
                 event_status,
                 http_response,
@@ -192,18 +240,121 @@ impl Service<ElasticsearchRequest> for ElasticsearchService {
     }
 }
 
-fn get_event_status(response: &Response<Bytes>) -> EventStatus {
+/// Applies the outcome of a bulk request to its documents, and returns the status of the request
+/// along with the documents delivered by this attempt.
+///
+/// When the request succeeds, each document is delivered, rejected or kept to be retried according
+/// to its own result in the response. The request is then errored while documents are left to
+/// retry, and rejected once none are if any document was rejected along the way. Otherwise, the
+/// documents are all kept pending, and finalized with the status of the last attempt if no retry
+/// delivers them.
+fn complete_items<T: EstimatedJsonEncodedSizeOf>(
+    items: &PartialRetryItems<T>,
+    response: &Response<Bytes>,
+    retry_partial: bool,
+) -> (EventStatus, CountByteSize) {
+    let nothing_delivered = CountByteSize(0, JsonSize::zero());
     let status = response.status();
-    if status.is_success() {
-        let body = String::from_utf8_lossy(response.body());
-        if body.contains("\"errors\":true") {
-            EventStatus::Rejected
+    if !status.is_success() {
+        let event_status = if status.is_server_error() {
+            EventStatus::Errored
         } else {
-            EventStatus::Delivered
+            EventStatus::Rejected
+        };
+        items.record_failure(event_status, Some(status.as_u16()), None);
+        return (event_status, nothing_delivered);
+    }
+
+    let body = String::from_utf8_lossy(response.body());
+    let outcomes = if !body.contains("\"errors\":true") {
+        let outcomes = iter::repeat(ItemOutcome::Delivered).take(items.len());
+        items.complete(outcomes)
+    } else {
+        match EsResultResponse::parse(&body) {
+            Ok(result) if result.len() == items.len() => {
+                items.complete(result.outcomes(retry_partial))
+            }
+            Ok(_) => {
+                let error =
+                    "the number of items in the response doesn't match the request".to_owned();
+                items.record_failure(EventStatus::Rejected, Some(status.as_u16()), Some(error));
+                return (EventStatus::Rejected, nothing_delivered);
+            }
+            Err(error) => {
+                items.record_failure(EventStatus::Rejected, Some(status.as_u16()), Some(error));
+                return (EventStatus::Rejected, nothing_delivered);
+            }
         }
-    } else if status.is_server_error() {
+    };
+
+    let event_status = if outcomes.pending > 0 {
         EventStatus::Errored
-    } else {
+    } else if outcomes.rejected > 0 {
         EventStatus::Rejected
+    } else {
+        EventStatus::Delivered
+    };
+    (event_status, outcomes.delivered)
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::finalization::{
+        BatchNotifier, BatchStatus, BatchStatusReceiver, EventFinalizer,
+    };
+
+    use super::*;
+
+    fn item(value: &str) -> ((String, EventFinalizers), BatchStatusReceiver) {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let finalizers = EventFinalizers::new(EventFinalizer::new(batch));
+        ((value.to_owned(), finalizers), receiver)
+    }
+
+    fn bulk_response(body: &'static str) -> Response<Bytes> {
+        Response::builder()
+            .status(200)
+            .body(Bytes::from_static(body.as_bytes()))
+            .unwrap()
+    }
+
+    #[test]
+    fn rejects_request_whose_items_all_failed() {
+        let (first, mut first_rx) = item("first");
+        let (second, mut second_rx) = item("second");
+        let items = PartialRetryItems::new([first, second]);
+        let response = bulk_response(
+            r#"{"took":3,"errors":true,"items":[{"index":{"_index":"test","_id":"1","status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse"}}},{"index":{"_index":"test","_id":"2","status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse"}}}]}"#,
+        );
+
+        let (status, delivered) = complete_items(&items, &response, true);
+        assert_eq!(status, EventStatus::Rejected);
+        assert_eq!(delivered.0, 0);
+        assert!(items.is_empty());
+        assert_eq!(first_rx.try_recv(), Ok(BatchStatus::Rejected));
+        assert_eq!(second_rx.try_recv(), Ok(BatchStatus::Rejected));
+    }
+
+    #[test]
+    fn counts_items_delivered_by_each_attempt() {
+        let (first, mut first_rx) = item("first");
+        let (second, mut second_rx) = item("second");
+        let items = PartialRetryItems::new([first, second]);
+
+        let response = bulk_response(
+            r#"{"took":3,"errors":true,"items":[{"index":{"_index":"test","_id":"1","status":201}},{"index":{"_index":"test","_id":"2","status":429,"error":{"type":"es_rejected_execution_exception","reason":"rejected execution"}}}]}"#,
+        );
+        let (status, delivered) = complete_items(&items, &response, true);
+        assert_eq!(status, EventStatus::Errored);
+        assert_eq!(delivered.0, 1);
+        assert_eq!(first_rx.try_recv(), Ok(BatchStatus::Delivered));
+
+        let response = bulk_response(
+            r#"{"took":3,"errors":true,"items":[{"index":{"_index":"test","_id":"2","status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse"}}}]}"#,
+        );
+        let (status, delivered) = complete_items(&items, &response, true);
+        assert_eq!(status, EventStatus::Rejected);
+        assert_eq!(delivered.0, 0);
+        assert_eq!(second_rx.try_recv(), Ok(BatchStatus::Rejected));
     }
 }
@@ -35,8 +35,11 @@ use crate::{
     template::Template,
 };
 mod bytes_path;
+mod rotation;
 
 use bytes_path::BytesPath;
+use rotation::FileState;
+pub use rotation::{RotationConfig, RotationSuffix};
 
 /// Configuration for the `file` sink.
 #[serde_as]
@@ -74,6 +77,9 @@ pub struct FileSinkConfig {
     )]
     pub compression: Compression,
 
+    #[configurable(derived)]
+    pub rotation: Option<RotationConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -90,6 +96,7 @@ impl GenerateConfig for FileSinkConfig {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Default::default(),
+            rotation: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -169,6 +176,18 @@ impl OutFile {
     }
 }
 
+/// An open file, along with the state its rotation depends on.
+struct OpenFile {
+    file: OutFile,
+    state: FileState,
+}
+
+impl OpenFile {
+    async fn close(&mut self) -> Result<(), std::io::Error> {
+        self.file.close().await
+    }
+}
+
 #[async_trait::async_trait]
 impl SinkConfig for FileSinkConfig {
     async fn build(
@@ -196,8 +215,9 @@ pub struct FileSink {
     transformer: Transformer,
     encoder: Encoder<Framer>,
     idle_timeout: Duration,
-    files: ExpiringHashMap<Bytes, OutFile>,
+    files: ExpiringHashMap<Bytes, OpenFile>,
     compression: Compression,
+    rotation: Option<RotationConfig>,
     events_sent: Registered<EventsSent>,
 }
 
@@ -214,6 +234,7 @@ impl FileSink {
             idle_timeout: config.idle_timeout,
             files: ExpiringHashMap::default(),
             compression: config.compression,
+            rotation: config.rotation.clone(),
             events_sent: register!(EventsSent::from(Output(None))),
         })
     }
@@ -319,6 +340,19 @@ impl FileSink {
             }
         };
 
+        if let Some(rotation) = &self.rotation {
+            if let Err(error) = rotate_if_due(&mut self.files, rotation, &path).await {
+                // Events keep being written to the current file.
+                emit!(FileIoError {
+                    code: "failed_rotating_file",
+                    message: "Failed to rotate the file.",
+                    error,
+                    path: &path,
+                    dropped_events: 0,
+                });
+            }
+        }
+
         let next_deadline = self.deadline_at();
         trace!(message = "Computed next deadline.", next_deadline = ?next_deadline, path = ?path);
 
@@ -345,7 +379,24 @@ impl FileSink {
                 }
             };
 
-            let outfile = OutFile::new(file, self.compression);
+            let state = match file.metadata().await {
+                Ok(metadata) => FileState::from_metadata(&metadata),
+                Err(error) => {
+                    emit!(FileIoError {
+                        code: "failed_opening_file",
+                        message: "Unable to read the file metadata.",
+                        error,
+                        path: &path,
+                        dropped_events: 1,
+                    });
+                    event.metadata().update_status(EventStatus::Errored);
+                    return;
+                }
+            };
+            let outfile = OpenFile {
+                file: OutFile::new(file, self.compression),
+                state,
+            };
 
             self.files.insert_at(path.clone(), outfile, next_deadline);
             emit!(FileOpen {
@@ -357,8 +408,10 @@ impl FileSink {
         trace!(message = "Writing an event to file.", path = ?path);
         let event_size = event.estimated_json_encoded_size_of();
         let finalizers = event.take_finalizers();
-        match write_event_to_file(file, event, &self.transformer, &mut self.encoder).await {
+        match write_event_to_file(&mut file.file, event, &self.transformer, &mut self.encoder).await
+        {
             Ok(byte_size) => {
+                file.state.size += byte_size as u64;
                 finalizers.update_status(EventStatus::Delivered);
                 self.events_sent.emit(CountByteSize(1, event_size));
                 emit!(FileBytesSent {
@@ -380,6 +433,38 @@ impl FileSink {
     }
 }
 
+/// Rotates the file at `path` if it is due, closing it first if it is open.
+async fn rotate_if_due(
+    files: &mut ExpiringHashMap<Bytes, OpenFile>,
+    rotation: &RotationConfig,
+    path: &Bytes,
+) -> std::io::Result<()> {
+    match files.get(path) {
+        Some(file) => {
+            if !rotation.is_due(&file.state) {
+                return Ok(());
+            }
+            if let Some((mut file, _)) = files.remove(path) {
+                emit!(FileOpen { count: files.len() });
+                file.close().await?;
+            }
+        }
+        // The file could have been left over the limits by a previous run, or closed when idle.
+        None => match fs::metadata(BytesPath::new(path.clone())).await {
+            Ok(metadata) => {
+                if !rotation.is_due(&FileState::from_metadata(&metadata)) {
+                    return Ok(());
+                }
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(error) => return Err(error),
+        },
+    }
+
+    trace!(message = "Rotating file.", ?path);
+    rotation.rotate(BytesPath::new(path.clone()).as_ref()).await
+}
+
 async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
     let parent = path.as_ref().parent();
 
@@ -452,6 +537,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: None,
             acknowledgements: Default::default(),
         };
 
@@ -474,6 +560,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::Gzip,
+            rotation: None,
             acknowledgements: Default::default(),
         };
 
@@ -496,6 +583,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::Zstd,
+            rotation: None,
             acknowledgements: Default::default(),
         };
 
@@ -523,6 +611,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: None,
             acknowledgements: Default::default(),
         };
 
@@ -600,6 +689,7 @@ mod tests {
             idle_timeout: Duration::from_secs(1),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: None,
             acknowledgements: Default::default(),
         };
 
@@ -643,6 +733,43 @@ mod tests {
         sink_handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn rotation_by_size() {
+        let directory = temp_dir();
+        let path = directory.join("test.log");
+
+        let config = FileSinkConfig {
+            path: path.to_string_lossy().to_string().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            compression: Compression::None,
+            rotation: Some(RotationConfig {
+                max_file_size: Some(100),
+                max_files: Some(2),
+                ..Default::default()
+            }),
+            acknowledgements: Default::default(),
+        };
+
+        // Each line is 10 bytes long once framed, so every 10 lines fill a file.
+        let input = (0..45)
+            .map(|i| format!("line {:04}", i))
+            .collect::<Vec<_>>();
+
+        run_assert_log_sink(config, input.clone()).await;
+
+        assert_eq!(lines_from_file(&path), input[40..].to_vec());
+        assert_eq!(
+            lines_from_file(directory.join("test.log.1")),
+            input[30..40].to_vec()
+        );
+        assert_eq!(
+            lines_from_file(directory.join("test.log.2")),
+            input[20..30].to_vec()
+        );
+        assert!(!directory.join("test.log.3").exists());
+    }
+
     async fn run_assert_log_sink(config: FileSinkConfig, events: Vec<String>) {
         run_assert_sink(
             config,
@@ -0,0 +1,341 @@
+//! Size and age based rotation of the files written by the `file` sink, along with the retention
+//! of the rotated files.
+
+use std::{
+    ffi::OsString,
+    fs::Metadata,
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use chrono::{NaiveDateTime, Utc};
+use serde_with::serde_as;
+use tokio::fs;
+use vector_config::configurable_component;
+
+/// The format of the suffix of timestamped files, which sorts chronologically.
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Rotation configuration.
+///
+/// When a file is rotated, it is closed and renamed with a suffix, and events are written to a
+/// new file at the original path. Each path rendered from the `path` template is rotated
+/// independently.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RotationConfig {
+    /// The size, in bytes, at which a file is rotated.
+    ///
+    /// For compressed files, this is the amount of data written before compression.
+    #[configurable(metadata(docs::examples = 104857600))]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_file_size: Option<u64>,
+
+    /// The age at which a file is rotated.
+    #[serde(default)]
+    #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+    #[serde(rename = "max_age_secs")]
+    #[configurable(metadata(docs::examples = 86400))]
+    #[configurable(metadata(docs::human_name = "Max Age"))]
+    pub max_age: Option<Duration>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub suffix: RotationSuffix,
+
+    /// The maximum number of rotated files to keep for each path.
+    ///
+    /// The oldest rotated files are deleted first.
+    #[configurable(metadata(docs::examples = 10))]
+    pub max_files: Option<usize>,
+
+    /// The maximum total size, in bytes, of the rotated files to keep for each path.
+    ///
+    /// The oldest rotated files are deleted first.
+    #[configurable(metadata(docs::examples = 1073741824))]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_total_bytes: Option<u64>,
+}
+
+/// The suffix appended to the name of rotated files.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RotationSuffix {
+    /// A number, where `.1` is the most recently rotated file.
+    ///
+    /// Previously rotated files are renamed to make room for it, like `logrotate` does.
+    #[default]
+    Numbered,
+
+    /// The UTC time of the rotation, such as `.20230601T120000Z`.
+    Timestamp,
+}
+
+/// The state of an open file that rotation decisions are based on.
+#[derive(Clone, Copy, Debug)]
+pub struct FileState {
+    pub size: u64,
+    pub created_at: SystemTime,
+}
+
+impl FileState {
+    pub fn from_metadata(metadata: &Metadata) -> Self {
+        let size = metadata.len();
+        // Files that are empty are as good as new, whatever their actual age.
+        let created_at = if size == 0 {
+            SystemTime::now()
+        } else {
+            metadata.created().unwrap_or_else(|_| SystemTime::now())
+        };
+        Self { size, created_at }
+    }
+}
+
+impl RotationConfig {
+    /// Whether the file has to be rotated before more events are written to it.
+    pub fn is_due(&self, state: &FileState) -> bool {
+        if state.size == 0 {
+            return false;
+        }
+
+        let too_big = self
+            .max_file_size
+            .map_or(false, |max_file_size| state.size >= max_file_size);
+        let too_old = self.max_age.map_or(false, |max_age| {
+            state
+                .created_at
+                .elapsed()
+                .map_or(false, |age| age >= max_age)
+        });
+
+        too_big || too_old
+    }
+
+    /// Renames the file at `path`, which must already be closed, and then deletes the oldest
+    /// rotated files exceeding the retention limits.
+    pub async fn rotate(&self, path: &Path) -> io::Result<()> {
+        let rotated_path = match self.suffix {
+            RotationSuffix::Numbered => {
+                // Shift the already rotated files, starting from the oldest one.
+                for (number, rotated) in self.rotated_files(path).await? {
+                    fs::rename(rotated.path, with_suffix(path, &(number + 1).to_string())).await?;
+                }
+                with_suffix(path, "1")
+            }
+            RotationSuffix::Timestamp => {
+                let timestamp = Utc::now().format(TIMESTAMP_FORMAT).to_string();
+                let mut rotated_path = with_suffix(path, &timestamp);
+                let mut collisions = 0;
+                while fs::try_exists(&rotated_path).await? {
+                    collisions += 1;
+                    rotated_path = with_suffix(path, &format!("{}-{}", timestamp, collisions));
+                }
+                rotated_path
+            }
+        };
+        fs::rename(path, &rotated_path).await?;
+
+        self.apply_retention(path).await
+    }
+
+    async fn apply_retention(&self, path: &Path) -> io::Result<()> {
+        if self.max_files.is_none() && self.max_total_bytes.is_none() {
+            return Ok(());
+        }
+
+        let rotated_files = self.rotated_files(path).await?;
+        let mut count = rotated_files.len();
+        let mut total_bytes = rotated_files
+            .iter()
+            .map(|(_, rotated)| rotated.size)
+            .sum::<u64>();
+
+        for (_, rotated) in rotated_files {
+            let too_many = self.max_files.map_or(false, |max_files| count > max_files);
+            let too_big = self
+                .max_total_bytes
+                .map_or(false, |max_total_bytes| total_bytes > max_total_bytes);
+            if !too_many && !too_big {
+                break;
+            }
+
+            fs::remove_file(&rotated.path).await?;
+            count -= 1;
+            total_bytes -= rotated.size;
+        }
+
+        Ok(())
+    }
+
+    /// Lists the rotated files of `path`, from the oldest to the most recent one, along with the
+    /// key they are ordered by.
+    async fn rotated_files(&self, path: &Path) -> io::Result<Vec<(u64, RotatedFile)>> {
+        let (directory, name) = match (path.parent(), path.file_name()) {
+            (Some(directory), Some(name)) => (directory, name.to_string_lossy()),
+            _ => return Ok(Vec::new()),
+        };
+        let directory = if directory.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            directory
+        };
+        let prefix = format!("{}.", name);
+
+        let mut rotated_files = Vec::new();
+        let mut entries = fs::read_dir(directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let file_name = entry.file_name();
+            let suffix = match file_name.to_str().and_then(|n| n.strip_prefix(&prefix)) {
+                Some(suffix) => suffix,
+                None => continue,
+            };
+            let key = match self.suffix {
+                RotationSuffix::Numbered => suffix.parse::<u64>().ok(),
+                RotationSuffix::Timestamp => parse_timestamp_suffix(suffix),
+            };
+            if let Some(key) = key {
+                let size = entry.metadata().await?.len();
+                rotated_files.push((
+                    key,
+                    RotatedFile {
+                        path: entry.path(),
+                        size,
+                    },
+                ));
+            }
+        }
+
+        match self.suffix {
+            // The higher the number, the older the file.
+            RotationSuffix::Numbered => rotated_files.sort_by(|a, b| b.0.cmp(&a.0)),
+            RotationSuffix::Timestamp => {
+                rotated_files.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.path.cmp(&b.1.path)))
+            }
+        }
+
+        Ok(rotated_files)
+    }
+}
+
+#[derive(Debug)]
+struct RotatedFile {
+    path: PathBuf,
+    size: u64,
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Parses the suffix of a timestamped file into the timestamp, in seconds, and the optional
+/// counter added when several rotations happen within the same second.
+fn parse_timestamp_suffix(suffix: &str) -> Option<u64> {
+    let (timestamp, collision) = match suffix.split_once('-') {
+        Some((timestamp, collision)) => (timestamp, collision.parse::<u64>().ok()?),
+        None => (suffix, 0),
+    };
+    let timestamp = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()?;
+    let seconds = timestamp.timestamp();
+    // Only the ordering matters, so the counter is folded into the low bits.
+    u64::try_from(seconds)
+        .ok()
+        .map(|seconds| (seconds << 16) | collision.min(0xffff))
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    async fn write(path: &Path, contents: &str) {
+        fs::write(path, contents).await.unwrap();
+    }
+
+    async fn read(path: &Path) -> String {
+        fs::read_to_string(path).await.unwrap()
+    }
+
+    #[test]
+    fn is_due() {
+        let config = RotationConfig {
+            max_file_size: Some(10),
+            max_age: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        let now = SystemTime::now();
+        let state = |size, age| FileState {
+            size,
+            created_at: now - Duration::from_secs(age),
+        };
+
+        assert!(!config.is_due(&state(0, 120)));
+        assert!(!config.is_due(&state(9, 0)));
+        assert!(config.is_due(&state(10, 0)));
+        assert!(config.is_due(&state(1, 120)));
+        assert!(!RotationConfig::default().is_due(&state(100, 120)));
+    }
+
+    #[tokio::test]
+    async fn rotate_numbered() {
+        let directory = temp_dir();
+        fs::create_dir_all(&directory).await.unwrap();
+        let path = directory.join("test.log");
+        let config = RotationConfig {
+            max_files: Some(2),
+            ..Default::default()
+        };
+
+        for contents in ["first", "second", "third"] {
+            write(&path, contents).await;
+            config.rotate(&path).await.unwrap();
+        }
+
+        assert!(!fs::try_exists(&path).await.unwrap());
+        assert_eq!(read(&directory.join("test.log.1")).await, "third");
+        assert_eq!(read(&directory.join("test.log.2")).await, "second");
+        assert!(!fs::try_exists(directory.join("test.log.3")).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn rotate_timestamp() {
+        let directory = temp_dir();
+        fs::create_dir_all(&directory).await.unwrap();
+        let path = directory.join("test.log");
+        let config = RotationConfig {
+            suffix: RotationSuffix::Timestamp,
+            max_total_bytes: Some(10),
+            ..Default::default()
+        };
+
+        for contents in ["aaaa", "bbbb", "cccc"] {
+            write(&path, contents).await;
+            config.rotate(&path).await.unwrap();
+        }
+
+        let mut contents = Vec::new();
+        for (_, rotated) in config.rotated_files(&path).await.unwrap() {
+            contents.push(read(&rotated.path).await);
+        }
+        assert_eq!(contents, vec!["bbbb", "cccc"]);
+    }
+
+    #[test]
+    fn timestamp_suffix_order() {
+        let first = parse_timestamp_suffix("20230601T120000Z").unwrap();
+        let second = parse_timestamp_suffix("20230601T120000Z-1").unwrap();
+        let third = parse_timestamp_suffix("20230601T120001Z").unwrap();
+        assert!(first < second);
+        assert!(second < third);
+        assert_eq!(parse_timestamp_suffix("1"), None);
+        assert_eq!(parse_timestamp_suffix("20230601T120000Z.gz"), None);
+    }
+}
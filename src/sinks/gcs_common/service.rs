@@ -94,6 +94,10 @@ impl DriverResponse for GcsResponse {
     fn bytes_sent(&self) -> Option<usize> {
         Some(self.metadata.request_encoded_size())
     }
+
+    fn status_code(&self) -> Option<u16> {
+        Some(self.inner.status().as_u16())
+    }
 }
 
 impl Service<GcsRequest> for GcsService {
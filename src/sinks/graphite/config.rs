@@ -0,0 +1,253 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use async_trait::async_trait;
+use snafu::Snafu;
+use vector_common::internal_event::Protocol;
+use vector_config::{component::GenerateConfig, configurable_component};
+use vector_core::{
+    config::{AcknowledgementsConfig, Input},
+    sink::VectorSink,
+};
+
+use crate::{
+    config::{SinkConfig, SinkContext},
+    internal_events::SocketMode,
+    sinks::{
+        util::{
+            metric_points::default_summary_quantiles,
+            service::net::{NetworkConnector, TcpConnectorConfig, UdpConnectorConfig},
+            statistic::validate_quantiles,
+            BatchConfig, SinkBatchSettings,
+        },
+        Healthcheck,
+    },
+};
+
+use super::{
+    encoder::GraphiteEncoder, request_builder::GraphiteRequestBuilder, service::GraphiteService,
+    sink::GraphiteSink,
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("The `pickle` format can only be sent over TCP."))]
+    PickleOverUdp,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GraphiteDefaultBatchSettings;
+
+impl SinkBatchSettings for GraphiteDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1000);
+    const MAX_BYTES: Option<usize> = None;
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `graphite` sink.
+#[configurable_component(sink("graphite"))]
+#[derive(Clone, Debug)]
+pub struct GraphiteSinkConfig {
+    /// Sets the default namespace for any metrics sent.
+    ///
+    /// This namespace is only used if a metric has no existing namespace. When a namespace is
+    /// present, it is used as a prefix to the metric name, and separated with a period (`.`).
+    #[configurable(metadata(docs::examples = "service"))]
+    pub default_namespace: Option<String>,
+
+    #[serde(flatten)]
+    pub mode: Mode,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub format: GraphiteFormat,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tag_format: GraphiteTagFormat,
+
+    /// The list of quantiles to calculate when sending distribution metrics.
+    #[serde(default = "default_summary_quantiles")]
+    pub quantiles: Vec<f64>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<GraphiteDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// Socket mode.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The type of socket to use."))]
+pub enum Mode {
+    /// Send over TCP.
+    Tcp(TcpConnectorConfig),
+
+    /// Send over UDP.
+    Udp(UdpConnectorConfig),
+}
+
+impl Mode {
+    const fn as_socket_mode(&self) -> SocketMode {
+        match self {
+            Self::Tcp(_) => SocketMode::Tcp,
+            Self::Udp(_) => SocketMode::Udp,
+        }
+    }
+
+    fn as_connector(&self) -> NetworkConnector {
+        match self {
+            Self::Tcp(config) => config.as_connector(),
+            Self::Udp(config) => config.as_connector(),
+        }
+    }
+}
+
+/// The protocol that metrics are sent with.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphiteFormat {
+    /// The [plaintext protocol][plaintext], with one `<path> <value> <timestamp>` line per data
+    /// point.
+    ///
+    /// Carbon listens for it on port 2003 by default.
+    ///
+    /// [plaintext]: https://graphite.readthedocs.io/en/latest/feeding-carbon.html#the-plaintext-protocol
+    #[default]
+    Plaintext,
+
+    /// The [pickle protocol][pickle], which sends each request as a length-prefixed, pickled list
+    /// of data points.
+    ///
+    /// This is more efficient than the plaintext protocol, and is only supported over TCP. Carbon
+    /// listens for it on port 2004 by default.
+    ///
+    /// [pickle]: https://graphite.readthedocs.io/en/latest/feeding-carbon.html#the-pickle-protocol
+    Pickle,
+}
+
+/// How metric tags are encoded.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphiteTagFormat {
+    /// Tags are flattened into the path, as `<name>.<key>.<value>` segments in the order of their
+    /// keys, followed by the statistic for metrics with several values.
+    ///
+    /// This works with any version of Graphite, and with existing dashboards that are based on
+    /// paths.
+    #[default]
+    Path,
+
+    /// Tags are sent as [tagged series][tags], as in `<name>;<key>=<value>`.
+    ///
+    /// This requires Graphite 1.1 or later.
+    ///
+    /// [tags]: https://graphite.readthedocs.io/en/latest/tags.html
+    Tagged,
+}
+
+fn default_address() -> SocketAddr {
+    SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 2003)
+}
+
+impl GenerateConfig for GraphiteSinkConfig {
+    fn generate_config() -> toml::Value {
+        let address = default_address();
+
+        toml::Value::try_from(Self {
+            default_namespace: None,
+            mode: Mode::Tcp(TcpConnectorConfig::from_address(
+                address.ip().to_string(),
+                address.port(),
+            )),
+            format: GraphiteFormat::default(),
+            tag_format: GraphiteTagFormat::default(),
+            quantiles: default_summary_quantiles(),
+            batch: Default::default(),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait]
+impl SinkConfig for GraphiteSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let socket_mode = self.mode.as_socket_mode();
+        if self.format == GraphiteFormat::Pickle && socket_mode == SocketMode::Udp {
+            return Err(Box::new(BuildError::PickleOverUdp));
+        }
+        validate_quantiles(&self.quantiles)?;
+        let batcher_settings = self.batch.into_batcher_settings()?;
+
+        let encoder = GraphiteEncoder::new(
+            self.default_namespace.clone(),
+            self.tag_format,
+            self.quantiles.clone(),
+        );
+        let request_builder = GraphiteRequestBuilder::new(encoder, self.format, socket_mode);
+        let protocol = Protocol::from(socket_mode.as_str());
+
+        let connector = self.mode.as_connector();
+        let service = connector.service();
+        let healthcheck = connector.healthcheck();
+
+        let sink = GraphiteSink::new(
+            GraphiteService::from_transport(service),
+            batcher_settings,
+            request_builder,
+            protocol,
+        );
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GraphiteSinkConfig>();
+    }
+
+    #[tokio::test]
+    async fn pickle_requires_tcp() {
+        let config = toml::from_str::<GraphiteSinkConfig>(
+            r#"
+            mode = "udp"
+            address = "127.0.0.1:2004"
+            format = "pickle"
+        "#,
+        )
+        .unwrap();
+
+        let error = config
+            .build(SinkContext::new_test())
+            .await
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The `pickle` format can only be sent over TCP."
+        );
+    }
+}
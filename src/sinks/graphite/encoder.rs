@@ -0,0 +1,292 @@
+use std::fmt::Write as _;
+
+use chrono::Utc;
+use vector_core::event::Metric;
+
+use super::config::{GraphiteFormat, GraphiteTagFormat};
+use crate::sinks::util::{
+    encode_namespace,
+    metric_points::{metric_points, MetricPoint},
+};
+
+/// A single data point, as stored by Graphite.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct GraphitePoint {
+    pub(super) path: String,
+    pub(super) value: f64,
+    pub(super) timestamp: i64,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct GraphiteEncoder {
+    default_namespace: Option<String>,
+    tag_format: GraphiteTagFormat,
+    quantiles: Vec<f64>,
+}
+
+impl GraphiteEncoder {
+    pub(super) const fn new(
+        default_namespace: Option<String>,
+        tag_format: GraphiteTagFormat,
+        quantiles: Vec<f64>,
+    ) -> Self {
+        Self {
+            default_namespace,
+            tag_format,
+            quantiles,
+        }
+    }
+
+    /// Converts a metric into the data points that represent it.
+    pub(super) fn points(&self, metric: &Metric) -> Vec<GraphitePoint> {
+        let namespace = metric.namespace().or(self.default_namespace.as_deref());
+        let name = sanitize(&encode_namespace(namespace, '.', metric.name()), true);
+        let timestamp = metric.timestamp().unwrap_or_else(Utc::now).timestamp();
+
+        metric_points(metric.value(), &self.quantiles)
+            .into_iter()
+            .map(|point| GraphitePoint {
+                path: self.path(&name, metric, &point),
+                value: point.value,
+                timestamp,
+            })
+            .collect()
+    }
+
+    fn path(&self, name: &str, metric: &Metric, point: &MetricPoint) -> String {
+        let tags = metric
+            .tags()
+            .into_iter()
+            .flat_map(|tags| tags.iter_single());
+        match self.tag_format {
+            GraphiteTagFormat::Path => {
+                let mut path = name.to_owned();
+                for (key, value) in tags {
+                    // Writing to a `String` never fails.
+                    _ = write!(path, ".{}.{}", sanitize(key, false), sanitize(value, false));
+                }
+                point.name(&path, '.')
+            }
+            GraphiteTagFormat::Tagged => {
+                let mut path = point.name(name, '.');
+                for (key, value) in tags.filter(|(_, value)| !value.is_empty()) {
+                    _ = write!(path, ";{}={}", sanitize(key, false), sanitize(value, false));
+                }
+                path
+            }
+        }
+    }
+}
+
+/// Replaces the characters that have a special meaning in paths or tags, or that the protocols
+/// cannot carry, with `_`.
+///
+/// Periods separate the segments of a path, so they are only kept in metric names.
+fn sanitize(segment: &str, keep_periods: bool) -> String {
+    segment
+        .chars()
+        .map(|c| match c {
+            '.' if keep_periods => c,
+            c if c.is_alphanumeric() || matches!(c, '-' | '_' | ':') => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+/// Encodes points into a request payload in the given format.
+pub(super) fn encode_points(format: GraphiteFormat, points: &[GraphitePoint]) -> Vec<u8> {
+    match format {
+        GraphiteFormat::Plaintext => {
+            let mut payload = Vec::new();
+            for point in points {
+                write_plaintext(point, &mut payload);
+            }
+            payload
+        }
+        GraphiteFormat::Pickle => encode_pickle(points),
+    }
+}
+
+/// Writes a point as a line of the plaintext protocol.
+pub(super) fn write_plaintext(point: &GraphitePoint, buf: &mut Vec<u8>) {
+    use std::io::Write as _;
+
+    // Writing to a `Vec` never fails.
+    _ = writeln!(buf, "{} {} {}", point.path, point.value, point.timestamp);
+}
+
+// Opcodes of the pickle protocol, version 2, which Carbon's unpickler supports.
+const PROTO: u8 = 0x80;
+const EMPTY_LIST: u8 = b']';
+const MARK: u8 = b'(';
+const APPENDS: u8 = b'e';
+const BINUNICODE: u8 = b'X';
+const BININT: u8 = b'J';
+const LONG1: u8 = 0x8a;
+const BINFLOAT: u8 = b'G';
+const TUPLE2: u8 = 0x86;
+const STOP: u8 = b'.';
+
+/// Encodes points as a pickled list of `(path, (timestamp, value))` tuples, prefixed with its
+/// length as a 32-bit big-endian integer.
+fn encode_pickle(points: &[GraphitePoint]) -> Vec<u8> {
+    let mut pickle = vec![PROTO, 2, EMPTY_LIST];
+    if !points.is_empty() {
+        pickle.push(MARK);
+        for point in points {
+            pickle.push(BINUNICODE);
+            pickle.extend_from_slice(&(point.path.len() as u32).to_le_bytes());
+            pickle.extend_from_slice(point.path.as_bytes());
+            match i32::try_from(point.timestamp) {
+                Ok(timestamp) => {
+                    pickle.push(BININT);
+                    pickle.extend_from_slice(&timestamp.to_le_bytes());
+                }
+                Err(_) => {
+                    pickle.extend_from_slice(&[LONG1, 8]);
+                    pickle.extend_from_slice(&point.timestamp.to_le_bytes());
+                }
+            }
+            pickle.push(BINFLOAT);
+            pickle.extend_from_slice(&point.value.to_be_bytes());
+            pickle.extend_from_slice(&[TUPLE2, TUPLE2]);
+        }
+        pickle.push(APPENDS);
+    }
+    pickle.push(STOP);
+
+    let mut payload = Vec::with_capacity(pickle.len() + 4);
+    payload.extend_from_slice(&(pickle.len() as u32).to_be_bytes());
+    payload.extend(pickle);
+    payload
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_core::{
+        event::{metric::Sample, MetricKind, MetricValue, StatisticKind},
+        metric_tags,
+    };
+
+    use super::*;
+
+    fn encoder(tag_format: GraphiteTagFormat) -> GraphiteEncoder {
+        GraphiteEncoder::new(Some("vector".into()), tag_format, vec![0.5])
+    }
+
+    fn metric(value: MetricValue) -> Metric {
+        Metric::new("requests", MetricKind::Absolute, value)
+            .with_namespace(Some("http server"))
+            .with_tags(Some(metric_tags!(
+                "host" => "web-1.example.com",
+                "method" => "GET",
+            )))
+            .with_timestamp(Some(Utc.timestamp_opt(1_680_000_000, 0).unwrap()))
+    }
+
+    fn paths(points: &[GraphitePoint]) -> Vec<&str> {
+        points.iter().map(|point| point.path.as_str()).collect()
+    }
+
+    #[test]
+    fn flattens_tags_into_path() {
+        let points = encoder(GraphiteTagFormat::Path)
+            .points(&metric(MetricValue::Counter { value: 2.0 }).with_namespace(None::<String>));
+        assert_eq!(
+            points,
+            vec![GraphitePoint {
+                path: "vector.requests.host.web-1_example_com.method.GET".into(),
+                value: 2.0,
+                timestamp: 1_680_000_000,
+            }]
+        );
+    }
+
+    #[test]
+    fn appends_statistics_after_tags() {
+        let value = MetricValue::Distribution {
+            samples: vector_core::samples![1.0 => 1],
+            statistic: StatisticKind::Summary,
+        };
+        let points = encoder(GraphiteTagFormat::Path).points(&metric(value.clone()));
+        assert_eq!(
+            paths(&points)[..2],
+            [
+                "http_server.requests.host.web-1_example_com.method.GET.min",
+                "http_server.requests.host.web-1_example_com.method.GET.max",
+            ]
+        );
+
+        let points = encoder(GraphiteTagFormat::Tagged).points(&metric(value));
+        assert_eq!(
+            paths(&points).last().unwrap(),
+            &"http_server.requests.p50;host=web-1_example_com;method=GET"
+        );
+    }
+
+    #[test]
+    fn encodes_plaintext() {
+        let points = encoder(GraphiteTagFormat::Tagged)
+            .points(&metric(MetricValue::Gauge { value: 0.25 }).with_tags(None));
+        assert_eq!(
+            encode_points(GraphiteFormat::Plaintext, &points),
+            b"http_server.requests 0.25 1680000000\n"
+        );
+    }
+
+    #[test]
+    fn encodes_pickle() {
+        let points = vec![GraphitePoint {
+            path: "a.b".into(),
+            value: 1.5,
+            timestamp: 1_680_000_000,
+        }];
+        let payload = encode_points(GraphiteFormat::Pickle, &points);
+
+        // The protocol 2 pickle of `[("a.b", (1680000000, 1.5))]`, without the memo opcodes that
+        // Python would add.
+        let mut expected = b"\x80\x02](X\x03\x00\x00\x00a.bJ\x00\xc4\x22\x64".to_vec();
+        expected.extend_from_slice(b"G\x3f\xf8\x00\x00\x00\x00\x00\x00\x86\x86e.");
+        assert_eq!(&payload[..4], (expected.len() as u32).to_be_bytes());
+        assert_eq!(&payload[4..], expected);
+    }
+
+    #[test]
+    fn encodes_large_timestamps_in_pickle() {
+        let points = vec![GraphitePoint {
+            path: "a".into(),
+            value: 0.0,
+            timestamp: i64::from(i32::MAX) + 1,
+        }];
+        let payload = encode_points(GraphiteFormat::Pickle, &points);
+        let timestamp = payload
+            .windows(2)
+            .position(|window| window == [LONG1, 8])
+            .unwrap();
+        assert_eq!(
+            payload[timestamp + 2..timestamp + 10],
+            (i64::from(i32::MAX) + 1).to_le_bytes()
+        );
+    }
+
+    #[test]
+    fn sanitizes_segments() {
+        assert_eq!(sanitize("a b;c=d~e.f", false), "a_b_c_d_e_f");
+        assert_eq!(sanitize("a.b c", true), "a.b_c");
+    }
+
+    #[test]
+    fn skips_empty_distributions() {
+        let value = MetricValue::Distribution {
+            samples: vec![Sample {
+                value: 1.0,
+                rate: 0,
+            }],
+            statistic: StatisticKind::Histogram,
+        };
+        assert!(encoder(GraphiteTagFormat::Path)
+            .points(&metric(value))
+            .is_empty());
+    }
+}
@@ -0,0 +1,10 @@
+mod config;
+mod encoder;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::config::GraphiteSinkConfig;
@@ -0,0 +1,127 @@
+use std::convert::Infallible;
+
+use vector_common::request_metadata::RequestMetadata;
+use vector_core::event::{EventFinalizers, Finalizable, Metric};
+
+use super::{
+    config::GraphiteFormat,
+    encoder::{encode_points, write_plaintext, GraphiteEncoder, GraphitePoint},
+    service::GraphiteRequest,
+};
+use crate::{
+    internal_events::SocketMode,
+    sinks::util::{
+        metadata::RequestMetadataBuilder, request_builder::EncodeResult, IncrementalRequestBuilder,
+    },
+};
+
+/// Incremental request builder specific to Graphite.
+#[derive(Clone)]
+pub(super) struct GraphiteRequestBuilder {
+    encoder: GraphiteEncoder,
+    format: GraphiteFormat,
+    request_max_size: usize,
+}
+
+impl GraphiteRequestBuilder {
+    pub(super) fn new(
+        encoder: GraphiteEncoder,
+        format: GraphiteFormat,
+        socket_mode: SocketMode,
+    ) -> Self {
+        let request_max_size = match socket_mode {
+            // Each datagram should fit within the MTU of common networks, like for the `statsd`
+            // sink, as Carbon does not reassemble lines that are split across datagrams.
+            SocketMode::Udp => 1432,
+
+            // Carbon reads streams line by line, or pickle by pickle, so bigger requests only
+            // improve I/O efficiency. Its pickle receiver rejects pickles larger than 1 MiB, which
+            // this stays well below.
+            SocketMode::Tcp | SocketMode::Unix => 65536,
+        };
+
+        Self {
+            encoder,
+            format,
+            request_max_size,
+        }
+    }
+}
+
+impl IncrementalRequestBuilder<Vec<Metric>> for GraphiteRequestBuilder {
+    type Metadata = (EventFinalizers, RequestMetadata);
+    type Payload = Vec<u8>;
+    type Request = GraphiteRequest;
+    type Error = Infallible;
+
+    fn encode_events_incremental(
+        &mut self,
+        input: Vec<Metric>,
+    ) -> Vec<Result<(Self::Metadata, Self::Payload), Self::Error>> {
+        let mut results = Vec::new();
+        let mut pending = PendingRequest::default();
+        let mut line = Vec::new();
+
+        for mut metric in input {
+            let points = self.encoder.points(&metric);
+
+            // The size of the plaintext encoding is used for both formats, as the pickle of a point
+            // is about as large as its line.
+            let size = points
+                .iter()
+                .map(|point| {
+                    line.clear();
+                    write_plaintext(point, &mut line);
+                    line.len()
+                })
+                .sum::<usize>();
+
+            // Metrics are never split across requests, so a request holds at least one metric even
+            // if that exceeds the maximum size.
+            if pending.metrics != 0 && pending.size + size > self.request_max_size {
+                results.push(Ok(std::mem::take(&mut pending).finish(self.format)));
+            }
+
+            pending.points.extend(points);
+            pending.size += size;
+            pending.metrics += 1;
+            pending.finalizers.merge(metric.take_finalizers());
+            pending.request_metadata_builder.track_event(metric);
+        }
+
+        if pending.metrics != 0 {
+            results.push(Ok(pending.finish(self.format)));
+        }
+
+        results
+    }
+
+    fn build_request(&mut self, metadata: Self::Metadata, payload: Self::Payload) -> Self::Request {
+        let (finalizers, metadata) = metadata;
+        GraphiteRequest {
+            payload,
+            finalizers,
+            metadata,
+        }
+    }
+}
+
+#[derive(Default)]
+struct PendingRequest {
+    points: Vec<GraphitePoint>,
+    size: usize,
+    metrics: usize,
+    finalizers: EventFinalizers,
+    request_metadata_builder: RequestMetadataBuilder,
+}
+
+impl PendingRequest {
+    fn finish(self, format: GraphiteFormat) -> ((EventFinalizers, RequestMetadata), Vec<u8>) {
+        let encode_result = EncodeResult::uncompressed(encode_points(format, &self.points));
+        let request_metadata = self.request_metadata_builder.build(&encode_result);
+        (
+            (self.finalizers, request_metadata),
+            encode_result.into_payload(),
+        )
+    }
+}
@@ -0,0 +1,106 @@
+use std::task::{Context, Poll};
+
+use futures_util::future::BoxFuture;
+use tower::Service;
+use vector_common::{
+    finalization::{EventFinalizers, EventStatus, Finalizable},
+    internal_event::CountByteSize,
+    request_metadata::{MetaDescriptive, RequestMetadata},
+};
+use vector_core::stream::DriverResponse;
+
+/// Generalized request for sending metrics to a Graphite server.
+#[derive(Clone, Debug)]
+pub struct GraphiteRequest {
+    pub payload: Vec<u8>,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for GraphiteRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for GraphiteRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+// Placeholder response to shuttle request metadata for Graphite requests.
+//
+// As Carbon sends no response back to a caller, there's no success/failure to report except for raw
+// I/O errors when sending the request. Primarily, this type shuttles the metadata around the
+// request -- events sent, bytes sent, etc -- that is required by `Driver`.
+#[derive(Debug)]
+pub struct GraphiteResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for GraphiteResponse {
+    fn event_status(&self) -> EventStatus {
+        // If we generated a response, that implies our send concluded without any I/O errors, so we
+        // assume things were delivered.
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_estimated_json_encoded_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_wire_size())
+    }
+}
+
+#[derive(Clone)]
+pub struct GraphiteService<T> {
+    transport: T,
+}
+
+impl<T> GraphiteService<T> {
+    /// Creates a new `GraphiteService` with the given `transport` service.
+    ///
+    /// The `transport` service is responsible for sending the actual encoded requests to the downstream
+    /// endpoint.
+    pub const fn from_transport(transport: T) -> Self {
+        Self { transport }
+    }
+}
+
+impl<T> Service<GraphiteRequest> for GraphiteService<T>
+where
+    T: Service<Vec<u8>>,
+    T::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    T::Future: Send + 'static,
+{
+    type Response = GraphiteResponse;
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.transport.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: GraphiteRequest) -> Self::Future {
+        let GraphiteRequest {
+            payload,
+            finalizers: _,
+            metadata,
+        } = request;
+
+        let send_future = self.transport.call(payload);
+
+        Box::pin(async move {
+            send_future
+                .await
+                .map(|_| GraphiteResponse { metadata })
+                .map_err(Into::into)
+        })
+    }
+}
@@ -0,0 +1,87 @@
+use std::{fmt, future::ready};
+
+use async_trait::async_trait;
+use futures_util::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use tower::Service;
+use vector_common::internal_event::Protocol;
+use vector_core::{
+    event::Event,
+    sink::StreamSink,
+    stream::{BatcherSettings, DriverResponse},
+};
+
+use crate::sinks::util::{metric_points::MetricPointNormalize, SinkBuilderExt};
+
+use super::{request_builder::GraphiteRequestBuilder, service::GraphiteRequest};
+
+pub(super) struct GraphiteSink<S> {
+    service: S,
+    batch_settings: BatcherSettings,
+    request_builder: GraphiteRequestBuilder,
+    protocol: Protocol,
+}
+
+impl<S> GraphiteSink<S>
+where
+    S: Service<GraphiteRequest> + Send,
+    S::Error: fmt::Debug + fmt::Display + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse,
+{
+    /// Creates a new `GraphiteSink`.
+    pub(super) const fn new(
+        service: S,
+        batch_settings: BatcherSettings,
+        request_builder: GraphiteRequestBuilder,
+        protocol: Protocol,
+    ) -> Self {
+        Self {
+            service,
+            batch_settings,
+            request_builder,
+            protocol,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        input
+            // Convert `Event` to `Metric` so we don't have to deal with constant conversions.
+            .filter_map(|event| ready(event.try_into_metric()))
+            // Graphite stores the current value of each series, so counters are sent as running
+            // totals and gauges as absolute values.
+            .normalized_with_default::<MetricPointNormalize>()
+            .batched(self.batch_settings.into_byte_size_config())
+            // A single metric may expand to many data points, so one batch may be split into
+            // several requests to stay within the size limits of the socket mode.
+            .incremental_request_builder(self.request_builder)
+            // This unrolls the vector of request results that our request builder generates.
+            .flat_map(stream::iter)
+            // Generating requests _cannot_ fail, so we just unwrap our built requests.
+            .unwrap_infallible()
+            // Finally, we generate the driver which will take our requests, send them off, and appropriately handle
+            // finalization of the events, and logging/metrics, as the requests are responded to.
+            .into_driver(self.service)
+            .protocol(self.protocol)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for GraphiteSink<S>
+where
+    S: Service<GraphiteRequest> + Send,
+    S::Error: fmt::Debug + fmt::Display + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        // Rust has issues with lifetimes and generics, which `async_trait` exacerbates, so we write
+        // a normal async fn in `GraphiteSink` itself, and then call out to it from this trait
+        // implementation, which makes the compiler happy.
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,99 @@
+use chrono::{TimeZone, Utc};
+use futures_util::stream;
+use tokio::{io::AsyncReadExt, net::TcpListener};
+use vector_core::{
+    event::{Event, Metric, MetricKind, MetricValue},
+    metric_tags,
+};
+
+use super::config::{GraphiteFormat, GraphiteSinkConfig, GraphiteTagFormat, Mode};
+use crate::{
+    config::{SinkConfig, SinkContext},
+    sinks::util::{metric_points::default_summary_quantiles, service::net::TcpConnectorConfig},
+    test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        next_addr, trace_init, CountReceiver,
+    },
+};
+
+fn config(address: std::net::SocketAddr, format: GraphiteFormat) -> GraphiteSinkConfig {
+    GraphiteSinkConfig {
+        default_namespace: Some("ns".into()),
+        mode: Mode::Tcp(TcpConnectorConfig::from_address(
+            address.ip().to_string(),
+            address.port(),
+        )),
+        format,
+        tag_format: GraphiteTagFormat::Path,
+        quantiles: default_summary_quantiles(),
+        batch: Default::default(),
+        acknowledgements: Default::default(),
+    }
+}
+
+fn counter(value: f64) -> Event {
+    Metric::new(
+        "requests",
+        MetricKind::Incremental,
+        MetricValue::Counter { value },
+    )
+    .with_namespace(Some("vector"))
+    .with_tags(Some(metric_tags!("host" => "web-1")))
+    .with_timestamp(Some(Utc.timestamp_opt(1_680_000_000, 0).unwrap()))
+    .into()
+}
+
+#[tokio::test]
+async fn sends_plaintext_over_tcp() {
+    trace_init();
+
+    let addr = next_addr();
+    let mut receiver = CountReceiver::receive_lines(addr);
+
+    let (sink, _healthcheck) = config(addr, GraphiteFormat::Plaintext)
+        .build(SinkContext::new_test())
+        .await
+        .unwrap();
+    let events = vec![counter(1.0), counter(2.0)];
+    run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+
+    receiver.connected().await;
+    // Incremental counters are sent as running totals.
+    assert_eq!(
+        receiver.await,
+        vec![
+            "vector.requests.host.web-1 1 1680000000",
+            "vector.requests.host.web-1 3 1680000000",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn sends_pickle_over_tcp() {
+    trace_init();
+
+    let addr = next_addr();
+    let listener = TcpListener::bind(addr).await.unwrap();
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut payload = Vec::new();
+        socket.read_to_end(&mut payload).await.unwrap();
+        payload
+    });
+
+    let (sink, _healthcheck) = config(addr, GraphiteFormat::Pickle)
+        .build(SinkContext::new_test())
+        .await
+        .unwrap();
+    run_and_assert_sink_compliance(sink, stream::iter(vec![counter(1.0)]), &SINK_TAGS).await;
+
+    let payload = server.await.unwrap();
+    let length = u32::from_be_bytes(payload[..4].try_into().unwrap()) as usize;
+    assert_eq!(payload.len(), length + 4);
+
+    let pickle = &payload[4..];
+    assert!(pickle.starts_with(b"\x80\x02]("));
+    assert!(pickle.ends_with(b"\x86\x86e."));
+    let path = b"vector.requests.host.web-1";
+    assert!(pickle.windows(path.len()).any(|window| window == path));
+}
@@ -14,17 +14,20 @@ use vector_config::configurable_component;
 
 use crate::{
     codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
+    common::http_signature::{HttpSignatureConfig, HttpSigner},
     components::validation::*,
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     event::Event,
     http::{Auth, HttpClient, MaybeAuth},
+    internal_events::{HttpSinkInvalidRenderedValueError, TemplateRenderingError},
     register_validatable_component,
     sinks::util::{
         self,
-        http::{BatchedHttpSink, HttpEventEncoder, RequestConfig},
-        BatchConfig, Buffer, Compression, Compressor, RealtimeSizeBasedDefaultBatchSettings,
-        TowerRequestConfig, UriSerde,
+        http::{HttpEventEncoder, PartitionHttpSink, RequestConfig},
+        BatchConfig, Buffer, Compression, Compressor, PartitionBuffer, PartitionInnerBuffer,
+        RealtimeSizeBasedDefaultBatchSettings, TowerRequestConfig, UriSerde,
     },
+    template::Template,
     tls::{TlsConfig, TlsSettings},
 };
 
@@ -33,11 +36,8 @@ use crate::{
 #[derive(Clone, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct HttpSinkConfig {
-    /// The full URI to make HTTP requests to.
-    ///
-    /// This should include the protocol and host, but can also include the port, path, and any other valid part of a URI.
-    #[configurable(metadata(docs::examples = "https://10.22.212.22:9000/endpoint"))]
-    pub uri: UriSerde,
+    #[configurable(derived)]
+    pub uri: HttpSinkUri,
 
     /// The HTTP method to use when making the request.
     #[serde(default)]
@@ -46,6 +46,13 @@ pub struct HttpSinkConfig {
     #[configurable(derived)]
     pub auth: Option<Auth>,
 
+    /// Signs each request with HMAC-SHA256, as expected by many webhook receivers.
+    ///
+    /// The signature covers the request as it is sent, once compressed. A request that is retried
+    /// keeps the signature, and timestamp, of its first attempt.
+    #[configurable(derived)]
+    pub signature: Option<HttpSignatureConfig>,
+
     /// A list of custom headers to add to each request.
     #[configurable(deprecated)]
     #[configurable(metadata(
@@ -53,6 +60,20 @@ pub struct HttpSinkConfig {
     ))]
     pub headers: Option<IndexMap<String, String>>,
 
+    /// Custom headers whose values are [templates][templates], rendered for each event and added
+    /// to each request.
+    ///
+    /// Events are batched separately for each distinct set of rendered values. Events for which a
+    /// value can't be rendered, or isn't a valid header value, are dropped.
+    ///
+    /// [templates]: https://vector.dev/docs/reference/configuration/template-syntax/
+    #[configurable(metadata(
+        docs::additional_props_description = "An HTTP request header and the template of its value."
+    ))]
+    #[configurable(metadata(docs::examples = "example_header_templates()"))]
+    #[serde(default)]
+    pub header_templates: IndexMap<String, Template>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub compression: Compression,
@@ -98,6 +119,43 @@ pub struct HttpSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// The URI of the `http` sink.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(untagged)]
+pub enum HttpSinkUri {
+    /// The full URI to make HTTP requests to.
+    ///
+    /// This should include the protocol and host, but can also include the port, path, and any other valid part of a URI.
+    #[configurable(metadata(docs::examples = "https://10.22.212.22:9000/endpoint"))]
+    Static(UriSerde),
+
+    /// A URI rendered for each event, set as `uri.template`.
+    Templated(HttpSinkUriTemplate),
+}
+
+/// A URI rendered for each event.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HttpSinkUriTemplate {
+    /// The [template][template] of the URI to make HTTP requests to.
+    ///
+    /// Events are batched separately for each distinct rendered URI. Events for which the URI
+    /// can't be rendered, or isn't a valid URI, are dropped. The URI can not contain credentials,
+    /// which have to be set with `auth` instead.
+    ///
+    /// As with any template, a literal `%` has to be written as `%%`.
+    ///
+    /// [template]: https://vector.dev/docs/reference/configuration/template-syntax/
+    #[configurable(metadata(docs::examples = "https://10.22.212.22:9000/tenants/{{ tenant }}"))]
+    pub template: Template,
+}
+
+fn example_header_templates() -> IndexMap<String, String> {
+    IndexMap::from([("X-Tenant".to_owned(), "{{ tenant }}".to_owned())])
+}
+
 /// HTTP method.
 ///
 /// A subset of the HTTP methods described in [RFC 9110, section 9.1][rfc9110] are supported.
@@ -167,9 +225,10 @@ impl HttpSinkConfig {
 }
 
 struct HttpSink {
-    pub uri: UriSerde,
+    pub uri: HttpSinkUri,
     pub method: HttpMethod,
     pub auth: Option<Auth>,
+    pub signer: Option<HttpSigner>,
     pub payload_prefix: String,
     pub payload_suffix: String,
     pub compression: Compression,
@@ -178,6 +237,7 @@ struct HttpSink {
     pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
     pub tower: TowerRequestConfig,
     pub headers: IndexMap<HeaderName, HeaderValue>,
+    pub header_templates: IndexMap<HeaderName, Template>,
 }
 
 #[cfg(test)]
@@ -186,9 +246,10 @@ fn default_sink(encoding: EncodingConfigWithFraming) -> HttpSink {
     let encoder = Encoder::<Framer>::new(framing, serializer);
 
     HttpSink {
-        uri: Default::default(),
+        uri: HttpSinkUri::Static(Default::default()),
         method: Default::default(),
         auth: Default::default(),
+        signer: Default::default(),
         compression: Default::default(),
         transformer: Default::default(),
         encoder,
@@ -197,6 +258,7 @@ fn default_sink(encoding: EncodingConfigWithFraming) -> HttpSink {
         batch: Default::default(),
         tower: Default::default(),
         headers: Default::default(),
+        header_templates: Default::default(),
     }
 }
 
@@ -218,6 +280,8 @@ impl SinkConfig for HttpSinkConfig {
         let mut request = self.request.clone();
         request.add_old_option(self.headers.clone());
         let headers = validate_headers(&request.headers, self.auth.is_some())?;
+        let header_templates =
+            validate_header_templates(&self.header_templates, &headers, self.auth.is_some())?;
 
         let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
         let encoder = Encoder::<Framer>::new(framer, serializer);
@@ -225,16 +289,32 @@ impl SinkConfig for HttpSinkConfig {
         let (payload_prefix, payload_suffix) =
             validate_payload_wrapper(&self.payload_prefix, &self.payload_suffix, &encoder)?;
 
+        let (uri, auth) = match &self.uri {
+            HttpSinkUri::Static(uri) => (
+                HttpSinkUri::Static(uri.with_default_parts()),
+                self.auth.choose_one(&uri.auth)?,
+            ),
+            HttpSinkUri::Templated(_) => (self.uri.clone(), self.auth.clone()),
+        };
+
+        let signer = self
+            .signature
+            .as_ref()
+            .map(HttpSignatureConfig::build)
+            .transpose()?;
+
         let sink = HttpSink {
-            uri: self.uri.with_default_parts(),
+            uri,
             method: self.method,
-            auth: self.auth.choose_one(&self.uri.auth)?,
+            auth,
+            signer,
             compression: self.compression,
             transformer: self.encoding.transformer(),
             encoder,
             batch: self.batch,
             tower: request.tower,
             headers,
+            header_templates,
             payload_prefix,
             payload_suffix,
         };
@@ -242,9 +322,9 @@ impl SinkConfig for HttpSinkConfig {
         let request = sink.tower.unwrap_with(&TowerRequestConfig::default());
 
         let batch = sink.batch.into_batch_settings()?;
-        let sink = BatchedHttpSink::new(
+        let sink = PartitionHttpSink::new(
             sink,
-            Buffer::new(batch.size, Compression::None),
+            PartitionBuffer::new(Buffer::new(batch.size, Compression::None)),
             request,
             batch.timeout,
             client,
@@ -271,9 +351,12 @@ impl ValidatableComponent for HttpSinkConfig {
         use codecs::{JsonSerializerConfig, MetricTagValues};
         use std::str::FromStr;
 
+        let uri = UriSerde::from_str("http://127.0.0.1:9000/endpoint")
+            .expect("should never fail to parse");
+        let external_uri = uri.uri.clone();
+
         let config = Self {
-            uri: UriSerde::from_str("http://127.0.0.1:9000/endpoint")
-                .expect("should never fail to parse"),
+            uri: HttpSinkUri::Static(uri),
             method: HttpMethod::Post,
             encoding: EncodingConfigWithFraming::new(
                 None,
@@ -281,7 +364,9 @@ impl ValidatableComponent for HttpSinkConfig {
                 Transformer::default(),
             ),
             auth: None,
+            signature: None,
             headers: None,
+            header_templates: IndexMap::new(),
             compression: Compression::default(),
             batch: BatchConfig::default(),
             request: RequestConfig::default(),
@@ -293,7 +378,7 @@ impl ValidatableComponent for HttpSinkConfig {
 
         let external_resource = ExternalResource::new(
             ResourceDirection::Push,
-            HttpResourceConfig::from_parts(config.uri.uri.clone(), Some(config.method.into())),
+            HttpResourceConfig::from_parts(external_uri, Some(config.method.into())),
             config.encoding.clone(),
         );
 
@@ -303,38 +388,130 @@ impl ValidatableComponent for HttpSinkConfig {
 
 register_validatable_component!(HttpSinkConfig);
 
+/// The rendered values of the templated parts of a request, which events are batched by.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PartitionKey {
+    uri: Uri,
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
 pub struct HttpSinkEventEncoder {
     encoder: Encoder<Framer>,
     transformer: Transformer,
+    uri: HttpSinkUri,
+    header_templates: IndexMap<HeaderName, Template>,
+}
+
+impl HttpSinkEventEncoder {
+    fn partition_key(&self, event: &Event) -> Option<PartitionKey> {
+        let uri = match &self.uri {
+            HttpSinkUri::Static(uri) => uri.uri.clone(),
+            HttpSinkUri::Templated(uri) => render_uri(&uri.template, event)?,
+        };
+        let headers = self
+            .header_templates
+            .iter()
+            .map(|(name, template)| {
+                render_header_value(name, template, event).map(|value| (name.clone(), value))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(PartitionKey { uri, headers })
+    }
 }
 
-impl HttpEventEncoder<BytesMut> for HttpSinkEventEncoder {
-    fn encode_event(&mut self, mut event: Event) -> Option<BytesMut> {
+impl HttpEventEncoder<PartitionInnerBuffer<BytesMut, PartitionKey>> for HttpSinkEventEncoder {
+    fn encode_event(
+        &mut self,
+        mut event: Event,
+    ) -> Option<PartitionInnerBuffer<BytesMut, PartitionKey>> {
+        let key = self.partition_key(&event)?;
+
         self.transformer.transform(&mut event);
 
         let mut body = BytesMut::new();
         self.encoder.encode(event, &mut body).ok()?;
 
-        Some(body)
+        Some(PartitionInnerBuffer::new(body, key))
     }
 }
 
+fn render_uri(template: &Template, event: &Event) -> Option<Uri> {
+    let rendered = template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some("uri"),
+                drop_event: true,
+            })
+        })
+        .ok()?;
+
+    match rendered.parse::<UriSerde>() {
+        Ok(uri) if uri.auth.is_some() => {
+            emit!(HttpSinkInvalidRenderedValueError {
+                field: "uri",
+                error: "Templated URI can not contain credentials.".into(),
+            });
+            None
+        }
+        Ok(uri) => Some(uri.with_default_parts().uri),
+        Err(error) => {
+            emit!(HttpSinkInvalidRenderedValueError {
+                field: "uri",
+                error: error.into(),
+            });
+            None
+        }
+    }
+}
+
+fn render_header_value(
+    name: &HeaderName,
+    template: &Template,
+    event: &Event,
+) -> Option<HeaderValue> {
+    let rendered = template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(name.as_str()),
+                drop_event: true,
+            })
+        })
+        .ok()?;
+
+    HeaderValue::from_str(&rendered)
+        .map_err(|error| {
+            emit!(HttpSinkInvalidRenderedValueError {
+                field: name.as_str(),
+                error: error.into(),
+            })
+        })
+        .ok()
+}
+
 #[async_trait::async_trait]
 impl util::http::HttpSink for HttpSink {
-    type Input = BytesMut;
-    type Output = BytesMut;
+    type Input = PartitionInnerBuffer<BytesMut, PartitionKey>;
+    type Output = PartitionInnerBuffer<BytesMut, PartitionKey>;
     type Encoder = HttpSinkEventEncoder;
 
     fn build_encoder(&self) -> Self::Encoder {
         HttpSinkEventEncoder {
             encoder: self.encoder.clone(),
             transformer: self.transformer.clone(),
+            uri: self.uri.clone(),
+            header_templates: self.header_templates.clone(),
         }
     }
 
-    async fn build_request(&self, mut body: Self::Output) -> crate::Result<http::Request<Bytes>> {
+    async fn build_request(&self, output: Self::Output) -> crate::Result<http::Request<Bytes>> {
+        let (mut body, key) = output.into_parts();
         let method: Method = self.method.into();
-        let uri: Uri = self.uri.uri.clone();
+        let uri = key.uri;
 
         let content_type = {
             use Framer::*;
@@ -399,6 +576,9 @@ impl util::http::HttpSink for HttpSink {
         for (header, value) in self.headers.iter() {
             headers.insert(header, value.clone());
         }
+        for (header, value) in key.headers {
+            headers.insert(header, value);
+        }
 
         let mut request = builder.body(body.freeze()).unwrap();
 
@@ -406,6 +586,10 @@ impl util::http::HttpSink for HttpSink {
             auth.apply(&mut request);
         }
 
+        if let Some(signer) = &self.signer {
+            signer.sign(&mut request)?;
+        }
+
         Ok(request)
     }
 }
@@ -442,6 +626,32 @@ fn validate_headers(
     Ok(headers)
 }
 
+fn validate_header_templates(
+    header_templates: &IndexMap<String, Template>,
+    headers: &IndexMap<HeaderName, HeaderValue>,
+    configures_auth: bool,
+) -> crate::Result<IndexMap<HeaderName, Template>> {
+    header_templates
+        .iter()
+        .map(|(name, template)| {
+            let name = HeaderName::from_bytes(name.as_bytes())?;
+            if configures_auth && name == AUTHORIZATION {
+                return Err(
+                    "Authorization header can not be used with defined auth options".into(),
+                );
+            }
+            if headers.contains_key(&name) {
+                return Err(format!(
+                    "Header {:?} can not have both a static and a templated value",
+                    name.as_str()
+                )
+                .into());
+            }
+            Ok((name, template.clone()))
+        })
+        .collect()
+}
+
 fn validate_payload_wrapper(
     payload_prefix: &str,
     payload_suffix: &str,
@@ -508,7 +718,7 @@ mod tests {
 
         let sink = default_sink((None::<FramingConfig>, TextSerializerConfig::default()).into());
         let mut encoder = sink.build_encoder();
-        let bytes = encoder.encode_event(event).unwrap();
+        let (bytes, _) = encoder.encode_event(event).unwrap().into_parts();
 
         assert_eq!(bytes, Vec::from("hello world\n"));
     }
@@ -525,7 +735,7 @@ mod tests {
                 .into(),
         );
         let mut encoder = sink.build_encoder();
-        let bytes = encoder.encode_event(event).unwrap();
+        let (bytes, _) = encoder.encode_event(event).unwrap().into_parts();
 
         #[derive(Deserialize, Debug)]
         #[serde(deny_unknown_fields)]
@@ -571,6 +781,108 @@ mod tests {
         );
     }
 
+    #[test]
+    fn http_keeps_static_uri_and_headers_as_is() {
+        let config = r#"
+        uri = "http://localhost/a%20b"
+        encoding.codec = "text"
+        [request.headers]
+        X-Percent = "%_{{"
+        "#;
+        let config: HttpSinkConfig = toml::from_str(config).unwrap();
+
+        match config.uri {
+            HttpSinkUri::Static(uri) => assert_eq!(uri.uri.path(), "/a%20b"),
+            HttpSinkUri::Templated(_) => panic!("URI should be static"),
+        }
+        let headers = super::validate_headers(&config.request.headers, false).unwrap();
+        assert_eq!(headers["x-percent"], "%_{{");
+    }
+
+    #[test]
+    fn http_parses_templated_uri_and_headers() {
+        let config = r#"
+        uri.template = "http://localhost/{{ tenant }}"
+        encoding.codec = "text"
+        [header_templates]
+        X-Tenant = "{{ tenant }}"
+        "#;
+        let config: HttpSinkConfig = toml::from_str(config).unwrap();
+
+        match &config.uri {
+            HttpSinkUri::Templated(uri) => {
+                assert_eq!(uri.template.get_ref(), "http://localhost/{{ tenant }}")
+            }
+            HttpSinkUri::Static(_) => panic!("URI should be templated"),
+        }
+        let header_templates =
+            super::validate_header_templates(&config.header_templates, &IndexMap::new(), false)
+                .unwrap();
+        assert_eq!(header_templates["x-tenant"].get_ref(), "{{ tenant }}");
+    }
+
+    #[test]
+    fn http_rejects_conflicting_header_templates() {
+        let config = r#"
+        uri = "http://localhost/"
+        encoding.codec = "text"
+        auth.strategy = "bearer"
+        auth.token = "token"
+        [request.headers]
+        X-Tenant = "static"
+        [header_templates]
+        X-Tenant = "{{ tenant }}"
+        "#;
+        let config: HttpSinkConfig = toml::from_str(config).unwrap();
+        let headers = super::validate_headers(&config.request.headers, true).unwrap();
+        assert!(
+            super::validate_header_templates(&config.header_templates, &headers, true).is_err()
+        );
+
+        let header_templates = IndexMap::from([(
+            "Authorization".to_owned(),
+            Template::try_from("Bearer {{ token }}").unwrap(),
+        )]);
+        assert!(
+            super::validate_header_templates(&header_templates, &IndexMap::new(), true).is_err()
+        );
+    }
+
+    #[test]
+    fn http_encode_event_partition_key() {
+        let mut sink =
+            default_sink((None::<FramingConfig>, TextSerializerConfig::default()).into());
+        sink.uri = HttpSinkUri::Templated(HttpSinkUriTemplate {
+            template: Template::try_from("http://localhost/{{ tenant }}").unwrap(),
+        });
+        sink.header_templates.insert(
+            HeaderName::from_static("x-tenant"),
+            Template::try_from("{{ tenant }}").unwrap(),
+        );
+        let mut encoder = sink.build_encoder();
+
+        let mut log = LogEvent::from("hello world");
+        log.insert("tenant", "foo");
+        let (_, key) = encoder.encode_event(Event::Log(log)).unwrap().into_parts();
+
+        assert_eq!(key.uri, Uri::from_static("http://localhost/foo"));
+        assert_eq!(
+            key.headers,
+            vec![(
+                HeaderName::from_static("x-tenant"),
+                HeaderValue::from_static("foo")
+            )]
+        );
+
+        // Events the templates can't be rendered for are dropped.
+        let event = Event::Log(LogEvent::from("hello world"));
+        assert!(encoder.encode_event(event).is_none());
+
+        let mut log = LogEvent::from("hello world");
+        log.insert("tenant", "bar\n");
+        assert!(encoder.encode_event(Event::Log(log)).is_none());
+    }
+
     #[test]
     fn http_validates_payload_prefix_and_suffix() {
         let config = r#"
@@ -698,6 +1010,70 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn http_signs_requests() {
+        run_sink(
+            r#"
+        signature.secret = "hunter2"
+        signature.signature_header = "X-Hub-Signature-256"
+    "#,
+            |parts| {
+                let signature = parts.headers["X-Hub-Signature-256"].to_str().unwrap();
+                assert!(signature.starts_with("sha256="));
+                assert!(parts.headers.contains_key("X-Signature-Timestamp"));
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn http_partitions_by_templated_uri_and_headers() {
+        let in_addr = next_addr();
+        let config = format!(
+            r#"
+                uri.template = "http://{addr}/frames/{{{{ tenant }}}}"
+                framing.method = "newline_delimited"
+                encoding.codec = "json"
+                [header_templates]
+                X-Tenant = "{{{{ tenant }}}}"
+            "#,
+            addr = in_addr,
+        );
+        let config: HttpSinkConfig = toml::from_str(&config).unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (rx, trigger, server) = build_test_server(in_addr);
+        tokio::spawn(server);
+
+        let events = (0..10)
+            .map(|i| {
+                let mut log = LogEvent::from(format!("line {}", i));
+                log.insert("tenant", if i % 2 == 0 { "even" } else { "odd" });
+                Event::Log(log)
+            })
+            .collect::<Vec<_>>();
+        components::run_and_assert_sink_compliance(sink, stream::iter(events), &HTTP_SINK_TAGS)
+            .await;
+        drop(trigger);
+
+        let mut received = rx
+            .flat_map(|(parts, body)| {
+                let tenant = parts.headers["x-tenant"].to_str().unwrap().to_owned();
+                assert_eq!(format!("/frames/{}", tenant), parts.uri.path());
+                stream::iter(BufReader::new(body.reader()).lines().map(move |line| {
+                    let val: serde_json::Value = serde_json::from_str(&line.unwrap()).unwrap();
+                    assert_eq!(val["tenant"], tenant.as_str());
+                    val["message"].as_str().unwrap().to_owned()
+                }))
+            })
+            .collect::<Vec<_>>()
+            .await;
+        received.sort();
+
+        let expected = (0..10).map(|i| format!("line {}", i)).collect::<Vec<_>>();
+        assert_eq!(expected, received);
+    }
+
     #[tokio::test]
     async fn retries_on_no_connection() {
         components::assert_sink_compliance(&HTTP_SINK_TAGS, async {
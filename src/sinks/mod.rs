@@ -56,6 +56,8 @@ pub mod file;
 pub mod gcp;
 #[cfg(any(feature = "sinks-gcp"))]
 pub mod gcs_common;
+#[cfg(feature = "sinks-graphite")]
+pub mod graphite;
 #[cfg(feature = "sinks-honeycomb")]
 pub mod honeycomb;
 #[cfg(feature = "sinks-http")]
@@ -76,8 +78,12 @@ pub mod nats;
 pub mod new_relic;
 #[cfg(feature = "sinks-webhdfs")]
 pub mod opendal_common;
+#[cfg(feature = "sinks-opentsdb")]
+pub mod opentsdb;
 #[cfg(feature = "sinks-papertrail")]
 pub mod papertrail;
+#[cfg(feature = "sinks-postgres")]
+pub mod postgres;
 #[cfg(feature = "sinks-prometheus")]
 pub mod prometheus;
 #[cfg(feature = "sinks-pulsar")]
@@ -97,6 +103,8 @@ pub mod socket;
 pub mod splunk_hec;
 #[cfg(feature = "sinks-statsd")]
 pub mod statsd;
+#[cfg(feature = "sinks-syslog")]
+pub mod syslog;
 #[cfg(feature = "sinks-vector")]
 pub mod vector;
 #[cfg(feature = "sinks-webhdfs")]
@@ -254,6 +262,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-gcp")]
     GcpPubsub(gcp::pubsub::PubsubConfig),
 
+    /// Deliver metric data to Graphite.
+    #[cfg(feature = "sinks-graphite")]
+    Graphite(graphite::GraphiteSinkConfig),
+
     /// WebHDFS.
     #[cfg(feature = "sinks-webhdfs")]
     Webhdfs(webhdfs::WebHdfsConfig),
@@ -306,10 +318,19 @@ pub enum Sinks {
     #[cfg(feature = "sinks-new_relic")]
     NewRelic(new_relic::NewRelicConfig),
 
+    /// Deliver metric data to OpenTSDB.
+    #[cfg(feature = "sinks-opentsdb")]
+    #[configurable(metadata(docs::human_name = "OpenTSDB"))]
+    Opentsdb(opentsdb::OpenTsdbConfig),
+
     /// Deliver log events to Papertrail from SolarWinds.
     #[cfg(feature = "sinks-papertrail")]
     Papertrail(papertrail::PapertrailConfig),
 
+    /// Deliver log data to a PostgreSQL database.
+    #[cfg(feature = "sinks-postgres")]
+    Postgres(postgres::PostgresConfig),
+
     /// Expose metric events on a Prometheus compatible endpoint.
     #[cfg(feature = "sinks-prometheus")]
     PrometheusExporter(prometheus::exporter::PrometheusExporterConfig),
@@ -350,6 +371,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-statsd")]
     Statsd(statsd::StatsdSinkConfig),
 
+    /// Deliver log data to a syslog server.
+    #[cfg(feature = "sinks-syslog")]
+    Syslog(syslog::SyslogSinkConfig),
+
     /// Test (adaptive concurrency).
     #[cfg(all(test, feature = "sources-demo_logs"))]
     TestArc(self::util::adaptive_concurrency::tests::TestConfig),
@@ -446,6 +471,8 @@ impl NamedComponent for Sinks {
             Self::GcpCloudStorage(config) => config.get_component_name(),
             #[cfg(feature = "sinks-gcp")]
             Self::GcpPubsub(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-graphite")]
+            Self::Graphite(config) => config.get_component_name(),
             #[cfg(feature = "sinks-webhdfs")]
             Self::Webhdfs(config) => config.get_component_name(),
             #[cfg(feature = "sinks-honeycomb")]
@@ -472,8 +499,12 @@ impl NamedComponent for Sinks {
             Self::Nats(config) => config.get_component_name(),
             #[cfg(feature = "sinks-new_relic")]
             Self::NewRelic(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-opentsdb")]
+            Self::Opentsdb(config) => config.get_component_name(),
             #[cfg(feature = "sinks-papertrail")]
             Self::Papertrail(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-postgres")]
+            Self::Postgres(config) => config.get_component_name(),
             #[cfg(feature = "sinks-prometheus")]
             Self::PrometheusExporter(config) => config.get_component_name(),
             #[cfg(feature = "sinks-prometheus")]
@@ -494,6 +525,8 @@ impl NamedComponent for Sinks {
             Self::SplunkHecMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sinks-statsd")]
             Self::Statsd(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-syslog")]
+            Self::Syslog(config) => config.get_component_name(),
             #[cfg(all(test, feature = "sources-demo_logs"))]
             Self::TestArc(config) => config.get_component_name(),
             #[cfg(test)]
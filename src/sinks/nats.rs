@@ -15,8 +15,11 @@ use crate::{
     codecs::{Encoder, EncodingConfig, Transformer},
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     event::{EstimatedJsonEncodedSizeOf, Event, EventStatus, Finalizable},
-    internal_events::{NatsEventSendError, TemplateRenderingError},
-    nats::{from_tls_auth_config, NatsAuthConfig, NatsConfigError},
+    internal_events::{NatsEventSendError, NatsJetStreamPublishError, TemplateRenderingError},
+    nats::{
+        from_tls_auth_config, jetstream_request, JetStreamPublishAck, NatsAuthConfig,
+        NatsConfigError,
+    },
     sinks::util::StreamSink,
     template::{Template, TemplateParseError},
     tls::TlsEnableableConfig,
@@ -92,6 +95,16 @@ pub struct NatsSinkConfig {
 
     #[configurable(derived)]
     auth: Option<NatsAuthConfig>,
+
+    /// Publish messages to [JetStream][jetstream], waiting for the stream to acknowledge each one.
+    ///
+    /// A stream capturing the subject must already exist. Events are only marked as delivered once
+    /// the stream has persisted them, making delivery lossless when used with end-to-end
+    /// acknowledgements.
+    ///
+    /// [jetstream]: https://docs.nats.io/nats-concepts/jetstream
+    #[serde(default)]
+    jetstream: bool,
 }
 
 fn default_name() -> String {
@@ -108,6 +121,7 @@ impl GenerateConfig for NatsSinkConfig {
             subject: "from.vector".into(),
             tls: None,
             url: "nats://127.0.0.1:4222".into(),
+            jetstream: false,
         })
         .unwrap()
     }
@@ -158,6 +172,7 @@ pub struct NatsSink {
     encoder: Encoder<()>,
     connection: nats::asynk::Connection,
     subject: Template,
+    jetstream: bool,
 }
 
 impl NatsSink {
@@ -172,6 +187,7 @@ impl NatsSink {
             transformer,
             encoder,
             subject: Template::try_from(config.subject).context(SubjectTemplateSnafu)?,
+            jetstream: config.jetstream,
         })
     }
 }
@@ -209,13 +225,21 @@ impl StreamSink<Event> for NatsSink {
                 continue;
             }
 
-            match self.connection.publish(&subject, &bytes).await {
-                Err(error) => {
-                    finalizers.update_status(EventStatus::Errored);
+            let result = if self.jetstream {
+                jetstream_request::<JetStreamPublishAck>(&self.connection, &subject, &bytes)
+                    .await
+                    .map(|_| ())
+                    .map_err(|error| emit!(NatsJetStreamPublishError { error }))
+            } else {
+                self.connection
+                    .publish(&subject, &bytes)
+                    .await
+                    .map_err(|error| emit!(NatsEventSendError { error }))
+            };
 
-                    emit!(NatsEventSendError { error });
-                }
-                Ok(_) => {
+            match result {
+                Err(()) => finalizers.update_status(EventStatus::Errored),
+                Ok(()) => {
                     finalizers.update_status(EventStatus::Delivered);
 
                     events_sent.emit(CountByteSize(1, event_byte_size));
@@ -312,6 +336,7 @@ mod integration_tests {
             url,
             tls: None,
             auth: None,
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -343,6 +368,7 @@ mod integration_tests {
                     password: "natspass".to_string().into(),
                 },
             }),
+            jetstream: false,
         };
 
         publish_and_check(conf)
@@ -371,6 +397,7 @@ mod integration_tests {
                     password: "wrongpass".to_string().into(),
                 },
             }),
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -401,6 +428,7 @@ mod integration_tests {
                     value: "secret".to_string().into(),
                 },
             }),
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -431,6 +459,7 @@ mod integration_tests {
                     value: "wrongsecret".to_string().into(),
                 },
             }),
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -462,6 +491,7 @@ mod integration_tests {
                     seed: "SUANIRXEZUROTXNFN3TJYMT27K7ZZVMD46FRIHF6KXKS4KGNVBS57YAFGY".into(),
                 },
             }),
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -493,6 +523,7 @@ mod integration_tests {
                     seed: "SBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB".into(),
                 },
             }),
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -525,6 +556,7 @@ mod integration_tests {
                 },
             }),
             auth: None,
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -551,6 +583,7 @@ mod integration_tests {
             url,
             tls: None,
             auth: None,
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -585,6 +618,7 @@ mod integration_tests {
                 },
             }),
             auth: None,
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -617,6 +651,7 @@ mod integration_tests {
                 },
             }),
             auth: None,
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -653,6 +688,7 @@ mod integration_tests {
                     path: "tests/data/nats/nats.creds".into(),
                 },
             }),
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -689,6 +725,7 @@ mod integration_tests {
                     path: "tests/data/nats/nats-bad.creds".into(),
                 },
             }),
+            jetstream: false,
         };
 
         let r = publish_and_check(conf).await;
@@ -698,4 +735,46 @@ mod integration_tests {
             r
         );
     }
+
+    #[tokio::test]
+    async fn nats_jetstream_publish() {
+        trace_init();
+
+        let name = format!("test-{}", random_string(10));
+        let url = std::env::var("NATS_JETSTREAM_ADDRESS")
+            .unwrap_or_else(|_| String::from("nats://localhost:4222"));
+
+        let conf = NatsSinkConfig {
+            acknowledgements: Default::default(),
+            encoding: TextSerializerConfig::default().into(),
+            connection_name: "".to_owned(),
+            subject: name.clone(),
+            url,
+            tls: None,
+            auth: None,
+            jetstream: true,
+        };
+
+        let connection = conf.connect().await.unwrap();
+        let _: serde_json::Value = jetstream_request(
+            &connection,
+            &format!("$JS.API.STREAM.CREATE.{}", name),
+            serde_json::json!({ "name": name, "subjects": [name] }).to_string(),
+        )
+        .await
+        .expect("failed to create stream");
+
+        let sink = NatsSink::new(conf).await.unwrap();
+        let sink = VectorSink::from_event_streamsink(sink);
+        let num_events = 100;
+        let (_input, events) = random_lines_with_stream(100, num_events, None);
+        run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+
+        // Every message is acknowledged by the stream before the sink completes.
+        let info: serde_json::Value =
+            jetstream_request(&connection, &format!("$JS.API.STREAM.INFO.{}", name), "")
+                .await
+                .unwrap();
+        assert_eq!(info["state"]["messages"], num_events);
+    }
 }
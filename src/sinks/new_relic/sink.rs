@@ -1,4 +1,9 @@
-use std::{convert::TryFrom, fmt::Debug, num::NonZeroUsize, sync::Arc};
+use std::{
+    convert::TryFrom,
+    fmt::{Debug, Display},
+    num::NonZeroUsize,
+    sync::Arc,
+};
 
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -147,7 +152,7 @@ where
     S: Service<NewRelicApiRequest> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: Debug + Into<crate::Error> + Send,
+    S::Error: Debug + Display + Into<crate::Error> + Send,
 {
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let builder_limit = NonZeroUsize::new(64);
@@ -186,7 +191,7 @@ where
     S: Service<NewRelicApiRequest> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: Debug + Into<crate::Error> + Send,
+    S::Error: Debug + Display + Into<crate::Error> + Send,
 {
     async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         self.run_inner(input).await
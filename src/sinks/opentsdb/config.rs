@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use futures::FutureExt;
+use http::{Request, StatusCode, Uri};
+use hyper::Body;
+use tower::ServiceBuilder;
+use vector_config::configurable_component;
+
+use super::{
+    encoder::OpenTsdbEncoder,
+    request_builder::OpenTsdbRequestBuilder,
+    service::{OpenTsdbResponse, OpenTsdbService},
+    sink::OpenTsdbSink,
+};
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::HttpClient,
+    sinks::{
+        util::{
+            http::HttpStatusRetryLogic, metric_points::default_summary_quantiles,
+            statistic::validate_quantiles, BatchConfig, Compression, ServiceBuilderExt,
+            SinkBatchSettings, TowerRequestConfig, UriSerde,
+        },
+        Healthcheck, HealthcheckError, VectorSink,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OpenTsdbDefaultBatchSettings;
+
+impl SinkBatchSettings for OpenTsdbDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(100);
+    const MAX_BYTES: Option<usize> = None;
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `opentsdb` sink.
+#[configurable_component(sink("opentsdb"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OpenTsdbConfig {
+    /// The base URI of the OpenTSDB server.
+    ///
+    /// Data points are sent to the `/api/put` endpoint under this URI.
+    #[configurable(metadata(docs::examples = "http://localhost:4242"))]
+    pub endpoint: UriSerde,
+
+    /// Sets the default namespace for any metrics sent.
+    ///
+    /// This namespace is only used if a metric has no existing namespace. When a namespace is
+    /// present, it is used as a prefix to the metric name, and separated with a period (`.`).
+    #[configurable(metadata(docs::examples = "service"))]
+    pub default_namespace: Option<String>,
+
+    /// A map of additional tags, in the key/value pair format, to add to each data point.
+    ///
+    /// OpenTSDB rejects data points without any tags, so metrics that may have no tags of their
+    /// own need at least one tag here.
+    #[configurable(metadata(docs::additional_props_description = "A tag key/value pair."))]
+    #[configurable(metadata(docs::examples = "example_tags()"))]
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+
+    /// The list of quantiles to calculate when sending distribution metrics.
+    #[serde(default = "default_summary_quantiles")]
+    pub quantiles: Vec<f64>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<OpenTsdbDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn example_tags() -> HashMap<String, String> {
+    HashMap::from([("host".to_string(), "vector".to_string())])
+}
+
+impl GenerateConfig for OpenTsdbConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "http://localhost:4242"
+            tags.host = "vector"
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+impl OpenTsdbConfig {
+    fn uri(&self, path: &str) -> crate::Result<Uri> {
+        Ok(self.endpoint.with_default_parts().append_path(path)?.uri)
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for OpenTsdbConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        validate_quantiles(&self.quantiles)?;
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, cx.proxy())?;
+
+        let healthcheck = healthcheck(self.uri("/api/version")?, client.clone()).boxed();
+
+        let service = OpenTsdbService::new(self.uri("/api/put")?, self.compression, client);
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let retry_logic = HttpStatusRetryLogic::new(|res: &OpenTsdbResponse| res.http_status);
+        let service = ServiceBuilder::new()
+            .settings(request_settings, retry_logic)
+            .service(service);
+
+        let sink = OpenTsdbSink {
+            service,
+            batch_settings: self.batch.into_batcher_settings()?,
+            request_builder: OpenTsdbRequestBuilder {
+                encoder: OpenTsdbEncoder::new(
+                    self.default_namespace.clone(),
+                    self.tags.clone(),
+                    self.quantiles.clone(),
+                ),
+                compression: self.compression,
+            },
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+async fn healthcheck(uri: Uri, client: HttpClient) -> crate::Result<()> {
+    let request = Request::get(uri).body(Body::empty()).unwrap();
+    let response = client.send(request).await?;
+
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use futures::{stream, StreamExt};
+    use vector_core::{
+        event::{Event, Metric, MetricKind, MetricValue},
+        metric_tags,
+    };
+
+    use super::*;
+    use crate::{
+        sinks::util::test::build_test_server,
+        test_util::{
+            components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+            next_addr,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpenTsdbConfig>();
+    }
+
+    #[tokio::test]
+    async fn puts_data_points() {
+        let addr = next_addr();
+        let (rx, trigger, server) = build_test_server(addr);
+        tokio::spawn(server);
+
+        let config = toml::from_str::<OpenTsdbConfig>(&format!(
+            r#"
+            endpoint = "http://{}/"
+            default_namespace = "vector"
+            tags.region = "us-west-1"
+        "#,
+            addr
+        ))
+        .unwrap();
+        let (sink, _healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let gauge = |value| -> Event {
+            Metric::new("load", MetricKind::Absolute, MetricValue::Gauge { value })
+                .with_tags(Some(metric_tags!("host" => "web-1")))
+                .with_timestamp(Some(Utc.timestamp_opt(1_680_000_000, 0).unwrap()))
+                .into()
+        };
+        let events = vec![gauge(0.5), gauge(0.75)];
+        run_and_assert_sink_compliance(sink, stream::iter(events), &HTTP_SINK_TAGS).await;
+        drop(trigger);
+
+        let requests = rx.collect::<Vec<_>>().await;
+        assert_eq!(requests.len(), 1);
+        let (parts, body) = &requests[0];
+        assert_eq!(parts.method, "POST");
+        assert_eq!(parts.uri.path(), "/api/put");
+        assert_eq!(parts.headers["content-type"], "application/json");
+
+        let body = serde_json::from_slice::<serde_json::Value>(body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!([
+                {
+                    "metric": "vector.load",
+                    "timestamp": 1_680_000_000,
+                    "value": 0.5,
+                    "tags": {"host": "web-1", "region": "us-west-1"},
+                },
+                {
+                    "metric": "vector.load",
+                    "timestamp": 1_680_000_000,
+                    "value": 0.75,
+                    "tags": {"host": "web-1", "region": "us-west-1"},
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+};
+
+use chrono::Utc;
+use serde::Serialize;
+use vector_core::event::Metric;
+
+use crate::sinks::util::{encode_namespace, encoding::Encoder, metric_points::metric_points};
+
+/// A data point, in the format of the `/api/put` endpoint.
+#[derive(Debug, PartialEq, Serialize)]
+pub(super) struct DataPoint {
+    metric: String,
+    timestamp: i64,
+    value: f64,
+    tags: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct OpenTsdbEncoder {
+    default_namespace: Option<String>,
+    tags: HashMap<String, String>,
+    quantiles: Vec<f64>,
+}
+
+impl OpenTsdbEncoder {
+    pub(super) const fn new(
+        default_namespace: Option<String>,
+        tags: HashMap<String, String>,
+        quantiles: Vec<f64>,
+    ) -> Self {
+        Self {
+            default_namespace,
+            tags,
+            quantiles,
+        }
+    }
+
+    /// Converts a metric into the data points that represent it.
+    ///
+    /// Points that are not finite are skipped, as OpenTSDB cannot store them.
+    pub(super) fn data_points(&self, metric: &Metric) -> Vec<DataPoint> {
+        let namespace = metric.namespace().or(self.default_namespace.as_deref());
+        let name = sanitize(&encode_namespace(namespace, '.', metric.name()));
+        let timestamp = metric.timestamp().unwrap_or_else(Utc::now).timestamp();
+
+        // OpenTSDB rejects empty tag values, so bare tags are dropped.
+        let mut tags = metric
+            .tags()
+            .into_iter()
+            .flat_map(|tags| tags.iter_single())
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(key, value)| (sanitize(key), sanitize(value)))
+            .collect::<BTreeMap<_, _>>();
+        tags.extend(
+            self.tags
+                .iter()
+                .map(|(key, value)| (sanitize(key), sanitize(value))),
+        );
+
+        metric_points(metric.value(), &self.quantiles)
+            .into_iter()
+            .filter(|point| point.value.is_finite())
+            .map(|point| DataPoint {
+                metric: point.name(&name, '.'),
+                timestamp,
+                value: point.value,
+                tags: tags.clone(),
+            })
+            .collect()
+    }
+}
+
+impl Encoder<Vec<Metric>> for OpenTsdbEncoder {
+    fn encode_input(&self, input: Vec<Metric>, writer: &mut dyn io::Write) -> io::Result<usize> {
+        let data_points = input
+            .iter()
+            .flat_map(|metric| self.data_points(metric))
+            .collect::<Vec<_>>();
+        let body = serde_json::to_vec(&data_points)?;
+        writer.write_all(&body)?;
+        Ok(body.len())
+    }
+}
+
+/// Replaces the characters that OpenTSDB does not allow in metric names and tags with `_`.
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '/') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_core::{
+        event::{
+            metric::{Quantile, TagValue},
+            MetricKind, MetricValue,
+        },
+        metric_tags,
+    };
+
+    use super::*;
+
+    fn encoder() -> OpenTsdbEncoder {
+        OpenTsdbEncoder::new(
+            Some("vector".into()),
+            HashMap::from([("region".to_owned(), "us-west 1".to_owned())]),
+            vec![],
+        )
+    }
+
+    fn metric(value: MetricValue) -> Metric {
+        Metric::new("requests", MetricKind::Absolute, value)
+            .with_tags(Some(metric_tags!(
+                "host" => "web-1",
+                "bare" => TagValue::Bare,
+            )))
+            .with_timestamp(Some(Utc.timestamp_opt(1_680_000_000, 0).unwrap()))
+    }
+
+    #[test]
+    fn encodes_put_request() {
+        let mut body = Vec::new();
+        encoder()
+            .encode_input(vec![metric(MetricValue::Counter { value: 2.0 })], &mut body)
+            .unwrap();
+
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+            serde_json::json!([{
+                "metric": "vector.requests",
+                "timestamp": 1_680_000_000,
+                "value": 2.0,
+                "tags": {"host": "web-1", "region": "us-west_1"},
+            }])
+        );
+    }
+
+    #[test]
+    fn splits_summaries_into_points() {
+        let value = MetricValue::AggregatedSummary {
+            quantiles: vec![Quantile {
+                quantile: 0.99,
+                value: f64::NAN,
+            }],
+            count: 3,
+            sum: 4.5,
+        };
+        let names = encoder()
+            .data_points(&metric(value))
+            .into_iter()
+            .map(|point| point.metric)
+            .collect::<Vec<_>>();
+
+        // The quantile has no value, so it is skipped.
+        assert_eq!(names, ["vector.requests.count", "vector.requests.sum"]);
+    }
+}
@@ -0,0 +1,7 @@
+mod config;
+mod encoder;
+mod request_builder;
+mod service;
+mod sink;
+
+pub use self::config::OpenTsdbConfig;
@@ -0,0 +1,84 @@
+use std::io;
+
+use bytes::Bytes;
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{
+    event::{EventFinalizers, Finalizable, Metric},
+    ByteSizeOf,
+};
+
+use super::encoder::OpenTsdbEncoder;
+use crate::sinks::util::{
+    metadata::RequestMetadataBuilder, request_builder::EncodeResult, Compression, RequestBuilder,
+};
+
+#[derive(Clone)]
+pub(super) struct OpenTsdbRequest {
+    pub(super) body: Bytes,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for OpenTsdbRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl ByteSizeOf for OpenTsdbRequest {
+    fn allocated_bytes(&self) -> usize {
+        self.body.allocated_bytes() + self.finalizers.allocated_bytes()
+    }
+}
+
+impl MetaDescriptive for OpenTsdbRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+pub(super) struct OpenTsdbRequestBuilder {
+    pub(super) encoder: OpenTsdbEncoder,
+    pub(super) compression: Compression,
+}
+
+impl RequestBuilder<Vec<Metric>> for OpenTsdbRequestBuilder {
+    type Metadata = EventFinalizers;
+    type Events = Vec<Metric>;
+    type Encoder = OpenTsdbEncoder;
+    type Payload = Bytes;
+    type Request = OpenTsdbRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        mut metrics: Vec<Metric>,
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let mut builder = RequestMetadataBuilder::default();
+        for metric in &metrics {
+            builder.track_event(metric);
+        }
+        (metrics.take_finalizers(), builder, metrics)
+    }
+
+    fn build_request(
+        &self,
+        finalizers: Self::Metadata,
+        metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        OpenTsdbRequest {
+            body: payload.into_payload(),
+            finalizers,
+            metadata,
+        }
+    }
+}
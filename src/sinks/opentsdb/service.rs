@@ -0,0 +1,101 @@
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures::future::{self, BoxFuture, Ready};
+use http::{Request, Uri};
+use tower::{Service, ServiceExt};
+use vector_common::request_metadata::{MetaDescriptive, RequestMetadata};
+use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
+
+use super::request_builder::OpenTsdbRequest;
+use crate::{
+    event::EventStatus,
+    http::HttpClient,
+    sinks::util::{http::HttpBatchService, sink::Response, Compression},
+};
+
+pub(super) struct OpenTsdbResponse {
+    event_status: EventStatus,
+    pub(super) http_status: http::StatusCode,
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for OpenTsdbResponse {
+    fn event_status(&self) -> EventStatus {
+        self.event_status
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_estimated_json_encoded_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        // HttpBatchService emits EndpointBytesSent
+        None
+    }
+
+    fn status_code(&self) -> Option<u16> {
+        Some(self.http_status.as_u16())
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct OpenTsdbService {
+    batch_http_service:
+        HttpBatchService<Ready<Result<http::Request<Bytes>, crate::Error>>, OpenTsdbRequest>,
+}
+
+impl OpenTsdbService {
+    pub(super) fn new(uri: Uri, compression: Compression, http_client: HttpClient) -> Self {
+        let batch_http_service = HttpBatchService::new(http_client, move |req| {
+            let req: OpenTsdbRequest = req;
+
+            let mut builder = Request::post(&uri).header("Content-Type", "application/json");
+            if let Some(content_encoding) = compression.content_encoding() {
+                builder = builder.header("Content-Encoding", content_encoding);
+            }
+            future::ready(builder.body(req.body).map_err(Into::into))
+        });
+
+        Self { batch_http_service }
+    }
+}
+
+impl Service<OpenTsdbRequest> for OpenTsdbService {
+    type Response = OpenTsdbResponse;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of Error internal event is handled upstream by the caller
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of Error internal event is handled upstream by the caller
+    fn call(&mut self, req: OpenTsdbRequest) -> Self::Future {
+        let mut http_service = self.batch_http_service.clone();
+
+        Box::pin(async move {
+            http_service.ready().await?;
+            let metadata = req.get_metadata();
+            let http_response = http_service.call(req).await?;
+            // OpenTSDB answers with `204 No Content` once all data points are stored, and with
+            // `400 Bad Request` if any of them is invalid, such as a data point without tags.
+            let event_status = if http_response.is_successful() {
+                EventStatus::Delivered
+            } else if http_response.is_transient() {
+                EventStatus::Errored
+            } else {
+                EventStatus::Rejected
+            };
+            Ok(OpenTsdbResponse {
+                event_status,
+                http_status: http_response.status(),
+                metadata,
+            })
+        })
+    }
+}
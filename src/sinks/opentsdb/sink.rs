@@ -0,0 +1,65 @@
+use std::{fmt, future::ready, num::NonZeroUsize};
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::Service;
+use vector_core::{
+    event::Event,
+    sink::StreamSink,
+    stream::{BatcherSettings, DriverResponse},
+};
+
+use super::request_builder::{OpenTsdbRequest, OpenTsdbRequestBuilder};
+use crate::{
+    internal_events::SinkRequestBuildError,
+    sinks::util::{metric_points::MetricPointNormalize, SinkBuilderExt},
+};
+
+pub(super) struct OpenTsdbSink<S> {
+    pub(super) service: S,
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) request_builder: OpenTsdbRequestBuilder,
+}
+
+impl<S> OpenTsdbSink<S>
+where
+    S: Service<OpenTsdbRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + fmt::Display + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        input
+            .filter_map(|event| ready(event.try_into_metric()))
+            // OpenTSDB stores the current value of each series, so counters are sent as running
+            // totals, which can be turned into rates at query time, and gauges as absolute values.
+            .normalized_with_default::<MetricPointNormalize>()
+            .batched(self.batch_settings.into_byte_size_config())
+            .request_builder(NonZeroUsize::new(50), self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for OpenTsdbSink<S>
+where
+    S: Service<OpenTsdbRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + fmt::Display + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
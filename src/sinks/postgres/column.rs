@@ -0,0 +1,301 @@
+use std::error::Error;
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use snafu::Snafu;
+use tokio_postgres::types::{to_sql_checked, IsNull, ToSql, Type};
+use vrl::value::Value;
+
+/// The column types that event values can be converted to.
+const SUPPORTED_TYPES: &[Type] = &[
+    Type::BOOL,
+    Type::INT2,
+    Type::INT4,
+    Type::INT8,
+    Type::FLOAT4,
+    Type::FLOAT8,
+    Type::TEXT,
+    Type::VARCHAR,
+    Type::BPCHAR,
+    Type::NAME,
+    Type::JSON,
+    Type::JSONB,
+    Type::TIMESTAMP,
+    Type::TIMESTAMPTZ,
+    Type::BYTEA,
+];
+
+pub(super) fn is_supported(ty: &Type) -> bool {
+    SUPPORTED_TYPES.contains(ty)
+}
+
+pub(super) fn supported_type_names() -> String {
+    SUPPORTED_TYPES
+        .iter()
+        .map(Type::name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("cannot convert {} value to `{}`", kind, type_name))]
+pub(super) struct ConversionError {
+    kind: String,
+    type_name: String,
+}
+
+/// A value that has been converted to the type of the column it is written to.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum ColumnValue {
+    Null,
+    Bool(bool),
+    Int2(i16),
+    Int4(i32),
+    Int8(i64),
+    Float4(f32),
+    Float8(f64),
+    Text(String),
+    Json(String),
+    Timestamp(NaiveDateTime),
+    TimestampTz(DateTime<Utc>),
+    Bytea(Vec<u8>),
+}
+
+impl ColumnValue {
+    /// Converts an event value to the given column type.
+    ///
+    /// Missing and null values are written as `NULL`.
+    pub(super) fn convert(value: Option<&Value>, ty: &Type) -> Result<Self, ConversionError> {
+        let value = match value {
+            None | Some(Value::Null) => return Ok(ColumnValue::Null),
+            Some(value) => value,
+        };
+        let converted = match *ty {
+            Type::BOOL => to_bool(value).map(ColumnValue::Bool),
+            Type::INT2 => to_i64(value)
+                .and_then(|int| i16::try_from(int).ok())
+                .map(ColumnValue::Int2),
+            Type::INT4 => to_i64(value)
+                .and_then(|int| i32::try_from(int).ok())
+                .map(ColumnValue::Int4),
+            Type::INT8 => to_i64(value).map(ColumnValue::Int8),
+            Type::FLOAT4 => to_f64(value).map(|float| ColumnValue::Float4(float as f32)),
+            Type::FLOAT8 => to_f64(value).map(ColumnValue::Float8),
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME => {
+                Some(ColumnValue::Text(to_text(value)))
+            }
+            Type::JSON | Type::JSONB => serde_json::to_string(value).ok().map(ColumnValue::Json),
+            Type::TIMESTAMP => to_timestamp(value).map(|ts| ColumnValue::Timestamp(ts.naive_utc())),
+            Type::TIMESTAMPTZ => to_timestamp(value).map(ColumnValue::TimestampTz),
+            Type::BYTEA => match value {
+                Value::Bytes(bytes) => Some(ColumnValue::Bytea(bytes.to_vec())),
+                _ => None,
+            },
+            _ => None,
+        };
+        converted.ok_or_else(|| ConversionError {
+            kind: value.kind_str().to_string(),
+            type_name: ty.name().to_string(),
+        })
+    }
+}
+
+fn to_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Boolean(bool) => Some(*bool),
+        Value::Integer(0) => Some(false),
+        Value::Integer(1) => Some(true),
+        Value::Bytes(bytes) => match bytes.as_ref() {
+            b"true" | b"t" | b"1" => Some(true),
+            b"false" | b"f" | b"0" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn to_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Integer(int) => Some(*int),
+        Value::Float(float) if float.fract() == 0.0 => Some(float.into_inner() as i64),
+        Value::Boolean(bool) => Some(i64::from(*bool)),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes).ok()?.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Float(float) => Some(float.into_inner()),
+        Value::Integer(int) => Some(*int as f64),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes).ok()?.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn to_text(value: &Value) -> String {
+    match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        Value::Timestamp(timestamp) => timestamp.to_rfc3339(),
+        Value::Object(_) | Value::Array(_) => {
+            serde_json::to_string(value).expect("values are always serializable")
+        }
+        value => value.to_string_lossy().into_owned(),
+    }
+}
+
+fn to_timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Timestamp(timestamp) => Some(*timestamp),
+        Value::Integer(seconds) => Utc.timestamp_opt(*seconds, 0).single(),
+        Value::Bytes(bytes) => DateTime::parse_from_rfc3339(std::str::from_utf8(bytes).ok()?)
+            .ok()
+            .map(|timestamp| timestamp.with_timezone(&Utc)),
+        _ => None,
+    }
+}
+
+impl ToSql for ColumnValue {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        match self {
+            ColumnValue::Null => Ok(IsNull::Yes),
+            ColumnValue::Bool(value) => value.to_sql(ty, out),
+            ColumnValue::Int2(value) => value.to_sql(ty, out),
+            ColumnValue::Int4(value) => value.to_sql(ty, out),
+            ColumnValue::Int8(value) => value.to_sql(ty, out),
+            ColumnValue::Float4(value) => value.to_sql(ty, out),
+            ColumnValue::Float8(value) => value.to_sql(ty, out),
+            ColumnValue::Text(value) => value.to_sql(ty, out),
+            ColumnValue::Json(value) => {
+                // The binary format of `jsonb` is a version number followed by the JSON text.
+                if *ty == Type::JSONB {
+                    out.put_u8(1);
+                }
+                out.put_slice(value.as_bytes());
+                Ok(IsNull::No)
+            }
+            ColumnValue::Timestamp(value) => value.to_sql(ty, out),
+            ColumnValue::TimestampTz(value) => value.to_sql(ty, out),
+            ColumnValue::Bytea(value) => value.to_sql(ty, out),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        is_supported(ty)
+    }
+
+    to_sql_checked!();
+}
+
+/// Quotes a possibly schema-qualified identifier, such as a table or column name.
+pub(super) fn quote_identifier(identifier: &str) -> String {
+    identifier
+        .split('.')
+        .map(|part| format!("\"{}\"", part.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vrl::value;
+
+    use super::*;
+
+    fn convert(value: Value, ty: Type) -> Result<ColumnValue, ConversionError> {
+        ColumnValue::convert(Some(&value), &ty)
+    }
+
+    #[test]
+    fn converts_missing_and_null_values_to_null() {
+        assert_eq!(
+            ColumnValue::convert(None, &Type::INT8).unwrap(),
+            ColumnValue::Null
+        );
+        assert_eq!(convert(Value::Null, Type::TEXT).unwrap(), ColumnValue::Null);
+    }
+
+    #[test]
+    fn converts_numbers() {
+        assert_eq!(
+            convert(value!(42), Type::INT2).unwrap(),
+            ColumnValue::Int2(42)
+        );
+        assert_eq!(
+            convert(value!("42"), Type::INT4).unwrap(),
+            ColumnValue::Int4(42)
+        );
+        assert_eq!(
+            convert(value!(4.0), Type::INT8).unwrap(),
+            ColumnValue::Int8(4)
+        );
+        assert_eq!(
+            convert(value!(1), Type::FLOAT8).unwrap(),
+            ColumnValue::Float8(1.0)
+        );
+        assert!(convert(value!(70000), Type::INT2).is_err());
+        assert!(convert(value!(4.5), Type::INT8).is_err());
+        assert!(convert(value!("four"), Type::INT8).is_err());
+    }
+
+    #[test]
+    fn converts_text_and_json() {
+        assert_eq!(
+            convert(value!(true), Type::TEXT).unwrap(),
+            ColumnValue::Text("true".to_owned())
+        );
+        assert_eq!(
+            convert(value!({"a": [1, 2]}), Type::VARCHAR).unwrap(),
+            ColumnValue::Text(r#"{"a":[1,2]}"#.to_owned())
+        );
+        assert_eq!(
+            convert(value!("plain"), Type::JSONB).unwrap(),
+            ColumnValue::Json(r#""plain""#.to_owned())
+        );
+    }
+
+    #[test]
+    fn converts_timestamps() {
+        let timestamp = Utc.with_ymd_and_hms(2023, 4, 5, 6, 7, 8).unwrap();
+        assert_eq!(
+            convert(Value::Timestamp(timestamp), Type::TIMESTAMPTZ).unwrap(),
+            ColumnValue::TimestampTz(timestamp)
+        );
+        assert_eq!(
+            convert(value!("2023-04-05T08:07:08+02:00"), Type::TIMESTAMP).unwrap(),
+            ColumnValue::Timestamp(timestamp.naive_utc())
+        );
+        assert_eq!(
+            convert(value!(1680674828), Type::TIMESTAMPTZ).unwrap(),
+            ColumnValue::TimestampTz(timestamp)
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_conversions() {
+        let error = convert(value!([1]), Type::BYTEA).unwrap_err();
+        assert_eq!(error.to_string(), "cannot convert array value to `bytea`");
+        assert!(convert(value!("yes"), Type::BOOL).is_err());
+    }
+
+    #[test]
+    fn encodes_jsonb_with_version() {
+        let mut out = BytesMut::new();
+        ColumnValue::Json("{}".to_owned())
+            .to_sql(&Type::JSONB, &mut out)
+            .unwrap();
+        assert_eq!(&out[..], b"\x01{}");
+    }
+
+    #[test]
+    fn quotes_identifiers() {
+        assert_eq!(quote_identifier("logs"), r#""logs""#);
+        assert_eq!(quote_identifier("app.logs"), r#""app"."logs""#);
+        assert_eq!(quote_identifier(r#"we"ird"#), r#""we""ird""#);
+    }
+}
@@ -0,0 +1,180 @@
+use chrono::{TimeZone, Utc};
+use futures::stream;
+use tokio_postgres::{Client, Config};
+use vector_common::finalization::{BatchNotifier, BatchStatus};
+
+use super::config::{InsertMode, PostgresConfig};
+use crate::{
+    config::{SinkConfig, SinkContext},
+    event::{Event, LogEvent},
+    postgresql::connect,
+    test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        random_string, trace_init,
+    },
+};
+
+fn pg_host() -> String {
+    std::env::var("PG_HOST").unwrap_or_else(|_| "localhost".into())
+}
+
+fn pg_url() -> String {
+    std::env::var("PG_URL")
+        .unwrap_or_else(|_| format!("postgres://vector:vector@{}/postgres", pg_host()))
+}
+
+async fn create_table(table: &str) -> Client {
+    let config: Config = pg_url().parse().unwrap();
+    let client = connect(&config, None).await.unwrap();
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE {table} (
+                ts TIMESTAMPTZ NOT NULL,
+                message TEXT,
+                status INT4,
+                payload JSONB
+            )"
+        ))
+        .await
+        .unwrap();
+    client
+}
+
+fn table_name() -> String {
+    format!("postgres_sink_{}", random_string(10).to_lowercase())
+}
+
+fn config(table: &str, mode: InsertMode) -> PostgresConfig {
+    toml::from_str(&format!(
+        r#"
+        endpoint = "{}"
+        table = "{table}"
+        mode = "{}"
+        columns.ts = "timestamp"
+        columns.message = "message"
+        columns.status = "status"
+        columns.payload = "payload"
+        "#,
+        pg_url(),
+        match mode {
+            InsertMode::Insert => "insert",
+            InsertMode::Copy => "copy",
+        },
+    ))
+    .unwrap()
+}
+
+fn event(index: i64) -> LogEvent {
+    let mut log = LogEvent::from(format!("message {index}"));
+    log.insert(
+        "timestamp",
+        Utc.timestamp_opt(1_680_000_000 + index, 0).unwrap(),
+    );
+    log.insert("status", 200_i64 + index);
+    log.insert("payload.index", index);
+    log
+}
+
+async fn writes_rows(mode: InsertMode) {
+    trace_init();
+
+    let table = table_name();
+    let client = create_table(&table).await;
+    let config = config(&table, mode);
+    let (sink, healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+    healthcheck.await.unwrap();
+
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let mut events = (0..10)
+        .map(|index| Event::from(event(index).with_batch_notifier(&batch)))
+        .collect::<Vec<_>>();
+    // Fields that are missing from an event are written as `NULL`.
+    events[9].as_mut_log().remove("payload");
+    drop(batch);
+
+    run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+    assert_eq!(receiver.await, BatchStatus::Delivered);
+
+    let rows = client
+        .query(
+            &format!("SELECT ts, message, status, payload::TEXT FROM {table} ORDER BY ts"),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 10);
+
+    let first = &rows[0];
+    assert_eq!(
+        first.get::<_, chrono::DateTime<Utc>>(0),
+        Utc.timestamp_opt(1_680_000_000, 0).unwrap()
+    );
+    assert_eq!(first.get::<_, &str>(1), "message 0");
+    assert_eq!(first.get::<_, i32>(2), 200);
+    assert_eq!(first.get::<_, &str>(3), r#"{"index": 0}"#);
+    assert_eq!(rows[9].get::<_, Option<&str>>(3), None);
+}
+
+#[tokio::test]
+async fn writes_rows_with_insert() {
+    writes_rows(InsertMode::Insert).await;
+}
+
+#[tokio::test]
+async fn writes_rows_with_copy() {
+    writes_rows(InsertMode::Copy).await;
+}
+
+#[tokio::test]
+async fn rejects_unconvertible_events() {
+    trace_init();
+
+    let table = table_name();
+    let client = create_table(&table).await;
+    let config = config(&table, InsertMode::Insert);
+    let (sink, _healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let mut bad = event(1);
+    bad.insert("status", "not a number");
+    let events = vec![
+        Event::from(event(0).with_batch_notifier(&batch)),
+        Event::from(bad.with_batch_notifier(&batch)),
+    ];
+    drop(batch);
+
+    sink.run(stream::iter(events)).await.unwrap();
+    assert_eq!(receiver.await, BatchStatus::Rejected);
+
+    let rows = client
+        .query(&format!("SELECT message FROM {table}"), &[])
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<_, &str>(0), "message 0");
+}
+
+#[tokio::test]
+async fn healthcheck_fails_on_unsupported_column() {
+    trace_init();
+
+    let table = table_name();
+    let client = create_table(&table).await;
+    client
+        .batch_execute(&format!("ALTER TABLE {table} ADD COLUMN address INET"))
+        .await
+        .unwrap();
+
+    let mut config = config(&table, InsertMode::Insert);
+    config
+        .columns
+        .insert("address".to_owned(), "host".to_owned().try_into().unwrap());
+    let (_sink, healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let error = healthcheck.await.unwrap_err();
+    assert!(
+        error.to_string().contains("`inet`, which is not supported"),
+        "{}",
+        error
+    );
+}
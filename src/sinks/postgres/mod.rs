@@ -0,0 +1,14 @@
+//! The `postgres` sink.
+//!
+//! Writes log events as rows of a PostgreSQL table, mapping event fields to columns. The column
+//! types are read from the table on startup, and each batch is written in a single transaction,
+//! either with multi-row `INSERT` statements or with `COPY`.
+
+mod column;
+mod config;
+#[cfg(all(test, feature = "postgres-integration-tests"))]
+mod integration_tests;
+mod service;
+mod sink;
+
+pub use self::config::PostgresConfig;
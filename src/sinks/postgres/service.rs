@@ -0,0 +1,360 @@
+use std::{
+    error::Error as _,
+    io,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use futures::{future::BoxFuture, pin_mut};
+use snafu::{ResultExt, Snafu};
+use tokio_postgres::{
+    binary_copy::BinaryCopyInWriter,
+    types::{ToSql, Type},
+    Client, Config, Error as PgError,
+};
+use tower::Service;
+use vector_common::{
+    finalization::{EventFinalizers, EventStatus, Finalizable},
+    internal_event::CountByteSize,
+    request_metadata::{MetaDescriptive, RequestMetadata},
+};
+use vector_core::stream::DriverResponse;
+
+use super::{
+    column::{is_supported, quote_identifier, supported_type_names, ColumnValue},
+    config::InsertMode,
+};
+use crate::{
+    internal_events::EndpointBytesSent,
+    postgresql::{config_to_endpoint, connect, PostgresqlConnectError, PostgresqlTlsConfig},
+    sinks::util::retries::RetryLogic,
+};
+
+/// The maximum number of parameters in a single statement, imposed by the wire protocol.
+const MAX_PARAMETERS: usize = u16::MAX as usize;
+
+#[derive(Debug, Snafu)]
+pub(super) enum PostgresError {
+    #[snafu(display("{}", source))]
+    Connect { source: PostgresqlConnectError },
+    #[snafu(display("failed to read the columns of table {}: {}", table, source))]
+    DescribeTable { source: PgError, table: String },
+    #[snafu(display(
+        "column {} has type `{}`, which is not supported (supported types are: {})",
+        column,
+        type_name,
+        supported_type_names()
+    ))]
+    UnsupportedColumnType { column: String, type_name: String },
+    #[snafu(display("failed to write rows: {}", source))]
+    Write { source: PgError },
+}
+
+impl PostgresError {
+    /// Whether the error is likely to go away on its own, such as a dropped connection or a
+    /// serialization failure, as opposed to a problem with the configuration or the data.
+    pub(super) fn is_transient(&self) -> bool {
+        match self {
+            PostgresError::Connect {
+                source: PostgresqlConnectError::ConnectionFailed { source, .. },
+            }
+            | PostgresError::DescribeTable { source, .. }
+            | PostgresError::Write { source } => is_transient(source),
+            PostgresError::Connect { .. } | PostgresError::UnsupportedColumnType { .. } => false,
+        }
+    }
+}
+
+fn is_transient(error: &PgError) -> bool {
+    match error.code() {
+        Some(state) => {
+            let code = state.code();
+            // connection_exception, insufficient_resources, operator_intervention (such as
+            // admin_shutdown), serialization_failure, deadlock_detected, lock_not_available.
+            code.starts_with("08")
+                || code.starts_with("53")
+                || code.starts_with("57P")
+                || matches!(code, "40001" | "40P01" | "55P03")
+        }
+        None => {
+            error.is_closed()
+                || error
+                    .source()
+                    .map_or(false, |source| source.is::<io::Error>())
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct PostgresRetryLogic;
+
+impl RetryLogic for PostgresRetryLogic {
+    type Error = PostgresError;
+    type Response = PostgresResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_transient()
+    }
+}
+
+/// A pool of idle connections, so that concurrent requests each get their own connection.
+#[derive(Clone)]
+pub(super) struct Connections {
+    config: Config,
+    tls: Option<PostgresqlTlsConfig>,
+    idle: Arc<Mutex<Vec<Client>>>,
+}
+
+impl Connections {
+    pub(super) fn new(config: Config, tls: Option<PostgresqlTlsConfig>) -> Self {
+        Self {
+            config,
+            tls,
+            idle: Default::default(),
+        }
+    }
+
+    pub(super) fn endpoint(&self) -> String {
+        config_to_endpoint(&self.config)
+    }
+
+    async fn get(&self) -> Result<Client, PostgresError> {
+        let idle = {
+            let mut idle = self.idle.lock().expect("poisoned lock");
+            std::iter::from_fn(|| idle.pop()).find(|client| !client.is_closed())
+        };
+        match idle {
+            Some(client) => Ok(client),
+            None => connect(&self.config, self.tls.as_ref())
+                .await
+                .context(ConnectSnafu),
+        }
+    }
+
+    fn put(&self, client: Client) {
+        if !client.is_closed() {
+            self.idle.lock().expect("poisoned lock").push(client);
+        }
+    }
+
+    /// Looks up the types of the given columns, making sure that they can be written to.
+    pub(super) async fn describe(
+        &self,
+        table: &str,
+        columns: &[String],
+    ) -> Result<Vec<Type>, PostgresError> {
+        let client = self.get().await?;
+        let query = format!(
+            "SELECT {} FROM {} LIMIT 0",
+            columns
+                .iter()
+                .map(|column| quote_identifier(column))
+                .collect::<Vec<_>>()
+                .join(", "),
+            quote_identifier(table)
+        );
+        let statement = client
+            .prepare(&query)
+            .await
+            .with_context(|_| DescribeTableSnafu { table })?;
+        self.put(client);
+
+        columns
+            .iter()
+            .zip(statement.columns())
+            .map(|(column, described)| {
+                let ty = described.type_();
+                if is_supported(ty) {
+                    Ok(ty.clone())
+                } else {
+                    UnsupportedColumnTypeSnafu {
+                        column,
+                        type_name: ty.name(),
+                    }
+                    .fail()
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct PostgresRequest {
+    pub(super) rows: Vec<Vec<ColumnValue>>,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for PostgresRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for PostgresRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+pub(super) struct PostgresResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for PostgresResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_estimated_json_encoded_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct PostgresService {
+    connections: Connections,
+    table: String,
+    columns: String,
+    types: Vec<Type>,
+    mode: InsertMode,
+}
+
+impl PostgresService {
+    pub(super) fn new(
+        connections: Connections,
+        table: &str,
+        columns: &[String],
+        types: Vec<Type>,
+        mode: InsertMode,
+    ) -> Self {
+        Self {
+            connections,
+            table: quote_identifier(table),
+            columns: columns
+                .iter()
+                .map(|column| quote_identifier(column))
+                .collect::<Vec<_>>()
+                .join(", "),
+            types,
+            mode,
+        }
+    }
+
+    fn insert_statement(&self, rows: usize) -> String {
+        let columns = self.types.len();
+        let values = (0..rows)
+            .map(|row| {
+                let params = (1..=columns)
+                    .map(|column| format!("${}", row * columns + column))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("({})", params)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "INSERT INTO {} ({}) VALUES {}",
+            self.table, self.columns, values
+        )
+    }
+
+    /// Writes all rows in a single transaction, so that either all or none of them are committed.
+    async fn write(&self, client: &mut Client, rows: &[Vec<ColumnValue>]) -> Result<(), PgError> {
+        let transaction = client.transaction().await?;
+
+        match self.mode {
+            InsertMode::Insert => {
+                let rows_per_statement = MAX_PARAMETERS / self.types.len();
+                for chunk in rows.chunks(rows_per_statement) {
+                    let types = self.types.repeat(chunk.len());
+                    let statement = transaction
+                        .prepare_typed(&self.insert_statement(chunk.len()), &types)
+                        .await?;
+                    let params = chunk
+                        .iter()
+                        .flatten()
+                        .map(|value| value as &(dyn ToSql + Sync))
+                        .collect::<Vec<_>>();
+                    transaction.execute(&statement, &params).await?;
+                }
+            }
+            InsertMode::Copy => {
+                let statement = format!(
+                    "COPY {} ({}) FROM STDIN (FORMAT binary)",
+                    self.table, self.columns
+                );
+                let sink = transaction.copy_in(&statement).await?;
+                let writer = BinaryCopyInWriter::new(sink, &self.types);
+                pin_mut!(writer);
+                for row in rows {
+                    let params = row
+                        .iter()
+                        .map(|value| value as &(dyn ToSql + Sync))
+                        .collect::<Vec<_>>();
+                    writer.as_mut().write(&params).await?;
+                }
+                writer.finish().await?;
+            }
+        }
+
+        transaction.commit().await
+    }
+}
+
+impl Service<PostgresRequest> for PostgresService {
+    type Response = PostgresResponse;
+    type Error = PostgresError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: PostgresRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let metadata = request.get_metadata();
+            let mut client = service.connections.get().await?;
+            let result = service.write(&mut client, &request.rows).await;
+            service.connections.put(client);
+            result.context(WriteSnafu)?;
+
+            emit!(EndpointBytesSent {
+                byte_size: metadata.request_encoded_size(),
+                protocol: "postgresql",
+                endpoint: &service.connections.endpoint(),
+            });
+            Ok(PostgresResponse { metadata })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_multi_row_insert_statement() {
+        let service = PostgresService::new(
+            Connections::new(Config::new(), None),
+            "app.logs",
+            &["message".to_owned(), "host".to_owned()],
+            vec![Type::TEXT, Type::TEXT],
+            InsertMode::Insert,
+        );
+
+        assert_eq!(
+            service.insert_statement(2),
+            r#"INSERT INTO "app"."logs" ("message", "host") VALUES ($1, $2), ($3, $4)"#
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use std::{num::NonZeroUsize, time::Duration};
+
+use futures_util::{stream::BoxStream, StreamExt};
+use lookup::lookup_v2::ConfigTargetPath;
+use tokio_postgres::types::Type;
+use tower::ServiceBuilder;
+use vector_common::finalization::{EventStatus, Finalizable};
+use vector_core::{
+    event::{Event, LogEvent},
+    sink::StreamSink,
+    stream::BatcherSettings,
+    EstimatedJsonEncodedSizeOf,
+};
+
+use super::{
+    column::ColumnValue,
+    config::InsertMode,
+    service::{Connections, PostgresRequest, PostgresRetryLogic, PostgresService},
+};
+use crate::{
+    internal_events::{PostgresSinkConversionError, PostgresSinkDescribeTableError},
+    sinks::util::{
+        metadata::RequestMetadataBuilder, retries::ExponentialBackoff, ServiceBuilderExt,
+        SinkBuilderExt, TowerRequestSettings,
+    },
+};
+
+pub(super) struct PostgresSink {
+    pub(super) connections: Connections,
+    pub(super) table: String,
+    pub(super) columns: Vec<(String, ConfigTargetPath)>,
+    pub(super) mode: InsertMode,
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) request_settings: TowerRequestSettings,
+}
+
+impl PostgresSink {
+    /// Looks up the column types, which are needed to convert events into rows, retrying for as
+    /// long as the failure is transient.
+    async fn describe(&self) -> Result<Vec<Type>, ()> {
+        let columns = self
+            .columns
+            .iter()
+            .map(|(column, _)| column.clone())
+            .collect::<Vec<_>>();
+        let mut backoff = ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(Duration::from_secs(60));
+
+        loop {
+            match self.connections.describe(&self.table, &columns).await {
+                Ok(types) => return Ok(types),
+                Err(error) if error.is_transient() => {
+                    emit!(PostgresSinkDescribeTableError {
+                        table: &self.table,
+                        error: &error,
+                    });
+                    tokio::time::sleep(backoff.next().unwrap()).await;
+                }
+                Err(error) => {
+                    error!(message = "Unable to write to the table.", %error);
+                    return Err(());
+                }
+            }
+        }
+    }
+
+    fn row(&self, log: &LogEvent, types: &[Type]) -> Option<Vec<ColumnValue>> {
+        self.columns
+            .iter()
+            .zip(types)
+            .map(|((column, path), ty)| {
+                ColumnValue::convert(log.get(path), ty)
+                    .map_err(|error| {
+                        emit!(PostgresSinkConversionError {
+                            column,
+                            error: &error,
+                        })
+                    })
+                    .ok()
+            })
+            .collect()
+    }
+
+    /// Converts a batch of events into rows, rejecting the events that cannot be converted.
+    fn build_request(&self, events: Vec<Event>, types: &[Type]) -> Option<PostgresRequest> {
+        let mut rows = Vec::with_capacity(events.len());
+        let mut accepted = Vec::with_capacity(events.len());
+        for mut event in events {
+            match self.row(event.as_log(), types) {
+                Some(row) => {
+                    rows.push(row);
+                    accepted.push(event);
+                }
+                None => event.take_finalizers().update_status(EventStatus::Rejected),
+            }
+        }
+
+        let request_size = NonZeroUsize::new(accepted.estimated_json_encoded_size_of().get())?;
+        let metadata =
+            RequestMetadataBuilder::from_events(&accepted).with_request_size(request_size);
+        Some(PostgresRequest {
+            rows,
+            finalizers: accepted.take_finalizers(),
+            metadata,
+        })
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let types = self.describe().await?;
+
+        let columns = self
+            .columns
+            .iter()
+            .map(|(column, _)| column.clone())
+            .collect::<Vec<_>>();
+        let service = PostgresService::new(
+            self.connections.clone(),
+            &self.table,
+            &columns,
+            types.clone(),
+            self.mode,
+        );
+        let service = ServiceBuilder::new()
+            .settings(self.request_settings.clone(), PostgresRetryLogic)
+            .service(service);
+
+        let sink = &self;
+        let types = &types;
+        input
+            .batched(self.batch_settings.into_byte_size_config())
+            .filter_map(|events| async move { sink.build_request(events, types) })
+            .into_driver(service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for PostgresSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
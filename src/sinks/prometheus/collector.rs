@@ -314,6 +314,20 @@ impl StringCollector {
     }
 }
 
+/// Encodes the given metrics in the Prometheus text exposition format, using the default
+/// histogram buckets and summary quantiles for any distributions.
+#[cfg(feature = "api")]
+pub(crate) fn encode_text<'a>(metrics: impl IntoIterator<Item = &'a Metric>) -> String {
+    let buckets = super::default_histogram_buckets();
+    let quantiles = super::default_summary_quantiles();
+
+    let mut collector = StringCollector::new();
+    for metric in metrics {
+        collector.encode_metric(None, &buckets, &quantiles, metric);
+    }
+    collector.finish()
+}
+
 type Labels = Vec<proto::Label>;
 
 pub(super) struct TimeSeries {
@@ -448,6 +462,38 @@ mod tests {
         test_util::stats::VariableHistogram,
     };
 
+    #[cfg(feature = "api")]
+    #[test]
+    fn encodes_text() {
+        let metrics = [
+            Metric::new(
+                "component_received_events_total",
+                MetricKind::Absolute,
+                MetricValue::Counter { value: 3.0 },
+            )
+            .with_namespace(Some("vector"))
+            .with_tags(Some(metric_tags!("component_id" => "in"))),
+            Metric::new(
+                "uptime_seconds",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 10.0 },
+            )
+            .with_namespace(Some("vector")),
+        ];
+
+        assert_eq!(
+            encode_text(&metrics),
+            indoc! {r#"
+                # HELP vector_component_received_events_total component_received_events_total
+                # TYPE vector_component_received_events_total counter
+                vector_component_received_events_total{component_id="in"} 3
+                # HELP vector_uptime_seconds uptime_seconds
+                # TYPE vector_uptime_seconds gauge
+                vector_uptime_seconds 10
+            "#}
+        );
+    }
+
     fn encode_one<T: MetricCollector>(
         default_namespace: Option<&str>,
         buckets: &[f64],
@@ -57,6 +57,8 @@ const LOCK_FAILED: &str = "Prometheus exporter data lock is poisoned";
 enum BuildError {
     #[snafu(display("Flush period for sets must be greater or equal to {} secs", min))]
     FlushPeriodTooShort { min: u64 },
+    #[snafu(display("The `oauth2` authentication strategy is not supported"))]
+    OAuth2Unsupported,
 }
 
 /// Configuration for the `prometheus_exporter` sink.
@@ -199,6 +201,10 @@ impl SinkConfig for PrometheusExporterConfig {
 
         validate_quantiles(&self.quantiles)?;
 
+        if let Some(Auth::OAuth2(_)) = self.auth {
+            return Err(Box::new(BuildError::OAuth2Unsupported));
+        }
+
         let sink = PrometheusExporter::new(self.clone());
         let healthcheck = future::ok(()).boxed();
 
@@ -365,6 +371,8 @@ fn authorized(req: &Request<Body>, auth: &Option<Auth>) -> bool {
                 Auth::Bearer { token } => {
                     HeaderValue::from_str(format!("Bearer {}", token.inner()).as_str())
                 }
+                // Rejected when building the sink, as access tokens can't be verified here.
+                Auth::OAuth2(_) => return false,
             };
 
             if let Ok(encoded_credentials) = encoded_credentials {
@@ -6,6 +6,9 @@ mod collector;
 pub(crate) mod exporter;
 pub(crate) mod remote_write;
 
+#[cfg(feature = "api")]
+pub(crate) use collector::encode_text;
+
 use vector_config::configurable_component;
 
 use crate::aws::AwsAuthentication;
@@ -2,7 +2,7 @@ use std::task::{Context, Poll};
 
 use bytes::{Bytes, BytesMut};
 use futures::{future::BoxFuture, stream, FutureExt, SinkExt, StreamExt};
-use redis::{aio::ConnectionManager, RedisError, RedisResult};
+use redis::{aio::ConnectionManager, streams::StreamMaxlen, RedisError, RedisResult};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::Encoder as _;
 use tower::{Service, ServiceBuilder};
@@ -53,6 +53,11 @@ pub enum DataTypeConfig {
     ///
     /// Redis channels function in a pub/sub fashion, allowing many-to-many broadcasting and receiving.
     Channel,
+
+    /// The Redis `stream` type.
+    ///
+    /// Messages are appended to the stream with `XADD`, each as the value of a single field.
+    Stream,
 }
 
 /// List-specific options.
@@ -64,7 +69,50 @@ pub struct ListOption {
     method: Method,
 }
 
-#[derive(Clone, Copy, Debug, Derivative)]
+/// Stream-specific options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The field of the stream entries to store the encoded message in.
+    #[serde(default = "default_stream_field")]
+    #[derivative(Default(value = "default_stream_field()"))]
+    #[configurable(metadata(docs::examples = "message"))]
+    field: String,
+
+    /// The maximum number of entries to keep in the stream.
+    ///
+    /// The oldest entries are trimmed as new ones are added. By default, the stream is not trimmed.
+    #[configurable(metadata(docs::examples = 100000))]
+    max_len: Option<usize>,
+
+    /// Whether to trim the stream approximately, with `MAXLEN ~`.
+    ///
+    /// Approximate trimming is much more efficient, at the cost of keeping slightly more than
+    /// `max_len` entries in the stream.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    approximate_max_len: bool,
+}
+
+fn default_stream_field() -> String {
+    "message".to_owned()
+}
+
+impl StreamOption {
+    fn max_len(&self) -> Option<StreamMaxlen> {
+        self.max_len.map(|max_len| {
+            if self.approximate_max_len {
+                StreamMaxlen::Approx(max_len)
+            } else {
+                StreamMaxlen::Equals(max_len)
+            }
+        })
+    }
+}
+
+#[derive(Clone, Debug, Derivative)]
 #[derivative(Default)]
 pub enum DataType {
     /// The Redis `list` type.
@@ -77,6 +125,9 @@ pub enum DataType {
     ///
     /// Redis channels function in a pub/sub fashion, allowing many-to-many broadcasting and receiving.
     Channel,
+
+    /// The Redis `stream` type.
+    Stream(StreamOption),
 }
 
 /// Method for pushing messages into a `list`.
@@ -124,6 +175,10 @@ pub struct RedisSinkConfig {
     #[serde(alias = "list")]
     list_option: Option<ListOption>,
 
+    #[configurable(derived)]
+    #[serde(alias = "stream")]
+    stream_option: Option<StreamOption>,
+
     /// The URL of the Redis endpoint to connect to.
     ///
     /// The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
@@ -212,6 +267,9 @@ impl RedisSinkConfig {
         let data_type = match self.data_type {
             DataTypeConfig::Channel => DataType::Channel,
             DataTypeConfig::List => DataType::List(method.unwrap_or_default()),
+            DataTypeConfig::Stream => {
+                DataType::Stream(self.stream_option.clone().unwrap_or_default())
+            }
         };
 
         let batch = self.batch.into_batch_settings()?;
@@ -380,12 +438,30 @@ impl Service<Vec<RedisKvEntry>> for RedisSink {
                         pipe.publish(kv.key, kv.value.as_ref());
                     }
                 }
+                DataType::Stream(ref options) => {
+                    if count > 1 {
+                        pipe.atomic();
+                    }
+                    let items = [(options.field.as_str(), kv.value.as_ref())];
+                    match options.max_len() {
+                        Some(max_len) => pipe.xadd_maxlen(kv.key, max_len, "*", &items),
+                        None => pipe.xadd(kv.key, "*", &items),
+                    };
+                }
             }
         }
 
+        let is_stream = matches!(self.data_type, DataType::Stream(_));
         let bytes_sent = self.bytes_sent.clone();
         Box::pin(async move {
-            let result: RedisPipeResult = pipe.query_async(&mut conn).await;
+            let result: RedisPipeResult = if is_stream {
+                // `XADD` replies with the ID of the new entry, rather than a count.
+                pipe.query_async(&mut conn)
+                    .await
+                    .map(|ids: Vec<String>| vec![true; ids.len()])
+            } else {
+                pipe.query_async(&mut conn).await
+            };
             if let Ok(res) = &result {
                 if res.is_successful() {
                     bytes_sent.emit(ByteSize(byte_size));
@@ -506,6 +582,7 @@ mod integration_tests {
             list_option: Some(ListOption {
                 method: Method::LPush,
             }),
+            stream_option: None,
             batch: BatchConfig::default(),
             request: TowerRequestConfig {
                 rate_limit_num: Some(u64::MAX),
@@ -569,6 +646,7 @@ mod integration_tests {
             list_option: Some(ListOption {
                 method: Method::RPush,
             }),
+            stream_option: None,
             batch: BatchConfig::default(),
             request: TowerRequestConfig {
                 rate_limit_num: Some(u64::MAX),
@@ -646,6 +724,7 @@ mod integration_tests {
             encoding: JsonSerializerConfig::default().into(),
             data_type: DataTypeConfig::Channel,
             list_option: None,
+            stream_option: None,
             batch: BatchConfig::default(),
             request: TowerRequestConfig {
                 rate_limit_num: Some(u64::MAX),
@@ -676,4 +755,64 @@ mod integration_tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn redis_sink_stream() {
+        trace_init();
+
+        let key = Template::try_from(format!("test-{}", random_string(10)))
+            .expect("should not fail to create key template");
+        debug!("Test key name: {}.", key);
+        let num_events = 1000;
+        let max_len = 100;
+
+        let cnf = RedisSinkConfig {
+            endpoint: redis_server(),
+            key: key.clone(),
+            encoding: JsonSerializerConfig::default().into(),
+            data_type: DataTypeConfig::Stream,
+            list_option: None,
+            stream_option: Some(StreamOption {
+                field: "message".to_owned(),
+                max_len: Some(max_len),
+                approximate_max_len: false,
+            }),
+            batch: BatchConfig::default(),
+            request: TowerRequestConfig {
+                rate_limit_num: Some(u64::MAX),
+                ..Default::default()
+            },
+            acknowledgements: Default::default(),
+        };
+
+        let mut events: Vec<Event> = Vec::new();
+        for i in 0..num_events {
+            let s: String = i.to_string();
+            let e = LogEvent::from(s);
+            events.push(e.into());
+        }
+        let input = stream::iter(events.clone().into_iter().map(Into::into));
+
+        // Publish events.
+        let cnf2 = cnf.clone();
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let conn = cnf2.build_client().await.unwrap();
+            cnf2.new(conn).unwrap().run(input).await
+        })
+        .await
+        .expect("Running sink failed");
+
+        let mut conn = cnf.build_client().await.unwrap();
+
+        // The stream is trimmed down to its most recent entries.
+        let len: usize = conn.xlen(key.to_string()).await.unwrap();
+        assert_eq!(len, max_len);
+
+        let reply: redis::streams::StreamRangeReply =
+            conn.xrange_all(key.to_string()).await.unwrap();
+        for (entry, event) in reply.ids.iter().zip(&events[num_events - max_len..]) {
+            let s = serde_json::to_string(event.as_log()).unwrap_or_default();
+            assert_eq!(entry.get::<String>("message").unwrap(), s);
+        }
+    }
 }
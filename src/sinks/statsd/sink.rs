@@ -30,7 +30,7 @@ pub(crate) struct StatsdSink<S> {
 impl<S> StatsdSink<S>
 where
     S: Service<StatsdRequest> + Send,
-    S::Error: fmt::Debug + Send + 'static,
+    S::Error: fmt::Debug + fmt::Display + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse,
 {
@@ -85,7 +85,7 @@ where
 impl<S> StreamSink<Event> for StatsdSink<S>
 where
     S: Service<StatsdRequest> + Send,
-    S::Error: fmt::Debug + Send + 'static,
+    S::Error: fmt::Debug + fmt::Display + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse,
 {
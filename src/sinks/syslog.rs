@@ -0,0 +1,330 @@
+use codecs::{
+    encoding::{Framer, Serializer},
+    NewlineDelimitedEncoder, OctetCountingEncoder, SyslogSerializerConfig, SyslogSerializerOptions,
+};
+use vector_config::configurable_component;
+
+#[cfg(unix)]
+use crate::sinks::util::unix::UnixSinkConfig;
+use crate::{
+    codecs::{Encoder, Transformer},
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::util::{tcp::TcpSinkConfig, udp::UdpSinkConfig},
+};
+
+/// Configuration for the `syslog` sink.
+#[configurable_component(sink("syslog"))]
+#[derive(Clone, Debug)]
+pub struct SyslogSinkConfig {
+    #[serde(flatten)]
+    pub mode: Mode,
+
+    #[serde(flatten)]
+    pub format: SyslogSerializerOptions,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// Syslog transport mode.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The type of socket to use."))]
+pub enum Mode {
+    /// Send over TCP, optionally secured with TLS.
+    Tcp(TcpMode),
+
+    /// Send over UDP.
+    ///
+    /// Each event is sent as a single datagram, without any framing.
+    Udp(UdpMode),
+
+    /// Send over a Unix domain socket (UDS).
+    #[cfg(unix)]
+    Unix(UnixMode),
+}
+
+/// TCP configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct TcpMode {
+    #[serde(flatten)]
+    pub config: TcpSinkConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub framing: SyslogFraming,
+}
+
+/// UDP configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct UdpMode {
+    #[serde(flatten)]
+    pub config: UdpSinkConfig,
+}
+
+/// Unix Domain Socket configuration.
+#[cfg(unix)]
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct UnixMode {
+    #[serde(flatten)]
+    pub config: UnixSinkConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub framing: SyslogFraming,
+}
+
+/// How syslog messages are framed on stream-based transports.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogFraming {
+    /// Each message is prefixed with its length in bytes, followed by a space.
+    ///
+    /// This is the octet counting method described in [RFC 6587][rfc6587], and is the
+    /// recommended framing for syslog over TCP and TLS ([RFC 5425][rfc5425]).
+    ///
+    /// [rfc6587]: https://datatracker.ietf.org/doc/html/rfc6587#section-3.4.1
+    /// [rfc5425]: https://datatracker.ietf.org/doc/html/rfc5425#section-4.3
+    #[default]
+    OctetCounting,
+
+    /// Each message is terminated by a newline (LF) character.
+    ///
+    /// This is the non-transparent framing method described in [RFC 6587][rfc6587], which is
+    /// common with legacy receivers. Messages containing newlines are split by the receiver.
+    ///
+    /// [rfc6587]: https://datatracker.ietf.org/doc/html/rfc6587#section-3.4.2
+    NewlineDelimited,
+}
+
+impl SyslogFraming {
+    fn build(self) -> Framer {
+        match self {
+            SyslogFraming::OctetCounting => OctetCountingEncoder::new().into(),
+            SyslogFraming::NewlineDelimited => NewlineDelimitedEncoder::new().into(),
+        }
+    }
+}
+
+impl GenerateConfig for SyslogSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "192.0.2.10:514"
+            mode = "tcp"
+            rfc = "rfc5424""#,
+        )
+        .unwrap()
+    }
+}
+
+impl SyslogSinkConfig {
+    fn serializer(&self) -> Serializer {
+        SyslogSerializerConfig::new(self.format.clone())
+            .build()
+            .into()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for SyslogSinkConfig {
+    async fn build(
+        &self,
+        _cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let transformer = self.encoding.clone();
+        match &self.mode {
+            Mode::Tcp(TcpMode { config, framing }) => {
+                let encoder = Encoder::<Framer>::new(framing.build(), self.serializer());
+                config.build(transformer, encoder)
+            }
+            Mode::Udp(UdpMode { config }) => {
+                let encoder = Encoder::<()>::new(self.serializer());
+                config.build(transformer, encoder)
+            }
+            #[cfg(unix)]
+            Mode::Unix(UnixMode { config, framing }) => {
+                let encoder = Encoder::<Framer>::new(framing.build(), self.serializer());
+                config.build(transformer, encoder)
+            }
+        }
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{future::ready, net::UdpSocket};
+
+    use chrono::{TimeZone, Utc};
+    use codecs::{encoding::format::SyslogRfc, OctetCountingDecoder};
+    use futures::{stream, StreamExt};
+    use tokio_util::codec::FramedRead;
+
+    use super::*;
+    use crate::{
+        event::{Event, LogEvent},
+        test_util::{
+            components::{run_and_assert_sink_compliance, SINK_TAGS},
+            next_addr, trace_init, CountReceiver,
+        },
+        tls::{self, MaybeTlsSettings, TlsConfig, TlsEnableableConfig},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SyslogSinkConfig>();
+    }
+
+    fn event(message: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert(
+            "timestamp",
+            Utc.with_ymd_and_hms(2023, 4, 5, 6, 7, 8).unwrap(),
+        );
+        log.insert("hostname", "web-1");
+        log.insert("appname", "app");
+        log.insert("facility", "local0");
+        log.insert("severity", "warning");
+        log.into()
+    }
+
+    fn config(mode: Mode, rfc: SyslogRfc) -> SyslogSinkConfig {
+        SyslogSinkConfig {
+            mode,
+            format: SyslogSerializerOptions {
+                rfc,
+                ..Default::default()
+            },
+            encoding: Default::default(),
+            acknowledgements: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn udp_rfc3164() {
+        trace_init();
+
+        let addr = next_addr();
+        let receiver = UdpSocket::bind(addr).unwrap();
+        let config = config(
+            Mode::Udp(UdpMode {
+                config: UdpSinkConfig::from_address(addr.to_string()),
+            }),
+            SyslogRfc::Rfc3164,
+        );
+
+        let (sink, _healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+        run_and_assert_sink_compliance(sink, stream::once(ready(event("hello"))), &SINK_TAGS).await;
+
+        let mut buf = [0; 256];
+        let (size, _) = receiver.recv_from(&mut buf).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&buf[..size]).unwrap(),
+            "<132>Apr  5 06:07:08 web-1 app: hello"
+        );
+    }
+
+    #[tokio::test]
+    async fn tcp_newline_delimited() {
+        trace_init();
+
+        let addr = next_addr();
+        let config = config(
+            Mode::Tcp(TcpMode {
+                config: TcpSinkConfig::from_address(addr.to_string()),
+                framing: SyslogFraming::NewlineDelimited,
+            }),
+            SyslogRfc::Rfc5424,
+        );
+        let mut receiver = CountReceiver::receive_lines(addr);
+
+        let (sink, _healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+        let events = vec![event("first"), event("second")];
+        run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+
+        receiver.connected().await;
+        let output = receiver.await;
+        assert_eq!(
+            output,
+            vec![
+                "<132>1 2023-04-05T06:07:08.000000Z web-1 app - - - first",
+                "<132>1 2023-04-05T06:07:08.000000Z web-1 app - - - second",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn tls_octet_counting() {
+        trace_init();
+
+        let addr = next_addr();
+        let tls =
+            MaybeTlsSettings::from_config(&Some(TlsEnableableConfig::test_config()), true).unwrap();
+        let listener = tls.bind(&addr).await.unwrap();
+        let server = tokio::spawn(async move {
+            let stream = listener.accept_stream().next().await.unwrap().unwrap();
+            FramedRead::new(stream, OctetCountingDecoder::new())
+                .map(|frame| String::from_utf8(frame.unwrap().to_vec()).unwrap())
+                .collect::<Vec<_>>()
+                .await
+        });
+
+        let config = config(
+            Mode::Tcp(TcpMode {
+                config: TcpSinkConfig::new(
+                    addr.to_string(),
+                    None,
+                    Some(TlsEnableableConfig {
+                        enabled: Some(true),
+                        options: TlsConfig {
+                            verify_certificate: Some(false),
+                            verify_hostname: Some(false),
+                            ca_file: Some(tls::TEST_PEM_CRT_PATH.into()),
+                            ..Default::default()
+                        },
+                    }),
+                    None,
+                ),
+                framing: SyslogFraming::OctetCounting,
+            }),
+            SyslogRfc::Rfc5424,
+        );
+
+        let (sink, _healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+        let events = vec![event("first\nline"), event("second")];
+        run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+
+        let output = server.await.unwrap();
+        assert_eq!(
+            output,
+            vec![
+                "<132>1 2023-04-05T06:07:08.000000Z web-1 app - - - first\nline",
+                "<132>1 2023-04-05T06:07:08.000000Z web-1 app - - - second",
+            ]
+        );
+    }
+}
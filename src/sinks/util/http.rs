@@ -441,6 +441,10 @@ impl<T: fmt::Debug> sink::Response for http::Response<T> {
     fn is_transient(&self) -> bool {
         self.status().is_server_error()
     }
+
+    fn status_code(&self) -> Option<u16> {
+        Some(self.status().as_u16())
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -0,0 +1,253 @@
+//! Flattening of metrics into single-valued data points.
+//!
+//! Backends such as Graphite and OpenTSDB only store plain numeric time series, so every metric
+//! is turned into one or more points: counters, gauges, and sets map to a single point, while
+//! distributions, histograms, summaries, and sketches are broken down into a point per statistic,
+//! each identified by a suffix that is appended to the metric name.
+
+use vector_core::event::{
+    metric::{MetricSketch, Quantile},
+    Metric, MetricValue,
+};
+
+use super::{
+    buffer::metrics::{MetricNormalize, MetricSet},
+    statistic::DistributionStatistic,
+};
+
+/// The quantiles that are computed for sketches, which carry no quantiles of their own.
+const SKETCH_QUANTILES: [f64; 4] = [0.5, 0.75, 0.9, 0.99];
+
+pub fn default_summary_quantiles() -> Vec<f64> {
+    vec![0.5, 0.75, 0.9, 0.95, 0.99]
+}
+
+/// A single value of a metric.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetricPoint {
+    /// The statistic that this point holds, such as `count` or `p99`, or `None` if the metric has
+    /// a single value.
+    ///
+    /// Suffixes only contain alphanumeric characters, `_`, and `-`, so they are safe to use in
+    /// paths that are separated by `.`.
+    pub suffix: Option<String>,
+    pub value: f64,
+}
+
+impl MetricPoint {
+    const fn single(value: f64) -> Self {
+        Self {
+            suffix: None,
+            value,
+        }
+    }
+
+    fn statistic(suffix: impl Into<String>, value: f64) -> Self {
+        Self {
+            suffix: Some(suffix.into()),
+            value,
+        }
+    }
+
+    /// Returns the name of the point, given the name of its metric and a separator.
+    pub fn name(&self, name: &str, separator: char) -> String {
+        match &self.suffix {
+            Some(suffix) => format!("{}{}{}", name, separator, suffix),
+            None => name.to_owned(),
+        }
+    }
+}
+
+/// Flattens a metric value into points, computing the given quantiles for distributions.
+pub fn metric_points(value: &MetricValue, quantiles: &[f64]) -> Vec<MetricPoint> {
+    match value {
+        MetricValue::Counter { value } | MetricValue::Gauge { value } => {
+            vec![MetricPoint::single(*value)]
+        }
+        MetricValue::Set { values } => vec![MetricPoint::single(values.len() as f64)],
+        MetricValue::Distribution { samples, .. } => DistributionStatistic::from_samples(
+            samples, quantiles,
+        )
+        .map_or_else(Vec::new, |statistic| {
+            let mut points = vec![
+                MetricPoint::statistic("min", statistic.min),
+                MetricPoint::statistic("max", statistic.max),
+                MetricPoint::statistic("median", statistic.median),
+                MetricPoint::statistic("avg", statistic.avg),
+                MetricPoint::statistic("sum", statistic.sum),
+                MetricPoint::statistic("count", statistic.count as f64),
+            ];
+            points.extend(
+                statistic
+                    .quantiles
+                    .iter()
+                    .map(|&(quantile, value)| quantile_point(quantile, value)),
+            );
+            points
+        }),
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => buckets
+            .iter()
+            .map(|bucket| {
+                let limit = bucket.upper_limit.to_string().replace('.', "_");
+                MetricPoint::statistic(format!("bucket_{}", limit), bucket.count as f64)
+            })
+            .chain([
+                MetricPoint::statistic("count", *count as f64),
+                MetricPoint::statistic("sum", *sum),
+            ])
+            .collect(),
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => quantiles
+            .iter()
+            .map(|quantile| quantile_point(quantile.quantile, quantile.value))
+            .chain([
+                MetricPoint::statistic("count", *count as f64),
+                MetricPoint::statistic("sum", *sum),
+            ])
+            .collect(),
+        MetricValue::Sketch { sketch } => match sketch {
+            MetricSketch::AgentDDSketch(ddsketch) => {
+                if ddsketch.is_empty() {
+                    return Vec::new();
+                }
+                SKETCH_QUANTILES
+                    .iter()
+                    .filter_map(|&quantile| {
+                        let value = ddsketch.quantile(quantile)?;
+                        Some(quantile_point(quantile, value))
+                    })
+                    .chain([
+                        MetricPoint::statistic("count", f64::from(ddsketch.count())),
+                        MetricPoint::statistic("min", ddsketch.min().unwrap_or(0.0)),
+                        MetricPoint::statistic("max", ddsketch.max().unwrap_or(0.0)),
+                        MetricPoint::statistic("sum", ddsketch.sum().unwrap_or(0.0)),
+                        MetricPoint::statistic("avg", ddsketch.avg().unwrap_or(0.0)),
+                    ])
+                    .collect()
+            }
+        },
+    }
+}
+
+fn quantile_point(quantile: f64, value: f64) -> MetricPoint {
+    let quantile = Quantile { quantile, value };
+    MetricPoint::statistic(format!("p{}", quantile.to_percentile_string()), value)
+}
+
+/// Normalizes metrics for backends that store the current value of each series.
+///
+/// Counters are sent as running totals, which can be turned into rates at query time, and gauges
+/// as absolute values. Other metrics are passed through as-is, so that incremental distributions
+/// describe the samples of a single interval.
+#[derive(Default)]
+pub struct MetricPointNormalize;
+
+impl MetricNormalize for MetricPointNormalize {
+    fn normalize(&mut self, state: &mut MetricSet, metric: Metric) -> Option<Metric> {
+        match metric.value() {
+            MetricValue::Counter { .. } | MetricValue::Gauge { .. } => state.make_absolute(metric),
+            _ => Some(metric),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::{
+        metric::{Bucket, Sample},
+        MetricKind, StatisticKind,
+    };
+
+    use super::*;
+
+    fn names(points: &[MetricPoint]) -> Vec<String> {
+        points.iter().map(|point| point.name("m", '.')).collect()
+    }
+
+    #[test]
+    fn single_valued_metrics() {
+        assert_eq!(
+            metric_points(&MetricValue::Gauge { value: 1.5 }, &[]),
+            vec![MetricPoint::single(1.5)]
+        );
+        assert_eq!(
+            metric_points(
+                &MetricValue::Set {
+                    values: ["a".into(), "b".into()].into()
+                },
+                &[]
+            ),
+            vec![MetricPoint::single(2.0)]
+        );
+    }
+
+    #[test]
+    fn distribution_statistics() {
+        let value = MetricValue::Distribution {
+            samples: vec![
+                Sample {
+                    value: 1.0,
+                    rate: 1,
+                },
+                Sample {
+                    value: 3.0,
+                    rate: 1,
+                },
+            ],
+            statistic: StatisticKind::Histogram,
+        };
+        let points = metric_points(&value, &[0.5, 0.999]);
+        assert_eq!(
+            names(&points),
+            ["m.min", "m.max", "m.median", "m.avg", "m.sum", "m.count", "m.p50", "m.p999"]
+        );
+        assert_eq!(points[4].value, 4.0);
+    }
+
+    #[test]
+    fn histogram_buckets_have_path_safe_names() {
+        let value = MetricValue::AggregatedHistogram {
+            buckets: vec![
+                Bucket {
+                    upper_limit: 0.25,
+                    count: 1,
+                },
+                Bucket {
+                    upper_limit: f64::INFINITY,
+                    count: 3,
+                },
+            ],
+            count: 3,
+            sum: 4.5,
+        };
+        assert_eq!(
+            names(&metric_points(&value, &[])),
+            ["m.bucket_0_25", "m.bucket_inf", "m.count", "m.sum"]
+        );
+    }
+
+    #[test]
+    fn counters_become_running_totals() {
+        let mut state = MetricSet::default();
+        let mut normalizer = MetricPointNormalize;
+        let counter = |value| {
+            Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value },
+            )
+        };
+
+        normalizer.normalize(&mut state, counter(1.0));
+        let total = normalizer.normalize(&mut state, counter(2.0)).unwrap();
+        assert_eq!(total.kind(), MetricKind::Absolute);
+        assert_eq!(total.value(), &MetricValue::Counter { value: 3.0 });
+    }
+}
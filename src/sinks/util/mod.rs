@@ -6,7 +6,10 @@ pub mod compressor;
 pub mod encoding;
 pub mod http;
 pub mod metadata;
+#[cfg(any(feature = "sinks-graphite", feature = "sinks-opentsdb"))]
+pub mod metric_points;
 pub mod normalizer;
+pub mod partial_retry;
 pub mod partitioner;
 pub mod processed_event;
 pub mod request_builder;
@@ -0,0 +1,330 @@
+//! Delivery of batch requests whose items succeed or fail independently of each other.
+//!
+//! Bulk APIs, such as the Elasticsearch `_bulk` endpoint, report an outcome for each item of a
+//! request. [`PartialRetryItems`] keeps the items of such a request along with their finalizers,
+//! so that delivered items are acknowledged, and rejected items reported, as soon as a response
+//! says so, while only the items that failed with a retriable error remain to be sent again.
+//!
+//! Only the `elasticsearch` sink uses it for now. APIs that report a single outcome for a whole
+//! request, such as Splunk HEC and Loki, have nothing to partially retry and keep retrying whole
+//! requests.
+//!
+//! The items are shared between the clones of a request. Since the retry policy sends a clone of
+//! the request that was originally sent, the request that is retried only contains the items that
+//! are still pending. Services are expected to rebuild their payload from
+//! [`PartialRetryItems::pending`] whenever [`PartialRetryItems::is_partial`] is true.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use vector_common::json_size::JsonSize;
+use vector_core::{
+    internal_event::CountByteSize, stream::current_attempt, EstimatedJsonEncodedSizeOf,
+};
+
+use crate::event::{DeliveryFailure, EventFinalizers, EventStatus};
+
+/// The outcome of sending a single item of a batch request, as reported by the destination.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ItemOutcome {
+    /// The item was delivered.
+    Delivered,
+
+    /// The item failed with an error that may go away if it is sent again, such as backpressure.
+    Retry {
+        status_code: Option<u16>,
+        error: Option<String>,
+    },
+
+    /// The item failed with an error that sending it again won't fix.
+    Rejected {
+        status_code: Option<u16>,
+        error: Option<String>,
+    },
+}
+
+struct PendingItem<T> {
+    item: T,
+    finalizers: EventFinalizers,
+}
+
+struct Items<T> {
+    pending: Vec<PendingItem<T>>,
+    partial: bool,
+    rejected: usize,
+    last_failure: Option<(EventStatus, DeliveryFailure)>,
+}
+
+/// What became of the pending items of a request after an attempt at sending it.
+pub struct Completion {
+    /// The number of items that are still pending, to be sent again.
+    pub pending: usize,
+
+    /// The number of items rejected so far, by this attempt or an earlier one.
+    pub rejected: usize,
+
+    /// The number of items delivered by this attempt, and their size.
+    pub delivered: CountByteSize,
+}
+
+impl<T> Drop for Items<T> {
+    fn drop(&mut self) {
+        // Items that are still pending once all clones of the request are dropped were not
+        // delivered, either because retries were exhausted or because the last attempt failed
+        // with a non-retriable error.
+        if let Some((status, failure)) = &self.last_failure {
+            for item in &self.pending {
+                item.finalizers.update_status_with_failure(*status, failure);
+            }
+        }
+    }
+}
+
+/// The items of a batch request, whose delivery is tracked individually.
+///
+/// Items that are still pending once all clones are dropped are finalized with the outcome of the
+/// last attempt, as recorded with [`PartialRetryItems::record_failure`] or reported for them by
+/// [`PartialRetryItems::complete`].
+pub struct PartialRetryItems<T> {
+    inner: Arc<Mutex<Items<T>>>,
+}
+
+impl<T> Clone for PartialRetryItems<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for PartialRetryItems<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let items = self.lock();
+        f.debug_struct("PartialRetryItems")
+            .field("pending", &items.pending.len())
+            .field("partial", &items.partial)
+            .field("rejected", &items.rejected)
+            .finish()
+    }
+}
+
+impl<T> PartialRetryItems<T> {
+    /// Creates the items of a request, each of them with the finalizers of the events it was
+    /// built from.
+    pub fn new(items: impl IntoIterator<Item = (T, EventFinalizers)>) -> Self {
+        let pending = items
+            .into_iter()
+            .map(|(item, finalizers)| PendingItem { item, finalizers })
+            .collect();
+        Self {
+            inner: Arc::new(Mutex::new(Items {
+                pending,
+                partial: false,
+                rejected: 0,
+                last_failure: None,
+            })),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Items<T>> {
+        self.inner.lock().expect("poisoned lock")
+    }
+
+    /// Returns the number of items that are still pending.
+    pub fn len(&self) -> usize {
+        self.lock().pending.len()
+    }
+
+    /// Returns whether no item is pending anymore.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns whether some of the items have completed, meaning that the payload of the request
+    /// must be rebuilt from the pending items before it is sent again.
+    pub fn is_partial(&self) -> bool {
+        self.lock().partial
+    }
+
+    /// Records the failure of an attempt at sending the request as a whole.
+    ///
+    /// The pending items are finalized with the given status if no later attempt delivers them.
+    pub fn record_failure(
+        &self,
+        status: EventStatus,
+        status_code: Option<u16>,
+        error: Option<String>,
+    ) {
+        let failure = DeliveryFailure {
+            attempts: current_attempt(),
+            status_code,
+            error,
+        };
+        self.lock().last_failure = Some((status, failure));
+    }
+
+    /// Applies the outcomes reported by the destination to the pending items, in order.
+    ///
+    /// Delivered and rejected items are finalized right away, while items to retry stay pending.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of outcomes doesn't match the number of pending items.
+    pub fn complete(&self, outcomes: impl IntoIterator<Item = ItemOutcome>) -> Completion
+    where
+        T: EstimatedJsonEncodedSizeOf,
+    {
+        let attempts = current_attempt();
+        let mut items = self.lock();
+        let mut outcomes = outcomes.into_iter();
+        let mut retry_failure = None;
+        let mut delivered = 0;
+        let mut delivered_byte_size = JsonSize::zero();
+
+        let pending = std::mem::take(&mut items.pending);
+        let total = pending.len();
+        for item in pending {
+            let outcome = outcomes
+                .next()
+                .expect("an outcome must be reported for each pending item");
+            match outcome {
+                ItemOutcome::Delivered => {
+                    delivered += 1;
+                    delivered_byte_size += item.item.estimated_json_encoded_size_of();
+                    item.finalizers.update_status(EventStatus::Delivered);
+                }
+                ItemOutcome::Retry { status_code, error } => {
+                    retry_failure.get_or_insert(DeliveryFailure {
+                        attempts,
+                        status_code,
+                        error,
+                    });
+                    items.pending.push(item);
+                }
+                ItemOutcome::Rejected { status_code, error } => {
+                    items.rejected += 1;
+                    let failure = DeliveryFailure {
+                        attempts,
+                        status_code,
+                        error,
+                    };
+                    item.finalizers
+                        .update_status_with_failure(EventStatus::Rejected, &failure);
+                }
+            }
+        }
+        assert!(
+            outcomes.next().is_none(),
+            "an outcome must be reported for each pending item"
+        );
+
+        items.partial |= items.pending.len() < total;
+        if let Some(failure) = retry_failure {
+            items.last_failure = Some((EventStatus::Errored, failure));
+        }
+        Completion {
+            pending: items.pending.len(),
+            rejected: items.rejected,
+            delivered: CountByteSize(delivered, delivered_byte_size),
+        }
+    }
+}
+
+impl<T: Clone> PartialRetryItems<T> {
+    /// Returns the items that are still pending.
+    pub fn pending(&self) -> Vec<T> {
+        self.lock()
+            .pending
+            .iter()
+            .map(|item| item.item.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::finalization::{
+        BatchNotifier, BatchStatus, BatchStatusReceiver, EventFinalizer,
+    };
+
+    use super::*;
+
+    fn item(value: &str) -> ((String, EventFinalizers), BatchStatusReceiver) {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let finalizers = EventFinalizers::new(EventFinalizer::new(batch));
+        ((value.to_owned(), finalizers), receiver)
+    }
+
+    fn rejected(status_code: u16) -> ItemOutcome {
+        ItemOutcome::Rejected {
+            status_code: Some(status_code),
+            error: Some("mapper_parsing_exception".to_owned()),
+        }
+    }
+
+    const fn retry() -> ItemOutcome {
+        ItemOutcome::Retry {
+            status_code: Some(429),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn finalizes_items_individually() {
+        let (first, mut first_rx) = item("first");
+        let (second, mut second_rx) = item("second");
+        let (third, mut third_rx) = item("third");
+        let items = PartialRetryItems::new([first, second, third]);
+        let retried = items.clone();
+
+        let completion = items.complete([ItemOutcome::Delivered, rejected(400), retry()]);
+        assert_eq!(completion.pending, 1);
+        assert_eq!(completion.rejected, 1);
+        assert_eq!(completion.delivered.0, 1);
+        assert!(retried.is_partial());
+        assert_eq!(retried.pending(), vec!["third".to_owned()]);
+
+        assert_eq!(first_rx.try_recv(), Ok(BatchStatus::Delivered));
+        assert_eq!(second_rx.try_recv(), Ok(BatchStatus::Rejected));
+        let failure = second_rx.failure().unwrap();
+        assert_eq!(failure.status_code, Some(400));
+        assert_eq!(failure.error.as_deref(), Some("mapper_parsing_exception"));
+        assert!(third_rx.try_recv().is_err());
+
+        let completion = retried.complete([ItemOutcome::Delivered]);
+        assert_eq!(completion.pending, 0);
+        assert_eq!(completion.rejected, 1);
+        assert_eq!(completion.delivered.0, 1);
+        drop(items);
+        drop(retried);
+        assert_eq!(third_rx.try_recv(), Ok(BatchStatus::Delivered));
+    }
+
+    #[test]
+    fn finalizes_pending_items_with_last_failure() {
+        let (first, mut first_rx) = item("first");
+        let (second, mut second_rx) = item("second");
+        let items = PartialRetryItems::new([first, second]);
+
+        items.complete([ItemOutcome::Delivered, retry()]);
+        items.record_failure(EventStatus::Errored, Some(503), None);
+        assert_eq!(first_rx.try_recv(), Ok(BatchStatus::Delivered));
+        assert!(second_rx.try_recv().is_err());
+
+        drop(items);
+        assert_eq!(second_rx.try_recv(), Ok(BatchStatus::Errored));
+        assert_eq!(second_rx.failure().unwrap().status_code, Some(503));
+    }
+
+    #[test]
+    fn retries_exhausted_leave_items_errored() {
+        let (first, mut first_rx) = item("first");
+        let items = PartialRetryItems::new([first]);
+
+        assert_eq!(items.complete([retry()]).pending, 1);
+        assert!(!items.is_partial());
+
+        drop(items);
+        assert_eq!(first_rx.try_recv(), Ok(BatchStatus::Errored));
+        assert_eq!(first_rx.failure().unwrap().status_code, Some(429));
+    }
+}
@@ -10,6 +10,7 @@ use std::{
 use futures::FutureExt;
 use tokio::time::{sleep, Sleep};
 use tower::{retry::Policy, timeout::error::Elapsed};
+use vector_core::stream::record_retry;
 
 use crate::Error;
 
@@ -85,6 +86,7 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
         let delay = Box::pin(sleep(self.backoff()));
 
         debug!(message = "Retrying request.", delay_ms = %self.backoff().as_millis());
+        record_retry();
         RetryPolicyFuture { delay, policy }
     }
 }
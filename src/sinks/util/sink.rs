@@ -51,6 +51,7 @@ use tracing::Instrument;
 use vector_common::internal_event::{
     CallError, CountByteSize, EventsSent, InternalEventHandle as _, Output,
 };
+use vector_core::stream::track_attempts;
 // === StreamSink<Event> ===
 pub use vector_core::sink::StreamSink;
 
@@ -60,7 +61,7 @@ use super::{
     service::{Map, ServiceBuilderExt},
     EncodedEvent,
 };
-use crate::event::EventStatus;
+use crate::event::{DeliveryFailure, EventStatus};
 
 // === BatchSink ===
 
@@ -442,12 +443,19 @@ where
             in_flight_requests = self.in_flight.len()
         );
         let events_sent = register!(EventsSent::from(Output(None)));
-        self.service
-            .call(items)
-            .err_into()
-            .map(move |result| {
+        track_attempts(self.service.call(items).err_into())
+            .map(move |(result, attempts)| {
                 let status = result_status(&result);
-                finalizers.update_status(status);
+                if matches!(status, EventStatus::Errored | EventStatus::Rejected) {
+                    let failure = DeliveryFailure {
+                        attempts,
+                        status_code: result.as_ref().ok().and_then(Response::status_code),
+                        error: result.as_ref().err().map(ToString::to_string),
+                    };
+                    finalizers.update_status_with_failure(status, &failure);
+                } else {
+                    finalizers.update_status(status);
+                }
                 match status {
                     EventStatus::Delivered => {
                         events_sent.emit(CountByteSize(count, json_byte_size));
@@ -560,6 +568,12 @@ pub trait Response: fmt::Debug {
     fn is_transient(&self) -> bool {
         true
     }
+
+    /// Return the status code sent by the destination in this response, for protocols that have
+    /// one.
+    fn status_code(&self) -> Option<u16> {
+        None
+    }
 }
 
 impl Response for () {}
@@ -1070,6 +1084,36 @@ mod tests {
         assert_eq!(ack_counter.load(Relaxed), 7);
     }
 
+    #[tokio::test]
+    async fn service_sink_records_failures() {
+        let svc = tower::service_fn(|_: Request| {
+            future::ok::<_, Infallible>(
+                http::Response::builder()
+                    .status(400)
+                    .body(Bytes::new())
+                    .unwrap(),
+            )
+        });
+        let mut sink = ServiceSink::new(svc);
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+
+        sink.call(EncodedBatch {
+            items: Request(1, EventFinalizers::default()),
+            finalizers: EventFinalizers::new(EventFinalizer::new(batch)),
+            count: 1,
+            byte_size: 1,
+            json_byte_size: JsonSize::new(1),
+        })
+        .await;
+
+        assert_eq!((&mut receiver).await, BatchStatus::Rejected);
+
+        let failure = receiver.failure().expect("failure should be recorded");
+        assert_eq!(failure.attempts, 1);
+        assert_eq!(failure.status_code, Some(400));
+        assert_eq!(failure.error, None);
+    }
+
     #[tokio::test]
     async fn partition_batch_sink_ordering_per_partition() {
         let sent_requests = Arc::new(Mutex::new(Vec::new()));
@@ -6,12 +6,10 @@ use vector_config::configurable_component;
 
 use crate::{
     codecs::EncodingConfig,
+    common::websocket::{ConnectSnafu, WebSocketConnector, WebSocketError},
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     http::Auth,
-    sinks::{
-        websocket::sink::{ConnectSnafu, WebSocketConnector, WebSocketError, WebSocketSink},
-        Healthcheck, VectorSink,
-    },
+    sinks::{websocket::sink::WebSocketSink, Healthcheck, VectorSink},
     tls::{MaybeTlsSettings, TlsEnableableConfig},
 };
 
@@ -80,6 +78,10 @@ impl GenerateConfig for WebSocketSinkConfig {
 #[async_trait::async_trait]
 impl SinkConfig for WebSocketSinkConfig {
     async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if let Some(Auth::OAuth2(_)) = self.auth {
+            return Err("The `oauth2` authentication strategy is not supported".into());
+        }
+
         let connector = self.build_connector()?;
         let ws_sink = WebSocketSink::new(self, connector.clone())?;
 
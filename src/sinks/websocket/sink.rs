@@ -1,29 +1,15 @@
 use std::{
-    fmt::Debug,
     io,
-    net::SocketAddr,
     num::NonZeroU64,
-    task::{Context, Poll},
     time::{Duration, Instant},
 };
 
 use async_trait::async_trait;
 use bytes::BytesMut;
 use futures::{pin_mut, sink::SinkExt, stream::BoxStream, Sink, Stream, StreamExt};
-use snafu::{ResultExt, Snafu};
-use tokio::{net::TcpStream, time};
-use tokio_tungstenite::{
-    client_async_with_config,
-    tungstenite::{
-        client::{uri_mode, IntoClientRequest},
-        error::{Error as WsError, ProtocolError, UrlError},
-        handshake::client::Request as WsRequest,
-        protocol::{Message, WebSocketConfig},
-        stream::Mode as UriMode,
-    },
-    WebSocketStream as WsStream,
-};
+use tokio_tungstenite::tungstenite::{error::Error as WsError, protocol::Message};
 use tokio_util::codec::Encoder as _;
+use vector_common::internal_event::error_stage;
 use vector_core::{
     internal_event::{
         ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle as _, Output, Protocol,
@@ -33,163 +19,14 @@ use vector_core::{
 
 use crate::{
     codecs::{Encoder, Transformer},
-    dns, emit,
+    common::websocket::{is_closed, PingInterval, WebSocketConnector},
+    emit,
     event::{Event, EventStatus, Finalizable},
-    http::Auth,
-    internal_events::{
-        ConnectionOpen, OpenGauge, WsConnectionError, WsConnectionEstablished,
-        WsConnectionFailedError, WsConnectionShutdown,
-    },
-    sinks::util::{retries::ExponentialBackoff, StreamSink},
+    internal_events::{ConnectionOpen, OpenGauge, WsConnectionError, WsConnectionShutdown},
+    sinks::util::StreamSink,
     sinks::websocket::config::WebSocketSinkConfig,
-    tls::{MaybeTlsSettings, MaybeTlsStream, TlsError},
 };
 
-#[derive(Debug, Snafu)]
-#[snafu(visibility(pub))]
-pub enum WebSocketError {
-    #[snafu(display("Creating WebSocket client failed: {}", source))]
-    CreateFailed { source: WsError },
-    #[snafu(display("Connect error: {}", source))]
-    ConnectError { source: TlsError },
-    #[snafu(display("Unable to resolve DNS: {}", source))]
-    DnsError { source: dns::DnsError },
-    #[snafu(display("No addresses returned."))]
-    NoAddresses,
-}
-
-#[derive(Clone)]
-pub struct WebSocketConnector {
-    uri: String,
-    host: String,
-    port: u16,
-    tls: MaybeTlsSettings,
-    auth: Option<Auth>,
-}
-
-impl WebSocketConnector {
-    pub fn new(
-        uri: String,
-        tls: MaybeTlsSettings,
-        auth: Option<Auth>,
-    ) -> Result<Self, WebSocketError> {
-        let request = (&uri).into_client_request().context(CreateFailedSnafu)?;
-        let (host, port) = Self::extract_host_and_port(&request).context(CreateFailedSnafu)?;
-
-        Ok(Self {
-            uri,
-            host,
-            port,
-            tls,
-            auth,
-        })
-    }
-
-    fn extract_host_and_port(request: &WsRequest) -> Result<(String, u16), WsError> {
-        let host = request
-            .uri()
-            .host()
-            .ok_or(WsError::Url(UrlError::NoHostName))?
-            .to_string();
-        let mode = uri_mode(request.uri())?;
-        let port = request.uri().port_u16().unwrap_or(match mode {
-            UriMode::Tls => 443,
-            UriMode::Plain => 80,
-        });
-
-        Ok((host, port))
-    }
-
-    const fn fresh_backoff() -> ExponentialBackoff {
-        ExponentialBackoff::from_millis(2)
-            .factor(250)
-            .max_delay(Duration::from_secs(60))
-    }
-
-    async fn tls_connect(&self) -> Result<MaybeTlsStream<TcpStream>, WebSocketError> {
-        let ip = dns::Resolver
-            .lookup_ip(self.host.clone())
-            .await
-            .context(DnsSnafu)?
-            .next()
-            .ok_or(WebSocketError::NoAddresses)?;
-
-        let addr = SocketAddr::new(ip, self.port);
-        self.tls
-            .connect(&self.host, &addr)
-            .await
-            .context(ConnectSnafu)
-    }
-
-    async fn connect(&self) -> Result<WsStream<MaybeTlsStream<TcpStream>>, WebSocketError> {
-        let mut request = (&self.uri)
-            .into_client_request()
-            .context(CreateFailedSnafu)?;
-
-        if let Some(auth) = &self.auth {
-            auth.apply(&mut request);
-        }
-
-        let maybe_tls = self.tls_connect().await?;
-
-        let ws_config = WebSocketConfig {
-            max_send_queue: None, // don't buffer messages
-            ..Default::default()
-        };
-
-        let (ws_stream, _response) = client_async_with_config(request, maybe_tls, Some(ws_config))
-            .await
-            .context(CreateFailedSnafu)?;
-
-        Ok(ws_stream)
-    }
-
-    async fn connect_backoff(&self) -> WsStream<MaybeTlsStream<TcpStream>> {
-        let mut backoff = Self::fresh_backoff();
-        loop {
-            match self.connect().await {
-                Ok(ws_stream) => {
-                    emit!(WsConnectionEstablished {});
-                    return ws_stream;
-                }
-                Err(error) => {
-                    emit!(WsConnectionFailedError {
-                        error: Box::new(error)
-                    });
-                    time::sleep(backoff.next().unwrap()).await;
-                }
-            }
-        }
-    }
-
-    pub async fn healthcheck(&self) -> crate::Result<()> {
-        self.connect().await.map(|_| ()).map_err(Into::into)
-    }
-}
-
-struct PingInterval {
-    interval: Option<time::Interval>,
-}
-
-impl PingInterval {
-    fn new(period: Option<u64>) -> Self {
-        Self {
-            interval: period.map(|period| time::interval(Duration::from_secs(period))),
-        }
-    }
-
-    fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<time::Instant> {
-        match self.interval.as_mut() {
-            Some(interval) => interval.poll_tick(cx),
-            None => Poll::Pending,
-        }
-    }
-
-    async fn tick(&mut self) -> time::Instant {
-        std::future::poll_fn(|cx| self.poll_tick(cx)).await
-    }
-}
-
 pub struct WebSocketSink {
     transformer: Transformer,
     encoder: Encoder<()>,
@@ -219,7 +56,7 @@ impl WebSocketSink {
         impl Sink<Message, Error = WsError>,
         impl Stream<Item = Result<Message, WsError>>,
     ) {
-        let ws_stream = self.connector.connect_backoff().await;
+        let ws_stream = self.connector.connect_backoff(error_stage::SENDING).await;
         ws_stream.split()
     }
 
@@ -254,7 +91,10 @@ impl WebSocketSink {
         let mut ping_interval = PingInterval::new(self.ping_interval.map(u64::from));
 
         if let Err(error) = ws_sink.send(Message::Ping(PING.to_vec())).await {
-            emit!(WsConnectionError { error });
+            emit!(WsConnectionError {
+                error,
+                stage: error_stage::SENDING,
+            });
             return Err(());
         }
         let mut last_pong = Instant::now();
@@ -325,7 +165,10 @@ impl WebSocketSink {
                 if is_closed(&error) {
                     emit!(WsConnectionShutdown);
                 } else {
-                    emit!(WsConnectionError { error });
+                    emit!(WsConnectionError {
+                        error,
+                        stage: error_stage::SENDING,
+                    });
                 }
                 return Err(());
             }
@@ -361,15 +204,6 @@ impl StreamSink<Event> for WebSocketSink {
     }
 }
 
-const fn is_closed(error: &WsError) -> bool {
-    matches!(
-        error,
-        WsError::ConnectionClosed
-            | WsError::AlreadyClosed
-            | WsError::Protocol(ProtocolError::ResetWithoutClosingHandshake)
-    )
-}
-
 #[cfg(test)]
 mod tests {
     use std::net::SocketAddr;
@@ -377,7 +211,7 @@ mod tests {
     use codecs::JsonSerializerConfig;
     use futures::{future, FutureExt, StreamExt};
     use serde_json::Value as JsonValue;
-    use tokio::time::timeout;
+    use tokio::time::{self, timeout};
     use tokio_tungstenite::{
         accept_async, accept_hdr_async,
         tungstenite::error::{Error as WsError, ProtocolError},
@@ -387,11 +221,12 @@ mod tests {
     use super::*;
     use crate::{
         config::{SinkConfig, SinkContext},
+        http::Auth,
         test_util::{
             components::{run_and_assert_sink_compliance, SINK_TAGS},
             next_addr, random_lines_with_stream, trace_init, CountReceiver,
         },
-        tls::{self, TlsConfig, TlsEnableableConfig},
+        tls::{self, MaybeTlsSettings, TlsConfig, TlsEnableableConfig},
     };
 
     #[tokio::test(flavor = "multi_thread")]
@@ -568,6 +403,7 @@ mod tests {
                                                 user: _user,
                                                 password: _password,
                                             } => { /* Not needed for tests at the moment */ }
+                                            Auth::OAuth2(_) => {}
                                         }
                                     }
                                     Ok(res)
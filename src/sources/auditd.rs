@@ -0,0 +1,856 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+    os::unix::{
+        fs::MetadataExt,
+        io::{AsRawFd, FromRawFd, OwnedFd},
+    },
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, TimeZone, Utc};
+use lookup::{owned_value_path, path};
+use nix::sys::socket::{
+    bind, recv, socket, AddressFamily, MsgFlags, NetlinkAddr, SockFlag, SockProtocol, SockType,
+};
+use serde_with::serde_as;
+use snafu::{ResultExt, Snafu};
+use tokio::{
+    fs::File,
+    io::{unix::AsyncFd, AsyncBufReadExt, AsyncSeekExt, BufReader},
+    time::sleep,
+};
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol, Registered,
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{kind::Collection, Kind, Value};
+
+use crate::{
+    config::{DataType, GenerateConfig, SourceConfig, SourceContext, SourceOutput},
+    event::LogEvent,
+    internal_events::{
+        AuditdInvalidRecordError, AuditdReadError, AuditdRecordsLost, EventsReceived,
+        StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// The read-only multicast group that the kernel publishes audit records to.
+const AUDIT_NLGRP_READLOG: u32 = 1;
+
+/// Marks the end of a multi-record event.
+const AUDIT_EOE: u16 = 1320;
+
+/// Records below this type are user space, daemon and control messages, which always stand alone.
+const AUDIT_FIRST_KERNEL_EVENT: u16 = 1300;
+
+const NETLINK_HEADER_SIZE: usize = 16;
+const READ_BUFFER_SIZE: usize = 65536;
+const FILE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const MIN_FLUSH_INTERVAL: Duration = Duration::from_millis(10);
+
+const RECORD_TYPES: &[(u16, &str)] = &[
+    (1006, "LOGIN"),
+    (1100, "USER_AUTH"),
+    (1101, "USER_ACCT"),
+    (1102, "USER_MGMT"),
+    (1103, "CRED_ACQ"),
+    (1104, "CRED_DISP"),
+    (1105, "USER_START"),
+    (1106, "USER_END"),
+    (1107, "USER_AVC"),
+    (1108, "USER_CHAUTHTOK"),
+    (1109, "USER_ERR"),
+    (1110, "CRED_REFR"),
+    (1111, "USYS_CONFIG"),
+    (1112, "USER_LOGIN"),
+    (1113, "USER_LOGOUT"),
+    (1114, "ADD_USER"),
+    (1115, "DEL_USER"),
+    (1116, "ADD_GROUP"),
+    (1117, "DEL_GROUP"),
+    (1123, "USER_CMD"),
+    (1124, "USER_TTY"),
+    (1127, "SYSTEM_BOOT"),
+    (1128, "SYSTEM_SHUTDOWN"),
+    (1129, "SYSTEM_RUNLEVEL"),
+    (1130, "SERVICE_START"),
+    (1131, "SERVICE_STOP"),
+    (1200, "DAEMON_START"),
+    (1201, "DAEMON_END"),
+    (1202, "DAEMON_ABORT"),
+    (1203, "DAEMON_CONFIG"),
+    (1300, "SYSCALL"),
+    (1302, "PATH"),
+    (1303, "IPC"),
+    (1304, "SOCKETCALL"),
+    (1305, "CONFIG_CHANGE"),
+    (1306, "SOCKADDR"),
+    (1307, "CWD"),
+    (1309, "EXECVE"),
+    (1311, "IPC_SET_PERM"),
+    (1312, "MQ_OPEN"),
+    (1313, "MQ_SENDRECV"),
+    (1314, "MQ_NOTIFY"),
+    (1315, "MQ_GETSETATTR"),
+    (1316, "KERNEL_OTHER"),
+    (1317, "FD_PAIR"),
+    (1318, "OBJ_PID"),
+    (1319, "TTY"),
+    (AUDIT_EOE, "EOE"),
+    (1321, "BPRM_FCAPS"),
+    (1322, "CAPSET"),
+    (1323, "MMAP"),
+    (1324, "NETFILTER_PKT"),
+    (1325, "NETFILTER_CFG"),
+    (1326, "SECCOMP"),
+    (1327, "PROCTITLE"),
+    (1328, "FEATURE_CHANGE"),
+    (1329, "REPLACE"),
+    (1330, "KERN_MODULE"),
+    (1331, "FANOTIFY"),
+    (1334, "BPF"),
+    (1400, "AVC"),
+    (1401, "SELINUX_ERR"),
+    (1700, "ANOM_PROMISCUOUS"),
+    (1701, "ANOM_ABEND"),
+    (1702, "ANOM_LINK"),
+    (2000, "KERNEL"),
+];
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Unable to open the audit netlink socket: {}", source))]
+    NetlinkSocket { source: io::Error },
+    #[snafu(display("Unable to open {:?}: {}", path, source))]
+    OpenFile { path: PathBuf, source: io::Error },
+}
+
+/// Configuration for the `auditd` source.
+#[serde_as]
+#[configurable_component(source(
+    "auditd",
+    "Collect security events from the Linux audit subsystem."
+))]
+#[derive(Clone, Debug)]
+pub struct AuditdConfig {
+    #[serde(flatten)]
+    mode: Mode,
+
+    /// The maximum amount of time to wait for all records of an event to arrive, in milliseconds.
+    ///
+    /// Records that share a serial number are assembled into a single event. The event is emitted
+    /// when its end-of-event record arrives, or once this timeout expires.
+    #[serde(default = "default_event_timeout_ms")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Event Timeout"))]
+    event_timeout_ms: Duration,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+/// Where the `auditd` source reads audit records from.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "Where to read audit records from."))]
+pub enum Mode {
+    /// Listen to the kernel's read-only audit multicast group over netlink.
+    ///
+    /// This requires the `CAP_AUDIT_READ` capability, and works alongside a running `auditd`.
+    Netlink,
+
+    /// Follow an audit log file written by `auditd`.
+    File {
+        /// The path of the audit log file.
+        #[serde(default = "default_log_path")]
+        #[configurable(metadata(docs::examples = "/var/log/audit/audit.log"))]
+        path: PathBuf,
+
+        /// Read the file from the beginning instead of only following new records.
+        #[serde(default)]
+        start_at_beginning: bool,
+    },
+}
+
+const fn default_event_timeout_ms() -> Duration {
+    Duration::from_millis(2000)
+}
+
+fn default_log_path() -> PathBuf {
+    PathBuf::from("/var/log/audit/audit.log")
+}
+
+impl GenerateConfig for AuditdConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"mode = "netlink""#).unwrap()
+    }
+}
+
+impl AuditdConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        let record = Kind::object(Collection::empty().with_unknown(Kind::bytes()));
+
+        Definition::new_with_default_metadata(Kind::object(Collection::empty()), [log_namespace])
+            .with_event_field(&owned_value_path!("serial"), Kind::integer(), None)
+            .with_event_field(
+                &owned_value_path!("records"),
+                Kind::array(Collection::empty().with_unknown(record)),
+                None,
+            )
+            .with_source_metadata(
+                AuditdConfig::NAME,
+                log_schema()
+                    .timestamp_key()
+                    .cloned()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp(),
+                Some("timestamp"),
+            )
+            .with_standard_vector_source_metadata()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "auditd")]
+impl SourceConfig for AuditdConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let reader = match &self.mode {
+            Mode::Netlink => RecordReader::netlink().context(NetlinkSocketSnafu)?,
+            Mode::File {
+                path,
+                start_at_beginning,
+            } => RecordReader::file(path.clone(), *start_at_beginning)
+                .await
+                .context(OpenFileSnafu { path: path.clone() })?,
+        };
+
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        Ok(Box::pin(auditd_source(
+            reader,
+            Reassembler::new(self.event_timeout_ms),
+            log_namespace,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn auditd_source(
+    mut reader: RecordReader,
+    mut reassembler: Reassembler,
+    log_namespace: LogNamespace,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let bytes_received = register!(BytesReceived::from(Protocol::from("auditd")));
+    let events_received = register!(EventsReceived);
+
+    let mut flush_interval =
+        tokio::time::interval((reassembler.timeout / 2).max(MIN_FLUSH_INTERVAL));
+
+    loop {
+        let events = tokio::select! {
+            _ = &mut shutdown => break,
+            _ = flush_interval.tick() => reassembler.flush_expired(Instant::now()),
+            lines = reader.read() => {
+                let lines = lines.map_err(|error| emit!(AuditdReadError { error }))?;
+                let mut events = Vec::new();
+                for line in lines {
+                    bytes_received.emit(ByteSize(line.len()));
+                    match parse_line(&line) {
+                        Ok(record) => events.extend(reassembler.push(record, Instant::now())),
+                        Err(error) => emit!(AuditdInvalidRecordError { error, text: line }),
+                    }
+                }
+                events
+            }
+        };
+
+        send_events(events, log_namespace, &events_received, &mut out).await?;
+    }
+
+    // Emit whatever was still waiting for its remaining records rather than dropping it.
+    send_events(
+        reassembler.flush_all(),
+        log_namespace,
+        &events_received,
+        &mut out,
+    )
+    .await
+}
+
+async fn send_events(
+    events: Vec<AuditEvent>,
+    log_namespace: LogNamespace,
+    events_received: &Registered<EventsReceived>,
+    out: &mut SourceSender,
+) -> Result<(), ()> {
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    let logs = events
+        .into_iter()
+        .map(|event| event.into_log_event(log_namespace))
+        .collect::<Vec<_>>();
+
+    let count = logs.len();
+    events_received.emit(CountByteSize(count, logs.estimated_json_encoded_size_of()));
+
+    out.send_batch(logs).await.map_err(|error| {
+        emit!(StreamClosedError { error, count });
+    })
+}
+
+enum RecordReader {
+    Netlink {
+        socket: AsyncFd<OwnedFd>,
+        buf: Vec<u8>,
+    },
+    File(FileFollower),
+}
+
+impl RecordReader {
+    fn netlink() -> io::Result<Self> {
+        let fd = socket(
+            AddressFamily::Netlink,
+            SockType::Raw,
+            SockFlag::SOCK_NONBLOCK | SockFlag::SOCK_CLOEXEC,
+            SockProtocol::NetlinkAudit,
+        )?;
+        // SAFETY: `socket` just returned this descriptor, so nothing else owns it.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+        bind(fd.as_raw_fd(), &NetlinkAddr::new(0, AUDIT_NLGRP_READLOG))?;
+
+        Ok(Self::Netlink {
+            socket: AsyncFd::new(fd)?,
+            buf: vec![0; READ_BUFFER_SIZE],
+        })
+    }
+
+    async fn file(path: PathBuf, start_at_beginning: bool) -> io::Result<Self> {
+        FileFollower::open(path, start_at_beginning)
+            .await
+            .map(Self::File)
+    }
+
+    /// Reads the next available records as audit log lines.
+    ///
+    /// This is cancellation safe, so it can be used in `tokio::select!`.
+    async fn read(&mut self) -> io::Result<Vec<String>> {
+        match self {
+            Self::Netlink { socket, buf } => loop {
+                let mut guard = socket.readable().await?;
+                match guard.try_io(|inner| {
+                    recv(inner.get_ref().as_raw_fd(), buf, MsgFlags::empty()).map_err(Into::into)
+                }) {
+                    Err(_would_block) => continue,
+                    Ok(Ok(size)) => return Ok(netlink_messages_to_lines(&buf[..size])),
+                    // The socket buffer overflowed, and the kernel discarded records.
+                    Ok(Err(error)) if error.raw_os_error() == Some(nix::libc::ENOBUFS) => {
+                        emit!(AuditdRecordsLost);
+                    }
+                    Ok(Err(error)) => return Err(error),
+                }
+            },
+            Self::File(follower) => follower.read_line().await.map(|line| vec![line]),
+        }
+    }
+}
+
+/// Converts the netlink messages in a datagram into lines in the `auditd` log format.
+fn netlink_messages_to_lines(mut data: &[u8]) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    while data.len() >= NETLINK_HEADER_SIZE {
+        let length = u32::from_ne_bytes(data[0..4].try_into().expect("slice is 4 bytes")) as usize;
+        let record_type = u16::from_ne_bytes(data[4..6].try_into().expect("slice is 2 bytes"));
+
+        // The kernel does not always account for the header in the length of audit messages.
+        let end = length.clamp(NETLINK_HEADER_SIZE, data.len());
+        let payload = &data[NETLINK_HEADER_SIZE..end];
+        let payload = String::from_utf8_lossy(payload);
+
+        lines.push(format!(
+            "type={} msg={}",
+            record_type_name(record_type),
+            payload.trim_end_matches(['\0', '\n'])
+        ));
+
+        data = &data[((end + 3) & !3).min(data.len())..];
+    }
+
+    lines
+}
+
+struct FileFollower {
+    path: PathBuf,
+    reader: BufReader<File>,
+    inode: u64,
+    position: u64,
+    buf: Vec<u8>,
+}
+
+impl FileFollower {
+    async fn open(path: PathBuf, start_at_beginning: bool) -> io::Result<Self> {
+        let mut file = File::open(&path).await?;
+        let inode = file.metadata().await?.ino();
+        let position = if start_at_beginning {
+            0
+        } else {
+            file.seek(io::SeekFrom::End(0)).await?
+        };
+
+        Ok(Self {
+            path,
+            reader: BufReader::new(file),
+            inode,
+            position,
+            buf: Vec::new(),
+        })
+    }
+
+    /// Reads the next complete line, waiting for it to be written and following the file across
+    /// rotation and truncation.
+    ///
+    /// Partially read lines are kept in `buf`, so this is cancellation safe.
+    async fn read_line(&mut self) -> io::Result<String> {
+        loop {
+            let size = self.reader.read_until(b'\n', &mut self.buf).await?;
+            self.position += size as u64;
+
+            if self.buf.ends_with(b"\n") {
+                let line = String::from_utf8_lossy(&self.buf).trim_end().to_owned();
+                self.buf.clear();
+                if !line.is_empty() {
+                    return Ok(line);
+                }
+                continue;
+            }
+
+            if size == 0 {
+                if self.rotated().await {
+                    *self = Self::open(self.path.clone(), true).await?;
+                    continue;
+                }
+                sleep(FILE_POLL_INTERVAL).await;
+            }
+        }
+    }
+
+    async fn rotated(&self) -> bool {
+        match tokio::fs::metadata(&self.path).await {
+            Ok(metadata) => metadata.ino() != self.inode || metadata.len() < self.position,
+            // The file was moved away and its replacement has not been created yet.
+            Err(_) => false,
+        }
+    }
+}
+
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub enum AuditdParseError {
+    #[snafu(display("Record has no `type=` prefix"))]
+    MissingType,
+    #[snafu(display("Record has no `msg=audit(...)` header"))]
+    MissingHeader,
+    #[snafu(display("Record has an invalid `{}` in its header", field))]
+    InvalidHeader { field: &'static str },
+}
+
+#[derive(Debug, PartialEq)]
+struct AuditRecord {
+    record_type: String,
+    timestamp: DateTime<Utc>,
+    serial: u64,
+    fields: BTreeMap<String, Value>,
+}
+
+/// Parses a record in the `auditd` log format:
+///
+/// ```text
+/// type=SYSCALL msg=audit(1364481363.243:24287): arch=c000003e syscall=2 success=no exit=-13
+/// ```
+fn parse_line(line: &str) -> Result<AuditRecord, AuditdParseError> {
+    // The `ENRICHED` log format separates the interpreted fields with a group separator.
+    let line = line.replace('\x1d', " ");
+
+    let rest = line
+        .strip_prefix("type=")
+        .ok_or(AuditdParseError::MissingType)?;
+    let (record_type, rest) = rest
+        .split_once(' ')
+        .ok_or(AuditdParseError::MissingHeader)?;
+    let rest = rest
+        .trim_start()
+        .strip_prefix("msg=audit(")
+        .ok_or(AuditdParseError::MissingHeader)?;
+    let (header, body) = rest
+        .split_once("):")
+        .ok_or(AuditdParseError::MissingHeader)?;
+
+    let (timestamp, serial) = header
+        .split_once(':')
+        .ok_or(AuditdParseError::InvalidHeader { field: "serial" })?;
+    let serial = serial
+        .parse::<u64>()
+        .map_err(|_| AuditdParseError::InvalidHeader { field: "serial" })?;
+    let (seconds, millis) = timestamp.split_once('.').unwrap_or((timestamp, "0"));
+    let timestamp = seconds
+        .parse::<i64>()
+        .ok()
+        .zip(millis.parse::<u32>().ok())
+        .and_then(|(seconds, millis)| Utc.timestamp_opt(seconds, millis * 1_000_000).single())
+        .ok_or(AuditdParseError::InvalidHeader { field: "timestamp" })?;
+
+    Ok(AuditRecord {
+        record_type: record_type.to_owned(),
+        timestamp,
+        serial,
+        fields: parse_fields(body),
+    })
+}
+
+/// Splits `key=value` pairs, where values may be wrapped in single or double quotes.
+fn parse_fields(body: &str) -> BTreeMap<String, Value> {
+    let mut fields = BTreeMap::new();
+    let mut rest = body.trim_start();
+
+    while let Some((key, after)) = rest.split_once('=') {
+        let key = key.trim();
+        let (value, remainder) = match after.chars().next() {
+            Some(quote @ ('"' | '\'')) => match after[1..].find(quote) {
+                Some(end) => (&after[1..=end], &after[end + 2..]),
+                None => (&after[1..], ""),
+            },
+            _ => after.split_once(' ').unwrap_or((after, "")),
+        };
+        if !key.is_empty() {
+            fields.insert(key.to_owned(), Value::from(value.to_owned()));
+        }
+        rest = remainder.trim_start();
+    }
+
+    fields
+}
+
+fn record_type_name(record_type: u16) -> String {
+    RECORD_TYPES
+        .iter()
+        .find(|(number, _)| *number == record_type)
+        .map(|(_, name)| (*name).to_owned())
+        .unwrap_or_else(|| format!("UNKNOWN[{}]", record_type))
+}
+
+fn record_type_number(name: &str) -> Option<u16> {
+    RECORD_TYPES
+        .iter()
+        .find(|(_, known)| *known == name)
+        .map(|(number, _)| *number)
+        .or_else(|| {
+            name.strip_prefix("UNKNOWN[")
+                .and_then(|name| name.strip_suffix(']'))
+                .and_then(|number| number.parse().ok())
+        })
+}
+
+#[derive(Debug)]
+struct AuditEvent {
+    timestamp: DateTime<Utc>,
+    serial: u64,
+    records: Vec<AuditRecord>,
+}
+
+impl AuditEvent {
+    fn into_log_event(self, log_namespace: LogNamespace) -> LogEvent {
+        let records = self
+            .records
+            .into_iter()
+            .map(|record| {
+                let mut fields = record.fields;
+                fields.insert("type".to_owned(), Value::from(record.record_type));
+                Value::from(fields)
+            })
+            .collect::<Vec<_>>();
+
+        let mut log = LogEvent::default();
+        log.insert("serial", self.serial as i64);
+        log.insert("records", records);
+
+        log_namespace.insert_source_metadata(
+            AuditdConfig::NAME,
+            &mut log,
+            log_schema().timestamp_key().map(LegacyKey::Overwrite),
+            path!("timestamp"),
+            self.timestamp,
+        );
+        log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            AuditdConfig::NAME,
+            Utc::now(),
+        );
+
+        log
+    }
+}
+
+struct PendingEvent {
+    event: AuditEvent,
+    started: Instant,
+}
+
+/// Groups records into events by their serial number.
+struct Reassembler {
+    pending: HashMap<u64, PendingEvent>,
+    timeout: Duration,
+}
+
+impl Reassembler {
+    fn new(timeout: Duration) -> Self {
+        Self {
+            pending: HashMap::new(),
+            timeout,
+        }
+    }
+
+    /// Adds a record, returning the event it completes, if any.
+    fn push(&mut self, record: AuditRecord, now: Instant) -> Option<AuditEvent> {
+        let record_type = record_type_number(&record.record_type);
+
+        if record_type == Some(AUDIT_EOE) {
+            return self
+                .pending
+                .remove(&record.serial)
+                .map(|pending| pending.event);
+        }
+
+        if record_type.map_or(false, |number| number < AUDIT_FIRST_KERNEL_EVENT) {
+            return Some(AuditEvent {
+                timestamp: record.timestamp,
+                serial: record.serial,
+                records: vec![record],
+            });
+        }
+
+        self.pending
+            .entry(record.serial)
+            .or_insert_with(|| PendingEvent {
+                event: AuditEvent {
+                    timestamp: record.timestamp,
+                    serial: record.serial,
+                    records: Vec::new(),
+                },
+                started: now,
+            })
+            .event
+            .records
+            .push(record);
+
+        None
+    }
+
+    /// Removes and returns the events that have waited longer than the timeout.
+    fn flush_expired(&mut self, now: Instant) -> Vec<AuditEvent> {
+        let expired = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| now.duration_since(pending.started) >= self.timeout)
+            .map(|(serial, _)| *serial)
+            .collect::<Vec<_>>();
+
+        let mut events = expired
+            .into_iter()
+            .filter_map(|serial| self.pending.remove(&serial))
+            .map(|pending| pending.event)
+            .collect::<Vec<_>>();
+        events.sort_by_key(|event| event.serial);
+        events
+    }
+
+    fn flush_all(&mut self) -> Vec<AuditEvent> {
+        let mut events = self
+            .pending
+            .drain()
+            .map(|(_, pending)| pending.event)
+            .collect::<Vec<_>>();
+        events.sort_by_key(|event| event.serial);
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tokio::time::timeout;
+
+    use super::*;
+    use crate::{event::Event, test_util::collect_n};
+
+    const SYSCALL: &str = r#"type=SYSCALL msg=audit(1364481363.243:24287): arch=c000003e syscall=2 success=no exit=-13 comm="cat" exe="/usr/bin/cat" key="sshd_config""#;
+    const CWD: &str = r#"type=CWD msg=audit(1364481363.243:24287): cwd="/home/shadowman""#;
+    const EOE: &str = "type=EOE msg=audit(1364481363.243:24287): ";
+    const USER_LOGIN: &str = r#"type=USER_LOGIN msg=audit(1364481370.100:24290): pid=1021 uid=0 msg='op=login acct="root" exe="/usr/sbin/sshd" res=success'"#;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AuditdConfig>();
+    }
+
+    #[test]
+    fn parses_log_line() {
+        let record = parse_line(SYSCALL).unwrap();
+
+        assert_eq!(record.record_type, "SYSCALL");
+        assert_eq!(record.serial, 24287);
+        assert_eq!(
+            record.timestamp,
+            Utc.timestamp_opt(1364481363, 243_000_000).single().unwrap()
+        );
+        assert_eq!(record.fields["syscall"], Value::from("2"));
+        assert_eq!(record.fields["comm"], Value::from("cat"));
+        assert_eq!(record.fields["key"], Value::from("sshd_config"));
+    }
+
+    #[test]
+    fn parses_single_quoted_values() {
+        let record = parse_line(USER_LOGIN).unwrap();
+
+        assert_eq!(
+            record.fields["msg"],
+            Value::from(r#"op=login acct="root" exe="/usr/sbin/sshd" res=success"#)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_lines() {
+        assert_eq!(
+            parse_line("msg=audit(1364481363.243:24287):"),
+            Err(AuditdParseError::MissingType)
+        );
+        assert_eq!(
+            parse_line("type=SYSCALL msg=audit(1364481363.243:abc): arch=c000003e"),
+            Err(AuditdParseError::InvalidHeader { field: "serial" })
+        );
+    }
+
+    #[test]
+    fn converts_netlink_messages() {
+        let payload = b"audit(1364481363.243:24287): cwd=\"/root\"\0";
+        let mut message = Vec::new();
+        message.extend(((NETLINK_HEADER_SIZE + payload.len()) as u32).to_ne_bytes());
+        message.extend(1307_u16.to_ne_bytes());
+        message.extend([0; 10]);
+        message.extend(payload);
+
+        assert_eq!(
+            netlink_messages_to_lines(&message),
+            vec![r#"type=CWD msg=audit(1364481363.243:24287): cwd="/root""#.to_owned()]
+        );
+    }
+
+    #[test]
+    fn reassembles_records_by_serial() {
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new(Duration::from_secs(2));
+
+        assert!(reassembler
+            .push(parse_line(SYSCALL).unwrap(), now)
+            .is_none());
+        assert!(reassembler.push(parse_line(CWD).unwrap(), now).is_none());
+
+        let login = reassembler
+            .push(parse_line(USER_LOGIN).unwrap(), now)
+            .unwrap();
+        assert_eq!(login.serial, 24290);
+        assert_eq!(login.records.len(), 1);
+
+        let event = reassembler.push(parse_line(EOE).unwrap(), now).unwrap();
+        assert_eq!(event.serial, 24287);
+        assert_eq!(
+            event
+                .records
+                .iter()
+                .map(|record| record.record_type.as_str())
+                .collect::<Vec<_>>(),
+            vec!["SYSCALL", "CWD"]
+        );
+        assert!(reassembler.pending.is_empty());
+    }
+
+    #[test]
+    fn flushes_incomplete_events_after_timeout() {
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new(Duration::from_secs(2));
+
+        reassembler.push(parse_line(SYSCALL).unwrap(), now);
+
+        assert!(reassembler
+            .flush_expired(now + Duration::from_secs(1))
+            .is_empty());
+        let events = reassembler.flush_expired(now + Duration::from_secs(2));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].records.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn reads_events_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        let mut file = std::fs::File::create(&path).unwrap();
+        writeln!(file, "{}\n{}\n{}", SYSCALL, CWD, EOE).unwrap();
+
+        let (tx, rx) = SourceSender::new_test();
+        let config = AuditdConfig {
+            mode: Mode::File {
+                path,
+                start_at_beginning: true,
+            },
+            event_timeout_ms: default_event_timeout_ms(),
+            log_namespace: None,
+        };
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        let events = timeout(Duration::from_secs(5), collect_n(rx, 1))
+            .await
+            .unwrap();
+        let log = match &events[0] {
+            Event::Log(log) => log,
+            _ => panic!("expected a log event"),
+        };
+
+        assert_eq!(log["serial"], Value::from(24287_i64));
+        assert_eq!(log["records[0].type"], Value::from("SYSCALL"));
+        assert_eq!(log["records[1].cwd"], Value::from("/home/shadowman"));
+        assert_eq!(log[log_schema().source_type_key()], "auditd".into());
+    }
+}
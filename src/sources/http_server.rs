@@ -1,4 +1,4 @@
-use std::{collections::HashMap, net::SocketAddr};
+use std::{borrow::Cow, collections::HashMap, net::SocketAddr};
 
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
@@ -8,7 +8,7 @@ use codecs::{
     NewlineDelimitedDecoderConfig,
 };
 
-use http::{StatusCode, Uri};
+use http::{Method, StatusCode, Uri};
 use lookup::{lookup_v2::OptionalValuePath, owned_value_path, path};
 use tokio_util::codec::Decoder as _;
 use vector_config::configurable_component;
@@ -21,6 +21,7 @@ use warp::http::{HeaderMap, HeaderValue};
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
+    common::http_signature::{HttpSignatureVerificationConfig, HttpSignatureVerifier},
     components::validation::*,
     config::{
         GenerateConfig, Resource, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
@@ -31,7 +32,7 @@ use crate::{
     serde::{bool_or_struct, default_decoding},
     sources::util::{
         http::{add_query_parameters, HttpMethod},
-        Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
+        Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig, VerifiedRequest,
     },
     tls::TlsEnableableConfig,
 };
@@ -104,6 +105,13 @@ pub struct SimpleHttpConfig {
     #[configurable(derived)]
     auth: Option<HttpSourceAuthConfig>,
 
+    /// Verifies the HMAC-SHA256 signature of each request.
+    ///
+    /// Requests that are unsigned, incorrectly signed, signed too long ago, or replayed are
+    /// rejected with a `401 Unauthorized` response.
+    #[configurable(derived)]
+    signature: Option<HttpSignatureVerificationConfig>,
+
     /// Whether or not to treat the configured `path` as an absolute path.
     ///
     /// If set to `true`, only requests using the exact URL path specified in `path` are accepted. Otherwise,
@@ -239,6 +247,7 @@ impl Default for SimpleHttpConfig {
             query_parameters: Vec::new(),
             tls: None,
             auth: None,
+            signature: None,
             path: default_path(),
             path_key: default_path_key(),
             method: default_http_method(),
@@ -319,10 +328,18 @@ impl SourceConfig for SimpleHttpConfig {
         let decoder = self.get_decoding_config()?.build();
         let log_namespace = cx.log_namespace(self.log_namespace);
 
+        let verifier = self
+            .signature
+            .as_ref()
+            .map(HttpSignatureVerificationConfig::build)
+            .transpose()?;
+
         let source = SimpleHttpSource {
             headers: remove_duplicates(self.headers.clone(), "headers"),
             query_parameters: remove_duplicates(self.query_parameters.clone(), "query_parameters"),
             path_key: self.path_key.clone(),
+            method: self.method.into(),
+            verifier,
             decoder,
             log_namespace,
         };
@@ -368,6 +385,8 @@ struct SimpleHttpSource {
     headers: Vec<String>,
     query_parameters: Vec<String>,
     path_key: OptionalValuePath,
+    method: Method,
+    verifier: Option<HttpSignatureVerifier>,
     decoder: Decoder,
     log_namespace: LogNamespace,
 }
@@ -427,6 +446,33 @@ impl HttpSource for SimpleHttpSource {
         }
     }
 
+    fn verify_request(
+        &self,
+        path: &str,
+        query: &str,
+        headers: &HeaderMap,
+        body: &Bytes,
+    ) -> Result<VerifiedRequest, ErrorMessage> {
+        let Some(verifier) = &self.verifier else {
+            return Ok(VerifiedRequest::default());
+        };
+
+        let path = if query.is_empty() {
+            Cow::Borrowed(path)
+        } else {
+            Cow::Owned(format!("{}?{}", path, query))
+        };
+        let signature = verifier
+            .verify(self.method.as_str(), &path, headers, body)
+            .map_err(|error| ErrorMessage::new(StatusCode::UNAUTHORIZED, error.to_string()))?;
+
+        // Forget the signature if the request fails to be handled, so that it can be retried.
+        let verifier = verifier.clone();
+        Ok(VerifiedRequest::undo_with(move || {
+            verifier.forget(&signature)
+        }))
+    }
+
     fn build_events(
         &self,
         body: Bytes,
@@ -471,6 +517,7 @@ mod tests {
     use vrl::value::kind::Collection;
     use vrl::value::Kind;
 
+    use bytes::Bytes;
     use codecs::{
         decoding::{DeserializerConfig, FramingConfig},
         BytesDecoderConfig, JsonDeserializerConfig,
@@ -485,6 +532,7 @@ mod tests {
     use similar_asserts::assert_eq;
 
     use super::{remove_duplicates, SimpleHttpConfig};
+    use crate::common::http_signature::HttpSignatureConfig;
     use crate::sources::http_server::HttpMethod;
     use crate::{
         config::{log_schema, SourceConfig, SourceContext},
@@ -533,6 +581,7 @@ mod tests {
                 query_parameters,
                 tls: None,
                 auth: None,
+                signature: None,
                 strict_path,
                 path_key,
                 path,
@@ -1208,6 +1257,89 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn http_verifies_signatures() {
+        components::init_test();
+        let address = next_addr();
+        let config = toml::from_str::<SimpleHttpConfig>(&format!(
+            r#"
+            address = "{}"
+            signature.secret = "hunter2"
+        "#,
+            address
+        ))
+        .unwrap();
+        let (sender, _rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let source = config
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+
+        assert_eq!(401, send(address, "hello").await);
+
+        let signer = toml::from_str::<HttpSignatureConfig>(r#"secret = "hunter2""#)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut request = http::Request::post(format!("http://{}/", address))
+            .body(Bytes::from("hello"))
+            .unwrap();
+        signer.sign(&mut request).unwrap();
+        let headers = request.headers().clone();
+
+        assert_eq!(
+            401,
+            send_with_headers(address, "goodbye", headers.clone()).await
+        );
+        assert_eq!(
+            200,
+            send_with_headers(address, "hello", headers.clone()).await
+        );
+        assert_eq!(401, send_with_headers(address, "hello", headers).await);
+    }
+
+    #[tokio::test]
+    async fn http_accepts_signed_retries_of_failed_requests() {
+        components::init_test();
+        let address = next_addr();
+        let config = toml::from_str::<SimpleHttpConfig>(&format!(
+            r#"
+            address = "{}"
+            acknowledgements = true
+            signature.secret = "hunter2"
+        "#,
+            address
+        ))
+        .unwrap();
+        let (sender, _rx) = SourceSender::new_test_finalize(EventStatus::Rejected);
+        let source = config
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+
+        let signer = toml::from_str::<HttpSignatureConfig>(r#"secret = "hunter2""#)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut request = http::Request::post(format!("http://{}/", address))
+            .body(Bytes::from("hello"))
+            .unwrap();
+        signer.sign(&mut request).unwrap();
+        let headers = request.headers().clone();
+
+        // The events of both attempts are rejected, rather than the retry being refused as a
+        // replay.
+        assert_eq!(
+            400,
+            send_with_headers(address, "hello", headers.clone()).await
+        );
+        assert_eq!(400, send_with_headers(address, "hello", headers).await);
+    }
+
     #[tokio::test]
     async fn http_wrong_path() {
         components::init_test();
@@ -0,0 +1,511 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    os::unix::fs::OpenOptionsExt,
+    path::PathBuf,
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, Duration, Utc};
+use codecs::BytesDeserializerConfig;
+use lookup::{owned_value_path, path};
+use nix::{
+    fcntl::OFlag,
+    time::{clock_gettime, ClockId},
+};
+use snafu::{ResultExt, Snafu};
+use tokio::io::unix::AsyncFd;
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol,
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{kind::Collection, Kind, Value};
+
+use crate::{
+    config::{DataType, SourceConfig, SourceContext, SourceOutput},
+    event::LogEvent,
+    internal_events::{
+        EventsReceived, KmsgInvalidRecordError, KmsgReadError, KmsgRecordsOverwritten,
+        StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// The kernel never hands out records larger than this from `/dev/kmsg`.
+const READ_BUFFER_SIZE: usize = 8192;
+
+const FACILITIES: [&str; 24] = [
+    "kern",
+    "user",
+    "mail",
+    "daemon",
+    "auth",
+    "syslog",
+    "lpr",
+    "news",
+    "uucp",
+    "cron",
+    "authpriv",
+    "ftp",
+    "ntp",
+    "security",
+    "console",
+    "solaris-cron",
+    "local0",
+    "local1",
+    "local2",
+    "local3",
+    "local4",
+    "local5",
+    "local6",
+    "local7",
+];
+
+const SEVERITIES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Unable to open {:?}: {}", path, source))]
+    Open { path: PathBuf, source: io::Error },
+    #[snafu(display("Unable to seek to the end of {:?}: {}", path, source))]
+    Seek { path: PathBuf, source: io::Error },
+    #[snafu(display("Unable to read the monotonic clock: {}", source))]
+    Clock { source: nix::Error },
+}
+
+/// Configuration for the `kmsg` source.
+#[configurable_component(source("kmsg", "Collect messages from the Linux kernel ring buffer."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KmsgConfig {
+    /// The path of the kernel message device.
+    #[serde(default = "default_path")]
+    #[configurable(metadata(docs::examples = "/dev/kmsg"))]
+    pub path: PathBuf,
+
+    /// Only include messages that are written to the ring buffer after Vector starts.
+    ///
+    /// By default, all messages still held in the ring buffer are read first.
+    #[serde(default)]
+    pub since_now: bool,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_path() -> PathBuf {
+    PathBuf::from("/dev/kmsg")
+}
+
+impl Default for KmsgConfig {
+    fn default() -> Self {
+        Self {
+            path: default_path(),
+            since_now: false,
+            log_namespace: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(KmsgConfig);
+
+impl KmsgConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        BytesDeserializerConfig
+            .schema_definition(log_namespace)
+            .with_source_metadata(
+                KmsgConfig::NAME,
+                log_schema()
+                    .timestamp_key()
+                    .cloned()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp(),
+                Some("timestamp"),
+            )
+            .with_source_metadata(
+                KmsgConfig::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("facility"))),
+                &owned_value_path!("facility"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                KmsgConfig::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("severity"))),
+                &owned_value_path!("severity"),
+                Kind::bytes(),
+                Some("severity"),
+            )
+            .with_source_metadata(
+                KmsgConfig::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("sequence"))),
+                &owned_value_path!("sequence"),
+                Kind::integer(),
+                None,
+            )
+            .with_source_metadata(
+                KmsgConfig::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("dictionary"))),
+                &owned_value_path!("dictionary"),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                None,
+            )
+            .with_standard_vector_source_metadata()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "kmsg")]
+impl SourceConfig for KmsgConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .custom_flags(OFlag::O_NONBLOCK.bits())
+            .open(&self.path)
+            .context(OpenSnafu {
+                path: self.path.clone(),
+            })?;
+
+        if self.since_now {
+            (&file).seek(SeekFrom::End(0)).context(SeekSnafu {
+                path: self.path.clone(),
+            })?;
+        }
+
+        let file = AsyncFd::new(file).context(OpenSnafu {
+            path: self.path.clone(),
+        })?;
+
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        Ok(Box::pin(kmsg_source(
+            file,
+            boot_time()?,
+            log_namespace,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// Record timestamps are relative to the monotonic clock, so the wall-clock time at which that
+/// clock started is needed to turn them into absolute timestamps.
+fn boot_time() -> Result<DateTime<Utc>, BuildError> {
+    let uptime = clock_gettime(ClockId::CLOCK_MONOTONIC).context(ClockSnafu)?;
+    let uptime = Duration::seconds(uptime.tv_sec()) + Duration::nanoseconds(uptime.tv_nsec());
+    Ok(Utc::now() - uptime)
+}
+
+async fn kmsg_source(
+    file: AsyncFd<File>,
+    boot_time: DateTime<Utc>,
+    log_namespace: LogNamespace,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let bytes_received = register!(BytesReceived::from(Protocol::from("kmsg")));
+    let events_received = register!(EventsReceived);
+
+    let mut buf = vec![0; READ_BUFFER_SIZE];
+
+    loop {
+        let mut guard = tokio::select! {
+            _ = &mut shutdown => break,
+            guard = file.readable() => guard.map_err(|error| emit!(KmsgReadError { error }))?,
+        };
+
+        // Every successful read of `/dev/kmsg` returns exactly one record.
+        let size = match guard.try_io(|inner| {
+            let mut file = inner.get_ref();
+            file.read(&mut buf)
+        }) {
+            Err(_would_block) => continue,
+            Ok(Ok(size)) => size,
+            Ok(Err(error)) if error.kind() == io::ErrorKind::BrokenPipe => {
+                emit!(KmsgRecordsOverwritten);
+                continue;
+            }
+            Ok(Err(error)) => {
+                emit!(KmsgReadError { error });
+                return Err(());
+            }
+        };
+
+        bytes_received.emit(ByteSize(size));
+
+        let record = match parse_record(&buf[..size]) {
+            Ok(record) => record,
+            Err(error) => {
+                emit!(KmsgInvalidRecordError {
+                    error,
+                    text: String::from_utf8_lossy(&buf[..size]).into_owned(),
+                });
+                continue;
+            }
+        };
+
+        let log = record.into_log_event(boot_time, log_namespace);
+        events_received.emit(CountByteSize(1, log.estimated_json_encoded_size_of()));
+
+        if let Err(error) = out.send_event(log).await {
+            emit!(StreamClosedError { error, count: 1 });
+            return Err(());
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub enum KmsgParseError {
+    #[snafu(display("Record has no message separator"))]
+    MissingSeparator,
+    #[snafu(display("Record has an invalid `{}` prefix field", field))]
+    InvalidPrefix { field: &'static str },
+}
+
+#[derive(Debug, PartialEq)]
+struct KmsgRecord {
+    facility: u8,
+    severity: u8,
+    sequence: u64,
+    timestamp_micros: i64,
+    message: Bytes,
+    dictionary: BTreeMap<String, Value>,
+}
+
+/// Parses a single record as formatted by the kernel:
+///
+/// ```text
+/// 6,339,5140900,-;NET: Registered protocol family 10
+///  SUBSYSTEM=net
+///  DEVICE=n1
+/// ```
+fn parse_record(data: &[u8]) -> Result<KmsgRecord, KmsgParseError> {
+    let separator = data
+        .iter()
+        .position(|&b| b == b';')
+        .ok_or(KmsgParseError::MissingSeparator)?;
+
+    let prefix = String::from_utf8_lossy(&data[..separator]);
+    let mut fields = prefix.split(',');
+    let mut next_field = |field: &'static str| {
+        fields
+            .next()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .ok_or(KmsgParseError::InvalidPrefix { field })
+    };
+
+    let priority = next_field("priority")?;
+    let sequence = next_field("sequence")?;
+    let timestamp_micros = next_field("timestamp")? as i64;
+
+    let mut lines = data[separator + 1..].split(|&b| b == b'\n');
+    let message = unescape(lines.next().unwrap_or_default());
+
+    let dictionary = lines
+        .filter_map(|line| line.strip_prefix(b" "))
+        .filter_map(|line| {
+            let line = String::from_utf8_lossy(line);
+            line.split_once('=')
+                .map(|(key, value)| (key.to_owned(), Value::from(value.to_owned())))
+        })
+        .collect();
+
+    Ok(KmsgRecord {
+        facility: (priority >> 3) as u8,
+        severity: (priority & 7) as u8,
+        sequence,
+        timestamp_micros,
+        message,
+        dictionary,
+    })
+}
+
+/// The kernel escapes non-printable bytes in messages as `\xNN`.
+fn unescape(data: &[u8]) -> Bytes {
+    let mut unescaped = Vec::with_capacity(data.len());
+    let mut i = 0;
+    while i < data.len() {
+        if data[i] == b'\\' && data.get(i + 1) == Some(&b'x') {
+            if let Some(byte) = data
+                .get(i + 2..i + 4)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                unescaped.push(byte);
+                i += 4;
+                continue;
+            }
+        }
+        unescaped.push(data[i]);
+        i += 1;
+    }
+    unescaped.into()
+}
+
+impl KmsgRecord {
+    fn into_log_event(self, boot_time: DateTime<Utc>, log_namespace: LogNamespace) -> LogEvent {
+        let mut log = match log_namespace {
+            LogNamespace::Vector => LogEvent::from(Value::Bytes(self.message)),
+            LogNamespace::Legacy => {
+                let mut log = LogEvent::default();
+                log.insert(log_schema().message_key(), self.message);
+                log
+            }
+        };
+
+        log_namespace.insert_source_metadata(
+            KmsgConfig::NAME,
+            &mut log,
+            log_schema().timestamp_key().map(LegacyKey::Overwrite),
+            path!("timestamp"),
+            boot_time + Duration::microseconds(self.timestamp_micros),
+        );
+        log_namespace.insert_source_metadata(
+            KmsgConfig::NAME,
+            &mut log,
+            Some(LegacyKey::Overwrite(path!("facility"))),
+            path!("facility"),
+            FACILITIES
+                .get(self.facility as usize)
+                .copied()
+                .unwrap_or("unknown"),
+        );
+        log_namespace.insert_source_metadata(
+            KmsgConfig::NAME,
+            &mut log,
+            Some(LegacyKey::Overwrite(path!("severity"))),
+            path!("severity"),
+            SEVERITIES[self.severity as usize],
+        );
+        log_namespace.insert_source_metadata(
+            KmsgConfig::NAME,
+            &mut log,
+            Some(LegacyKey::Overwrite(path!("sequence"))),
+            path!("sequence"),
+            self.sequence as i64,
+        );
+        if !self.dictionary.is_empty() {
+            log_namespace.insert_source_metadata(
+                KmsgConfig::NAME,
+                &mut log,
+                Some(LegacyKey::Overwrite(path!("dictionary"))),
+                path!("dictionary"),
+                self.dictionary,
+            );
+        }
+
+        log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            KmsgConfig::NAME,
+            Utc::now(),
+        );
+
+        log
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<KmsgConfig>();
+    }
+
+    #[test]
+    fn parses_record() {
+        let record = parse_record(b"6,339,5140900,-;NET: Registered protocol family 10\n").unwrap();
+
+        assert_eq!(
+            record,
+            KmsgRecord {
+                facility: 0,
+                severity: 6,
+                sequence: 339,
+                timestamp_micros: 5140900,
+                message: Bytes::from("NET: Registered protocol family 10"),
+                dictionary: BTreeMap::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_record_with_dictionary_and_extra_prefix_fields() {
+        let record = parse_record(
+            b"30,2195,3498761,-,caller=T1;usb 1-1: new device\\x20found\n SUBSYSTEM=usb\n DEVICE=c189:1\n",
+        )
+        .unwrap();
+
+        assert_eq!(record.facility, 3);
+        assert_eq!(record.severity, 6);
+        assert_eq!(record.message, Bytes::from("usb 1-1: new device found"));
+        assert_eq!(
+            record.dictionary,
+            BTreeMap::from([
+                ("DEVICE".to_owned(), Value::from("c189:1")),
+                ("SUBSYSTEM".to_owned(), Value::from("usb")),
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_records() {
+        assert_eq!(
+            parse_record(b"6,339,5140900,- no separator"),
+            Err(KmsgParseError::MissingSeparator)
+        );
+        assert_eq!(
+            parse_record(b"6,abc,5140900,-;message"),
+            Err(KmsgParseError::InvalidPrefix { field: "sequence" })
+        );
+    }
+
+    #[test]
+    fn creates_legacy_event() {
+        let boot_time = Utc.timestamp_opt(1_600_000_000, 0).single().unwrap();
+        let record = parse_record(b"4,12,1500000,-;oom-killer invoked\n").unwrap();
+        let log = record.into_log_event(boot_time, LogNamespace::Legacy);
+
+        assert_eq!(log[log_schema().message_key()], "oom-killer invoked".into());
+        assert_eq!(log["facility"], "kern".into());
+        assert_eq!(log["severity"], "warning".into());
+        assert_eq!(log["sequence"], Value::from(12_i64));
+        assert_eq!(
+            log[log_schema().timestamp_key().unwrap().to_string()],
+            Utc.timestamp_opt(1_600_000_001, 500_000_000)
+                .single()
+                .unwrap()
+                .into()
+        );
+        assert_eq!(log[log_schema().source_type_key()], "kmsg".into());
+    }
+}
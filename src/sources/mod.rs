@@ -5,6 +5,8 @@ use snafu::Snafu;
 pub mod amqp;
 #[cfg(feature = "sources-apache_metrics")]
 pub mod apache_metrics;
+#[cfg(all(target_os = "linux", feature = "sources-auditd"))]
+pub mod auditd;
 #[cfg(feature = "sources-aws_ecs_metrics")]
 pub mod aws_ecs_metrics;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
@@ -52,6 +54,8 @@ pub mod internal_metrics;
 pub mod journald;
 #[cfg(feature = "sources-kafka")]
 pub mod kafka;
+#[cfg(all(target_os = "linux", feature = "sources-kmsg"))]
+pub mod kmsg;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub mod kubernetes_logs;
 #[cfg(all(feature = "sources-logstash"))]
@@ -66,8 +70,12 @@ pub mod nginx_metrics;
 pub mod opentelemetry;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub mod postgresql_metrics;
+#[cfg(feature = "sources-postgresql_query")]
+pub mod postgresql_query;
 #[cfg(feature = "sources-prometheus")]
 pub mod prometheus;
+#[cfg(feature = "sources-pulsar")]
+pub mod pulsar;
 #[cfg(feature = "sources-redis")]
 pub mod redis;
 #[cfg(feature = "sources-socket")]
@@ -80,6 +88,8 @@ pub mod statsd;
 pub mod syslog;
 #[cfg(feature = "sources-vector")]
 pub mod vector;
+#[cfg(feature = "sources-websocket")]
+pub mod websocket;
 
 pub mod util;
 
@@ -4,12 +4,15 @@ use futures::{pin_mut, stream, Stream, StreamExt};
 use lookup::{lookup_v2::OptionalValuePath, owned_value_path};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
-use vector_common::internal_event::{
-    ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+use vector_common::{
+    finalizer::UnorderedFinalizer,
+    internal_event::{
+        ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+    },
 };
 use vector_config::configurable_component;
 use vector_core::{
-    config::{LegacyKey, LogNamespace},
+    config::{LegacyKey, LogNamespace, SourceAcknowledgementsConfig},
     EstimatedJsonEncodedSizeOf,
 };
 use vrl::value::Kind;
@@ -17,10 +20,12 @@ use vrl::value::Kind;
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
-    event::Event,
-    internal_events::StreamClosedError,
-    nats::{from_tls_auth_config, NatsAuthConfig, NatsConfigError},
-    serde::{default_decoding, default_framing_message_based},
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{NatsAcknowledgementError, StreamClosedError},
+    nats::{
+        from_tls_auth_config, jetstream_request, JetStreamError, NatsAuthConfig, NatsConfigError,
+    },
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
     tls::TlsEnableableConfig,
     SourceSender,
@@ -34,8 +39,19 @@ enum BuildError {
     Connect { source: std::io::Error },
     #[snafu(display("NATS Subscribe Error: {}", source))]
     Subscribe { source: std::io::Error },
+    #[snafu(display("NATS JetStream Consumer Error: {}", source))]
+    JetStreamConsumer { source: JetStreamError },
 }
 
+/// Acknowledges that a JetStream message was processed.
+const JETSTREAM_ACK: &str = "+ACK";
+
+/// Asks the server to redeliver a JetStream message.
+const JETSTREAM_NAK: &str = "-NAK";
+
+/// Tells the server to never redeliver a JetStream message.
+const JETSTREAM_TERM: &str = "+TERM";
+
 /// Configuration for the `nats` source.
 #[configurable_component(source(
     "nats",
@@ -71,8 +87,13 @@ pub struct NatsSourceConfig {
     subject: String,
 
     /// The NATS queue group to join.
+    ///
+    /// When consuming from JetStream, this is used as the deliver group of the consumer.
     queue: Option<String>,
 
+    #[configurable(derived)]
+    jetstream: Option<NatsJetStreamConfig>,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -97,6 +118,48 @@ pub struct NatsSourceConfig {
     /// The `NATS` subject key.
     #[serde(default = "default_subject_key_field")]
     subject_key_field: OptionalValuePath,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+}
+
+/// Configuration for consuming messages from a [JetStream][jetstream] stream.
+///
+/// A durable push consumer is created on the stream, filtered on `subject`, if it does not already
+/// exist. Messages are acknowledged once they have been processed, or once they have been delivered
+/// to all sinks when acknowledgements are enabled.
+///
+/// [jetstream]: https://docs.nats.io/nats-concepts/jetstream
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NatsJetStreamConfig {
+    /// The name of the stream to consume from.
+    #[configurable(metadata(docs::examples = "EVENTS"))]
+    stream: String,
+
+    /// The name of the durable consumer.
+    ///
+    /// The server keeps track of which messages were acknowledged by this consumer, so that
+    /// consumption resumes where it left off after a restart.
+    #[configurable(metadata(docs::examples = "vector"))]
+    durable_name: String,
+
+    /// The maximum number of messages delivered to the consumer that are not yet acknowledged.
+    #[serde(default = "default_max_ack_pending")]
+    max_ack_pending: u32,
+}
+
+const fn default_max_ack_pending() -> u32 {
+    1000
+}
+
+impl NatsJetStreamConfig {
+    /// The subject the server pushes the consumer's messages to.
+    fn deliver_subject(&self) -> String {
+        format!("_vector.deliver.{}.{}", self.stream, self.durable_name)
+    }
 }
 
 fn default_subject_key_field() -> OptionalValuePath {
@@ -120,6 +183,7 @@ impl GenerateConfig for NatsSourceConfig {
 impl SourceConfig for NatsSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
         let (connection, subscription) = create_subscription(self).await?;
         let decoder =
             DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
@@ -132,6 +196,7 @@ impl SourceConfig for NatsSourceConfig {
             log_namespace,
             cx.shutdown,
             cx.out,
+            acknowledgements,
         )))
     }
 
@@ -161,7 +226,7 @@ impl SourceConfig for NatsSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        self.jetstream.is_some()
     }
 }
 
@@ -188,65 +253,98 @@ fn get_subscription_stream(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn nats_source(
     config: NatsSourceConfig,
     // Take ownership of the connection so it doesn't get dropped.
-    _connection: nats::asynk::Connection,
+    connection: nats::asynk::Connection,
     subscription: nats::asynk::Subscription,
     decoder: Decoder,
     log_namespace: LogNamespace,
     shutdown: ShutdownSignal,
     mut out: SourceSender,
+    acknowledgements: bool,
 ) -> Result<(), ()> {
     let events_received = register!(EventsReceived);
+    let bytes_received = register!(BytesReceived::from(Protocol::TCP));
+
+    // Only messages consumed from JetStream can be acknowledged.
+    let jetstream = config.jetstream.is_some();
+    let (finalizer, mut ack_stream) = UnorderedFinalizer::<String>::maybe_new(
+        acknowledgements && jetstream,
+        Some(shutdown.clone()),
+    );
+
     let stream = get_subscription_stream(subscription).take_until(shutdown);
     pin_mut!(stream);
-    let bytes_received = register!(BytesReceived::from(Protocol::TCP));
-    while let Some(msg) = stream.next().await {
-        bytes_received.emit(ByteSize(msg.data.len()));
-        let mut stream = FramedRead::new(msg.data.as_ref(), decoder.clone());
-        while let Some(next) = stream.next().await {
-            match next {
-                Ok((events, _byte_size)) => {
-                    let count = events.len();
-                    let byte_size = events.estimated_json_encoded_size_of();
-                    events_received.emit(CountByteSize(count, byte_size));
-
-                    let now = Utc::now();
-
-                    let events = events.into_iter().map(|mut event| {
-                        if let Event::Log(ref mut log) = event {
-                            log_namespace.insert_standard_vector_source_metadata(
-                                log,
-                                NatsSourceConfig::NAME,
-                                now,
-                            );
-
-                            let legacy_subject_key_field = config
-                                .subject_key_field
-                                .path
-                                .as_ref()
-                                .map(LegacyKey::InsertIfEmpty);
-                            log_namespace.insert_source_metadata(
-                                NatsSourceConfig::NAME,
-                                log,
-                                legacy_subject_key_field,
-                                "subject",
-                                msg.subject.as_str(),
-                            )
+    loop {
+        tokio::select! {
+            entry = ack_stream.next() => {
+                if let Some((status, reply)) = entry {
+                    handle_ack(&connection, status, &reply).await;
+                }
+            },
+            msg = stream.next() => {
+                let msg = match msg {
+                    Some(msg) => msg,
+                    None => break,
+                };
+
+                bytes_received.emit(ByteSize(msg.data.len()));
+                let (batch, receiver) =
+                    BatchNotifier::maybe_new_with_receiver(finalizer.is_some());
+                let mut stream = FramedRead::new(msg.data.as_ref(), decoder.clone());
+                while let Some(next) = stream.next().await {
+                    match next {
+                        Ok((events, _byte_size)) => {
+                            let count = events.len();
+                            let byte_size = events.estimated_json_encoded_size_of();
+                            events_received.emit(CountByteSize(count, byte_size));
+
+                            let now = Utc::now();
+
+                            let events = events.into_iter().map(|mut event| {
+                                if let Event::Log(ref mut log) = event {
+                                    log_namespace.insert_standard_vector_source_metadata(
+                                        log,
+                                        NatsSourceConfig::NAME,
+                                        now,
+                                    );
+
+                                    let legacy_subject_key_field = config
+                                        .subject_key_field
+                                        .path
+                                        .as_ref()
+                                        .map(LegacyKey::InsertIfEmpty);
+                                    log_namespace.insert_source_metadata(
+                                        NatsSourceConfig::NAME,
+                                        log,
+                                        legacy_subject_key_field,
+                                        "subject",
+                                        msg.subject.as_str(),
+                                    )
+                                }
+                                event.with_batch_notifier_option(&batch)
+                            });
+
+                            out.send_batch(events).await.map_err(|error| {
+                                emit!(StreamClosedError { error, count });
+                            })?;
                         }
-                        event
-                    });
-
-                    out.send_batch(events).await.map_err(|error| {
-                        emit!(StreamClosedError { error, count });
-                    })?;
+                        Err(error) => {
+                            // Error is logged by `crate::codecs`, no further
+                            // handling is needed here.
+                            if !error.can_continue() {
+                                break;
+                            }
+                        }
+                    }
                 }
-                Err(error) => {
-                    // Error is logged by `crate::codecs`, no further
-                    // handling is needed here.
-                    if !error.can_continue() {
-                        break;
+
+                if let (true, Some(reply)) = (jetstream, msg.reply) {
+                    match (finalizer.as_ref(), receiver) {
+                        (Some(finalizer), Some(receiver)) => finalizer.add(reply, receiver),
+                        _ => acknowledge(&connection, &reply, JETSTREAM_ACK).await,
                     }
                 }
             }
@@ -255,14 +353,68 @@ async fn nats_source(
     Ok(())
 }
 
+async fn handle_ack(connection: &nats::asynk::Connection, status: BatchStatus, reply: &str) {
+    let response = match status {
+        BatchStatus::Delivered => JETSTREAM_ACK,
+        BatchStatus::Errored => JETSTREAM_NAK,
+        // Redelivering a rejected message would only get it rejected again.
+        BatchStatus::Rejected => JETSTREAM_TERM,
+    };
+    acknowledge(connection, reply, response).await;
+}
+
+async fn acknowledge(connection: &nats::asynk::Connection, reply: &str, response: &str) {
+    if let Err(error) = connection.publish(reply, response).await {
+        emit!(NatsAcknowledgementError { error });
+    }
+}
+
+/// Creates the durable push consumer of the stream, or reuses it if it already exists.
+async fn create_jetstream_consumer(
+    connection: &nats::asynk::Connection,
+    config: &NatsSourceConfig,
+    jetstream: &NatsJetStreamConfig,
+) -> Result<(), BuildError> {
+    let request = serde_json::json!({
+        "stream_name": jetstream.stream,
+        "config": {
+            "durable_name": jetstream.durable_name,
+            "deliver_subject": jetstream.deliver_subject(),
+            "deliver_group": config.queue,
+            "filter_subject": config.subject,
+            "ack_policy": "explicit",
+            "deliver_policy": "all",
+            "max_ack_pending": jetstream.max_ack_pending,
+        },
+    });
+    let subject = format!(
+        "$JS.API.CONSUMER.DURABLE.CREATE.{}.{}",
+        jetstream.stream, jetstream.durable_name
+    );
+
+    jetstream_request::<serde_json::Value>(connection, &subject, request.to_string())
+        .await
+        .context(JetStreamConsumerSnafu)?;
+
+    Ok(())
+}
+
 async fn create_subscription(
     config: &NatsSourceConfig,
 ) -> Result<(nats::asynk::Connection, nats::asynk::Subscription), BuildError> {
     let nc = config.connect().await?;
 
+    let subject = match &config.jetstream {
+        None => config.subject.clone(),
+        Some(jetstream) => {
+            create_jetstream_consumer(&nc, config, jetstream).await?;
+            jetstream.deliver_subject()
+        }
+    };
+
     let subscription = match &config.queue {
-        None => nc.subscribe(&config.subject).await,
-        Some(queue) => nc.queue_subscribe(&config.subject, queue).await,
+        None => nc.subscribe(&subject).await,
+        Some(queue) => nc.queue_subscribe(&subject, queue).await,
     };
 
     let subscription = subscription.context(SubscribeSnafu)?;
@@ -352,6 +504,7 @@ mod integration_tests {
     use vector_core::config::log_schema;
 
     use super::*;
+    use crate::event::EventStatus;
     use crate::nats::{NatsAuthCredentialsFile, NatsAuthNKey, NatsAuthToken, NatsAuthUserPassword};
     use crate::test_util::{
         collect_n,
@@ -382,6 +535,7 @@ mod integration_tests {
                 LogNamespace::Legacy,
                 ShutdownSignal::noop(),
                 tx,
+                false,
             ));
             nc_pub.publish(&subject, msg).await.unwrap();
 
@@ -411,6 +565,8 @@ mod integration_tests {
             auth: None,
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -443,6 +599,8 @@ mod integration_tests {
             }),
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -475,6 +633,8 @@ mod integration_tests {
             }),
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -506,6 +666,8 @@ mod integration_tests {
             }),
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -537,6 +699,8 @@ mod integration_tests {
             }),
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -569,6 +733,8 @@ mod integration_tests {
             }),
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -601,6 +767,8 @@ mod integration_tests {
             }),
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -634,6 +802,8 @@ mod integration_tests {
             auth: None,
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -661,6 +831,8 @@ mod integration_tests {
             auth: None,
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -696,6 +868,8 @@ mod integration_tests {
             auth: None,
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -729,6 +903,8 @@ mod integration_tests {
             auth: None,
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -766,6 +942,8 @@ mod integration_tests {
             }),
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -803,6 +981,8 @@ mod integration_tests {
             }),
             log_namespace: None,
             subject_key_field: default_subject_key_field(),
+            jetstream: None,
+            acknowledgements: Default::default(),
         };
 
         let r = publish_and_check(conf).await;
@@ -812,4 +992,94 @@ mod integration_tests {
             r
         );
     }
+
+    #[tokio::test]
+    async fn nats_jetstream_consume() {
+        let name = format!("test-{}", random_string(10));
+        let url = std::env::var("NATS_JETSTREAM_ADDRESS")
+            .unwrap_or_else(|_| String::from("nats://localhost:4222"));
+
+        let conf = NatsSourceConfig {
+            connection_name: "".to_owned(),
+            subject: name.clone(),
+            url,
+            queue: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            tls: None,
+            auth: None,
+            log_namespace: None,
+            subject_key_field: default_subject_key_field(),
+            jetstream: Some(NatsJetStreamConfig {
+                stream: name.clone(),
+                durable_name: "vector".to_owned(),
+                max_ack_pending: default_max_ack_pending(),
+            }),
+            acknowledgements: true.into(),
+        };
+
+        // Messages published before the source starts are still stored by the stream.
+        let connection = conf.connect().await.unwrap();
+        let _: serde_json::Value = jetstream_request(
+            &connection,
+            &format!("$JS.API.STREAM.CREATE.{}", name),
+            serde_json::json!({ "name": name, "subjects": [name] }).to_string(),
+        )
+        .await
+        .expect("failed to create stream");
+        let messages = (0..10)
+            .map(|i| format!("message {}", i))
+            .collect::<Vec<_>>();
+        for message in &messages {
+            let _: crate::nats::JetStreamPublishAck =
+                jetstream_request(&connection, &name, message)
+                    .await
+                    .unwrap();
+        }
+
+        let (nc, sub) = create_subscription(&conf).await.unwrap();
+        let events = assert_source_compliance(&SOURCE_TAGS, async move {
+            let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let decoder = DecodingConfig::new(
+                conf.framing.clone(),
+                conf.decoding.clone(),
+                LogNamespace::Legacy,
+            )
+            .build();
+            tokio::spawn(nats_source(
+                conf.clone(),
+                nc,
+                sub,
+                decoder,
+                LogNamespace::Legacy,
+                ShutdownSignal::noop(),
+                tx,
+                true,
+            ));
+
+            collect_n(rx, messages.len()).await
+        })
+        .await;
+
+        for (event, message) in events.iter().zip(&messages) {
+            assert_eq!(
+                event.as_log()[log_schema().message_key()],
+                message.as_str().into()
+            );
+        }
+
+        // Wait for the acknowledgements to reach the server.
+        let subject = format!("$JS.API.CONSUMER.INFO.{}.vector", name);
+        let mut pending = serde_json::Value::Null;
+        for _ in 0..50 {
+            let info: serde_json::Value =
+                jetstream_request(&connection, &subject, "").await.unwrap();
+            pending = info["num_ack_pending"].clone();
+            if pending == 0 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+        assert_eq!(pending, 0);
+    }
 }
@@ -2,7 +2,6 @@ use std::{
     collections::HashSet,
     fmt::Write as _,
     iter,
-    path::PathBuf,
     time::{Duration, Instant},
 };
 
@@ -11,19 +10,10 @@ use futures::{
     future::{join_all, try_join_all},
     FutureExt, StreamExt,
 };
-use openssl::{
-    error::ErrorStack,
-    ssl::{SslConnector, SslMethod},
-};
-use postgres_openssl::MakeTlsConnector;
 use serde_with::serde_as;
 use snafu::{ResultExt, Snafu};
 use tokio::time;
-use tokio_postgres::{
-    config::{ChannelBinding, Host, SslMode, TargetSessionAttrs},
-    types::FromSql,
-    Client, Config, Error as PgError, NoTls, Row,
-};
+use tokio_postgres::{config::Host, types::FromSql, Client, Config, Error as PgError, Row};
 use tokio_stream::wrappers::IntervalStream;
 use vector_common::{
     internal_event::{CountByteSize, InternalEventHandle as _, Registered},
@@ -40,6 +30,7 @@ use crate::{
         CollectionCompleted, EndpointBytesReceived, EventsReceived, PostgresqlMetricsCollectError,
         StreamClosedError,
     },
+    postgresql::{config_to_endpoint, connect, PostgresqlConnectError, PostgresqlTlsConfig},
 };
 
 macro_rules! tags {
@@ -83,10 +74,8 @@ enum BuildError {
 
 #[derive(Debug, Snafu)]
 enum ConnectError {
-    #[snafu(display("failed to create tls connector: {}", source))]
-    TlsFailed { source: ErrorStack },
-    #[snafu(display("failed to connect ({}): {}", endpoint, source))]
-    ConnectionFailed { source: PgError, endpoint: String },
+    #[snafu(display("{}", source))]
+    Connect { source: PostgresqlConnectError },
     #[snafu(display("failed to get PostgreSQL version ({}): {}", endpoint, source))]
     SelectVersionFailed { source: PgError, endpoint: String },
     #[snafu(display("version ({}) is not supported", version))]
@@ -101,18 +90,6 @@ enum CollectError {
     QueryError { source: PgError },
 }
 
-/// Configuration of TLS when connecting to PostgreSQL.
-#[configurable_component]
-#[derive(Clone, Debug)]
-#[serde(deny_unknown_fields)]
-struct PostgresqlMetricsTlsConfig {
-    /// Absolute path to an additional CA certificate file.
-    ///
-    /// The certificate must be in the DER or PEM (X.509) format.
-    #[configurable(metadata(docs::examples = "certs/ca.pem"))]
-    ca_file: PathBuf,
-}
-
 /// Configuration for the `postgresql_metrics` source.
 #[serde_as]
 #[configurable_component(source(
@@ -167,7 +144,7 @@ pub struct PostgresqlMetricsConfig {
     namespace: String,
 
     #[configurable(derived)]
-    tls: Option<PostgresqlMetricsTlsConfig>,
+    tls: Option<PostgresqlTlsConfig>,
 }
 
 impl Default for PostgresqlMetricsConfig {
@@ -249,13 +226,13 @@ impl SourceConfig for PostgresqlMetricsConfig {
 #[derive(Debug)]
 struct PostgresqlClient {
     config: Config,
-    tls_config: Option<PostgresqlMetricsTlsConfig>,
+    tls_config: Option<PostgresqlTlsConfig>,
     client: Option<(Client, usize)>,
     endpoint: String,
 }
 
 impl PostgresqlClient {
-    fn new(config: Config, tls_config: Option<PostgresqlMetricsTlsConfig>) -> Self {
+    fn new(config: Config, tls_config: Option<PostgresqlTlsConfig>) -> Self {
         let endpoint = config_to_endpoint(&config);
         Self {
             config,
@@ -278,36 +255,9 @@ impl PostgresqlClient {
 
     async fn build_client(&self) -> Result<(Client, usize), ConnectError> {
         // Create postgresql client
-        let client = match &self.tls_config {
-            Some(tls_config) => {
-                let mut builder =
-                    SslConnector::builder(SslMethod::tls_client()).context(TlsFailedSnafu)?;
-                builder
-                    .set_ca_file(tls_config.ca_file.clone())
-                    .context(TlsFailedSnafu)?;
-                let connector = MakeTlsConnector::new(builder.build());
-
-                let (client, connection) =
-                    self.config.connect(connector).await.with_context(|_| {
-                        ConnectionFailedSnafu {
-                            endpoint: &self.endpoint,
-                        }
-                    })?;
-                tokio::spawn(connection);
-                client
-            }
-            None => {
-                let (client, connection) =
-                    self.config
-                        .connect(NoTls)
-                        .await
-                        .with_context(|_| ConnectionFailedSnafu {
-                            endpoint: &self.endpoint,
-                        })?;
-                tokio::spawn(connection);
-                client
-            }
-        };
+        let client = connect(&self.config, self.tls_config.as_ref())
+            .await
+            .context(ConnectSnafu)?;
 
         // Log version if required
         if tracing::level_enabled!(tracing::Level::DEBUG) {
@@ -498,7 +448,7 @@ impl PostgresqlMetrics {
         endpoint: String,
         datname_filter: DatnameFilter,
         namespace: Option<String>,
-        tls_config: Option<PostgresqlMetricsTlsConfig>,
+        tls_config: Option<PostgresqlTlsConfig>,
     ) -> Result<Self, BuildError> {
         // Takes the raw endpoint, parses it into a configuration, and then we set `endpoint` back to a sanitized
         // version of the original value, dropping things like username/password, etc.
@@ -886,102 +836,6 @@ fn row_get_value<'a, T: FromSql<'a> + ByteSizeOf>(
         .map_err(|source| CollectError::PostgresGetValue { source, key })
 }
 
-fn config_to_endpoint(config: &Config) -> String {
-    let mut params: Vec<(&'static str, String)> = vec![];
-
-    // options
-    if let Some(options) = config.get_options() {
-        params.push(("options", options.to_string()));
-    }
-
-    // application_name
-    if let Some(name) = config.get_application_name() {
-        params.push(("application_name", name.to_string()));
-    }
-
-    // ssl_mode, ignore default value (SslMode::Prefer)
-    match config.get_ssl_mode() {
-        SslMode::Disable => params.push(("sslmode", "disable".to_string())),
-        SslMode::Prefer => {} // default, ignore
-        SslMode::Require => params.push(("sslmode", "require".to_string())),
-        // non_exhaustive enum
-        _ => {
-            warn!("Unknown variant of \"SslMode\".");
-        }
-    };
-
-    // host
-    for host in config.get_hosts() {
-        match host {
-            Host::Tcp(host) => params.push(("host", host.to_string())),
-            #[cfg(unix)]
-            Host::Unix(path) => params.push(("host", path.to_string_lossy().to_string())),
-        }
-    }
-
-    // port
-    for port in config.get_ports() {
-        params.push(("port", port.to_string()));
-    }
-
-    // connect_timeout
-    if let Some(connect_timeout) = config.get_connect_timeout() {
-        params.push(("connect_timeout", connect_timeout.as_secs().to_string()));
-    }
-
-    // keepalives, ignore default value (true)
-    if !config.get_keepalives() {
-        params.push(("keepalives", "1".to_owned()));
-    }
-
-    // keepalives_idle, ignore default value (2 * 60 * 60)
-    let keepalives_idle = config.get_keepalives_idle().as_secs();
-    if keepalives_idle != 2 * 60 * 60 {
-        params.push(("keepalives_idle", keepalives_idle.to_string()));
-    }
-
-    // target_session_attrs, ignore default value (TargetSessionAttrs::Any)
-    match config.get_target_session_attrs() {
-        TargetSessionAttrs::Any => {} // default, ignore
-        TargetSessionAttrs::ReadWrite => {
-            params.push(("target_session_attrs", "read-write".to_owned()))
-        }
-        // non_exhaustive enum
-        _ => {
-            warn!("Unknown variant of \"TargetSessionAttr\".");
-        }
-    }
-
-    // channel_binding, ignore default value (ChannelBinding::Prefer)
-    match config.get_channel_binding() {
-        ChannelBinding::Disable => params.push(("channel_binding", "disable".to_owned())),
-        ChannelBinding::Prefer => {} // default, ignore
-        ChannelBinding::Require => params.push(("channel_binding", "require".to_owned())),
-        // non_exhaustive enum
-        _ => {
-            warn!("Unknown variant of \"ChannelBinding\".");
-        }
-    }
-
-    format!(
-        "postgresql:///{}?{}",
-        config.get_dbname().unwrap_or(""),
-        params
-            .into_iter()
-            .map(|(k, v)| format!(
-                "{}={}",
-                percent_encoding(k.as_bytes()),
-                percent_encoding(v.as_bytes())
-            ))
-            .collect::<Vec<String>>()
-            .join("&")
-    )
-}
-
-fn percent_encoding(input: &'_ [u8]) -> String {
-    percent_encoding::percent_encode(input, percent_encoding::NON_ALPHANUMERIC).to_string()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1026,7 +880,7 @@ mod integration_tests {
 
     async fn test_postgresql_metrics(
         endpoint: String,
-        tls: Option<PostgresqlMetricsTlsConfig>,
+        tls: Option<PostgresqlTlsConfig>,
         include_databases: Option<Vec<String>>,
         exclude_databases: Option<Vec<String>>,
     ) -> Vec<Event> {
@@ -1127,7 +981,7 @@ mod integration_tests {
     async fn test_host_ssl() {
         test_postgresql_metrics(
             format!("{}?sslmode=require", pg_url()),
-            Some(PostgresqlMetricsTlsConfig {
+            Some(PostgresqlTlsConfig {
                 ca_file: tls::TEST_PEM_CA_PATH.into(),
             }),
             None,
@@ -0,0 +1,570 @@
+use std::collections::BTreeMap;
+
+use chrono::{TimeZone, Utc};
+use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
+use futures::StreamExt;
+use lookup::{owned_value_path, path};
+use pulsar::{
+    consumer::Message,
+    message::proto::{MessageIdData, MessageMetadata},
+    Consumer, Error as PulsarError, SubType, TokioExecutor,
+};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::FramedRead;
+use vector_common::{
+    finalizer::UnorderedFinalizer,
+    internal_event::{
+        ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+        Registered,
+    },
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, LegacyKey, LogNamespace},
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{kind::Collection, Kind, Value};
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{SourceAcknowledgementsConfig, SourceConfig, SourceContext, SourceOutput},
+    event::{BatchNotifier, BatchStatus, Event, LogEvent},
+    internal_events::{
+        PulsarAcknowledgmentError, PulsarNegativeAcknowledgmentError, PulsarReadError,
+        StreamClosedError,
+    },
+    pulsar::{create_pulsar_client, PulsarAuthConfig},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+type Finalizer = UnorderedFinalizer<FinalizerEntry>;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Could not create Pulsar client: {}", source))]
+    CreateClient { source: PulsarError },
+    #[snafu(display("Could not subscribe to Pulsar topics: {}", source))]
+    Subscribe { source: PulsarError },
+}
+
+/// Configuration for the `pulsar` source.
+#[configurable_component(source("pulsar", "Collect logs from topics on Apache Pulsar."))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct PulsarSourceConfig {
+    /// The endpoint to which the Pulsar client should connect to.
+    ///
+    /// The endpoint should specify the pulsar protocol and port.
+    #[serde(alias = "address")]
+    #[derivative(Default(value = "default_endpoint()"))]
+    #[configurable(metadata(docs::examples = "pulsar://127.0.0.1:6650"))]
+    endpoint: String,
+
+    /// The Pulsar topics to read events from.
+    #[derivative(Default(value = "vec![\"topic-1234\".to_owned()]"))]
+    #[configurable(metadata(docs::examples = "topic-1234"))]
+    #[configurable(metadata(docs::examples = "persistent://public/default/topic-1234"))]
+    topics: Vec<String>,
+
+    /// The name of the consumer. If not specified, the default name assigned by Pulsar is used.
+    #[configurable(metadata(docs::examples = "consumer-name"))]
+    consumer_name: Option<String>,
+
+    /// The name of the subscription to consume the topics with.
+    #[serde(default = "default_subscription_name")]
+    #[derivative(Default(value = "default_subscription_name()"))]
+    #[configurable(metadata(docs::examples = "subscription-name"))]
+    subscription_name: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    subscription_type: PulsarSubscriptionType,
+
+    #[configurable(derived)]
+    auth: Option<PulsarAuthConfig>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+/// The type of the subscription, which determines how messages are delivered to the consumers
+/// sharing it.
+///
+/// See the [Pulsar documentation][subscription_types] for details.
+///
+/// [subscription_types]: https://pulsar.apache.org/docs/concepts-messaging/#subscription-types
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PulsarSubscriptionType {
+    /// Only a single consumer is allowed to attach to the subscription.
+    #[derivative(Default)]
+    Exclusive,
+
+    /// Messages are distributed round-robin across the consumers attached to the subscription.
+    Shared,
+
+    /// A single consumer receives the messages, and another one takes over if it disconnects.
+    Failover,
+
+    /// Messages are distributed across the consumers attached to the subscription, with messages
+    /// sharing a key always going to the same consumer.
+    KeyShared,
+}
+
+impl From<PulsarSubscriptionType> for SubType {
+    fn from(subscription_type: PulsarSubscriptionType) -> Self {
+        match subscription_type {
+            PulsarSubscriptionType::Exclusive => SubType::Exclusive,
+            PulsarSubscriptionType::Shared => SubType::Shared,
+            PulsarSubscriptionType::Failover => SubType::Failover,
+            PulsarSubscriptionType::KeyShared => SubType::KeyShared,
+        }
+    }
+}
+
+fn default_endpoint() -> String {
+    "pulsar://127.0.0.1:6650".to_owned()
+}
+
+fn default_subscription_name() -> String {
+    "vector".to_owned()
+}
+
+impl_generate_config_from_default!(PulsarSourceConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "pulsar")]
+impl SourceConfig for PulsarSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+
+        let consumer = self.create_consumer().await?;
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+
+        Ok(Box::pin(pulsar_source(
+            consumer,
+            decoder,
+            log_namespace,
+            acknowledgements,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                log_schema()
+                    .timestamp_key()
+                    .cloned()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp(),
+                Some("timestamp"),
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("topic"))),
+                &owned_value_path!("topic"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("message_key"))),
+                &owned_value_path!("message_key"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("producer_name"))),
+                &owned_value_path!("producer_name"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("properties"))),
+                &owned_value_path!("properties"),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())),
+                None,
+            );
+
+        vec![SourceOutput::new_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl PulsarSourceConfig {
+    async fn create_consumer(&self) -> crate::Result<Consumer<Vec<u8>, TokioExecutor>> {
+        let client = create_pulsar_client(&self.endpoint, self.auth.as_ref())
+            .await
+            .context(CreateClientSnafu)?;
+
+        let mut builder = client
+            .consumer()
+            .with_topics(self.topics.clone())
+            .with_subscription(&self.subscription_name)
+            .with_subscription_type(self.subscription_type.into());
+        if let Some(consumer_name) = &self.consumer_name {
+            builder = builder.with_consumer_name(consumer_name);
+        }
+
+        Ok(builder.build().await.context(SubscribeSnafu)?)
+    }
+}
+
+#[derive(Debug)]
+struct FinalizerEntry {
+    topic: String,
+    message_id: MessageIdData,
+}
+
+impl From<&Message<Vec<u8>>> for FinalizerEntry {
+    fn from(message: &Message<Vec<u8>>) -> Self {
+        Self {
+            topic: message.topic.clone(),
+            message_id: message.message_id().clone(),
+        }
+    }
+}
+
+async fn pulsar_source(
+    mut consumer: Consumer<Vec<u8>, TokioExecutor>,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    acknowledgements: bool,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let (finalizer, mut ack_stream) =
+        Finalizer::maybe_new(acknowledgements, Some(shutdown.clone()));
+    let bytes_received = register!(BytesReceived::from(Protocol::TCP));
+    let events_received = register!(EventsReceived);
+    let mut shutdown = shutdown.fuse();
+
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            entry = ack_stream.next() => if let Some((status, entry)) = entry {
+                handle_ack(&mut consumer, status, entry).await;
+            },
+            message = consumer.next() => match message {
+                Some(Ok(message)) => {
+                    bytes_received.emit(ByteSize(message.payload.data.len()));
+
+                    let (batch, receiver) =
+                        BatchNotifier::maybe_new_with_receiver(acknowledgements);
+                    let events =
+                        decode_message(&message, &decoder, log_namespace, &events_received)
+                            .await
+                            .into_iter()
+                            .map(|event| event.with_batch_notifier_option(&batch))
+                            .collect::<Vec<_>>();
+                    drop(batch);
+
+                    let count = events.len();
+                    if let Err(error) = out.send_batch(events).await {
+                        emit!(StreamClosedError { error, count });
+                        return Err(());
+                    }
+
+                    match (finalizer.as_ref(), receiver) {
+                        (Some(finalizer), Some(receiver)) => {
+                            finalizer.add((&message).into(), receiver)
+                        }
+                        _ => {
+                            let entry = (&message).into();
+                            handle_ack(&mut consumer, BatchStatus::Delivered, entry).await
+                        }
+                    }
+                }
+                Some(Err(error)) => emit!(PulsarReadError { error }),
+                None => break,
+            },
+        }
+    }
+
+    Ok(())
+}
+
+/// Acknowledges the message once its events are delivered, otherwise negatively acknowledges it so
+/// that Pulsar redelivers it.
+async fn handle_ack(
+    consumer: &mut Consumer<Vec<u8>, TokioExecutor>,
+    status: BatchStatus,
+    entry: FinalizerEntry,
+) {
+    match status {
+        BatchStatus::Delivered => {
+            if let Err(error) = consumer.ack_with_id(&entry.topic, entry.message_id).await {
+                emit!(PulsarAcknowledgmentError {
+                    error: error.into()
+                });
+            }
+        }
+        BatchStatus::Errored | BatchStatus::Rejected => {
+            if let Err(error) = consumer.nack_with_id(&entry.topic, entry.message_id).await {
+                emit!(PulsarNegativeAcknowledgmentError {
+                    error: error.into()
+                });
+            }
+        }
+    }
+}
+
+async fn decode_message(
+    message: &Message<Vec<u8>>,
+    decoder: &Decoder,
+    log_namespace: LogNamespace,
+    events_received: &Registered<EventsReceived>,
+) -> Vec<Event> {
+    let mut decoded = Vec::new();
+    let mut stream = FramedRead::new(message.payload.data.as_slice(), decoder.clone());
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((events, _byte_size)) => decoded.extend(events),
+            Err(error) => {
+                // Error is logged by `crate::codecs`, no further handling is needed here.
+                if !error.can_continue() {
+                    break;
+                }
+            }
+        }
+    }
+
+    events_received.emit(CountByteSize(
+        decoded.len(),
+        decoded.estimated_json_encoded_size_of(),
+    ));
+
+    let now = Utc::now();
+    for event in &mut decoded {
+        if let Event::Log(log) = event {
+            insert_metadata(
+                log,
+                &message.topic,
+                &message.payload.metadata,
+                log_namespace,
+            );
+            log_namespace.insert_standard_vector_source_metadata(
+                log,
+                PulsarSourceConfig::NAME,
+                now,
+            );
+        }
+    }
+    decoded
+}
+
+fn insert_metadata(
+    log: &mut LogEvent,
+    topic: &str,
+    metadata: &MessageMetadata,
+    log_namespace: LogNamespace,
+) {
+    if let Some(timestamp) = Utc
+        .timestamp_millis_opt(metadata.publish_time as i64)
+        .latest()
+    {
+        log_namespace.insert_source_metadata(
+            PulsarSourceConfig::NAME,
+            log,
+            log_schema().timestamp_key().map(LegacyKey::Overwrite),
+            path!("timestamp"),
+            timestamp,
+        );
+    }
+
+    log_namespace.insert_source_metadata(
+        PulsarSourceConfig::NAME,
+        log,
+        Some(LegacyKey::InsertIfEmpty(path!("topic"))),
+        path!("topic"),
+        topic,
+    );
+
+    if let Some(key) = &metadata.partition_key {
+        log_namespace.insert_source_metadata(
+            PulsarSourceConfig::NAME,
+            log,
+            Some(LegacyKey::InsertIfEmpty(path!("message_key"))),
+            path!("message_key"),
+            key.as_str(),
+        );
+    }
+
+    log_namespace.insert_source_metadata(
+        PulsarSourceConfig::NAME,
+        log,
+        Some(LegacyKey::InsertIfEmpty(path!("producer_name"))),
+        path!("producer_name"),
+        metadata.producer_name.as_str(),
+    );
+
+    let properties = metadata
+        .properties
+        .iter()
+        .map(|property| (property.key.clone(), Value::from(property.value.as_str())))
+        .collect::<BTreeMap<_, _>>();
+    log_namespace.insert_source_metadata(
+        PulsarSourceConfig::NAME,
+        log,
+        Some(LegacyKey::InsertIfEmpty(path!("properties"))),
+        path!("properties"),
+        properties,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use pulsar::message::proto::KeyValue;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<PulsarSourceConfig>();
+    }
+
+    fn metadata() -> MessageMetadata {
+        MessageMetadata {
+            producer_name: "producer".to_owned(),
+            publish_time: 1_684_000_000_123,
+            partition_key: Some("key".to_owned()),
+            properties: vec![KeyValue {
+                key: "foo".to_owned(),
+                value: "bar".to_owned(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn inserts_legacy_metadata() {
+        let mut log = LogEvent::from("hello");
+        insert_metadata(&mut log, "topic-1234", &metadata(), LogNamespace::Legacy);
+
+        assert_eq!(log["topic"], "topic-1234".into());
+        assert_eq!(log["message_key"], "key".into());
+        assert_eq!(log["producer_name"], "producer".into());
+        assert_eq!(log["properties.foo"], "bar".into());
+        assert_eq!(
+            log[log_schema().timestamp_key().unwrap().to_string()],
+            Value::Timestamp(Utc.timestamp_millis_opt(1_684_000_000_123).unwrap())
+        );
+    }
+
+    #[test]
+    fn inserts_vector_metadata() {
+        let mut log = LogEvent::from(Value::from("hello"));
+        insert_metadata(&mut log, "topic-1234", &metadata(), LogNamespace::Vector);
+
+        let meta = log.metadata().value();
+        assert_eq!(
+            meta.get(path!("pulsar", "topic")).unwrap(),
+            &Value::from("topic-1234")
+        );
+        assert_eq!(
+            meta.get(path!("pulsar", "properties", "foo")).unwrap(),
+            &Value::from("bar")
+        );
+        assert!(log.get("topic").is_none());
+    }
+}
+
+#[cfg(feature = "pulsar-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::test_util::{
+        collect_n,
+        components::{assert_source_compliance, SOURCE_TAGS},
+        random_string, trace_init,
+    };
+
+    fn pulsar_address() -> String {
+        std::env::var("PULSAR_ADDRESS").unwrap_or_else(|_| "pulsar://127.0.0.1:6650".into())
+    }
+
+    #[tokio::test]
+    async fn consumes_and_acknowledges_messages() {
+        trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let config = PulsarSourceConfig {
+            endpoint: pulsar_address(),
+            topics: vec![topic.clone()],
+            subscription_type: PulsarSubscriptionType::Shared,
+            acknowledgements: true.into(),
+            ..Default::default()
+        };
+
+        let events = assert_source_compliance(&SOURCE_TAGS, async move {
+            let (tx, rx) = SourceSender::new_test();
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+
+            let client = create_pulsar_client(&pulsar_address(), None).await.unwrap();
+            let mut producer = client.producer().with_topic(&topic).build().await.unwrap();
+            for line in ["one", "two", "three"] {
+                producer.send(line.to_owned()).await.unwrap().await.unwrap();
+            }
+
+            tokio::time::timeout(Duration::from_secs(10), collect_n(rx, 3))
+                .await
+                .expect("timed out waiting for messages")
+        })
+        .await;
+
+        let messages = events
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, ["one", "two", "three"]);
+        assert_eq!(
+            events[0].as_log()["topic"],
+            format!("persistent://public/default/{topic}").into()
+        );
+    }
+}
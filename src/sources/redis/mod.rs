@@ -13,7 +13,7 @@ use vector_common::internal_event::{
 };
 use vector_config::configurable_component;
 use vector_core::{
-    config::{LegacyKey, LogNamespace},
+    config::{LegacyKey, LogNamespace, SourceAcknowledgementsConfig},
     EstimatedJsonEncodedSizeOf,
 };
 use vrl::value::Kind;
@@ -21,13 +21,14 @@ use vrl::value::Kind;
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{log_schema, GenerateConfig, SourceConfig, SourceContext, SourceOutput},
-    event::Event,
+    event::{BatchNotifier, Event},
     internal_events::{EventsReceived, StreamClosedError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
 };
 
 mod channel;
 mod list;
+mod stream;
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -49,6 +50,11 @@ pub enum DataTypeConfig {
     ///
     /// This is based on Redis' Pub/Sub capabilities.
     Channel,
+
+    /// The `stream` data type.
+    ///
+    /// Entries are read through a consumer group, and acknowledged once processed.
+    Stream,
 }
 
 /// Options for the Redis `list` data type.
@@ -74,6 +80,69 @@ pub enum Method {
     Rpop,
 }
 
+/// Options for the Redis `stream` data type.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The consumer group to read the stream with.
+    ///
+    /// The group, and the stream, are created if they do not exist.
+    #[serde(default = "default_stream_group")]
+    #[derivative(Default(value = "default_stream_group()"))]
+    #[configurable(metadata(docs::examples = "vector"))]
+    group: String,
+
+    /// The name of the consumer within the group.
+    ///
+    /// Entries delivered to a consumer but never acknowledged are read again when it restarts, so
+    /// the name must be stable across restarts.
+    #[serde(default = "default_stream_consumer")]
+    #[derivative(Default(value = "default_stream_consumer()"))]
+    #[configurable(metadata(docs::examples = "vector-0"))]
+    consumer: String,
+
+    /// The field of the stream entries holding the message to decode.
+    #[serde(default = "default_stream_field")]
+    #[derivative(Default(value = "default_stream_field()"))]
+    #[configurable(metadata(docs::examples = "message"))]
+    field: String,
+
+    /// The ID of the entry the consumer group starts reading after, when it is created.
+    ///
+    /// `$` reads only the entries added from then on, while `0` reads the whole stream.
+    #[serde(default = "default_stream_start_id")]
+    #[derivative(Default(value = "default_stream_start_id()"))]
+    #[configurable(metadata(docs::examples = "$", docs::examples = "0"))]
+    start_id: String,
+
+    /// The maximum number of entries to read at once.
+    #[serde(default = "default_stream_batch_size")]
+    #[derivative(Default(value = "default_stream_batch_size()"))]
+    batch_size: usize,
+}
+
+fn default_stream_group() -> String {
+    "vector".to_owned()
+}
+
+fn default_stream_consumer() -> String {
+    "vector".to_owned()
+}
+
+fn default_stream_field() -> String {
+    "message".to_owned()
+}
+
+fn default_stream_start_id() -> String {
+    "$".to_owned()
+}
+
+const fn default_stream_batch_size() -> usize {
+    100
+}
+
 pub struct ConnectionInfo {
     protocol: &'static str,
     endpoint: String,
@@ -98,13 +167,16 @@ impl From<&redis::ConnectionInfo> for ConnectionInfo {
 #[derive(Clone, Debug, Derivative)]
 #[serde(deny_unknown_fields)]
 pub struct RedisSourceConfig {
-    /// The Redis data type (`list` or `channel`) to use.
+    /// The Redis data type (`list`, `channel` or `stream`) to use.
     #[serde(default)]
     data_type: DataTypeConfig,
 
     #[configurable(derived)]
     list: Option<ListOption>,
 
+    #[configurable(derived)]
+    stream: Option<StreamOption>,
+
     /// The Redis URL to connect to.
     ///
     /// The URL must take the form of `protocol://server:port/db` where the `protocol` can either be `redis` or `rediss` for connections secured using TLS.
@@ -137,6 +209,10 @@ pub struct RedisSourceConfig {
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
     log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
 }
 
 impl GenerateConfig for RedisSourceConfig {
@@ -159,6 +235,7 @@ impl GenerateConfig for RedisSourceConfig {
 impl SourceConfig for RedisSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
 
         // A key must be specified to actually query i.e. the list to pop from, or the channel to subscribe to.
         if self.key.is_empty() {
@@ -192,6 +269,10 @@ impl SourceConfig for RedisSourceConfig {
                 handler.watch(method).await
             }
             DataTypeConfig::Channel => handler.subscribe(connection_info).await,
+            DataTypeConfig::Stream => {
+                let options = self.stream.clone().unwrap_or_default();
+                handler.consume(options, acknowledgements).await
+            }
         }
     }
 
@@ -223,7 +304,7 @@ impl SourceConfig for RedisSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        matches!(self.data_type, DataTypeConfig::Stream)
     }
 }
 
@@ -240,11 +321,19 @@ pub(self) struct InputHandler {
 
 impl InputHandler {
     async fn handle_line(&mut self, line: String) -> Result<(), ()> {
+        self.handle_message(line.as_bytes(), &None).await
+    }
+
+    async fn handle_message(
+        &mut self,
+        message: &[u8],
+        batch: &Option<BatchNotifier>,
+    ) -> Result<(), ()> {
         let now = Utc::now();
 
-        self.bytes_received.emit(ByteSize(line.len()));
+        self.bytes_received.emit(ByteSize(message.len()));
 
-        let mut stream = FramedRead::new(line.as_ref(), self.decoder.clone());
+        let mut stream = FramedRead::new(message, self.decoder.clone());
         while let Some(next) = stream.next().await {
             match next {
                 Ok((events, _byte_size)) => {
@@ -276,7 +365,7 @@ impl InputHandler {
                             );
                         };
 
-                        event
+                        event.with_batch_notifier_option(batch)
                     });
 
                     if let Err(error) = self.cx.out.send_batch(events).await {
@@ -314,6 +403,7 @@ mod integration_test {
     use super::*;
     use crate::{
         config::log_schema,
+        event::EventStatus,
         test_util::{
             collect_n,
             components::{run_and_assert_source_compliance_n, SOURCE_TAGS},
@@ -344,12 +434,14 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Rpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace: Some(false),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
@@ -376,12 +468,14 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Rpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: Some(OptionalValuePath::from(owned_value_path!("remapped_key"))),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace: Some(true),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 1, &SOURCE_TAGS).await;
@@ -417,12 +511,14 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Lpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace: Some(false),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
@@ -441,12 +537,14 @@ mod integration_test {
         let config = RedisSourceConfig {
             data_type: DataTypeConfig::Channel,
             list: None,
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace: Some(false),
+            acknowledgements: Default::default(),
         };
 
         let (tx, rx) = SourceSender::new_test();
@@ -487,4 +585,63 @@ mod integration_test {
             );
         }
     }
+
+    #[tokio::test]
+    async fn redis_source_stream_consume_and_acknowledge() {
+        // Add some entries to a stream, before the consumer group reading it exists.
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_tokio_connection_manager().await.unwrap();
+
+        let key = format!("test-stream-{}", random_string(10));
+        debug!("Test key name: {}.", key);
+
+        for i in 1..=3 {
+            let _: String = conn
+                .xadd(&key, "*", &[("message", i.to_string())])
+                .await
+                .unwrap();
+        }
+
+        let config = RedisSourceConfig {
+            data_type: DataTypeConfig::Stream,
+            list: None,
+            stream: Some(StreamOption {
+                start_id: "0".to_owned(),
+                ..Default::default()
+            }),
+            url: REDIS_SERVER.to_owned(),
+            key: key.clone(),
+            redis_key: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            log_namespace: Some(false),
+            acknowledgements: true.into(),
+        };
+
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let context = SourceContext::new_test(tx, None);
+        let source = config
+            .build(context)
+            .await
+            .expect("source should not fail to build");
+        tokio::spawn(source);
+
+        let events = collect_n(rx, 3).await;
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "1".into());
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "2".into());
+        assert_eq!(events[2].as_log()[log_schema().message_key()], "3".into());
+
+        // Wait for the entries to be acknowledged.
+        let mut pending = usize::MAX;
+        for _ in 0..50 {
+            let reply: redis::streams::StreamPendingReply =
+                conn.xpending(&key, "vector").await.unwrap();
+            pending = reply.count();
+            if pending == 0 {
+                break;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+        assert_eq!(pending, 0);
+    }
 }
@@ -0,0 +1,193 @@
+use futures::{stream::BoxStream, StreamExt};
+use redis::{
+    aio::ConnectionManager,
+    streams::{StreamId, StreamReadOptions, StreamReadReply},
+    AsyncCommands, ErrorKind, RedisError, RedisResult,
+};
+use snafu::{ResultExt, Snafu};
+use vector_common::finalizer::UnorderedFinalizer;
+
+use super::{list::backoff_exponential, InputHandler, StreamOption};
+use crate::{
+    event::{BatchNotifier, BatchStatus},
+    internal_events::{
+        RedisAcknowledgementError, RedisReceiveEventError, RedisStreamMissingFieldError,
+    },
+    sources::Source,
+};
+
+/// How long a read waits for new entries before being issued again.
+const READ_BLOCK_MS: usize = 1000;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Failed to create connection: {}", source))]
+    Connection { source: RedisError },
+    #[snafu(display("Failed to create consumer group: {}", source))]
+    CreateGroup { source: RedisError },
+}
+
+impl InputHandler {
+    pub(super) async fn consume(
+        mut self,
+        options: StreamOption,
+        acknowledgements: bool,
+    ) -> crate::Result<Source> {
+        let mut conn = self
+            .client
+            .get_tokio_connection_manager()
+            .await
+            .context(ConnectionSnafu {})?;
+
+        create_group(&mut conn, &self.key, &options)
+            .await
+            .context(CreateGroupSnafu {})?;
+
+        let (finalizer, ack_stream) = UnorderedFinalizer::<Vec<String>>::maybe_new(
+            acknowledgements,
+            Some(self.cx.shutdown.clone()),
+        );
+        if finalizer.is_some() {
+            // Acknowledgements go through their own connection, so they are not queued behind a
+            // blocking read.
+            let ack_conn = self
+                .client
+                .get_tokio_connection_manager()
+                .await
+                .context(ConnectionSnafu {})?;
+            tokio::spawn(handle_acks(
+                ack_conn,
+                self.key.clone(),
+                options.group.clone(),
+                ack_stream,
+            ));
+        }
+
+        Ok(Box::pin(async move {
+            let mut shutdown = self.cx.shutdown.clone();
+            let mut retry: u32 = 0;
+
+            // Entries delivered to this consumer by a previous run, but never acknowledged, are
+            // read first. Once they are exhausted, only new entries are read.
+            let mut pending_cursor = Some("0".to_owned());
+            loop {
+                let id = pending_cursor.as_deref().unwrap_or(">");
+                let res = tokio::select! {
+                    res = read_group(&mut conn, &self.key, &options, id) => res,
+                    _ = &mut shutdown => break
+                };
+
+                let entries = match res {
+                    Err(error) => {
+                        let kind = error.kind();
+
+                        emit!(RedisReceiveEventError::from(error));
+
+                        if kind == ErrorKind::IoError {
+                            retry += 1;
+                            backoff_exponential(retry).await
+                        }
+                        continue;
+                    }
+                    Ok(entries) => entries,
+                };
+                retry = 0;
+
+                if pending_cursor.is_some() {
+                    pending_cursor = entries.last().map(|entry| entry.id.clone());
+                }
+                if entries.is_empty() {
+                    continue;
+                }
+
+                let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(finalizer.is_some());
+                let ids = match self.handle_entries(entries, &options.field, &batch).await {
+                    Ok(ids) => ids,
+                    Err(()) => break,
+                };
+
+                match (finalizer.as_ref(), receiver) {
+                    (Some(finalizer), Some(receiver)) => finalizer.add(ids, receiver),
+                    _ => acknowledge(&mut conn, &self.key, &options.group, &ids).await,
+                }
+            }
+            Ok(())
+        }))
+    }
+
+    /// Forwards the message of each entry, returning the IDs of the entries handled.
+    async fn handle_entries(
+        &mut self,
+        entries: Vec<StreamId>,
+        field: &str,
+        batch: &Option<BatchNotifier>,
+    ) -> Result<Vec<String>, ()> {
+        let mut ids = Vec::with_capacity(entries.len());
+        for entry in entries {
+            match entry.get::<Vec<u8>>(field) {
+                Some(message) => self.handle_message(&message, batch).await?,
+                // The entry is still acknowledged, as reading it again would not make it valid.
+                None => emit!(RedisStreamMissingFieldError {
+                    field,
+                    id: &entry.id
+                }),
+            }
+            ids.push(entry.id);
+        }
+        Ok(ids)
+    }
+}
+
+async fn create_group(
+    conn: &mut ConnectionManager,
+    key: &str,
+    options: &StreamOption,
+) -> RedisResult<()> {
+    match conn
+        .xgroup_create_mkstream(key, &options.group, &options.start_id)
+        .await
+    {
+        // The group already exists, and keeps its position in the stream.
+        Err(error) if error.code() == Some("BUSYGROUP") => Ok(()),
+        res => res,
+    }
+}
+
+async fn read_group(
+    conn: &mut ConnectionManager,
+    key: &str,
+    options: &StreamOption,
+    id: &str,
+) -> RedisResult<Vec<StreamId>> {
+    let read_options = StreamReadOptions::default()
+        .group(&options.group, &options.consumer)
+        .count(options.batch_size)
+        .block(READ_BLOCK_MS);
+
+    conn.xread_options(&[key], &[id], &read_options)
+        .await
+        .map(|reply: StreamReadReply| reply.keys.into_iter().flat_map(|key| key.ids).collect())
+}
+
+async fn handle_acks(
+    mut conn: ConnectionManager,
+    key: String,
+    group: String,
+    mut ack_stream: BoxStream<'static, (BatchStatus, Vec<String>)>,
+) {
+    while let Some((status, ids)) = ack_stream.next().await {
+        match status {
+            BatchStatus::Delivered => acknowledge(&mut conn, &key, &group, &ids).await,
+            // Errored entries are left pending, and are read again when the consumer restarts.
+            BatchStatus::Errored => {}
+            // Reading rejected entries again would only get them rejected again.
+            BatchStatus::Rejected => acknowledge(&mut conn, &key, &group, &ids).await,
+        }
+    }
+}
+
+async fn acknowledge(conn: &mut ConnectionManager, key: &str, group: &str, ids: &[String]) {
+    if let Err(error) = conn.xack::<_, _, _, i64>(key, group, ids).await {
+        emit!(RedisAcknowledgementError { error });
+    }
+}
@@ -35,6 +35,38 @@ use super::{
     error::ErrorMessage,
 };
 
+/// The outcome of [`HttpSource::verify_request`], undone when it is dropped unless the request is
+/// handled successfully.
+///
+/// This lets a request that failed to be handled be retried, even if its verification has side
+/// effects such as recording its signature to reject replays.
+#[derive(Default)]
+pub struct VerifiedRequest {
+    undo: Option<Box<dyn FnOnce() + Send>>,
+}
+
+impl VerifiedRequest {
+    /// Creates the outcome of a verification that is undone by calling `undo`.
+    pub fn undo_with(undo: impl FnOnce() + Send + 'static) -> Self {
+        Self {
+            undo: Some(Box::new(undo)),
+        }
+    }
+
+    /// Keeps the effects of the verification, as the request was handled successfully.
+    fn keep(mut self) {
+        self.undo = None;
+    }
+}
+
+impl Drop for VerifiedRequest {
+    fn drop(&mut self) {
+        if let Some(undo) = self.undo.take() {
+            undo();
+        }
+    }
+}
+
 #[async_trait]
 pub trait HttpSource: Clone + Send + Sync + 'static {
     // This function can be defined to enrich events with additional HTTP
@@ -49,6 +81,20 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
     ) {
     }
 
+    /// Checks a request before its body is decoded, for instance by verifying its signature.
+    ///
+    /// The body is the one received, before it is decompressed. The returned [`VerifiedRequest`]
+    /// is undone if the request isn't handled successfully.
+    fn verify_request(
+        &self,
+        _path: &str,
+        _query: &str,
+        _headers: &HeaderMap,
+        _body: &Bytes,
+    ) -> Result<VerifiedRequest, ErrorMessage> {
+        Ok(VerifiedRequest::default())
+    }
+
     fn build_events(
         &self,
         body: Bytes,
@@ -112,13 +158,15 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                 .and(warp::header::headers_cloned())
                 .and(warp::body::bytes())
                 .and(warp::query::<HashMap<String, String>>())
+                .and(warp::query::raw().or(warp::any().map(String::new)).unify())
                 .and_then(
                     move |path: FullPath,
                           auth_header,
                           encoding_header,
                           headers: HeaderMap,
                           body: Bytes,
-                          query_parameters: HashMap<String, String>| {
+                          query_parameters: HashMap<String, String>,
+                          raw_query: String| {
                         debug!(message = "Handling HTTP request.", headers = ?headers);
                         let http_path = path.as_str();
 
@@ -130,11 +178,22 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
 
                         let events = auth
                             .is_valid(&auth_header)
-                            .and_then(|()| decode(&encoding_header, body))
-                            .and_then(|body| {
-                                self.build_events(body, &headers, &query_parameters, path.as_str())
+                            .and_then(|()| {
+                                self.verify_request(http_path, &raw_query, &headers, &body)
                             })
-                            .map(|mut events| {
+                            .and_then(|verified| {
+                                decode(&encoding_header, body)
+                                    .and_then(|body| {
+                                        self.build_events(
+                                            body,
+                                            &headers,
+                                            &query_parameters,
+                                            path.as_str(),
+                                        )
+                                    })
+                                    .map(|events| (events, verified))
+                            })
+                            .map(|(mut events, verified)| {
                                 emit!(HttpEventsReceived {
                                     count: events.len(),
                                     byte_size: events.estimated_json_encoded_size_of(),
@@ -149,7 +208,7 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                                     &query_parameters,
                                 );
 
-                                events
+                                (events, verified)
                             });
 
                         handle_request(events, acknowledgements, cx.out.clone())
@@ -203,16 +262,17 @@ impl fmt::Debug for RejectShuttingDown {
 impl warp::reject::Reject for RejectShuttingDown {}
 
 async fn handle_request(
-    events: Result<Vec<Event>, ErrorMessage>,
+    events: Result<(Vec<Event>, VerifiedRequest), ErrorMessage>,
     acknowledgements: bool,
     mut out: SourceSender,
 ) -> Result<impl warp::Reply, Rejection> {
     match events {
-        Ok(mut events) => {
+        Ok((mut events, verified)) => {
             let receiver = BatchNotifier::maybe_apply_to(acknowledgements, &mut events);
 
             let count = events.len();
-            out.send_batch(events)
+            let reply = out
+                .send_batch(events)
                 .map_err(move |error: crate::source_sender::ClosedError| {
                     // can only fail if receiving end disconnected, so we are shutting down,
                     // probably not gracefully.
@@ -220,7 +280,11 @@ async fn handle_request(
                     warp::reject::custom(RejectShuttingDown)
                 })
                 .and_then(|_| handle_batch_status(receiver))
-                .await
+                .await;
+            if reply.is_ok() {
+                verified.keep();
+            }
+            reply
         }
         Err(error) => {
             emit!(HttpBadRequest::new(error.code(), error.message()));
@@ -57,10 +57,10 @@ pub use self::http::add_query_parameters;
 pub use self::http::decode;
 #[cfg(feature = "sources-utils-http-error")]
 pub use self::http::ErrorMessage;
-#[cfg(feature = "sources-utils-http-prelude")]
-pub use self::http::HttpSource;
 #[cfg(feature = "sources-utils-http-auth")]
 pub use self::http::HttpSourceAuthConfig;
+#[cfg(feature = "sources-utils-http-prelude")]
+pub use self::http::{HttpSource, VerifiedRequest};
 #[cfg(any(feature = "sources-aws_sqs", feature = "sources-gcp_pubsub"))]
 pub use self::message_decoding::decode_message;
 
@@ -0,0 +1,355 @@
+use std::{
+    io,
+    num::NonZeroU64,
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use futures::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    tungstenite::{error::Error as WsError, Message},
+    WebSocketStream,
+};
+use vector_common::internal_event::error_stage;
+use vector_config::configurable_component;
+
+use super::MessageHandler;
+use crate::{
+    common::websocket::{is_closed, PingInterval, WebSocketConnector},
+    config::SourceContext,
+    http::Auth,
+    internal_events::{ConnectionOpen, OpenGauge, WsConnectionError, WsConnectionShutdown},
+    shutdown::ShutdownSignal,
+    sources::Source,
+    tls::{MaybeTlsSettings, MaybeTlsStream, TlsEnableableConfig},
+    SourceSender,
+};
+
+const PING: &[u8] = b"PING";
+
+/// Client configuration for the `websocket` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ClientConfig {
+    /// The WebSocket URI to connect to.
+    ///
+    /// This should include the protocol and host, but can also include the port, path, and any
+    /// other valid part of a URI.
+    #[configurable(metadata(docs::examples = "wss://stream.example.com/events"))]
+    uri: String,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    auth: Option<Auth>,
+
+    /// Text messages to send to the server each time a connection is established.
+    ///
+    /// Streaming APIs often only start sending data once a subscription request is received.
+    #[configurable(metadata(docs::examples = "example_initial_messages()"))]
+    #[serde(default)]
+    initial_messages: Vec<String>,
+
+    /// The interval, in seconds, between sending [Ping][ping]s to the remote peer.
+    ///
+    /// If this option is not configured, pings are not sent on an interval.
+    ///
+    /// If the `ping_timeout` is not set, pings are still sent but there is no expectation of pong
+    /// response times.
+    ///
+    /// [ping]: https://www.rfc-editor.org/rfc/rfc6455#section-5.5.2
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    ping_interval: Option<NonZeroU64>,
+
+    /// The number of seconds to wait for a [Pong][pong] response from the remote peer.
+    ///
+    /// If a response is not received within this time, the connection is re-established.
+    ///
+    /// [pong]: https://www.rfc-editor.org/rfc/rfc6455#section-5.5.3
+    // NOTE: this option is not relevant if the `ping_interval` is not configured.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    ping_timeout: Option<NonZeroU64>,
+}
+
+fn example_initial_messages() -> Vec<String> {
+    vec![r#"{"type": "subscribe", "channel": "events"}"#.to_string()]
+}
+
+impl ClientConfig {
+    pub(super) fn build(
+        &self,
+        handler: MessageHandler,
+        cx: SourceContext,
+    ) -> crate::Result<Source> {
+        if let Some(Auth::OAuth2(_)) = self.auth {
+            return Err("The `oauth2` authentication strategy is not supported".into());
+        }
+
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let connector = WebSocketConnector::new(self.uri.clone(), tls, self.auth.clone())?;
+
+        Ok(Box::pin(self.clone().run_client(
+            connector,
+            handler,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    async fn run_client(
+        self,
+        connector: WebSocketConnector,
+        handler: MessageHandler,
+        mut shutdown: ShutdownSignal,
+        mut out: SourceSender,
+    ) -> Result<(), ()> {
+        loop {
+            let ws_stream = tokio::select! {
+                ws_stream = connector.connect_backoff(error_stage::RECEIVING) => ws_stream,
+                _ = &mut shutdown => break,
+            };
+
+            let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
+
+            if self
+                .handle_connection(
+                    ws_stream,
+                    connector.host(),
+                    &handler,
+                    &mut shutdown,
+                    &mut out,
+                )
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Receives messages until the connection is lost.
+    ///
+    /// Returns an error if the source should stop instead of reconnecting.
+    async fn handle_connection(
+        &self,
+        mut ws_stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+        host: &str,
+        handler: &MessageHandler,
+        shutdown: &mut ShutdownSignal,
+        out: &mut SourceSender,
+    ) -> Result<(), ()> {
+        for message in &self.initial_messages {
+            if let Err(error) = ws_stream.send(Message::text(message.as_str())).await {
+                emit!(WsConnectionError {
+                    error,
+                    stage: error_stage::RECEIVING,
+                });
+                return Ok(());
+            }
+        }
+
+        // tokio::time::Interval panics if the period arg is zero. Since the struct members are
+        // using NonZeroU64 that is not something we need to account for.
+        let mut ping_interval = PingInterval::new(self.ping_interval.map(u64::from));
+        let mut last_pong = Instant::now();
+
+        loop {
+            let result = tokio::select! {
+                _ = ping_interval.tick() => match self.check_received_pong_time(last_pong) {
+                    Ok(()) => ws_stream.send(Message::Ping(PING.to_vec())).await,
+                    Err(error) => Err(error),
+                },
+
+                message = ws_stream.next() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        handler.handle(Bytes::from(text), host, out).await?;
+                        Ok(())
+                    }
+                    Some(Ok(Message::Binary(data))) => {
+                        handler.handle(Bytes::from(data), host, out).await?;
+                        Ok(())
+                    }
+                    Some(Ok(Message::Pong(_))) => {
+                        last_pong = Instant::now();
+                        Ok(())
+                    }
+                    // Pongs are sent automatically by tungstenite during reading from the stream.
+                    Some(Ok(_)) => Ok(()),
+                    Some(Err(error)) => Err(error),
+                    None => Err(WsError::ConnectionClosed),
+                },
+
+                _ = &mut *shutdown => {
+                    _ = ws_stream.close(None).await;
+                    return Err(());
+                }
+            };
+
+            if let Err(error) = result {
+                if is_closed(&error) {
+                    emit!(WsConnectionShutdown);
+                } else {
+                    emit!(WsConnectionError {
+                        error,
+                        stage: error_stage::RECEIVING,
+                    });
+                }
+                return Ok(());
+            }
+        }
+    }
+
+    fn check_received_pong_time(&self, last_pong: Instant) -> Result<(), WsError> {
+        if let Some(ping_timeout) = self.ping_timeout {
+            if last_pong.elapsed() > Duration::from_secs(ping_timeout.into()) {
+                return Err(WsError::Io(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "Pong not received in time",
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use futures::Stream;
+    use tokio::{net::TcpListener, sync::oneshot, time::timeout};
+    use tokio_tungstenite::accept_async;
+    use vector_core::config::log_schema;
+
+    use super::*;
+    use crate::{
+        config::SourceConfig,
+        event::Event,
+        sources::websocket::WebSocketSourceConfig,
+        test_util::{collect_n, next_addr},
+    };
+
+    fn client_config(config: &str) -> ClientConfig {
+        toml::from_str(&format!("uri = \"ws://localhost\"\n{}", config)).unwrap()
+    }
+
+    async fn start_client(addr: SocketAddr, config: &str) -> impl Stream<Item = Event> {
+        let config = toml::from_str::<WebSocketSourceConfig>(&format!(
+            r#"
+            mode = "client"
+            uri = "ws://{}"
+            {}
+        "#,
+            addr, config
+        ))
+        .unwrap();
+
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        rx
+    }
+
+    #[test]
+    fn checks_pong_time_against_timeout() {
+        let stale = Instant::now().checked_sub(Duration::from_secs(2)).unwrap();
+
+        let config = client_config("ping_timeout = 1");
+        assert!(config.check_received_pong_time(Instant::now()).is_ok());
+        assert!(matches!(
+            config.check_received_pong_time(stale),
+            Err(WsError::Io(error)) if error.kind() == io::ErrorKind::TimedOut
+        ));
+
+        // Without a timeout, pongs are never late.
+        assert!(client_config("").check_received_pong_time(stale).is_ok());
+    }
+
+    #[tokio::test]
+    async fn reconnects_and_resubscribes_after_connection_is_closed() {
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        tokio::spawn(async move {
+            for text in ["first", "second"] {
+                let (socket, _) = listener.accept().await.unwrap();
+                let mut ws_stream = accept_async(socket).await.unwrap();
+
+                let subscription = ws_stream.next().await.unwrap().unwrap();
+                assert_eq!(subscription, Message::text("subscribe"));
+
+                ws_stream.send(Message::text(text)).await.unwrap();
+                ws_stream.close(None).await.unwrap();
+            }
+        });
+
+        let rx = start_client(addr, r#"initial_messages = ["subscribe"]"#).await;
+        let events = timeout(Duration::from_secs(10), collect_n(rx, 2))
+            .await
+            .expect("client should have reconnected");
+
+        let messages = events
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, ["first", "second"]);
+    }
+
+    #[tokio::test]
+    async fn sends_pings_on_interval() {
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let (tx, rx) = oneshot::channel();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws_stream = accept_async(socket).await.unwrap();
+
+            while let Some(Ok(message)) = ws_stream.next().await {
+                if let Message::Ping(payload) = message {
+                    _ = tx.send(payload);
+                    break;
+                }
+            }
+        });
+
+        let _events = start_client(addr, "ping_interval = 1").await;
+        let payload = timeout(Duration::from_secs(5), rx)
+            .await
+            .expect("client should have sent a ping")
+            .unwrap();
+
+        assert_eq!(payload, PING);
+    }
+
+    #[tokio::test]
+    async fn reconnects_when_pong_is_late() {
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let (tx, rx) = oneshot::channel();
+        tokio::spawn(async move {
+            // The server only answers pings while reading from the connection, which this one
+            // never does.
+            let (socket, _) = listener.accept().await.unwrap();
+            let _unresponsive = accept_async(socket).await.unwrap();
+
+            let (socket, _) = listener.accept().await.unwrap();
+            let _ws_stream = accept_async(socket).await.unwrap();
+            _ = tx.send(());
+        });
+
+        let _events = start_client(addr, "ping_interval = 1\nping_timeout = 1").await;
+
+        timeout(Duration::from_secs(10), rx)
+            .await
+            .expect("client should have reconnected")
+            .unwrap();
+    }
+}
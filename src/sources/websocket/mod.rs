@@ -0,0 +1,352 @@
+mod client;
+mod server;
+
+use bytes::Bytes;
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
+use futures::StreamExt;
+use lookup::{lookup_v2::OptionalValuePath, owned_value_path, path};
+use tokio_util::codec::FramedRead;
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+    Registered,
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, LegacyKey, LogNamespace},
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::Kind;
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{GenerateConfig, Resource, SourceConfig, SourceContext, SourceOutput},
+    event::Event,
+    internal_events::StreamClosedError,
+    serde::{default_decoding, default_framing_message_based},
+    SourceSender,
+};
+
+/// Configuration for the `websocket` source.
+#[configurable_component(source(
+    "websocket",
+    "Collect events from WebSocket connections, as a server or as a client."
+))]
+#[derive(Clone, Debug)]
+pub struct WebSocketSourceConfig {
+    #[serde(flatten)]
+    pub mode: Mode,
+
+    /// Overrides the name of the log field used to add the peer host to each event.
+    ///
+    /// In `server` mode, the value is the IP address of the client. In `client` mode, the value
+    /// is the host of the `uri`.
+    ///
+    /// By default, the [global `log_schema.host_key` option][global_host_key] is used.
+    ///
+    /// Set to `""` to suppress this key.
+    ///
+    /// [global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
+    #[serde(default = "default_host_key")]
+    pub host_key: OptionalValuePath,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    pub decoding: DeserializerConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    pub log_namespace: Option<bool>,
+}
+
+/// Connection mode for the `websocket` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+#[configurable(metadata(
+    docs::enum_tag_description = "Whether to accept connections or to connect to a remote server."
+))]
+pub enum Mode {
+    /// Accept connections from WebSocket clients, such as browsers.
+    Server(server::ServerConfig),
+
+    /// Connect to a remote WebSocket server, reconnecting whenever the connection is lost.
+    Client(client::ClientConfig),
+}
+
+fn default_host_key() -> OptionalValuePath {
+    OptionalValuePath::from(owned_value_path!(log_schema().host_key()))
+}
+
+impl GenerateConfig for WebSocketSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"mode = "server"
+            address = "0.0.0.0:8080""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "websocket")]
+impl SourceConfig for WebSocketSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace).build();
+        let handler = MessageHandler::new(decoder, self.host_key.clone(), log_namespace);
+
+        match &self.mode {
+            Mode::Server(config) => config.build(handler, cx),
+            Mode::Client(config) => config.build(handler, cx),
+        }
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let legacy_host_key = self.host_key.clone().path.map(LegacyKey::InsertIfEmpty);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                legacy_host_key,
+                &owned_value_path!("host"),
+                Kind::bytes(),
+                Some("host"),
+            );
+
+        vec![SourceOutput::new_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        match &self.mode {
+            Mode::Server(config) => vec![Resource::tcp(config.address)],
+            Mode::Client(_) => vec![],
+        }
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// Decodes the payloads of WebSocket messages into events, and forwards them.
+///
+/// Each text or binary message is decoded on its own, so a frame never spans multiple messages.
+#[derive(Clone)]
+struct MessageHandler {
+    decoder: Decoder,
+    host_key: OptionalValuePath,
+    log_namespace: LogNamespace,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+}
+
+impl MessageHandler {
+    fn new(decoder: Decoder, host_key: OptionalValuePath, log_namespace: LogNamespace) -> Self {
+        Self {
+            decoder,
+            host_key,
+            log_namespace,
+            bytes_received: register!(BytesReceived::from(Protocol("websocket".into()))),
+            events_received: register!(EventsReceived),
+        }
+    }
+
+    /// Decodes the payload of a message received from `host`.
+    ///
+    /// Returns an error if the events could not be forwarded because the source is shutting down.
+    async fn handle(&self, payload: Bytes, host: &str, out: &mut SourceSender) -> Result<(), ()> {
+        self.bytes_received.emit(ByteSize(payload.len()));
+
+        let mut stream = FramedRead::new(payload.as_ref(), self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            let mut events = match next {
+                Ok((events, _byte_size)) => events,
+                Err(error) => {
+                    // Error is logged by `crate::codecs`, no further handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                    continue;
+                }
+            };
+
+            let count = events.len();
+            self.events_received.emit(CountByteSize(
+                count,
+                events.estimated_json_encoded_size_of(),
+            ));
+
+            let now = Utc::now();
+            for event in &mut events {
+                if let Event::Log(log) = event {
+                    self.log_namespace.insert_standard_vector_source_metadata(
+                        log,
+                        WebSocketSourceConfig::NAME,
+                        now,
+                    );
+                    self.log_namespace.insert_source_metadata(
+                        WebSocketSourceConfig::NAME,
+                        log,
+                        self.host_key.path.as_ref().map(LegacyKey::InsertIfEmpty),
+                        path!("host"),
+                        host.to_owned(),
+                    );
+                }
+            }
+
+            if let Err(error) = out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                return Err(());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use futures::{SinkExt, Stream};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::{
+        accept_async, connect_async,
+        tungstenite::{client::IntoClientRequest, Error as WsError, Message},
+    };
+
+    use super::*;
+    use crate::test_util::{
+        collect_n,
+        components::{assert_source_compliance, run_and_assert_source_compliance_n, SOURCE_TAGS},
+        next_addr, wait_for_tcp,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebSocketSourceConfig>();
+    }
+
+    fn message(event: &Event) -> String {
+        event.as_log()[log_schema().message_key()]
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    async fn start_server(config: &str) -> (SocketAddr, impl Stream<Item = Event>) {
+        let addr = next_addr();
+        let config = toml::from_str::<WebSocketSourceConfig>(&format!(
+            r#"
+            mode = "server"
+            address = "{}"
+            {}
+        "#,
+            addr, config
+        ))
+        .unwrap();
+
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(addr).await;
+
+        (addr, rx)
+    }
+
+    #[tokio::test]
+    async fn server_decodes_text_and_binary_messages() {
+        let events = assert_source_compliance(&SOURCE_TAGS, async {
+            let (addr, rx) = start_server("").await;
+
+            let (mut ws_stream, _) = connect_async(format!("ws://{}", addr)).await.unwrap();
+            ws_stream.send(Message::text("hello")).await.unwrap();
+            ws_stream
+                .send(Message::binary(b"world".to_vec()))
+                .await
+                .unwrap();
+
+            collect_n(rx, 2).await
+        })
+        .await;
+
+        assert_eq!(message(&events[0]), "hello");
+        assert_eq!(message(&events[1]), "world");
+        assert_eq!(
+            events[0].as_log()[log_schema().host_key()],
+            "127.0.0.1".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn server_rejects_unauthenticated_clients() {
+        let (addr, _rx) = start_server(
+            r#"
+            auth.username = "user"
+            auth.password = "secret"
+        "#,
+        )
+        .await;
+
+        let error = connect_async(format!("ws://{}", addr)).await.unwrap_err();
+        assert!(matches!(error, WsError::Http(response) if response.status() == 401));
+
+        let mut request = format!("ws://{}", addr).into_client_request().unwrap();
+        request
+            .headers_mut()
+            .insert("authorization", "Basic dXNlcjpzZWNyZXQ=".parse().unwrap());
+        assert!(connect_async(request).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn client_subscribes_and_receives_messages() {
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws_stream = accept_async(socket).await.unwrap();
+
+            let subscription = ws_stream.next().await.unwrap().unwrap();
+            assert_eq!(subscription, Message::text("subscribe"));
+
+            ws_stream.send(Message::text("first")).await.unwrap();
+            ws_stream.send(Message::text("second")).await.unwrap();
+
+            // Keep the connection open until the source shuts down.
+            while ws_stream.next().await.is_some() {}
+        });
+
+        let config = toml::from_str::<WebSocketSourceConfig>(&format!(
+            r#"
+            mode = "client"
+            uri = "ws://{}"
+            initial_messages = ["subscribe"]
+        "#,
+            addr
+        ))
+        .unwrap();
+        let events = run_and_assert_source_compliance_n(config, 2, &SOURCE_TAGS).await;
+
+        assert_eq!(message(&events[0]), "first");
+        assert_eq!(message(&events[1]), "second");
+        assert_eq!(
+            events[0].as_log()[log_schema().host_key()],
+            "127.0.0.1".into()
+        );
+    }
+}
@@ -0,0 +1,357 @@
+use std::net::SocketAddr;
+
+use bytes::Bytes;
+use futures::{FutureExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    accept_hdr_async,
+    tungstenite::{
+        handshake::server::{ErrorResponse, Request, Response},
+        Message,
+    },
+};
+use tracing::Instrument;
+use vector_common::internal_event::error_stage;
+use vector_config::configurable_component;
+
+use super::MessageHandler;
+use crate::{
+    common::websocket::is_closed,
+    config::SourceContext,
+    internal_events::{
+        ConnectionOpen, OpenGauge, SocketBindError, SocketMode, SocketReceiveError,
+        TcpSocketTlsConnectionError, WsConnectionError,
+    },
+    shutdown::ShutdownSignal,
+    sources::{
+        util::http::{HttpSourceAuth, HttpSourceAuthConfig},
+        Source,
+    },
+    tls::{MaybeTlsIncomingStream, MaybeTlsSettings, TlsEnableableConfig},
+    SourceSender,
+};
+
+/// Server configuration for the `websocket` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ServerConfig {
+    /// The socket address to listen for connections on.
+    ///
+    /// It _must_ include a port.
+    #[configurable(metadata(docs::examples = "0.0.0.0:8080"))]
+    #[configurable(metadata(docs::examples = "localhost:8080"))]
+    pub address: SocketAddr,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    /// HTTP Basic authentication that clients must provide in the opening handshake.
+    ///
+    /// Connections that fail to authenticate are rejected with a `401 Unauthorized` response.
+    #[configurable(derived)]
+    auth: Option<HttpSourceAuthConfig>,
+
+    /// The maximum number of connections that are allowed at any given time.
+    #[configurable(metadata(docs::type_unit = "connections"))]
+    connection_limit: Option<u32>,
+}
+
+impl ServerConfig {
+    pub(super) fn build(
+        &self,
+        handler: MessageHandler,
+        cx: SourceContext,
+    ) -> crate::Result<Source> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
+        let auth = HttpSourceAuth::try_from(self.auth.as_ref())?;
+
+        Ok(Box::pin(run_server(
+            self.address,
+            tls,
+            auth,
+            self.connection_limit,
+            handler,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+}
+
+async fn run_server(
+    address: SocketAddr,
+    tls: MaybeTlsSettings,
+    auth: HttpSourceAuth,
+    connection_limit: Option<u32>,
+    handler: MessageHandler,
+    shutdown: ShutdownSignal,
+    out: SourceSender,
+) -> Result<(), ()> {
+    let listener = tls.bind(&address).await.map_err(|error| {
+        emit!(SocketBindError {
+            mode: SocketMode::Tcp,
+            error,
+        })
+    })?;
+
+    info!(
+        message = "Listening.",
+        addr = %listener.local_addr().unwrap_or(address),
+    );
+
+    let connection_gauge = OpenGauge::new();
+    let mut connections = listener
+        .accept_stream_limited(connection_limit)
+        .take_until(shutdown.clone());
+
+    while let Some((connection, connection_permit)) = connections.next().await {
+        let socket = match connection {
+            Ok(socket) => socket,
+            Err(error) => {
+                emit!(SocketReceiveError {
+                    mode: SocketMode::Tcp,
+                    error,
+                });
+                continue;
+            }
+        };
+
+        let peer_addr = socket.peer_addr();
+        let span = info_span!("connection", %peer_addr);
+        span.in_scope(|| debug!(message = "Accepted a new connection.", %peer_addr));
+
+        let open_token = connection_gauge
+            .clone()
+            .open(|count| emit!(ConnectionOpen { count }));
+        let fut = handle_connection(
+            socket,
+            auth.clone(),
+            handler.clone(),
+            shutdown.clone(),
+            out.clone(),
+        );
+
+        tokio::spawn(
+            fut.map(move |()| {
+                drop(open_token);
+                drop(connection_permit);
+            })
+            .instrument(span.or_current()),
+        );
+    }
+
+    Ok(())
+}
+
+async fn handle_connection(
+    mut socket: MaybeTlsIncomingStream<TcpStream>,
+    auth: HttpSourceAuth,
+    handler: MessageHandler,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) {
+    tokio::select! {
+        result = socket.handshake() => {
+            if let Err(error) = result {
+                emit!(TcpSocketTlsConnectionError { error });
+                return;
+            }
+        },
+        _ = &mut shutdown => return,
+    };
+
+    let host = socket.peer_addr().ip().to_string();
+    let authenticate = |request: &Request, response: Response| {
+        let header = request
+            .headers()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .map(ToOwned::to_owned);
+
+        auth.is_valid(&header).map(|()| response).map_err(|error| {
+            let mut response = ErrorResponse::new(Some(error.message().to_owned()));
+            *response.status_mut() = error.status_code();
+            response
+        })
+    };
+
+    let mut ws_stream = tokio::select! {
+        result = accept_hdr_async(socket, authenticate) => match result {
+            Ok(ws_stream) => ws_stream,
+            Err(error) => {
+                emit!(WsConnectionError {
+                    error,
+                    stage: error_stage::RECEIVING,
+                });
+                return;
+            }
+        },
+        _ = &mut shutdown => return,
+    };
+
+    loop {
+        tokio::select! {
+            message = ws_stream.next() => {
+                // Pings are answered, and close frames acknowledged, by tungstenite while reading
+                // from the stream.
+                let payload = match message {
+                    Some(Ok(Message::Text(text))) => Bytes::from(text),
+                    Some(Ok(Message::Binary(data))) => Bytes::from(data),
+                    Some(Ok(_)) => continue,
+                    Some(Err(error)) => {
+                        if !is_closed(&error) {
+                            emit!(WsConnectionError {
+                                error,
+                                stage: error_stage::RECEIVING,
+                            });
+                        }
+                        break;
+                    }
+                    None => break,
+                };
+
+                if handler.handle(payload, &host, &mut out).await.is_err() {
+                    break;
+                }
+            },
+            _ = &mut shutdown => {
+                _ = ws_stream.close(None).await;
+                break;
+            },
+        }
+    }
+
+    debug!(message = "Connection closed.");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::{SinkExt, Stream};
+    use tokio::time::{timeout, Instant};
+    use tokio_tungstenite::connect_async;
+    use vector_core::config::log_schema;
+
+    use super::*;
+    use crate::{
+        config::{ComponentKey, SourceConfig},
+        event::Event,
+        sources::websocket::WebSocketSourceConfig,
+        test_util::{collect_n, next_addr, wait_for_tcp},
+    };
+
+    fn source_config(addr: SocketAddr, config: &str) -> WebSocketSourceConfig {
+        toml::from_str(&format!(
+            r#"
+            mode = "server"
+            address = "{}"
+            {}
+        "#,
+            addr, config
+        ))
+        .unwrap()
+    }
+
+    async fn start_server(config: &str) -> (String, impl Stream<Item = Event>) {
+        let addr = next_addr();
+        let (tx, rx) = SourceSender::new_test();
+        let source = source_config(addr, config)
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(addr).await;
+
+        (format!("ws://{}", addr), rx)
+    }
+
+    fn messages(events: &[Event]) -> Vec<String> {
+        events
+            .iter()
+            .map(|event| {
+                event.as_log()[log_schema().message_key()]
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn decodes_every_frame_of_a_message() {
+        let (url, rx) = start_server(r#"framing.method = "newline_delimited""#).await;
+
+        let (mut ws_stream, _) = connect_async(url).await.unwrap();
+        ws_stream.send(Message::text("one\ntwo\n")).await.unwrap();
+        ws_stream.send(Message::text("three")).await.unwrap();
+
+        let events = collect_n(rx, 3).await;
+        assert_eq!(messages(&events), ["one", "two", "three"]);
+    }
+
+    #[tokio::test]
+    async fn keeps_connection_after_decoding_error() {
+        let (url, rx) = start_server(r#"decoding.codec = "json""#).await;
+
+        let (mut ws_stream, _) = connect_async(url).await.unwrap();
+        ws_stream.send(Message::text("not json")).await.unwrap();
+        ws_stream
+            .send(Message::text(r#"{"message": "valid"}"#))
+            .await
+            .unwrap();
+
+        let events = collect_n(rx, 1).await;
+        assert_eq!(messages(&events), ["valid"]);
+    }
+
+    #[tokio::test]
+    async fn answers_pings() {
+        let (url, _rx) = start_server("").await;
+
+        let (mut ws_stream, _) = connect_async(url).await.unwrap();
+        ws_stream
+            .send(Message::Ping(b"ping".to_vec()))
+            .await
+            .unwrap();
+
+        let message = ws_stream.next().await.unwrap().unwrap();
+        assert_eq!(message, Message::Pong(b"ping".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn limits_connections() {
+        let (url, _rx) = start_server("connection_limit = 1").await;
+
+        let (first, _) = connect_async(&url).await.unwrap();
+        assert!(
+            timeout(Duration::from_millis(500), connect_async(&url))
+                .await
+                .is_err(),
+            "second connection should wait for the first one to close"
+        );
+
+        drop(first);
+        timeout(Duration::from_secs(5), connect_async(&url))
+            .await
+            .expect("connection should be accepted once the first one closed")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn closes_connections_on_shutdown() {
+        let addr = next_addr();
+        let key = ComponentKey::from("websocket");
+        let (tx, _rx) = SourceSender::new_test();
+        let (cx, mut shutdown) = SourceContext::new_shutdown(&key, tx);
+        let source = source_config(addr, "").build(cx).await.unwrap();
+        let source = tokio::spawn(source);
+        wait_for_tcp(addr).await;
+
+        let (mut ws_stream, _) = connect_async(format!("ws://{}", addr)).await.unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        assert!(shutdown.shutdown_source(&key, deadline).await);
+        source.await.unwrap().unwrap();
+
+        let message = ws_stream.next().await.unwrap().unwrap();
+        assert!(matches!(message, Message::Close(_)));
+    }
+}
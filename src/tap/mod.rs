@@ -47,4 +47,7 @@ pub struct Opts {
     /// Whether to reconnect if the underlying Vector API connection drops. By default, tap will attempt to reconnect if the connection drops.
     #[arg(short, long)]
     no_reconnect: bool,
+
+    #[command(flatten)]
+    credentials: vector_api_client::Credentials,
 }
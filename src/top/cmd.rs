@@ -38,7 +38,7 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
     });
 
     // Create a new API client for connecting to the local/remote Vector instance.
-    let client = match Client::new_with_healthcheck(url.clone()).await {
+    let client = match Client::new_with_healthcheck(url.clone(), opts.credentials.clone()).await {
         Some(client) => client,
         None => return exitcode::UNAVAILABLE,
     };
@@ -74,13 +74,14 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
             };
             _ = tx.send(EventType::InitializeState(state)).await;
 
-            let subscription_client = match connect_subscription_client(ws_url.clone()).await {
-                Ok(c) => c,
-                Err(_) => {
-                    tokio::time::sleep(Duration::from_millis(RECONNECT_DELAY)).await;
-                    continue;
-                }
-            };
+            let subscription_client =
+                match connect_subscription_client(ws_url.clone(), &opts_clone.credentials).await {
+                    Ok(c) => c,
+                    Err(_) => {
+                        tokio::time::sleep(Duration::from_millis(RECONNECT_DELAY)).await;
+                        continue;
+                    }
+                };
 
             // Subscribe to updated metrics
             let finished =
@@ -28,4 +28,7 @@ pub struct Opts {
     /// By default, top will attempt to reconnect if the connection drops.
     #[arg(short, long)]
     no_reconnect: bool,
+
+    #[command(flatten)]
+    credentials: vector_api_client::Credentials,
 }
@@ -12,7 +12,10 @@ use once_cell::sync::Lazy;
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
 use tokio::{
     select,
-    sync::{mpsc::UnboundedSender, oneshot},
+    sync::{
+        mpsc::{self, UnboundedSender},
+        oneshot, watch,
+    },
     time::{timeout, Duration},
 };
 use tracing::Instrument;
@@ -34,8 +37,10 @@ use vector_core::{
 };
 
 use super::{
+    dead_letter,
+    drain::{DrainHandle, SinkDrain},
     fanout::{self, Fanout},
-    schema,
+    pause, schema,
     task::{Task, TaskOutput, TaskResult},
     BuiltBuffer, ConfigDiff,
 };
@@ -43,6 +48,7 @@ use crate::{
     config::{
         ComponentKey, DataType, EnrichmentTableConfig, Input, Inputs, OutputId, ProxyConfig,
         SinkConfig, SinkContext, SourceContext, TransformContext, TransformOuter, TransformOutput,
+        DEAD_LETTER_OUTPUT,
     },
     event::{EventArray, EventContainer},
     internal_events::EventsReceived,
@@ -91,6 +97,10 @@ struct Builder<'a> {
     inputs: HashMap<ComponentKey, (BufferSender<EventArray>, Inputs<OutputId>)>,
     healthchecks: HashMap<ComponentKey, Task>,
     detach_triggers: HashMap<ComponentKey, Trigger>,
+    source_pauses: HashMap<ComponentKey, watch::Sender<bool>>,
+    sink_drains: HashMap<ComponentKey, DrainHandle>,
+    transform_swaps: HashMap<ComponentKey, TransformSwapTx>,
+    swapped_transforms: HashMap<ComponentKey, Box<dyn SyncTransform>>,
 }
 
 impl<'a> Builder<'a> {
@@ -110,6 +120,10 @@ impl<'a> Builder<'a> {
             inputs: HashMap::new(),
             healthchecks: HashMap::new(),
             detach_triggers: HashMap::new(),
+            source_pauses: HashMap::new(),
+            sink_drains: HashMap::new(),
+            transform_swaps: HashMap::new(),
+            swapped_transforms: HashMap::new(),
         }
     }
 
@@ -133,6 +147,10 @@ impl<'a> Builder<'a> {
                 healthchecks: self.healthchecks,
                 shutdown_coordinator: self.shutdown_coordinator,
                 detach_triggers: self.detach_triggers,
+                source_pauses: self.source_pauses,
+                sink_drains: self.sink_drains,
+                transform_swaps: self.transform_swaps,
+                swapped_transforms: self.swapped_transforms,
             })
         } else {
             Err(self.errors)
@@ -241,9 +259,11 @@ impl<'a> Builder<'a> {
             let mut pumps = Vec::new();
             let mut controls = HashMap::new();
             let mut schema_definitions = HashMap::with_capacity(source_outputs.len());
+            let (pause_tx, pause_rx) = watch::channel(false);
 
             for output in source_outputs.into_iter() {
                 let mut rx = builder.add_source_output(output.clone());
+                let mut pause_rx = pause_rx.clone();
 
                 let (mut fanout, control) = Fanout::new();
                 let source = Arc::new(OutputId {
@@ -254,7 +274,14 @@ impl<'a> Builder<'a> {
                 let pump = async move {
                     debug!("Source pump starting.");
 
-                    while let Some(mut array) = rx.next().await {
+                    loop {
+                        // While the source is paused, we leave its events where they are, so that
+                        // it gets backpressured once its channel fills up.
+                        pause::wait_until_resumed(&mut pause_rx).await;
+                        let Some(mut array) = rx.next().await else {
+                            break;
+                        };
+
                         array.set_output_id(&source);
                         fanout.send(array).await.map_err(|e| {
                             debug!("Source pump finished with an error.");
@@ -392,6 +419,7 @@ impl<'a> Builder<'a> {
 
             self.outputs.extend(controls);
             self.tasks.insert(key.clone(), pump);
+            self.source_pauses.insert(key.clone(), pause_tx);
             source_tasks.insert(key.clone(), server);
         }
 
@@ -401,11 +429,9 @@ impl<'a> Builder<'a> {
     async fn build_transforms(&mut self, enrichment_tables: &enrichment::TableRegistry) {
         let mut definition_cache = HashMap::default();
 
-        for (key, transform) in self
-            .config
-            .transforms()
-            .filter(|(key, _)| self.diff.transforms.contains_new(key))
-        {
+        for (key, transform) in self.config.transforms().filter(|(key, _)| {
+            self.diff.transforms.contains_new(key) || self.diff.transforms.is_swapped(key)
+        }) {
             debug!(component = %key, "Building new transform.");
 
             let input_definitions = match schema::input_definitions(
@@ -485,19 +511,39 @@ impl<'a> Builder<'a> {
                 Ok(transform) => transform,
             };
 
+            // A transform swapped in place is handed over to the running one, which keeps its
+            // input buffer and outputs.
+            if self.diff.transforms.is_swapped(key) {
+                match transform {
+                    Transform::Function(t) => {
+                        self.swapped_transforms.insert(key.clone(), Box::new(t));
+                    }
+                    Transform::Synchronous(t) => {
+                        self.swapped_transforms.insert(key.clone(), t);
+                    }
+                    Transform::Task(_) => self
+                        .errors
+                        .push(format!("Transform \"{}\": can't be swapped in place.", key)),
+                }
+                continue;
+            }
+
             let (input_tx, input_rx) =
                 TopologyBuilder::standalone_memory(TOPOLOGY_BUFFER_SIZE, WhenFull::Block).await;
 
             self.inputs
                 .insert(key.clone(), (input_tx, node.inputs.clone()));
 
-            let (transform_task, transform_outputs) = {
+            let (transform_task, transform_outputs, swap_tx) = {
                 let _span = span.enter();
                 build_transform(transform, node, input_rx)
             };
 
             self.outputs.extend(transform_outputs);
             self.tasks.insert(key.clone(), transform_task);
+            if let Some(swap_tx) = swap_tx {
+                self.transform_swaps.insert(key.clone(), swap_tx);
+            }
         }
     }
 
@@ -511,6 +557,7 @@ impl<'a> Builder<'a> {
 
             let sink_inputs = &sink.inputs;
             let healthcheck = sink.healthcheck();
+            let enable_dead_letter = sink.dead_letter.enabled;
             let enable_healthcheck = healthcheck.enabled && self.config.healthchecks.enabled;
 
             let typetag = sink.inner.get_component_name();
@@ -567,15 +614,29 @@ impl<'a> Builder<'a> {
                 schema: self.config.schema,
             };
 
-            let (sink, healthcheck) = match sink.inner.build(cx).await {
+            let (sink, healthcheck) = match sink.inner.build(cx.clone()).await {
                 Err(error) => {
                     self.errors.push(format!("Sink \"{}\": {}", key, error));
                     continue;
                 }
                 Ok(built) => built,
             };
+            // Kept to rebuild the sink when it's resumed after being drained.
+            let sink_config = sink.inner.clone();
 
             let (trigger, tripwire) = Tripwire::new();
+            let (drain_handle, mut drain) = SinkDrain::new();
+
+            let (dead_letter_tracker, dead_letter_router) = if enable_dead_letter {
+                let (tracker, control, router) = dead_letter::build(key, typetag);
+                self.outputs.insert(
+                    OutputId::from((key, DEAD_LETTER_OUTPUT.to_owned())),
+                    control,
+                );
+                (Some(tracker), Some(router))
+            } else {
+                (None, None)
+            };
 
             let sink = async move {
                 debug!("Sink starting.");
@@ -595,26 +656,70 @@ impl<'a> Builder<'a> {
                 let mut rx = wrap(rx);
 
                 let events_received = register!(EventsReceived);
-                sink.run(
-                    rx.by_ref()
-                        .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
-                        .inspect(|events| {
-                            events_received.emit(CountByteSize(
-                                events.len(),
-                                events.estimated_json_encoded_size_of(),
-                            ))
-                        })
-                        .take_until_if(tripwire),
-                )
-                .await
-                .map(|_| {
-                    debug!("Sink finished normally.");
-                    TaskOutput::Sink(rx)
-                })
-                .map_err(|_| {
-                    debug!("Sink finished with an error.");
-                    TaskError::Opaque
-                })
+                let run = async move {
+                    let mut sink = sink;
+                    loop {
+                        sink.run(
+                            rx.by_ref()
+                                .filter(|events: &EventArray| {
+                                    ready(filter_events_type(events, input_type))
+                                })
+                                .inspect(|events| {
+                                    events_received.emit(CountByteSize(
+                                        events.len(),
+                                        events.estimated_json_encoded_size_of(),
+                                    ))
+                                })
+                                .take_until_if(tripwire.clone())
+                                .take_until(drain.requested())
+                                .map(|events| match &dead_letter_tracker {
+                                    Some(tracker) => tracker.track(events),
+                                    None => events,
+                                }),
+                        )
+                        .await?;
+
+                        if !drain.take_drained() {
+                            break;
+                        }
+
+                        // The events sent to a stopped sink are left in its buffer, so that it
+                        // carries on from there once it's resumed. The sink itself was consumed by
+                        // running it, so it's rebuilt.
+                        info!("Sink drained.");
+                        sink = loop {
+                            if !drain.stopped_until_resumed().await {
+                                return Ok(rx);
+                            }
+                            match sink_config.build(cx.clone()).await {
+                                Ok((sink, _)) => break sink,
+                                Err(error) => {
+                                    error!(message = "Failed to resume sink, retrying.", %error);
+                                    tokio::time::sleep(Duration::from_secs(5)).await;
+                                }
+                            }
+                        };
+                        info!("Sink resumed.");
+                    }
+                    Ok(rx)
+                };
+
+                // The events that the sink fails to deliver are routed until the delivery of all
+                // the events it received has completed, which can happen after it stopped.
+                let result = match dead_letter_router {
+                    Some(router) => futures::join!(run, router).0,
+                    None => run.await,
+                };
+
+                result
+                    .map(|rx| {
+                        debug!("Sink finished normally.");
+                        TaskOutput::Sink(rx)
+                    })
+                    .map_err(|_: ()| {
+                        debug!("Sink finished with an error.");
+                        TaskError::Opaque
+                    })
             };
 
             let task = Task::new(key.clone(), typetag, sink);
@@ -666,6 +771,7 @@ impl<'a> Builder<'a> {
             self.healthchecks.insert(key.clone(), healthcheck_task);
             self.tasks.insert(key.clone(), task);
             self.detach_triggers.insert(key.clone(), trigger);
+            self.sink_drains.insert(key.clone(), drain_handle);
         }
     }
 }
@@ -678,6 +784,45 @@ pub struct Pieces {
     pub(super) healthchecks: HashMap<ComponentKey, Task>,
     pub(crate) shutdown_coordinator: SourceShutdownCoordinator,
     pub(crate) detach_triggers: HashMap<ComponentKey, Trigger>,
+    pub(crate) source_pauses: HashMap<ComponentKey, watch::Sender<bool>>,
+    pub(super) sink_drains: HashMap<ComponentKey, DrainHandle>,
+    pub(super) transform_swaps: HashMap<ComponentKey, TransformSwapTx>,
+    pub(super) swapped_transforms: HashMap<ComponentKey, Box<dyn SyncTransform>>,
+}
+
+/// Sends a transform to swap in place of a running synchronous transform.
+pub(super) type TransformSwapTx = UnboundedSender<Box<dyn SyncTransform>>;
+
+/// Checks whether or not the outputs of a transform, including their schema definitions, are the
+/// same in both configs.
+///
+/// A transform can only be swapped in place if they are, as the components downstream of it were
+/// built for the schema definitions of its outputs.
+pub(super) fn transform_outputs_unchanged(
+    old: &super::Config,
+    new: &super::Config,
+    key: &ComponentKey,
+) -> bool {
+    let outputs = |config: &super::Config| {
+        let transform = config.transform(key)?;
+        let input_definitions = schema::input_definitions(
+            &transform.inputs,
+            config,
+            ENRICHMENT_TABLES.clone(),
+            &mut HashMap::default(),
+        )
+        .ok()?;
+        Some(transform.inner.outputs(
+            ENRICHMENT_TABLES.clone(),
+            &input_definitions,
+            config.schema.log_namespace(),
+        ))
+    };
+
+    match (outputs(old), outputs(new)) {
+        (Some(old_outputs), Some(new_outputs)) => old_outputs == new_outputs,
+        _ => false,
+    }
 }
 
 const fn filter_events_type(events: &EventArray, data_type: DataType) -> bool {
@@ -721,22 +866,31 @@ impl TransformNode {
     }
 }
 
+/// Builds the task of a transform. Synchronous transforms can be swapped in place, through the
+/// returned sender.
 fn build_transform(
     transform: Transform,
     node: TransformNode,
     input_rx: BufferReceiver<EventArray>,
-) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
+) -> (
+    Task,
+    HashMap<OutputId, fanout::ControlChannel>,
+    Option<TransformSwapTx>,
+) {
     match transform {
         // TODO: avoid the double boxing for function transforms here
         Transform::Function(t) => build_sync_transform(Box::new(t), node, input_rx),
         Transform::Synchronous(t) => build_sync_transform(t, node, input_rx),
-        Transform::Task(t) => build_task_transform(
-            t,
-            input_rx,
-            node.input_details.data_type(),
-            node.typetag,
-            &node.key,
-        ),
+        Transform::Task(t) => {
+            let (task, outputs) = build_task_transform(
+                t,
+                input_rx,
+                node.input_details.data_type(),
+                node.typetag,
+                &node.key,
+            );
+            (task, outputs, None)
+        }
     }
 }
 
@@ -744,10 +898,21 @@ fn build_sync_transform(
     t: Box<dyn SyncTransform>,
     node: TransformNode,
     input_rx: BufferReceiver<EventArray>,
-) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
+) -> (
+    Task,
+    HashMap<OutputId, fanout::ControlChannel>,
+    Option<TransformSwapTx>,
+) {
     let (outputs, controls) = TransformOutputs::new(node.outputs);
-
-    let runner = Runner::new(t, input_rx, node.input_details.data_type(), outputs);
+    let (swap_tx, swap_rx) = mpsc::unbounded_channel();
+
+    let runner = Runner::new(
+        t,
+        input_rx,
+        swap_rx,
+        node.input_details.data_type(),
+        outputs,
+    );
     let transform = if node.enable_concurrency {
         runner.run_concurrently().boxed()
     } else {
@@ -779,12 +944,13 @@ fn build_sync_transform(
 
     let task = Task::new(node.key.clone(), node.typetag, transform);
 
-    (task, output_controls)
+    (task, output_controls, Some(swap_tx))
 }
 
 struct Runner {
     transform: Box<dyn SyncTransform>,
     input_rx: Option<BufferReceiver<EventArray>>,
+    swap_rx: mpsc::UnboundedReceiver<Box<dyn SyncTransform>>,
     input_type: DataType,
     outputs: TransformOutputs,
     timer: crate::utilization::Timer,
@@ -796,12 +962,14 @@ impl Runner {
     fn new(
         transform: Box<dyn SyncTransform>,
         input_rx: BufferReceiver<EventArray>,
+        swap_rx: mpsc::UnboundedReceiver<Box<dyn SyncTransform>>,
         input_type: DataType,
         outputs: TransformOutputs,
     ) -> Self {
         Self {
             transform,
             input_rx: Some(input_rx),
+            swap_rx,
             input_type,
             outputs,
             timer: crate::utilization::Timer::new(),
@@ -823,6 +991,15 @@ impl Runner {
         ));
     }
 
+    /// Swaps in the latest transform sent on reload, if any, so that the events received from now
+    /// on are processed by it. The events already being processed are left to the previous one.
+    fn swap_transform(&mut self) {
+        while let Ok(transform) = self.swap_rx.try_recv() {
+            debug!("Transform swapped in place.");
+            self.transform = transform;
+        }
+    }
+
     async fn send_outputs(&mut self, outputs_buf: &mut TransformOutputsBuf) -> crate::Result<()> {
         self.timer.start_wait();
         self.outputs.send(outputs_buf).await
@@ -844,6 +1021,7 @@ impl Runner {
         self.timer.start_wait();
         while let Some(events) = input_rx.next().await {
             self.on_events_received(&events);
+            self.swap_transform();
             self.transform.transform_all(events, &mut outputs_buf);
             self.send_outputs(&mut outputs_buf)
                 .await
@@ -892,6 +1070,7 @@ impl Runner {
                                 len += events.len();
                             }
 
+                            self.swap_transform();
                             let mut t = self.transform.clone();
                             let mut outputs_buf = self.outputs.new_buf_with_capacity(len);
                             let task = tokio::spawn(async move {
@@ -0,0 +1,339 @@
+//! Routing of the events that sinks fail to deliver to their dead-letter output.
+//!
+//! Before events are handed to a sink, a copy of them is kept aside and the events are attached to
+//! a new batch notifier. Once the sink finalizes the events, the copy is either dropped, if they
+//! were delivered, or annotated with the details of the failure and sent to the dead-letter output.
+
+use std::sync::Arc;
+
+use futures::{stream::FuturesUnordered, Future, StreamExt};
+use lookup::{lookup_v2::ValuePath, metadata_path, path, PathPrefix};
+use tokio::{select, sync::mpsc};
+use vector_common::finalization::AddBatchNotifier;
+use vector_core::config::{log_schema, LogNamespace};
+
+use super::fanout::{ControlChannel, Fanout};
+use crate::{
+    config::{ComponentKey, OutputId, DEAD_LETTER_OUTPUT},
+    event::{
+        BatchNotifier, BatchStatus, BatchStatusReceiver, DeliveryFailure, EventArray, EventMutRef,
+        Finalizable,
+    },
+};
+
+/// Events that were handed to a sink, along with the receiver for their delivery status.
+struct PendingEvents {
+    receiver: BatchStatusReceiver,
+    events: EventArray,
+}
+
+/// Keeps track of the events that are handed to a sink.
+pub(super) struct DeadLetterTracker {
+    pending: mpsc::UnboundedSender<PendingEvents>,
+}
+
+impl DeadLetterTracker {
+    /// Prepares events to be handed to the sink, keeping a copy of them aside.
+    ///
+    /// The copy takes over the finalizers of the events, so that sources and disk buffers are only
+    /// acknowledged once the copy is dropped, after a successful delivery, or once it has been
+    /// delivered by the components consuming the dead-letter output.
+    pub(super) fn track(&self, mut events: EventArray) -> EventArray {
+        let copy = events.clone();
+        drop(events.take_finalizers());
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        events.add_batch_notifier(batch);
+
+        // The router only stops once all trackers are dropped, so this can't fail.
+        _ = self.pending.send(PendingEvents {
+            receiver,
+            events: copy,
+        });
+
+        events
+    }
+}
+
+/// Creates the tracker for the events handed to the given sink, along with the control channel of
+/// its dead-letter output and the future routing failed events to that output.
+///
+/// The future completes once the tracker is dropped and the delivery of all tracked events has
+/// completed.
+pub(super) fn build(
+    key: &ComponentKey,
+    typetag: &'static str,
+) -> (
+    DeadLetterTracker,
+    ControlChannel,
+    impl Future<Output = ()> + Send + 'static,
+) {
+    let (pending_tx, pending_rx) = mpsc::unbounded_channel();
+    let (fanout, control) = Fanout::new();
+    let router = DeadLetterRouter {
+        output: Arc::new(OutputId::from((key, DEAD_LETTER_OUTPUT.to_owned()))),
+        typetag,
+    };
+
+    (
+        DeadLetterTracker {
+            pending: pending_tx,
+        },
+        control,
+        router.run(pending_rx, fanout),
+    )
+}
+
+struct DeadLetterRouter {
+    output: Arc<OutputId>,
+    typetag: &'static str,
+}
+
+impl DeadLetterRouter {
+    async fn run(self, mut pending_rx: mpsc::UnboundedReceiver<PendingEvents>, mut fanout: Fanout) {
+        let mut in_flight = FuturesUnordered::new();
+
+        loop {
+            select! {
+                Some(pending) = pending_rx.recv() => {
+                    in_flight.push(async move {
+                        let PendingEvents { mut receiver, events } = pending;
+                        let status = (&mut receiver).await;
+                        (status, receiver.failure(), events)
+                    });
+                }
+
+                Some(finished) = in_flight.next(), if !in_flight.is_empty() => {
+                    let (status, failure, mut events) = finished;
+                    if status == BatchStatus::Delivered {
+                        continue;
+                    }
+
+                    self.annotate(&mut events, status, failure);
+                    events.set_output_id(&self.output);
+                    if let Err(error) = fanout.send(events).await {
+                        debug!(message = "Dead-letter output closed.", %error);
+                        break;
+                    }
+                }
+
+                else => break,
+            }
+        }
+    }
+
+    fn failure_data(
+        &self,
+        status: BatchStatus,
+        failure: Option<&DeliveryFailure>,
+    ) -> serde_json::Value {
+        let mut data = serde_json::json!({
+            "status": status_name(status),
+            "component_id": self.output.component,
+            "component_type": self.typetag,
+            "component_kind": "sink",
+        });
+        if let Some(failure) = failure {
+            data["attempts"] = failure.attempts.into();
+            if let Some(status_code) = failure.status_code {
+                data["status_code"] = status_code.into();
+            }
+            if let Some(error) = &failure.error {
+                data["error"] = error.as_str().into();
+            }
+        }
+        data
+    }
+
+    fn annotate(
+        &self,
+        events: &mut EventArray,
+        status: BatchStatus,
+        failure: Option<DeliveryFailure>,
+    ) {
+        let data = self.failure_data(status, failure.as_ref());
+
+        for event in events.iter_events_mut() {
+            match event {
+                EventMutRef::Log(log) => match log.namespace() {
+                    LogNamespace::Legacy => {
+                        log.insert(
+                            (
+                                PathPrefix::Event,
+                                log_schema().metadata_key().concat(path!("dead_letter")),
+                            ),
+                            data.clone(),
+                        );
+                    }
+                    LogNamespace::Vector => {
+                        log.insert(metadata_path!("vector", "dead_letter"), data.clone());
+                    }
+                },
+                EventMutRef::Metric(metric) => {
+                    let m = log_schema().metadata_key();
+                    metric.replace_tag(
+                        format!("{}.dead_letter.status", m),
+                        status_name(status).into(),
+                    );
+                    metric.replace_tag(
+                        format!("{}.dead_letter.component_id", m),
+                        self.output.component.to_string(),
+                    );
+                    metric.replace_tag(
+                        format!("{}.dead_letter.component_type", m),
+                        self.typetag.into(),
+                    );
+                    metric.replace_tag(format!("{}.dead_letter.component_kind", m), "sink".into());
+                    if let Some(failure) = &failure {
+                        metric.replace_tag(
+                            format!("{}.dead_letter.attempts", m),
+                            failure.attempts.to_string(),
+                        );
+                        if let Some(status_code) = failure.status_code {
+                            metric.replace_tag(
+                                format!("{}.dead_letter.status_code", m),
+                                status_code.to_string(),
+                            );
+                        }
+                        if let Some(error) = &failure.error {
+                            metric.replace_tag(format!("{}.dead_letter.error", m), error.clone());
+                        }
+                    }
+                }
+                EventMutRef::Trace(trace) => {
+                    trace.insert(
+                        format!("{}.dead_letter", log_schema().metadata_key()),
+                        data.clone(),
+                    );
+                }
+            }
+        }
+    }
+}
+
+const fn status_name(status: BatchStatus) -> &'static str {
+    match status {
+        BatchStatus::Delivered => "delivered",
+        BatchStatus::Errored => "errored",
+        BatchStatus::Rejected => "rejected",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::oneshot::error::TryRecvError;
+    use vector_core::buffers::{
+        topology::{builder::TopologyBuilder, channel::BufferReceiverStream},
+        WhenFull,
+    };
+
+    use super::*;
+    use crate::{
+        event::{EventStatus, LogEvent, TraceEvent},
+        topology::{builder::TOPOLOGY_BUFFER_SIZE, fanout::ControlMessage},
+    };
+
+    async fn connect_output(control: &ControlChannel) -> BufferReceiverStream<EventArray> {
+        let (tx, rx) =
+            TopologyBuilder::standalone_memory(TOPOLOGY_BUFFER_SIZE, WhenFull::Block).await;
+        control
+            .send(ControlMessage::Add(ComponentKey::from("fallback"), tx))
+            .unwrap();
+        rx.into_stream()
+    }
+
+    #[tokio::test]
+    async fn routes_failed_events_to_output() {
+        let (tracker, control, router) = build(&ComponentKey::from("primary"), "test");
+        let mut output = connect_output(&control).await;
+        let router = tokio::spawn(router);
+
+        let (batch, mut source_receiver) = BatchNotifier::new_with_receiver();
+        let mut events = tracker.track(LogEvent::from("hello").with_batch_notifier(&batch).into());
+        drop(batch);
+
+        let failure = DeliveryFailure {
+            attempts: 3,
+            status_code: Some(503),
+            error: None,
+        };
+        events
+            .take_finalizers()
+            .update_status_with_failure(EventStatus::Errored, &failure);
+        drop(events);
+
+        let routed = output.next().await.unwrap();
+        let EventArray::Logs(logs) = routed else {
+            panic!("expected logs");
+        };
+        assert_eq!(logs[0]["message"], "hello".into());
+        assert_eq!(logs[0]["metadata.dead_letter.status"], "errored".into());
+        assert_eq!(logs[0]["metadata.dead_letter.attempts"], 3.into());
+        assert_eq!(logs[0]["metadata.dead_letter.status_code"], 503.into());
+        assert_eq!(
+            logs[0]["metadata.dead_letter.component_id"],
+            "primary".into()
+        );
+        assert!(logs[0].get("metadata.dead_letter.error").is_none());
+
+        // The source is only acknowledged once the routed events are finalized.
+        assert_eq!(source_receiver.try_recv(), Err(TryRecvError::Empty));
+        drop(logs);
+        assert_eq!(source_receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        drop(tracker);
+        router.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn annotates_failed_traces() {
+        let (tracker, control, router) = build(&ComponentKey::from("primary"), "test");
+        let mut output = connect_output(&control).await;
+        let router = tokio::spawn(router);
+
+        let mut trace = TraceEvent::default();
+        trace.insert("name", "span");
+        let mut events = tracker.track(trace.into());
+        events
+            .take_finalizers()
+            .update_status(EventStatus::Rejected);
+        drop(events);
+
+        let routed = output.next().await.unwrap();
+        let EventArray::Traces(traces) = routed else {
+            panic!("expected traces");
+        };
+        assert_eq!(traces[0].get("name"), Some(&"span".into()));
+        assert_eq!(
+            traces[0].get("metadata.dead_letter.status"),
+            Some(&"rejected".into())
+        );
+        assert_eq!(
+            traces[0].get("metadata.dead_letter.component_id"),
+            Some(&"primary".into())
+        );
+
+        drop(tracker);
+        router.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn drops_delivered_events() {
+        let (tracker, control, router) = build(&ComponentKey::from("primary"), "test");
+        let mut output = connect_output(&control).await;
+        let router = tokio::spawn(router);
+
+        let (batch, mut source_receiver) = BatchNotifier::new_with_receiver();
+        let mut events = tracker.track(LogEvent::from("hello").with_batch_notifier(&batch).into());
+        drop(batch);
+        events
+            .take_finalizers()
+            .update_status(EventStatus::Delivered);
+        drop(events);
+
+        drop(tracker);
+        router.await.unwrap();
+        assert!(output.next().await.is_none());
+        assert_eq!(source_receiver.try_recv(), Ok(BatchStatus::Delivered));
+    }
+}
@@ -0,0 +1,221 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use tokio::sync::watch;
+
+use crate::config::ComponentKey;
+
+/// State of a sink that can be drained.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SinkState {
+    /// The sink is sending the events from its buffer.
+    Running,
+
+    /// The sink was asked to drain, and is finishing sending the events it pulled from its buffer.
+    Draining,
+
+    /// The sink sent all the events it pulled from its buffer, and is stopped until it's resumed.
+    Stopped,
+}
+
+/// Handles shared between a sink's task and [`SinkDrains`].
+#[derive(Debug)]
+pub(super) struct DrainHandle {
+    drain_tx: watch::Sender<bool>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl DrainHandle {
+    fn state(&self) -> SinkState {
+        match (
+            *self.drain_tx.borrow(),
+            self.stopped.load(Ordering::Acquire),
+        ) {
+            (false, _) => SinkState::Running,
+            (true, false) => SinkState::Draining,
+            (true, true) => SinkState::Stopped,
+        }
+    }
+}
+
+/// Shared handle for draining, and resuming, the sinks of a running topology.
+///
+/// A drained sink stops pulling events from its buffer, finishes sending the events it already
+/// pulled, and then stops, which closes its connections. In the meantime, its inputs keep writing
+/// to its buffer, until it's full and they're backpressured. Once resumed, the sink is rebuilt and
+/// carries on from where it stopped in its buffer, so no event is lost or sent twice.
+///
+/// Stopped sinks are resumed when the topology is stopped, so they can send the events left in
+/// their buffer. Stopped sinks that are removed or changed by a reload aren't resumed: their
+/// buffer is handed over to the new sink, if it can be reused.
+#[derive(Clone, Debug, Default)]
+pub struct SinkDrains(Arc<Mutex<HashMap<ComponentKey, DrainHandle>>>);
+
+impl SinkDrains {
+    /// Drains the given sink.
+    ///
+    /// Returns the state of the sink beforehand, or `None` if there is no such sink.
+    pub fn drain(&self, key: &ComponentKey) -> Option<SinkState> {
+        self.set_draining(key, true)
+    }
+
+    /// Resumes the given sink, if it's draining or stopped.
+    ///
+    /// Returns the state of the sink beforehand, or `None` if there is no such sink.
+    pub fn resume(&self, key: &ComponentKey) -> Option<SinkState> {
+        self.set_draining(key, false)
+    }
+
+    /// Gets the state of the given sink, or `None` if there is no such sink.
+    pub fn state(&self, key: &ComponentKey) -> Option<SinkState> {
+        let sinks = self.0.lock().expect("sink drains lock poisoned");
+        sinks.get(key).map(DrainHandle::state)
+    }
+
+    fn set_draining(&self, key: &ComponentKey, draining: bool) -> Option<SinkState> {
+        let sinks = self.0.lock().expect("sink drains lock poisoned");
+        sinks.get(key).map(|handle| {
+            let state = handle.state();
+            handle.drain_tx.send_replace(draining);
+            state
+        })
+    }
+
+    pub(super) fn insert(&self, key: ComponentKey, handle: DrainHandle) {
+        let mut sinks = self.0.lock().expect("sink drains lock poisoned");
+        sinks.insert(key, handle);
+    }
+
+    /// Removes the given sink, which stops it for good if it was stopped.
+    pub(super) fn remove(&self, key: &ComponentKey) {
+        let mut sinks = self.0.lock().expect("sink drains lock poisoned");
+        sinks.remove(key);
+    }
+
+    /// Resumes and removes all sinks, so that stopped sinks send the events left in their buffer.
+    pub(super) fn clear(&self) {
+        let mut sinks = self.0.lock().expect("sink drains lock poisoned");
+        for (_, handle) in sinks.drain() {
+            handle.drain_tx.send_replace(false);
+        }
+    }
+}
+
+/// The side of a sink's drain handle used by its task.
+pub(super) struct SinkDrain {
+    drain_rx: watch::Receiver<bool>,
+    stopped: Arc<AtomicBool>,
+    drained: bool,
+}
+
+impl SinkDrain {
+    pub(super) fn new() -> (DrainHandle, Self) {
+        let (drain_tx, drain_rx) = watch::channel(false);
+        let stopped = Arc::new(AtomicBool::new(false));
+        let handle = DrainHandle {
+            drain_tx,
+            stopped: Arc::clone(&stopped),
+        };
+        let drain = Self {
+            drain_rx,
+            stopped,
+            drained: false,
+        };
+        (handle, drain)
+    }
+
+    /// Waits until the sink is asked to drain. Never completes once the sink is removed from
+    /// `SinkDrains`.
+    pub(super) async fn requested(&mut self) {
+        while !*self.drain_rx.borrow() {
+            if self.drain_rx.changed().await.is_err() {
+                futures::future::pending::<()>().await;
+            }
+        }
+        self.drained = true;
+    }
+
+    /// Returns whether or not the sink's input ended because it was asked to drain, rather than
+    /// because the sink is shutting down.
+    pub(super) fn take_drained(&mut self) -> bool {
+        std::mem::take(&mut self.drained)
+    }
+
+    /// Marks the sink as stopped, and waits until it's resumed.
+    ///
+    /// Returns `false` if the sink was removed from `SinkDrains` instead, in which case it mustn't
+    /// be resumed.
+    pub(super) async fn stopped_until_resumed(&mut self) -> bool {
+        self.stopped.store(true, Ordering::Release);
+        let resumed = loop {
+            if !*self.drain_rx.borrow() {
+                break true;
+            }
+            if self.drain_rx.changed().await.is_err() {
+                break false;
+            }
+        };
+        self.stopped.store(false, Ordering::Release);
+        resumed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::FutureExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn drains_and_resumes_sinks() {
+        let drains = SinkDrains::default();
+        let key = ComponentKey::from("out");
+        let (handle, mut drain) = SinkDrain::new();
+        drains.insert(key.clone(), handle);
+
+        assert!(drain.requested().now_or_never().is_none());
+        assert!(!drain.take_drained());
+
+        assert_eq!(drains.drain(&key), Some(SinkState::Running));
+        assert_eq!(drains.state(&key), Some(SinkState::Draining));
+        assert!(drain.requested().now_or_never().is_some());
+        assert!(drain.take_drained());
+
+        let stopped = tokio::spawn(async move { drain.stopped_until_resumed().await });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(drains.state(&key), Some(SinkState::Stopped));
+
+        assert_eq!(drains.resume(&key), Some(SinkState::Stopped));
+        let resumed = tokio::time::timeout(Duration::from_secs(1), stopped)
+            .await
+            .expect("sink should be resumed")
+            .unwrap();
+        assert!(resumed);
+        assert_eq!(drains.state(&key), Some(SinkState::Running));
+
+        assert_eq!(drains.drain(&ComponentKey::from("other")), None);
+    }
+
+    #[tokio::test]
+    async fn removing_stopped_sink_stops_it() {
+        let drains = SinkDrains::default();
+        let key = ComponentKey::from("out");
+        let (handle, mut drain) = SinkDrain::new();
+        drains.insert(key.clone(), handle);
+        drains.drain(&key);
+
+        drains.remove(&key);
+        let resumed = tokio::time::timeout(Duration::from_secs(1), drain.stopped_until_resumed())
+            .await
+            .expect("sink should be stopped");
+        assert!(!resumed);
+        assert_eq!(drains.state(&key), None);
+    }
+}
@@ -0,0 +1,54 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::config::ComponentKey;
+
+/// Health of a component, as reported by the API.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ComponentHealth {
+    /// The component's healthcheck hasn't completed yet.
+    Starting,
+
+    /// The component's healthcheck passed, or is disabled.
+    Healthy,
+
+    /// The component's healthcheck failed, or timed out.
+    Unhealthy,
+}
+
+/// Shared record of the health of the components of a running topology.
+///
+/// Sinks are `Starting` until their healthcheck completes, after which they're `Healthy` or
+/// `Unhealthy` depending on its outcome. Sources are `Healthy` once they're running. Transforms
+/// have no healthcheck, so they aren't recorded.
+#[derive(Clone, Debug, Default)]
+pub struct HealthRegistry(Arc<Mutex<HashMap<ComponentKey, ComponentHealth>>>);
+
+impl HealthRegistry {
+    /// Gets the health of the given component, or `None` if it isn't recorded.
+    pub fn get(&self, key: &ComponentKey) -> Option<ComponentHealth> {
+        let components = self.0.lock().expect("health registry lock poisoned");
+        components.get(key).copied()
+    }
+
+    /// Gets the health of all recorded components.
+    pub fn snapshot(&self) -> Vec<(ComponentKey, ComponentHealth)> {
+        let components = self.0.lock().expect("health registry lock poisoned");
+        components
+            .iter()
+            .map(|(key, health)| (key.clone(), *health))
+            .collect()
+    }
+
+    pub(super) fn set(&self, key: ComponentKey, health: ComponentHealth) {
+        let mut components = self.0.lock().expect("health registry lock poisoned");
+        components.insert(key, health);
+    }
+
+    pub(super) fn remove(&self, key: &ComponentKey) {
+        let mut components = self.0.lock().expect("health registry lock poisoned");
+        components.remove(key);
+    }
+}
@@ -12,6 +12,10 @@ pub mod schema;
 
 pub mod builder;
 mod controller;
+mod dead_letter;
+mod drain;
+mod health;
+mod pause;
 mod ready_arrays;
 mod running;
 mod task;
@@ -26,7 +30,10 @@ use std::{
 };
 
 pub use controller::{ReloadOutcome, SharedTopologyController, TopologyController};
+pub use drain::{SinkDrains, SinkState};
 use futures::{Future, FutureExt};
+pub use health::{ComponentHealth, HealthRegistry};
+pub use pause::SourcePauses;
 pub(super) use running::RunningTopology;
 use tokio::sync::{mpsc, watch};
 use vector_buffers::topology::channel::{BufferReceiverStream, BufferSender};
@@ -0,0 +1,106 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use tokio::sync::watch;
+
+use crate::config::ComponentKey;
+
+/// Shared handle for pausing, and resuming, the sources of a running topology.
+///
+/// A paused source stops having its events forwarded downstream. Once the channel between the
+/// source and the rest of the topology is full, the source is backpressured, which means it stops
+/// pulling in new events while keeping its connections, and any other state, intact.
+///
+/// Sources are resumed when they're removed or changed by a reload, and when the topology is
+/// stopped, so they can finish sending the events they hold.
+#[derive(Clone, Debug, Default)]
+pub struct SourcePauses(Arc<Mutex<HashMap<ComponentKey, watch::Sender<bool>>>>);
+
+impl SourcePauses {
+    /// Pauses, or resumes, the given source.
+    ///
+    /// Returns whether or not the source was paused beforehand, or `None` if there is no such
+    /// source.
+    pub fn set_paused(&self, key: &ComponentKey, paused: bool) -> Option<bool> {
+        let sources = self.0.lock().expect("source pauses lock poisoned");
+        sources.get(key).map(|tx| tx.send_replace(paused))
+    }
+
+    /// Gets whether or not the given source is paused, or `None` if there is no such source.
+    pub fn is_paused(&self, key: &ComponentKey) -> Option<bool> {
+        let sources = self.0.lock().expect("source pauses lock poisoned");
+        sources.get(key).map(|tx| *tx.borrow())
+    }
+
+    pub(super) fn insert(&self, key: ComponentKey, tx: watch::Sender<bool>) {
+        let mut sources = self.0.lock().expect("source pauses lock poisoned");
+        sources.insert(key, tx);
+    }
+
+    /// Removes the given source, which resumes it if it was paused.
+    pub(super) fn remove(&self, key: &ComponentKey) {
+        let mut sources = self.0.lock().expect("source pauses lock poisoned");
+        sources.remove(key);
+    }
+
+    /// Removes all sources, which resumes any that were paused.
+    pub(super) fn clear(&self) {
+        let mut sources = self.0.lock().expect("source pauses lock poisoned");
+        sources.clear();
+    }
+}
+
+/// Waits until the source is resumed, or is no longer pausable at all.
+pub(super) async fn wait_until_resumed(rx: &mut watch::Receiver<bool>) {
+    while *rx.borrow() {
+        // The sender going away means the source was removed from `SourcePauses`, which resumes it.
+        if rx.changed().await.is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::FutureExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn pauses_and_resumes_sources() {
+        let pauses = SourcePauses::default();
+        let key = ComponentKey::from("in");
+        let (tx, mut rx) = watch::channel(false);
+        pauses.insert(key.clone(), tx);
+
+        assert_eq!(pauses.set_paused(&key, true), Some(false));
+        assert_eq!(pauses.is_paused(&key), Some(true));
+        assert!(wait_until_resumed(&mut rx).now_or_never().is_none());
+
+        assert_eq!(pauses.set_paused(&key, false), Some(true));
+        tokio::time::timeout(Duration::from_secs(1), wait_until_resumed(&mut rx))
+            .await
+            .expect("source should be resumed");
+
+        assert_eq!(pauses.set_paused(&ComponentKey::from("other"), true), None);
+    }
+
+    #[tokio::test]
+    async fn removing_source_resumes_it() {
+        let pauses = SourcePauses::default();
+        let key = ComponentKey::from("in");
+        let (tx, mut rx) = watch::channel(false);
+        pauses.insert(key.clone(), tx);
+        pauses.set_paused(&key, true);
+
+        pauses.remove(&key);
+        tokio::time::timeout(Duration::from_secs(1), wait_until_resumed(&mut rx))
+            .await
+            .expect("source should be resumed");
+        assert_eq!(pauses.is_paused(&key), None);
+    }
+}
@@ -23,11 +23,12 @@ use crate::{
     spawn_named,
     topology::{
         build_or_log_errors, builder,
-        builder::Pieces,
+        builder::{Pieces, TransformSwapTx},
         fanout::{ControlChannel, ControlMessage},
         handle_errors, retain, take_healthchecks,
         task::TaskOutput,
-        BuiltBuffer, TaskHandle, WatchRx, WatchTx,
+        BuiltBuffer, ComponentHealth, HealthRegistry, SinkDrains, SourcePauses, TaskHandle,
+        WatchRx, WatchTx,
     },
 };
 
@@ -41,10 +42,14 @@ pub struct RunningTopology {
     tasks: HashMap<ComponentKey, TaskHandle>,
     shutdown_coordinator: SourceShutdownCoordinator,
     detach_triggers: HashMap<ComponentKey, DisabledTrigger>,
+    transform_swaps: HashMap<ComponentKey, TransformSwapTx>,
     pub(crate) config: Config,
     abort_tx: mpsc::UnboundedSender<()>,
     watch: (WatchTx, WatchRx),
     pub(crate) running: Arc<AtomicBool>,
+    pub(crate) source_pauses: SourcePauses,
+    pub(crate) sink_drains: SinkDrains,
+    pub(crate) component_health: HealthRegistry,
     graceful_shutdown_duration: Option<Duration>,
 }
 
@@ -57,11 +62,15 @@ impl RunningTopology {
             outputs_tap_metadata: HashMap::new(),
             shutdown_coordinator: SourceShutdownCoordinator::default(),
             detach_triggers: HashMap::new(),
+            transform_swaps: HashMap::new(),
             source_tasks: HashMap::new(),
             tasks: HashMap::new(),
             abort_tx,
             watch: watch::channel(TapResource::default()),
             running: Arc::new(AtomicBool::new(true)),
+            source_pauses: SourcePauses::default(),
+            sink_drains: SinkDrains::default(),
+            component_health: HealthRegistry::default(),
             graceful_shutdown_duration: config.graceful_shutdown_duration,
             config,
         }
@@ -106,6 +115,10 @@ impl RunningTopology {
     pub fn stop(self) -> impl Future<Output = ()> {
         // Update the API's health endpoint to signal shutdown
         self.running.store(false, Ordering::Relaxed);
+        // Resume any paused sources, so they can finish sending the events they hold.
+        self.source_pauses.clear();
+        // Resume any drained sinks, so they can send the events left in their buffer.
+        self.sink_drains.clear();
         // Create handy handles collections of all tasks for the subsequent
         // operations.
         let mut wait_handles = Vec::new();
@@ -232,8 +245,20 @@ impl RunningTopology {
         // shutdown any components that are changing so that we can reclaim their buffers before
         // spawning the new version of the component.
         //
+        // Changed transforms that can be swapped in place are left running instead, so that they
+        // keep their input buffer and outputs, and the events flowing through them aren't held up.
+        // Transforms whose outputs have different schema definitions are rebuilt, along with the
+        // rest of the changed components.
+        //
         // We also shutdown any component that is simply being removed entirely.
-        let diff = ConfigDiff::new(&self.config, &new_config);
+        let diff = ConfigDiff::new(&self.config, &new_config).with_swapped_transforms(
+            &self.config,
+            &new_config,
+            |key| {
+                self.transform_swaps.contains_key(key)
+                    && builder::transform_outputs_unchanged(&self.config, &new_config, key)
+            },
+        );
         let buffers = self.shutdown_diff(&diff, &new_config).await;
 
         // Gives windows some time to make available any port
@@ -300,14 +325,27 @@ impl RunningTopology {
         if options.enabled {
             let healthchecks = take_healthchecks(diff, pieces)
                 .into_iter()
-                .map(|(_, task)| task);
-            let healthchecks = future::try_join_all(healthchecks);
+                .map(|(key, task)| {
+                    let component_health = self.component_health.clone();
+                    component_health.set(key.clone(), ComponentHealth::Starting);
+                    task.inspect(move |result| {
+                        let health = if result.is_ok() {
+                            ComponentHealth::Healthy
+                        } else {
+                            ComponentHealth::Unhealthy
+                        };
+                        component_health.set(key, health);
+                    })
+                })
+                .collect::<Vec<_>>();
+            // Wait on every healthcheck rather than bailing on the first failure, so that each
+            // sink's health gets recorded.
+            let healthchecks =
+                future::join_all(healthchecks).map(|results| results.iter().all(Result::is_ok));
 
             info!("Running healthchecks.");
             if options.require_healthy {
-                let success = healthchecks.await;
-
-                if success.is_ok() {
+                if healthchecks.await {
                     info!("All healthchecks passed.");
                     true
                 } else {
@@ -319,6 +357,10 @@ impl RunningTopology {
                 true
             }
         } else {
+            for key in diff.sinks.changed_and_added() {
+                self.component_health
+                    .set(key.clone(), ComponentHealth::Healthy);
+            }
             true
         }
     }
@@ -345,6 +387,8 @@ impl RunningTopology {
                 drop(previous); // detach and forget
 
                 self.remove_outputs(key);
+                self.source_pauses.remove(key);
+                self.component_health.remove(key);
                 source_shutdown_handles
                     .push(self.shutdown_coordinator.shutdown_source(key, deadline));
             }
@@ -353,6 +397,7 @@ impl RunningTopology {
                 debug!(component = %key, "Changing source.");
 
                 self.remove_outputs(key);
+                self.source_pauses.remove(key);
                 source_shutdown_handles
                     .push(self.shutdown_coordinator.shutdown_source(key, deadline));
             }
@@ -386,6 +431,7 @@ impl RunningTopology {
 
             self.remove_inputs(key, diff, new_config).await;
             self.remove_outputs(key);
+            self.transform_swaps.remove(key);
         }
 
         for key in &diff.transforms.to_change {
@@ -393,6 +439,7 @@ impl RunningTopology {
 
             self.remove_inputs(key, diff, new_config).await;
             self.remove_outputs(key);
+            self.transform_swaps.remove(key);
         }
 
         // Now we'll process any changed/removed sinks.
@@ -450,6 +497,9 @@ impl RunningTopology {
         for key in &diff.sinks.to_remove {
             debug!(component = %key, "Removing sink.");
             self.remove_inputs(key, diff, new_config).await;
+            self.remove_outputs(key);
+            self.sink_drains.remove(key);
+            self.component_health.remove(key);
         }
 
         // After that, for any changed sinks, we temporarily detach their inputs (not remove) so
@@ -478,6 +528,8 @@ impl RunningTopology {
                 buffer_tx.insert(key.clone(), self.inputs.get(key).unwrap().clone());
             }
             self.remove_inputs(key, diff, new_config).await;
+            self.remove_outputs(key);
+            self.sink_drains.remove(key);
         }
 
         // Now that we've disconnected or temporarily detached the inputs to all changed/removed
@@ -543,7 +595,8 @@ impl RunningTopology {
             }
 
             for key in &diff.sinks.to_remove {
-                // Sinks only have inputs
+                // Sinks can have a dead-letter output
+                self.outputs_tap_metadata.remove(key);
                 self.inputs_tap_metadata.remove(key);
             }
 
@@ -561,6 +614,18 @@ impl RunningTopology {
                 }
             }
 
+            for key in diff.sinks.changed_and_added() {
+                match new_pieces.tasks.get(key) {
+                    Some(task) if new_pieces.outputs.contains_key(key) => {
+                        self.outputs_tap_metadata
+                            .insert(key.clone(), ("sink", task.typetag().to_string()));
+                    }
+                    _ => {
+                        self.outputs_tap_metadata.remove(key);
+                    }
+                }
+            }
+
             for (key, input) in &new_pieces.inputs {
                 self.inputs_tap_metadata
                     .insert(key.clone(), input.1.clone());
@@ -581,6 +646,14 @@ impl RunningTopology {
             self.setup_outputs(key, new_pieces).await;
         }
 
+        // Sinks with a dead-letter output can also feed transforms and other sinks.
+        for key in diff.sinks.changed_and_added() {
+            if new_pieces.outputs.contains_key(key) {
+                debug!(component = %key, "Configuring outputs for sink.");
+                self.setup_outputs(key, new_pieces).await;
+            }
+        }
+
         // Now that all possible outputs are configured, we can start wiring up inputs, starting
         // with transforms.
         for key in diff.transforms.changed_and_added() {
@@ -630,6 +703,7 @@ impl RunningTopology {
 
             let mut removals = diff.sources.to_remove.clone();
             removals.extend(diff.transforms.to_remove.iter().cloned());
+            removals.extend(diff.sinks.to_remove.iter().cloned());
             self.watch
                 .0
                 .send(TapResource {
@@ -645,8 +719,8 @@ impl RunningTopology {
                         .changed_and_added()
                         .map(|key| key.to_string())
                         .collect(),
-                    // Note, only sources and transforms are relevant. Sinks do
-                    // not have outputs to tap.
+                    // Note, only sources, transforms, and sinks with a dead-letter
+                    // output are relevant. Other sinks do not have outputs to tap.
                     removals,
                 })
                 .expect("Couldn't broadcast config changes.");
@@ -814,6 +888,12 @@ impl RunningTopology {
             self.spawn_transform(key, &mut new_pieces);
         }
 
+        for key in &diff.transforms.to_swap {
+            debug!(message = "Swapping changed transform in place.", key = %key);
+            let transform = new_pieces.swapped_transforms.remove(key).unwrap();
+            _ = self.transform_swaps[key].send(transform);
+        }
+
         for key in &diff.sinks.to_change {
             debug!(message = "Spawning changed sink.", key = %key);
             self.spawn_sink(key, &mut new_pieces);
@@ -854,6 +934,9 @@ impl RunningTopology {
             group_id.attach_to_span(&task_span);
         }
 
+        self.sink_drains
+            .insert(key.clone(), new_pieces.sink_drains.remove(key).unwrap());
+
         let task_name = format!(">> {} ({})", task.typetag(), task.id());
         let task = handle_errors(task, self.abort_tx.clone()).instrument(task_span);
         let spawned = spawn_named(task, task_name.as_ref());
@@ -891,6 +974,10 @@ impl RunningTopology {
             group_id.attach_to_span(&task_span);
         }
 
+        if let Some(swap_tx) = new_pieces.transform_swaps.remove(key) {
+            self.transform_swaps.insert(key.clone(), swap_tx);
+        }
+
         let task_name = format!(">> {} ({}) >>", task.typetag(), task.id());
         let task = handle_errors(task, self.abort_tx.clone()).instrument(task_span);
         let spawned = spawn_named(task, task_name.as_ref());
@@ -938,6 +1025,10 @@ impl RunningTopology {
 
         self.shutdown_coordinator
             .takeover_source(key, &mut new_pieces.shutdown_coordinator);
+        self.source_pauses
+            .insert(key.clone(), new_pieces.source_pauses.remove(key).unwrap());
+        self.component_health
+            .set(key.clone(), ComponentHealth::Healthy);
 
         // Now spawn the actual source task.
         let source_task = new_pieces.source_tasks.remove(key).unwrap();
@@ -968,5 +1059,14 @@ fn get_changed_outputs(diff: &ConfigDiff, output_ids: Inputs<OutputId>) -> Vec<O
         );
     }
 
+    for sink_key in &diff.sinks.to_change {
+        changed_outputs.extend(
+            output_ids
+                .iter()
+                .filter(|id| &id.component == sink_key)
+                .cloned(),
+        );
+    }
+
     changed_outputs
 }
@@ -6,7 +6,9 @@ use vector_core::config::SourceOutput;
 pub(super) use crate::schema::Definition;
 
 use crate::{
-    config::{ComponentKey, Config, OutputId, SinkConfig, SinkOuter, TransformOutput},
+    config::{
+        ComponentKey, Config, OutputId, SinkConfig, SinkOuter, TransformOutput, DEAD_LETTER_OUTPUT,
+    },
     topology,
 };
 
@@ -90,6 +92,12 @@ pub fn possible_definitions(
 
             definitions.append(&mut transform_definition);
         }
+
+        // If the input is the dead-letter output of a sink, the events can be of any shape, as
+        // they are the ones the sink received, annotated with the details of the failure.
+        if config.is_dead_letter_output(input) {
+            definitions.push((input.clone(), Definition::any()));
+        }
     }
 
     Ok(definitions)
@@ -193,6 +201,11 @@ pub(super) fn expanded_definitions(
             // Append whatever number of additional pipelines we created to the existing
             // pipeline definitions.
             definitions.append(&mut transform_definition);
+
+        // The dead-letter output of a sink sends the events the sink received, annotated with the
+        // details of the failure, so they can be of any shape.
+        } else if config.is_dead_letter_output(input) {
+            definitions.push((input.clone(), Definition::any()));
         }
     }
 
@@ -283,6 +296,12 @@ pub(crate) fn input_definitions(
 
             definitions.append(&mut transform_definitions);
         }
+
+        // If the input is the dead-letter output of a sink, the events can be of any shape, as
+        // they are the ones the sink received, annotated with the details of the failure.
+        if config.is_dead_letter_output(input) {
+            definitions.push((input.clone(), Definition::any()));
+        }
     }
 
     Ok(definitions)
@@ -356,6 +375,11 @@ pub trait ComponentContainer {
         input_definitions: &[(OutputId, Definition)],
     ) -> Option<Vec<TransformOutput>>;
 
+    /// Returns whether the given output is the dead-letter output of a sink.
+    fn is_dead_letter_output(&self, _output: &OutputId) -> bool {
+        false
+    }
+
     /// Gets the transform output for the given port.
     ///
     /// Returns Err(()) if there is no transform with the given key
@@ -435,6 +459,13 @@ impl ComponentContainer for Config {
             )
         })
     }
+
+    fn is_dead_letter_output(&self, output: &OutputId) -> bool {
+        output.port.as_deref() == Some(DEAD_LETTER_OUTPUT)
+            && self
+                .sink(&output.component)
+                .map_or(false, |sink| sink.dead_letter.enabled)
+    }
 }
 
 #[cfg(test)]
@@ -100,6 +100,7 @@ async fn buffer_drop_fan_out() {
     sink_outer.buffer = BufferConfig::Single(BufferType::Memory {
         max_events: MEMORY_BUFFER_DEFAULT_MAX_EVENTS,
         when_full: WhenFull::DropNewest,
+        max_age_secs: None,
     });
     config.add_sink_outer("out2", sink_outer);
 
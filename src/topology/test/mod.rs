@@ -8,7 +8,7 @@ use std::{
 };
 
 use crate::{
-    config::{Config, ConfigDiff, SinkOuter},
+    config::{ComponentKey, Config, ConfigDiff, SinkOuter},
     event::{into_event_stream, Event, EventArray, EventContainer, LogEvent},
     test_util::{
         mock::{
@@ -18,14 +18,14 @@ use crate::{
         },
         start_topology, trace_init,
     },
-    topology::{self, builder},
+    topology::{self, builder, SinkState},
 };
-use futures::{future, stream, StreamExt};
+use futures::{future, stream, FutureExt, StreamExt};
 use tokio::{
     task::yield_now,
     time::{sleep, Duration},
 };
-use vector_buffers::{BufferConfig, BufferType, WhenFull};
+use vector_buffers::{BufferConfig, BufferType, DiskBufferCompression, WhenFull};
 use vector_core::config::OutputId;
 
 mod backpressure;
@@ -336,6 +336,46 @@ async fn topology_remove_one_sink() {
     assert_eq!(Vec::<Event>::new(), res2);
 }
 
+#[tokio::test]
+async fn topology_drain_and_resume_sink() {
+    trace_init();
+
+    let (mut in1, source1) = basic_source();
+    let (out1, sink1) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_sink("out1", &["in1"], sink1);
+
+    let (topology, _) = start_topology(config.build().unwrap(), false).await;
+    let mut out1 = out1.flat_map(into_message_stream).boxed();
+    let key = ComponentKey::from("out1");
+
+    in1.send_event(Event::Log(LogEvent::from("before")))
+        .await
+        .unwrap();
+    assert_eq!(out1.next().await.as_deref(), Some("before"));
+
+    assert_eq!(topology.sink_drains.drain(&key), Some(SinkState::Running));
+    while topology.sink_drains.state(&key) != Some(SinkState::Stopped) {
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    // Events sent while the sink is stopped are kept in its buffer.
+    in1.send_event(Event::Log(LogEvent::from("during")))
+        .await
+        .unwrap();
+    sleep(Duration::from_millis(50)).await;
+    assert!(out1.next().now_or_never().is_none());
+
+    assert_eq!(topology.sink_drains.resume(&key), Some(SinkState::Stopped));
+    assert_eq!(out1.next().await.as_deref(), Some("during"));
+    assert_eq!(topology.sink_drains.state(&key), Some(SinkState::Running));
+
+    topology.stop().await;
+    assert_eq!(out1.next().await, None);
+}
+
 #[tokio::test]
 async fn topology_remove_one_transform() {
     trace_init();
@@ -503,6 +543,82 @@ async fn topology_swap_transform() {
     assert_eq!(Vec::<String>::new(), res2);
 }
 
+#[tokio::test]
+async fn topology_swap_transform_in_place() {
+    trace_init();
+
+    let (mut in1, source1) = basic_source();
+    let (out1, sink1) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_transform("t1", &["in1"], basic_transform(" v1", 0.0));
+    config.add_sink("out1", &["t1"], sink1);
+
+    let (mut topology, _) = start_topology(config.build().unwrap(), false).await;
+    let mut out1 = out1.flat_map(into_message_stream).boxed();
+
+    in1.send_event(Event::Log(LogEvent::from("before")))
+        .await
+        .unwrap();
+    assert_eq!(out1.next().await.as_deref(), Some("before v1"));
+
+    // Only the options of the transform change, so it's swapped in place, and keeps running
+    // between the same source and sink.
+    let mut config = Config::builder();
+    config.add_source("in1", basic_source().1);
+    config.add_transform("t1", &["in1"], basic_transform(" v2", 0.0));
+    config.add_sink("out1", &["t1"], basic_sink(10).1);
+
+    assert!(topology
+        .reload_config_and_respawn(config.build().unwrap())
+        .await
+        .unwrap());
+
+    in1.send_event(Event::Log(LogEvent::from("after")))
+        .await
+        .unwrap();
+    assert_eq!(out1.next().await.as_deref(), Some("after v2"));
+
+    drop(in1);
+    topology.stop().await;
+    assert_eq!(out1.next().await, None);
+}
+
+#[cfg(feature = "transforms-remap")]
+#[tokio::test]
+async fn topology_swap_transform_in_place_requires_same_output_definitions() {
+    use crate::transforms::remap::RemapConfig;
+
+    let config = |source: &str| {
+        let mut config = Config::builder();
+        config.add_source("in1", basic_source().1);
+        config.add_transform(
+            "t1",
+            &["in1"],
+            RemapConfig {
+                source: Some(source.to_owned()),
+                ..Default::default()
+            },
+        );
+        config.add_sink("out1", &["t1"], basic_sink(10).1);
+        config.build().unwrap()
+    };
+    let key = ComponentKey::from("t1");
+    let old = config(".tag = 1");
+
+    assert!(builder::transform_outputs_unchanged(
+        &old,
+        &config(".tag = 2"),
+        &key
+    ));
+    assert!(!builder::transform_outputs_unchanged(
+        &old,
+        &config(".tag = \"one\""),
+        &key
+    ));
+}
+
 #[tokio::test]
 async fn topology_swap_sink() {
     trace_init();
@@ -809,6 +925,9 @@ async fn topology_disk_buffer_flushes_on_idle() {
     sink1_outer.buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: std::num::NonZeroU64::new(268435488).unwrap(),
         when_full: WhenFull::DropNewest,
+        max_age_secs: None,
+        compression: DiskBufferCompression::None,
+        encryption: None,
     });
     config.add_sink_outer("out1", sink1_outer);
 
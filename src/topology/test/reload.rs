@@ -7,7 +7,7 @@ use std::{
 use futures::StreamExt;
 use tokio::time::sleep;
 use tokio_stream::wrappers::UnboundedReceiverStream;
-use vector_buffers::{BufferConfig, BufferType, WhenFull};
+use vector_buffers::{BufferConfig, BufferType, DiskBufferCompression, WhenFull};
 use vector_core::config::ComponentKey;
 
 use crate::{
@@ -188,6 +188,9 @@ async fn topology_disk_buffer_conflict() {
     old_config.sinks[&sink_key].buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: NonZeroU64::new(268435488).unwrap(),
         when_full: WhenFull::Block,
+        max_age_secs: None,
+        compression: DiskBufferCompression::None,
+        encryption: None,
     });
 
     let mut new_config = old_config.clone();
@@ -195,6 +198,9 @@ async fn topology_disk_buffer_conflict() {
     new_config.sinks[&sink_key].buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: NonZeroU64::new(268435488).unwrap(),
         when_full: WhenFull::Block,
+        max_age_secs: None,
+        compression: DiskBufferCompression::None,
+        encryption: None,
     });
 
     reload_sink_test(
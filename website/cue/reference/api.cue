@@ -47,6 +47,100 @@ api: {
 				of the address set using the `bind` parameter.
 				"""
 		}
+		metrics: {
+			common:   false
+			required: false
+			type: bool: default: true
+			description: """
+				Whether Vector's internal metrics are exposed on the `/metrics` endpoint,
+				in the Prometheus text exposition format. This makes every instance with
+				the API enabled scrapable, without configuring an `internal_metrics`
+				source and a `prometheus_exporter` sink.
+				"""
+		}
+		allow_mutations: {
+			common:   false
+			required: false
+			type: bool: default: false
+			description: """
+				Whether GraphQL mutations are enabled for the API. Mutations allow
+				pausing and resuming sources, draining and resuming sinks, reloading
+				the configuration, resetting the counters of a component, and capping
+				the number of events sampled by `vector tap`. They're also used by
+				`vector control`.
+				"""
+		}
+		readiness_buffer_threshold: {
+			common:   false
+			required: false
+			type: float: default: 0.9
+			description: """
+				The utilization of a sink's buffer, by events or by bytes, above which
+				the `/health/ready` endpoint reports Vector as not ready. Must be
+				between `0` and `1`.
+				"""
+		}
+		auth: {
+			common:   false
+			required: false
+			description: "Authentication for the API."
+			type: object: options: {
+				token: {
+					required: true
+					type: string: {
+						examples: ["${VECTOR_API_TOKEN}"]
+					}
+					description: """
+						The bearer token that must be set in the `Authorization` header
+						of requests to the `/graphql` endpoint, including the WebSocket
+						connections used for subscriptions by `vector top` and
+						`vector tap`, and of requests to the `/metrics` endpoint. The
+						`/health` endpoints remain available without it.
+						"""
+				}
+			}
+		}
+		tls: {
+			common:   false
+			required: false
+			description: """
+				Serves the API over TLS. Setting `verify_certificate` to `true`, along
+				with a `ca_file`, requires clients to present a certificate issued by
+				that CA (mutual TLS).
+				"""
+			type: object: options: {
+				enabled: {
+					required: false
+					type: bool: default: false
+					description: "Whether or not to serve the API over TLS."
+				}
+				crt_file: {
+					required: false
+					type: string: examples: ["/path/to/host_certificate.crt"]
+					description: "Absolute path to the certificate file identifying the API server."
+				}
+				key_file: {
+					required: false
+					type: string: examples: ["/path/to/host_certificate.key"]
+					description: "Absolute path to the private key of the certificate."
+				}
+				key_pass: {
+					required: false
+					type: string: examples: ["${KEY_PASS_ENV_VAR}"]
+					description: "Passphrase used to unlock the encrypted key file."
+				}
+				ca_file: {
+					required: false
+					type: string: examples: ["/path/to/certificate_authority.crt"]
+					description: "Absolute path to the CA certificate that client certificates are verified against."
+				}
+				verify_certificate: {
+					required: false
+					type: bool: default: false
+					description: "Whether or not to require, and verify, client certificates."
+				}
+			}
+		}
 	}
 
 	endpoints: {
@@ -82,6 +176,59 @@ api: {
 				}
 			}
 		}
+		"/health/live": {
+			GET: {
+				description: """
+					Liveness endpoint. Same as `/health`, for orchestrators
+					that probe liveness and readiness separately.
+					"""
+				responses: {
+					"200": {
+						description: "Vector is initialized and running."
+					}
+					"503": {
+						description: "Vector is shutting down."
+					}
+				}
+			}
+		}
+		"/health/ready": {
+			GET: {
+				description: """
+					Readiness endpoint. Vector is ready once the healthchecks
+					of its sinks have passed and its sources are running, as
+					long as no sink's buffer is fuller than
+					`readiness_buffer_threshold`. The response lists the
+					components that are `starting` or `unhealthy`, and the
+					utilization of the `buffers` above the threshold.
+					"""
+				responses: {
+					"200": {
+						description: "Vector is ready to receive events."
+					}
+					"503": {
+						description: """
+							Vector is starting, shutting down, has unhealthy
+							components, or has buffers above the threshold.
+							"""
+					}
+				}
+			}
+		}
+		"/metrics": {
+			GET: {
+				description: """
+					Vector's internal metrics, in the Prometheus text
+					exposition format. Available unless `metrics` is set
+					to `false`.
+					"""
+				responses: {
+					"200": {
+						description: "The internal metrics of the Vector instance."
+					}
+				}
+			}
+		}
 		"/playground": {
 			GET: {
 				description: """
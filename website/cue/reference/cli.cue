@@ -184,6 +184,101 @@ cli: {
 	options: _core_options
 
 	commands: {
+		"buffer": {
+			description: """
+				Inspect the disk buffer of a sink, while Vector is stopped. The `status` command shows
+				how many events the buffer holds and how old they are, `dump` writes its events to
+				stdout as newline-delimited JSON, `truncate` deletes the buffer, `move` moves it aside,
+				and `restore` moves a buffer that was moved aside back in place so that its events are
+				sent the next time Vector starts.
+				"""
+
+			example: "vector buffer status --data-dir /var/lib/vector my_sink"
+
+			options: {
+				"data-dir": {
+					description: "The data directory of Vector, as set by the global `data_dir` option"
+					type:        "string"
+					default:     "/var/lib/vector/"
+					env_var:     "VECTOR_DATA_DIR"
+				}
+				"encryption-key": {
+					description: "The key an encrypted buffer is encrypted with, as set by the `buffer.encryption.key` option of the sink. Only used by `dump`."
+					type:        "string"
+					env_var:     "VECTOR_BUFFER_ENCRYPTION_KEY"
+				}
+			}
+
+			args: {
+				command: {
+					description: "The operation to perform: `status`, `dump`, `truncate`, `move`, or `restore`"
+					type:        "string"
+				}
+				component_id: {
+					description: "The ID of the sink the disk buffer belongs to"
+					type:        "string"
+				}
+			}
+		}
+
+		"control": {
+			description: """
+				Pause and resume sources, or drain and resume sinks, of a local or remote Vector
+				instance, through its API. The API must allow mutations. A paused source stops
+				sending events downstream but keeps its connections open. A drained sink finishes
+				sending the events it pulled from its buffer and then stops, while its inputs keep
+				writing to its buffer; once resumed, it carries on from where it stopped in its
+				buffer, so that no event is lost or sent twice.
+				"""
+
+			example: "vector control drain-sink --wait my_sink"
+
+			flags: _default_flags & {
+				"wait": {
+					description: "Wait until the sink has stopped. Only used by `drain-sink`."
+				}
+			}
+
+			options: {
+				"url": {
+					_short:      "u"
+					description: "Vector GraphQL API server endpoint"
+					type:        "string"
+				}
+				"api-token": {
+					description: "Bearer token to present to the Vector API server, as set in its `api.auth.token` option"
+					type:        "string"
+					env_var:     "VECTOR_API_TOKEN"
+				}
+				"api-ca-file": {
+					description: "CA certificate (PEM) to verify the Vector API server's certificate with, in addition to the system's trusted certificates"
+					type:        "string"
+					env_var:     "VECTOR_API_CA_FILE"
+				}
+				"api-crt-file": {
+					description: "Client certificate (PEM) to present to the Vector API server, for mutual TLS"
+					type:        "string"
+					env_var:     "VECTOR_API_CRT_FILE"
+				}
+				"api-key-file": {
+					description: "Private key (PEM, PKCS#8) of the client certificate"
+					type:        "string"
+					env_var:     "VECTOR_API_KEY_FILE"
+				}
+			}
+
+			args: {
+				command: {
+					description: "The operation to perform: `pause-source`, `resume-source`, `drain-sink`, or `resume-sink`"
+					type:        "string"
+				}
+				component_id: {
+					description: "The ID of the source or sink"
+					type:        "string"
+				}
+			}
+		}
+
 		"graph": {
 			description: """
 				Generate a visual representation of topologies. The output is in the [DOT format](\(urls.dot_format)),
@@ -338,6 +433,26 @@ cli: {
 					description: "Components (sources, transforms) to observe for their inputs (comma-separated; accepts glob patterns)"
 					type:        "list"
 				}
+				"api-token": {
+					description: "Bearer token to present to the Vector API server, as set in its `api.auth.token` option"
+					type:        "string"
+					env_var:     "VECTOR_API_TOKEN"
+				}
+				"api-ca-file": {
+					description: "CA certificate (PEM) to verify the Vector API server's certificate with, in addition to the system's trusted certificates"
+					type:        "string"
+					env_var:     "VECTOR_API_CA_FILE"
+				}
+				"api-crt-file": {
+					description: "Client certificate (PEM) to present to the Vector API server, for mutual TLS"
+					type:        "string"
+					env_var:     "VECTOR_API_CRT_FILE"
+				}
+				"api-key-file": {
+					description: "Private key (PEM, PKCS#8) of the client certificate"
+					type:        "string"
+					env_var:     "VECTOR_API_KEY_FILE"
+				}
 			}
 
 			args: {
@@ -387,6 +502,26 @@ cli: {
 					description: "The URL for the GraphQL endpoint of the running Vector instance"
 					type:        "string"
 				}
+				"api-token": {
+					description: "Bearer token to present to the Vector API server, as set in its `api.auth.token` option"
+					type:        "string"
+					env_var:     "VECTOR_API_TOKEN"
+				}
+				"api-ca-file": {
+					description: "CA certificate (PEM) to verify the Vector API server's certificate with, in addition to the system's trusted certificates"
+					type:        "string"
+					env_var:     "VECTOR_API_CA_FILE"
+				}
+				"api-crt-file": {
+					description: "Client certificate (PEM) to present to the Vector API server, for mutual TLS"
+					type:        "string"
+					env_var:     "VECTOR_API_CRT_FILE"
+				}
+				"api-key-file": {
+					description: "Private key (PEM, PKCS#8) of the client certificate"
+					type:        "string"
+					env_var:     "VECTOR_API_KEY_FILE"
+				}
 			}
 		}
 
@@ -12,6 +12,61 @@ base: components: sinks: configuration: {
 			"""
 		required: false
 		type: object: options: {
+			compression: {
+				description:   "Compression applied to the events written to a disk buffer."
+				relevant_when: "type = \"disk\""
+				required:      false
+				type: string: {
+					default: "none"
+					enum: {
+						none: "Events are stored uncompressed."
+						zstd: """
+														Events are compressed with [Zstandard][zstd].
+
+														This trades some CPU usage for less disk usage and I/O, which is typically worthwhile
+														for repetitive data, such as JSON logs. Events that don't get any smaller when compressed
+														are stored uncompressed.
+
+														[zstd]: https://facebook.github.io/zstd/
+														"""
+					}
+				}
+			}
+			encryption: {
+				description:   "Encryption of the events written to a disk buffer."
+				relevant_when: "type = \"disk\""
+				required:      false
+				type: object: options: {
+					key: {
+						description: """
+							The key to encrypt events with, using AES-256-GCM.
+
+							Must be 32 bytes, encoded as base64, such as the output of `openssl rand -base64 32`. To
+							avoid storing the key in the configuration itself, load it with the [secrets][secrets]
+							functionality.
+
+							Events already in the buffer can only be read with the key they were written with, so
+							changing the key discards them.
+
+							[secrets]: https://vector.dev/docs/reference/configuration/global-options/#secret
+							"""
+						required: true
+						type: string: examples: ["SECRET[backend.buffer_key]"]
+					}
+				}
+			}
+			max_age_secs: {
+				description: """
+					The maximum amount of time, in seconds, that events can spend in the buffer.
+
+					Events that have been in the buffer for longer than this are discarded when they are
+					read from the buffer, instead of being sent on to the sink.
+
+					By default, events do not expire.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
 			max_events: {
 				description:   "The maximum number of events allowed in the buffer."
 				relevant_when: "type = \"memory\""
@@ -71,6 +126,33 @@ base: components: sinks: configuration: {
 														highest priority, and it is preferable to temporarily lose events rather than cause a
 														slowdown in the acceptance/consumption of events.
 														"""
+						drop_oldest: """
+														Drops the oldest events in the buffer to make room for the event.
+
+														Events that have been waiting in the buffer the longest are intentionally dropped, so that
+														the most recent events are always kept. This mode is typically used for real-time data, such
+														as metrics, where recent data is more valuable than data that has fallen behind.
+
+														Disk buffers make room one data file at a time, starting with the oldest data file the
+														sink has not started reading yet. If there is no such data file, the event is dropped
+														instead, as with `drop_newest`.
+														"""
+						overflow: """
+														Overflows to the next stage in the buffer topology.
+
+														If the current buffer stage is full, attempt to send this event to the next buffer stage.
+														That stage may also be configured overflow, and so on, but ultimately the last stage in a
+														buffer topology must use one of the other handling behaviors. This means that next stage may
+														potentially be able to buffer the event, but it may also block or drop the event.
+
+														Once an event has overflowed, subsequent events are sent to the next stage as well, until
+														all of the events that overflowed have been read back out of it. Events are read out of the
+														buffer in the order they were sent, so, for example, a memory stage overflowing to a disk
+														stage only spills to disk when the sink falls behind, and returns to memory once the sink
+														has caught up.
+
+														This mode can only be used when two or more buffer stages are configured.
+														"""
 					}
 				}
 			}
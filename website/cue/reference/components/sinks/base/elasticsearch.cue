@@ -681,6 +681,9 @@ base: components: sinks: elasticsearch: configuration: {
 		description: """
 			Whether or not to retry successful requests containing partial failures.
 
+			Only the documents that failed with a retriable error, such as backpressure, are sent again.
+			Otherwise, these documents are rejected like the ones that failed with any other error.
+
 			To avoid duplicates in Elasticsearch, please use option `id_key`.
 			"""
 		required: false
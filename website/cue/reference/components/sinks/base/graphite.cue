@@ -0,0 +1,263 @@
+package metadata
+
+base: components: sinks: graphite: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled for this sink.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: """
+				Whether or not end-to-end acknowledgements are enabled.
+
+				When enabled for a sink, any source connected to that sink, where the source supports
+				end-to-end acknowledgements as well, waits for events to be acknowledged by the sink
+				before acknowledging them at the source.
+
+				Enabling or disabling acknowledgements at the sink level takes precedence over any global
+				[`acknowledgements`][global_acks] configuration.
+
+				[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+				"""
+			required: false
+			type: bool: {}
+		}
+	}
+	address: {
+		description: """
+			The address to connect to.
+
+			Both IP addresses and hostnames/fully qualified domain names (FQDNs) are accepted formats.
+
+			The address _must_ include a port.
+			"""
+		required: true
+		type: string: examples: ["92.12.333.224:5000", "somehost:5000"]
+	}
+	batch: {
+		description: "Event batching behavior."
+		required:    false
+		type: object: options: {
+			max_bytes: {
+				description: "The maximum size of a batch before it is flushed."
+				required:    false
+				type: uint: unit: "bytes"
+			}
+			max_events: {
+				description: "The maximum size of a batch before it is flushed."
+				required:    false
+				type: uint: {
+					default: 1000
+					unit:    "events"
+				}
+			}
+			timeout_secs: {
+				description: "The maximum age of a batch before it is flushed."
+				required:    false
+				type: float: {
+					default: 1.0
+					unit:    "seconds"
+				}
+			}
+		}
+	}
+	default_namespace: {
+		description: """
+			Sets the default namespace for any metrics sent.
+
+			This namespace is only used if a metric has no existing namespace. When a namespace is
+			present, it is used as a prefix to the metric name, and separated with a period (`.`).
+			"""
+		required: false
+		type: string: examples: ["service"]
+	}
+	format: {
+		description: "The protocol that metrics are sent with."
+		required:    false
+		type: string: {
+			default: "plaintext"
+			enum: {
+				pickle: """
+					The [pickle protocol][pickle], which sends each request as a length-prefixed, pickled list
+					of data points.
+
+					This is more efficient than the plaintext protocol, and is only supported over TCP. Carbon
+					listens for it on port 2004 by default.
+
+					[pickle]: https://graphite.readthedocs.io/en/latest/feeding-carbon.html#the-pickle-protocol
+					"""
+				plaintext: """
+					The [plaintext protocol][plaintext], with one `<path> <value> <timestamp>` line per data
+					point.
+
+					Carbon listens for it on port 2003 by default.
+
+					[plaintext]: https://graphite.readthedocs.io/en/latest/feeding-carbon.html#the-plaintext-protocol
+					"""
+			}
+		}
+	}
+	keepalive: {
+		description:   "TCP keepalive settings for socket-based components."
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: object: options: time_secs: {
+			description: "The time to wait before starting to send TCP keepalive probes on an idle connection."
+			required:    false
+			type: uint: unit: "seconds"
+		}
+	}
+	mode: {
+		description: "The type of socket to use."
+		required:    true
+		type: string: enum: {
+			tcp: "Send over TCP."
+			udp: "Send over UDP."
+		}
+	}
+	quantiles: {
+		description: "The list of quantiles to calculate when sending distribution metrics."
+		required:    false
+		type: array: {
+			default: [0.5, 0.75, 0.9, 0.95, 0.99]
+			items: type: float: {}
+		}
+	}
+	send_buffer_size: {
+		description: """
+			The size of the socket's send buffer.
+
+			If set, the value of the setting is passed via the `SO_SNDBUF` option.
+			"""
+		required: false
+		type: uint: {
+			examples: [
+				65536,
+			]
+			unit: "bytes"
+		}
+	}
+	tag_format: {
+		description: "How metric tags are encoded."
+		required:    false
+		type: string: {
+			default: "path"
+			enum: {
+				path: """
+					Tags are flattened into the path, as `<name>.<key>.<value>` segments in the order of their
+					keys, followed by the statistic for metrics with several values.
+
+					This works with any version of Graphite, and with existing dashboards that are based on
+					paths.
+					"""
+				tagged: """
+					Tags are sent as [tagged series][tags], as in `<name>;<key>=<value>`.
+
+					This requires Graphite 1.1 or later.
+
+					[tags]: https://graphite.readthedocs.io/en/latest/tags.html
+					"""
+			}
+		}
+	}
+	tls: {
+		description:   "Configures the TLS options for incoming/outgoing connections."
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: object: options: {
+			alpn_protocols: {
+				description: """
+					Sets the list of supported ALPN protocols.
+
+					Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
+					that they are defined.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["h2"]
+			}
+			ca_file: {
+				description: """
+					Absolute path to an additional CA certificate file.
+
+					The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/certificate_authority.crt"]
+			}
+			crt_file: {
+				description: """
+					Absolute path to a certificate file used to identify this server.
+
+					The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+					an inline string in PEM format.
+
+					If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.crt"]
+			}
+			enabled: {
+				description: """
+					Whether or not to require TLS for incoming or outgoing connections.
+
+					When enabled and used for incoming connections, an identity certificate is also required. See `tls.crt_file` for
+					more information.
+					"""
+				required: false
+				type: bool: {}
+			}
+			key_file: {
+				description: """
+					Absolute path to a private key file used to identify this server.
+
+					The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.key"]
+			}
+			key_pass: {
+				description: """
+					Passphrase used to unlock the encrypted key file.
+
+					This has no effect unless `key_file` is set.
+					"""
+				required: false
+				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+			}
+			verify_certificate: {
+				description: """
+					Enables certificate verification.
+
+					If enabled, certificates must not be expired and must be issued by a trusted
+					issuer. This verification operates in a hierarchical manner, checking that the leaf certificate (the
+					certificate presented by the client/server) is not only valid, but that the issuer of that certificate is also valid, and
+					so on until the verification process reaches a root certificate.
+
+					Relevant for both incoming and outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Enables hostname verification.
+
+					If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+					the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+					Only relevant for outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+}
@@ -304,6 +304,28 @@ base: components: sinks: http: configuration: {
 			}
 		}
 	}
+	header_templates: {
+		description: """
+			Custom headers whose values are [templates][templates], rendered for each event and added
+			to each request.
+
+			Events are batched separately for each distinct set of rendered values. Events for which a
+			value can't be rendered, or isn't a valid header value, are dropped.
+
+			[templates]: https://vector.dev/docs/reference/configuration/template-syntax/
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				"X-Tenant": "{{ tenant }}"
+			}]
+			options: "*": {
+				description: "An HTTP request header and the template of its value."
+				required:    true
+				type: string: syntax: "template"
+			}
+		}
+	}
 	headers: {
 		deprecated:  true
 		description: "A list of custom headers to add to each request."
@@ -519,6 +541,66 @@ base: components: sinks: http: configuration: {
 			}
 		}
 	}
+	signature: {
+		description: "Configuration for signing HTTP requests with HMAC-SHA256."
+		required:    false
+		type: object: options: {
+			components: {
+				description: """
+					The parts of the request that are signed.
+
+					The signature is computed over a canonical string made of these parts, in the given order,
+					separated by newlines.
+					"""
+				required: false
+				type: array: {
+					default: ["method", "path", "timestamp", "body_digest"]
+					items: type: string: enum: {
+						body_digest: "The hex-encoded SHA-256 digest of the request body, as sent over the wire."
+						method:      "The HTTP method of the request, such as `POST`."
+						path:        "The path of the request URI, including the query string if there is one."
+						timestamp:   "The Unix timestamp, in seconds, at which the request was signed."
+					}
+				}
+			}
+			encoding: {
+				description: "The encoding of the signature."
+				required:    false
+				type: string: {
+					default: "hex"
+					enum: {
+						base64: "Standard Base64 encoding, with padding."
+						hex:    "Lowercase hexadecimal encoding."
+					}
+				}
+			}
+			secret: {
+				description: "The secret key that requests are signed with, shared by the sender and the receiver."
+				required:    true
+				type: string: {}
+			}
+			signature_header: {
+				description: "The name of the header that holds the signature."
+				required:    false
+				type: string: default: "X-Signature"
+			}
+			signature_prefix: {
+				description: "A prefix for the value of the signature header, such as `sha256=`."
+				required:    false
+				type: string: default: "sha256="
+			}
+			timestamp_header: {
+				description: """
+					The name of the header that holds the timestamp at which the request was signed.
+
+					The timestamp is sent even if it isn't part of the signed `components`. It must be signed
+					for requests to be verified, as it is used to reject replayed requests.
+					"""
+				required: false
+				type: string: default: "X-Signature-Timestamp"
+			}
+		}
+	}
 	tls: {
 		description: "TLS configuration."
 		required:    false
@@ -605,12 +687,28 @@ base: components: sinks: http: configuration: {
 		}
 	}
 	uri: {
-		description: """
-			The full URI to make HTTP requests to.
+		description: "The URI of the `http` sink."
+		required:    true
+		type: {
+			object: options: template: {
+				description: """
+					The [template][template] of the URI to make HTTP requests to.
 
-			This should include the protocol and host, but can also include the port, path, and any other valid part of a URI.
-			"""
-		required: true
-		type: string: examples: ["https://10.22.212.22:9000/endpoint"]
+					Events are batched separately for each distinct rendered URI. Events for which the URI
+					can't be rendered, or isn't a valid URI, are dropped. The URI can not contain credentials,
+					which have to be set with `auth` instead.
+
+					As with any template, a literal `%` has to be written as `%%`.
+
+					[template]: https://vector.dev/docs/reference/configuration/template-syntax/
+					"""
+				required: true
+				type: string: {
+					examples: ["https://10.22.212.22:9000/tenants/{{ tenant }}"]
+					syntax: "template"
+				}
+			}
+			string: examples: ["https://10.22.212.22:9000/endpoint"]
+		}
 	}
 }
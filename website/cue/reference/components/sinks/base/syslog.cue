@@ -0,0 +1,311 @@
+package metadata
+
+base: components: sinks: syslog: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled for this sink.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: """
+				Whether or not end-to-end acknowledgements are enabled.
+
+				When enabled for a sink, any source connected to that sink, where the source supports
+				end-to-end acknowledgements as well, waits for events to be acknowledged by the sink
+				before acknowledging them at the source.
+
+				Enabling or disabling acknowledgements at the sink level takes precedence over any global
+				[`acknowledgements`][global_acks] configuration.
+
+				[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+				"""
+			required: false
+			type: bool: {}
+		}
+	}
+	address: {
+		description: """
+			The address to connect to.
+
+			Both IP address and hostname are accepted formats.
+
+			The address _must_ include a port.
+			"""
+		relevant_when: "mode = \"tcp\" or mode = \"udp\""
+		required:      true
+		type: string: examples: ["92.12.333.224:5000", "https://somehost:5000"]
+	}
+	default_app_name: {
+		description: "The application name to use when the event has no `appname` field."
+		required:    false
+		type: string: default: "vector"
+	}
+	default_facility: {
+		description: "The facility to use when the event has no valid `facility` field."
+		required:    false
+		type: string: {
+			default: "user"
+			enum: {
+				alert:    "Log alert."
+				audit:    "Log audit."
+				auth:     "Security/authorization messages."
+				authpriv: "Security/authorization messages (private)."
+				clockd:   "Clock daemon (note 2)."
+				cron:     "Clock daemon."
+				daemon:   "System daemons."
+				ftp:      "FTP daemon."
+				kern:     "Kernel messages."
+				local0:   "Local use 0."
+				local1:   "Local use 1."
+				local2:   "Local use 2."
+				local3:   "Local use 3."
+				local4:   "Local use 4."
+				local5:   "Local use 5."
+				local6:   "Local use 6."
+				local7:   "Local use 7."
+				lpr:      "Line printer subsystem."
+				mail:     "Mail system."
+				news:     "Network news subsystem."
+				ntp:      "NTP subsystem."
+				syslog:   "Messages generated internally by syslogd."
+				user:     "User-level messages."
+				uucp:     "UUCP subsystem."
+			}
+		}
+	}
+	default_severity: {
+		description: "The severity to use when the event has no valid `severity` field."
+		required:    false
+		type: string: {
+			default: "info"
+			enum: {
+				alert:   "Action must be taken immediately."
+				crit:    "Critical conditions."
+				debug:   "Debug-level messages."
+				emerg:   "System is unusable."
+				err:     "Error conditions."
+				info:    "Informational messages."
+				notice:  "Normal but significant conditions."
+				warning: "Warning conditions."
+			}
+		}
+	}
+	encoding: {
+		description: "Transformations to prepare an event for serialization."
+		required:    false
+		type: object: options: {
+			except_fields: {
+				description: "List of fields that are excluded from the encoded event."
+				required:    false
+				type: array: items: type: string: {}
+			}
+			only_fields: {
+				description: "List of fields that are included in the encoded event."
+				required:    false
+				type: array: items: type: string: {}
+			}
+			timestamp_format: {
+				description: "Format used for timestamp fields."
+				required:    false
+				type: string: enum: {
+					rfc3339: "Represent the timestamp as a RFC 3339 timestamp."
+					unix:    "Represent the timestamp as a Unix timestamp."
+				}
+			}
+		}
+	}
+	framing: {
+		description:   "How syslog messages are framed on stream-based transports."
+		relevant_when: "mode = \"tcp\" or mode = \"unix\""
+		required:      false
+		type: string: {
+			default: "octet_counting"
+			enum: {
+				newline_delimited: """
+					Each message is terminated by a newline (LF) character.
+
+					This is the non-transparent framing method described in [RFC 6587][rfc6587], which is
+					common with legacy receivers. Messages containing newlines are split by the receiver.
+
+					[rfc6587]: https://datatracker.ietf.org/doc/html/rfc6587#section-3.4.2
+					"""
+				octet_counting: """
+					Each message is prefixed with its length in bytes, followed by a space.
+
+					This is the octet counting method described in [RFC 6587][rfc6587], and is the
+					recommended framing for syslog over TCP and TLS ([RFC 5425][rfc5425]).
+
+					[rfc6587]: https://datatracker.ietf.org/doc/html/rfc6587#section-3.4.1
+					[rfc5425]: https://datatracker.ietf.org/doc/html/rfc5425#section-4.3
+					"""
+			}
+		}
+	}
+	keepalive: {
+		description:   "TCP keepalive settings for socket-based components."
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: object: options: time_secs: {
+			description: "The time to wait before starting to send TCP keepalive probes on an idle connection."
+			required:    false
+			type: uint: unit: "seconds"
+		}
+	}
+	mode: {
+		description: "The type of socket to use."
+		required:    true
+		type: string: enum: {
+			tcp: "Send over TCP, optionally secured with TLS."
+			udp: """
+				Send over UDP.
+
+				Each event is sent as a single datagram, without any framing.
+				"""
+			unix: "Send over a Unix domain socket (UDS)."
+		}
+	}
+	path: {
+		description: """
+			The Unix socket path.
+
+			This should be an absolute path.
+			"""
+		relevant_when: "mode = \"unix\""
+		required:      true
+		type: string: examples: ["/path/to/socket"]
+	}
+	rfc: {
+		description: "The syslog message format to produce."
+		required:    false
+		type: string: {
+			default: "rfc5424"
+			enum: {
+				rfc3164: """
+					The legacy BSD syslog format, as described in [RFC 3164][rfc3164].
+
+					[rfc3164]: https://datatracker.ietf.org/doc/html/rfc3164
+					"""
+				rfc5424: """
+					The syslog protocol format, as described in [RFC 5424][rfc5424].
+
+					[rfc5424]: https://datatracker.ietf.org/doc/html/rfc5424
+					"""
+			}
+		}
+	}
+	send_buffer_bytes: {
+		description: """
+			The size of the socket's send buffer.
+
+			If set, the value of the setting is passed via the `SO_SNDBUF` option.
+			"""
+		relevant_when: "mode = \"tcp\" or mode = \"udp\""
+		required:      false
+		type: uint: {
+			examples: [
+				65536,
+			]
+			unit: "bytes"
+		}
+	}
+	tls: {
+		description:   "Configures the TLS options for incoming/outgoing connections."
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: object: options: {
+			alpn_protocols: {
+				description: """
+					Sets the list of supported ALPN protocols.
+
+					Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
+					that they are defined.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["h2"]
+			}
+			ca_file: {
+				description: """
+					Absolute path to an additional CA certificate file.
+
+					The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/certificate_authority.crt"]
+			}
+			crt_file: {
+				description: """
+					Absolute path to a certificate file used to identify this server.
+
+					The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+					an inline string in PEM format.
+
+					If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.crt"]
+			}
+			enabled: {
+				description: """
+					Whether or not to require TLS for incoming or outgoing connections.
+
+					When enabled and used for incoming connections, an identity certificate is also required. See `tls.crt_file` for
+					more information.
+					"""
+				required: false
+				type: bool: {}
+			}
+			key_file: {
+				description: """
+					Absolute path to a private key file used to identify this server.
+
+					The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.key"]
+			}
+			key_pass: {
+				description: """
+					Passphrase used to unlock the encrypted key file.
+
+					This has no effect unless `key_file` is set.
+					"""
+				required: false
+				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+			}
+			verify_certificate: {
+				description: """
+					Enables certificate verification.
+
+					If enabled, certificates must not be expired and must be issued by a trusted
+					issuer. This verification operates in a hierarchical manner, checking that the leaf certificate (the
+					certificate presented by the client/server) is not only valid, but that the issuer of that certificate is also valid, and
+					so on until the verification process reaches a root certificate.
+
+					Relevant for both incoming and outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Enables hostname verification.
+
+					If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+					the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+					Only relevant for outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+}
@@ -0,0 +1,95 @@
+package metadata
+
+components: sinks: graphite: {
+	title: "Graphite"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			request: enabled:     false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.graphite
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp", "udp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.graphite.configuration
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		data_points: {
+			title: "Data points"
+			body: """
+				Graphite only stores plain numeric time series, so each metric is sent as one or more
+				data points. Counters, gauges, and sets are sent as a single point, while distributions,
+				histograms, and summaries are broken down into a point per statistic, such as `count`,
+				`sum`, or `p99`, which is appended to the metric name. The quantiles computed for
+				distributions are set with the `quantiles` option.
+				"""
+		}
+
+		tags: {
+			title: "Tags"
+			body: """
+				By default, tags are flattened into the metric path. Setting `tag_format` to `tagged`
+				sends them as Graphite tagged series instead, which requires Graphite 1.1 or later.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
+	}
+}
@@ -0,0 +1,105 @@
+package metadata
+
+components: sinks: opentsdb: {
+	title: "OpenTSDB"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   100
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip", "zlib", "zstd"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: enabled: false
+			proxy: enabled:    true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.opentsdb
+
+				interface: {
+					socket: {
+						api: {
+							title: "OpenTSDB HTTP API"
+							url:   urls.opentsdb
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.opentsdb.configuration
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		data_points: {
+			title: "Data points"
+			body: """
+				OpenTSDB only stores plain numeric time series, so each metric is sent as one or more
+				data points. Counters, gauges, and sets are sent as a single point, while distributions,
+				histograms, and summaries are broken down into a point per statistic, such as `count`,
+				`sum`, or `p99`, which is appended to the metric name. The quantiles computed for
+				distributions are set with the `quantiles` option.
+				"""
+		}
+
+		tags: {
+			title: "Tags"
+			body: """
+				The metric's tags are sent as OpenTSDB tags, along with those set in the `tags` option.
+				OpenTSDB rejects data points without any tags, so set at least one tag there if some
+				metrics may have no tags of their own.
+				"""
+		}
+	}
+}
@@ -0,0 +1,86 @@
+package metadata
+
+components: sinks: postgres: {
+	title: "PostgreSQL"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    false
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.postgresql
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.postgres.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		columns: {
+			title: "Columns"
+			body: """
+				Each configured column is populated from an event field, and converted to the type of the
+				column as reported by the server. The healthcheck checks that the table and all of the
+				configured columns exist, and that their types are supported.
+				"""
+		}
+
+		insert_modes: {
+			title: "Insert modes"
+			body: """
+				By default, each batch is written with multi-row `INSERT` statements. Setting `mode` to
+				`copy` streams each batch with `COPY ... FROM STDIN` in the binary format instead, which is
+				faster for large batches but does not fire rules.
+				"""
+		}
+	}
+}
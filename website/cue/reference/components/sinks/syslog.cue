@@ -0,0 +1,91 @@
+package metadata
+
+components: sinks: syslog: {
+	title: "Syslog"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			send_buffer_bytes: {
+				enabled:       true
+				relevant_when: "mode = `tcp` or mode = `udp`"
+			}
+			keepalive: enabled: true
+			request: enabled:   false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.syslog
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp", "udp", "unix"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.syslog.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		header_fields: {
+			title: "Header fields"
+			body: """
+				Each syslog header field is read from the event field of the same name that the `syslog`
+				source produces: `facility`, `severity`, `hostname`, `appname`, `procid`, `msgid`, and
+				`structured_data`. When a field is absent or invalid, the `default_facility`,
+				`default_severity`, and `default_app_name` options are used instead. The event's message
+				becomes the syslog message.
+				"""
+		}
+
+		framing: {
+			title: "Framing"
+			body: """
+				Over TCP and Unix sockets, messages are framed with octet counting, as recommended by
+				[RFC 6587](\(urls.rfc_6587_3_4_1)), unless `framing` is set to `newline_delimited` for
+				legacy receivers. Over UDP, each message is sent as a single datagram.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
+	}
+}
@@ -0,0 +1,111 @@
+package metadata
+
+components: sources: auditd: {
+	title: "Auditd"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_most_once"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		auto_generated:   true
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.auditd
+
+				interface: file_system: {
+					directory: "/var/log/audit"
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            false
+			"x86_64-pc-windows-msv":          false
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+
+		requirements: [
+			"""
+				In `netlink` mode, the running user must have the `CAP_AUDIT_READ` capability, and the
+				kernel must be 3.16 or newer. In `file` mode, the running user must be able to read the
+				audit log file.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.auditd.configuration
+
+	output: logs: event: {
+		description: "An audit event, made of the records that share its serial number."
+		fields: {
+			serial: {
+				description: "The serial number of the event."
+				required:    true
+				type: uint: {
+					examples: [24287]
+					unit: null
+				}
+			}
+			records: {
+				description: "The records of the event, with their fields and their `type`, in the order they were received."
+				required:    true
+				type: array: items: type: object: {
+					examples: [{"type": "SYSCALL", "syscall": "2", "success": "no", "comm": "\"cat\""}]
+					options: {}
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["auditd"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time at which the event was recorded."
+			}
+		}
+	}
+
+	how_it_works: {
+		reassembly: {
+			title: "Event Reassembly"
+			body: """
+				The audit subsystem reports a single event as several records that share a serial number,
+				such as a `SYSCALL` record followed by `CWD` and `PATH` records. These records are assembled
+				into a single event, which is emitted when the end-of-event record arrives, or once
+				`event_timeout_ms` expires for the records that aren't followed by one.
+				"""
+		}
+		netlink: {
+			title: "Netlink Mode"
+			body: """
+				In `netlink` mode, records are read from the kernel's read-only audit multicast group. This
+				works alongside a running `auditd`, which keeps control of the audit rules and of its own
+				log file.
+				"""
+		}
+	}
+}
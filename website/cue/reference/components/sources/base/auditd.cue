@@ -0,0 +1,44 @@
+package metadata
+
+base: components: sources: auditd: configuration: {
+	event_timeout_ms: {
+		description: """
+			The maximum amount of time to wait for all records of an event to arrive, in milliseconds.
+
+			Records that share a serial number are assembled into a single event. The event is emitted
+			when its end-of-event record arrives, or once this timeout expires.
+			"""
+		required: false
+		type: uint: {
+			default: 2000
+			unit:    "milliseconds"
+		}
+	}
+	mode: {
+		description: "Where to read audit records from."
+		required:    true
+		type: string: enum: {
+			file: "Follow an audit log file written by `auditd`."
+			netlink: """
+				Listen to the kernel's read-only audit multicast group over netlink.
+
+				This requires the `CAP_AUDIT_READ` capability, and works alongside a running `auditd`.
+				"""
+		}
+	}
+	path: {
+		description:   "The path of the audit log file."
+		relevant_when: "mode = \"file\""
+		required:      false
+		type: string: {
+			default: "/var/log/audit/audit.log"
+			examples: ["/var/log/audit/audit.log"]
+		}
+	}
+	start_at_beginning: {
+		description:   "Read the file from the beginning instead of only following new records."
+		relevant_when: "mode = \"file\""
+		required:      false
+		type: bool: default: false
+	}
+}
@@ -268,6 +268,83 @@ base: components: sources: http_server: configuration: {
 			items: type: string: examples: ["application", "source"]
 		}
 	}
+	signature: {
+		description: "Configuration for verifying the HMAC-SHA256 signatures of HTTP requests."
+		required:    false
+		type: object: options: {
+			components: {
+				description: """
+					The parts of the request that are signed.
+
+					The signature is computed over a canonical string made of these parts, in the given order,
+					separated by newlines.
+					"""
+				required: false
+				type: array: {
+					default: ["method", "path", "timestamp", "body_digest"]
+					items: type: string: enum: {
+						body_digest: "The hex-encoded SHA-256 digest of the request body, as sent over the wire."
+						method:      "The HTTP method of the request, such as `POST`."
+						path:        "The path of the request URI, including the query string if there is one."
+						timestamp:   "The Unix timestamp, in seconds, at which the request was signed."
+					}
+				}
+			}
+			encoding: {
+				description: "The encoding of the signature."
+				required:    false
+				type: string: {
+					default: "hex"
+					enum: {
+						base64: "Standard Base64 encoding, with padding."
+						hex:    "Lowercase hexadecimal encoding."
+					}
+				}
+			}
+			max_age_secs: {
+				description: """
+					The maximum age, in seconds, of the timestamp of signed requests.
+
+					Requests that were signed longer ago than this, or as far in the future, are rejected.
+					Signatures are remembered for as long, so that requests replayed within this window are
+					rejected as well. The signature of a request that isn't handled successfully is forgotten,
+					so that the request can be retried.
+
+					The `timestamp` component must be part of the signed `components`.
+					"""
+				required: false
+				type: uint: {
+					default: 300
+					unit:    "seconds"
+				}
+			}
+			secret: {
+				description: "The secret key that requests are signed with, shared by the sender and the receiver."
+				required:    true
+				type: string: {}
+			}
+			signature_header: {
+				description: "The name of the header that holds the signature."
+				required:    false
+				type: string: default: "X-Signature"
+			}
+			signature_prefix: {
+				description: "A prefix for the value of the signature header, such as `sha256=`."
+				required:    false
+				type: string: default: "sha256="
+			}
+			timestamp_header: {
+				description: """
+					The name of the header that holds the timestamp at which the request was signed.
+
+					The timestamp is sent even if it isn't part of the signed `components`. It must be signed
+					for requests to be verified, as it is used to reject replayed requests.
+					"""
+				required: false
+				type: string: default: "X-Signature-Timestamp"
+			}
+		}
+	}
 	strict_path: {
 		description: """
 			Whether or not to treat the configured `path` as an absolute path.
@@ -0,0 +1,21 @@
+package metadata
+
+base: components: sources: kmsg: configuration: {
+	path: {
+		description: "The path of the kernel message device."
+		required:    false
+		type: string: {
+			default: "/dev/kmsg"
+			examples: ["/dev/kmsg"]
+		}
+	}
+	since_now: {
+		description: """
+			Only include messages that are written to the ring buffer after Vector starts.
+
+			By default, all messages still held in the ring buffer are read first.
+			"""
+		required: false
+		type: bool: default: false
+	}
+}
@@ -0,0 +1,289 @@
+package metadata
+
+base: components: sources: pulsar: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	auth: {
+		description: "Authentication configuration."
+		required:    false
+		type: object: options: {
+			name: {
+				description: """
+					Basic authentication name/username.
+
+					This can be used either for basic authentication (username/password) or JWT authentication.
+					When used for JWT, the value should be `token`.
+					"""
+				required: false
+				type: string: examples: ["${PULSAR_NAME}", "name123"]
+			}
+			oauth2: {
+				description: "OAuth2-specific authentication configuration."
+				required:    false
+				type: object: options: {
+					audience: {
+						description: "The OAuth2 audience."
+						required:    false
+						type: string: examples: ["${OAUTH2_AUDIENCE}", "pulsar"]
+					}
+					credentials_url: {
+						description: """
+																The credentials URL.
+
+																A data URL is also supported.
+																"""
+						required: true
+						type: string: examples: ["{OAUTH2_CREDENTIALS_URL}", "file:///oauth2_credentials", "data:application/json;base64,cHVsc2FyCg=="]
+					}
+					issuer_url: {
+						description: "The issuer URL."
+						required:    true
+						type: string: examples: ["${OAUTH2_ISSUER_URL}", "https://oauth2.issuer"]
+					}
+					scope: {
+						description: "The OAuth2 scope."
+						required:    false
+						type: string: examples: ["${OAUTH2_SCOPE}", "admin"]
+					}
+				}
+			}
+			token: {
+				description: """
+					Basic authentication password/token.
+
+					This can be used either for basic authentication (username/password) or JWT authentication.
+					When used for JWT, the value should be the signed JWT, in the compact representation.
+					"""
+				required: false
+				type: string: examples: ["${PULSAR_TOKEN}", "123456789"]
+			}
+		}
+	}
+	consumer_name: {
+		description: "The name of the consumer. If not specified, the default name assigned by Pulsar is used."
+		required:    false
+		type: string: examples: ["consumer-name"]
+	}
+	decoding: {
+		description: "Configures how events are decoded from raw bytes."
+		required:    false
+		type: object: options: {
+			codec: {
+				description: "The codec to use for decoding events."
+				required:    false
+				type: string: {
+					default: "bytes"
+					enum: {
+						bytes: "Uses the raw bytes as-is."
+						gelf: """
+															Decodes the raw bytes as a [GELF][gelf] message.
+
+															[gelf]: https://docs.graylog.org/docs/gelf
+															"""
+						json: """
+															Decodes the raw bytes as [JSON][json].
+
+															[json]: https://www.json.org/
+															"""
+						native: """
+															Decodes the raw bytes as Vector’s [native Protocol Buffers format][vector_native_protobuf].
+
+															This codec is **[experimental][experimental]**.
+
+															[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
+															[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+															"""
+						native_json: """
+															Decodes the raw bytes as Vector’s [native JSON format][vector_native_json].
+
+															This codec is **[experimental][experimental]**.
+
+															[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
+															[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+															"""
+						syslog: """
+															Decodes the raw bytes as a Syslog message.
+
+															Decodes either as the [RFC 3164][rfc3164]-style format ("old" style) or the
+															[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+
+															[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+															[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+															"""
+					}
+				}
+			}
+			json: {
+				description:   "Options for the JSON deserializer."
+				relevant_when: "codec = \"json\""
+				required:      false
+				type: object: options: lossy: {
+					description: """
+						Determines whether or not to replace invalid UTF-8 sequences instead of returning an error.
+
+						When true, invalid UTF-8 sequences are replaced with the [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+
+						[U+FFFD]: https://en.wikipedia.org/wiki/Specials_(Unicode_block)#Replacement_character
+						"""
+					required: false
+					type: bool: default: true
+				}
+			}
+		}
+	}
+	endpoint: {
+		description: """
+			The endpoint to which the Pulsar client should connect to.
+
+			The endpoint should specify the pulsar protocol and port.
+			"""
+		required: true
+		type: string: examples: ["pulsar://127.0.0.1:6650"]
+	}
+	framing: {
+		description: """
+			Framing configuration.
+
+			Framing handles how events are separated when encoded in a raw byte form, where each event is
+			a frame that must be prefixed, or delimited, in a way that marks where an event begins and
+			ends within the byte stream.
+			"""
+		required: false
+		type: object: options: {
+			character_delimited: {
+				description:   "Options for the character delimited decoder."
+				relevant_when: "method = \"character_delimited\""
+				required:      true
+				type: object: options: {
+					delimiter: {
+						description: "The character that delimits byte sequences."
+						required:    true
+						type: uint: {}
+					}
+					max_length: {
+						description: """
+																The maximum length of the byte buffer.
+
+																This length does *not* include the trailing delimiter.
+
+																By default, there is no maximum length enforced. If events are malformed, this can lead to
+																additional resource usage as events continue to be buffered in memory, and can potentially
+																lead to memory exhaustion in extreme cases.
+
+																If there is a risk of processing malformed data, such as logs with user-controlled input,
+																consider setting the maximum length to a reasonably large value as a safety net. This
+																ensures that processing is not actually unbounded.
+																"""
+						required: false
+						type: uint: {}
+					}
+				}
+			}
+			method: {
+				description: "The framing method."
+				required:    false
+				type: string: {
+					default: "bytes"
+					enum: {
+						bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (for example, split between messages or stream segments)."
+						character_delimited: "Byte frames which are delimited by a chosen character."
+						length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
+						newline_delimited:   "Byte frames which are delimited by a newline character."
+						octet_counting: """
+															Byte frames according to the [octet counting][octet_counting] format.
+
+															[octet_counting]: https://tools.ietf.org/html/rfc6587#section-3.4.1
+															"""
+					}
+				}
+			}
+			newline_delimited: {
+				description:   "Options for the newline delimited decoder."
+				relevant_when: "method = \"newline_delimited\""
+				required:      false
+				type: object: options: max_length: {
+					description: """
+						The maximum length of the byte buffer.
+
+						This length does *not* include the trailing delimiter.
+
+						By default, there is no maximum length enforced. If events are malformed, this can lead to
+						additional resource usage as events continue to be buffered in memory, and can potentially
+						lead to memory exhaustion in extreme cases.
+
+						If there is a risk of processing malformed data, such as logs with user-controlled input,
+						consider setting the maximum length to a reasonably large value as a safety net. This
+						ensures that processing is not actually unbounded.
+						"""
+					required: false
+					type: uint: {}
+				}
+			}
+			octet_counting: {
+				description:   "Options for the octet counting decoder."
+				relevant_when: "method = \"octet_counting\""
+				required:      false
+				type: object: options: max_length: {
+					description: "The maximum length of the byte buffer."
+					required:    false
+					type: uint: {}
+				}
+			}
+		}
+	}
+	subscription_name: {
+		description: "The name of the subscription to consume the topics with."
+		required:    false
+		type: string: {
+			default:  "vector"
+			examples: ["subscription-name"]
+		}
+	}
+	subscription_type: {
+		description: """
+			The type of the subscription, which determines how messages are delivered to the consumers
+			sharing it.
+
+			See the [Pulsar documentation][subscription_types] for details.
+
+			[subscription_types]: https://pulsar.apache.org/docs/concepts-messaging/#subscription-types
+			"""
+		required: false
+		type: string: {
+			default: "exclusive"
+			enum: {
+				exclusive: "Only a single consumer is allowed to attach to the subscription."
+				failover:  "A single consumer receives the messages, and another one takes over if it disconnects."
+				key_shared: """
+					Messages are distributed across the consumers attached to the subscription, with messages
+					sharing a key always going to the same consumer.
+					"""
+				shared: "Messages are distributed round-robin across the consumers attached to the subscription."
+			}
+		}
+	}
+	topics: {
+		description: "The Pulsar topics to read events from."
+		required:    true
+		type: array: items: type: string: examples: ["topic-1234", "persistent://public/default/topic-1234"]
+	}
+}
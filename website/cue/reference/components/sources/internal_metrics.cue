@@ -392,6 +392,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		buffer_expired_events_total: {
+			description:       "The number of events discarded by this buffer because they were in the buffer for longer than the configured maximum age."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		buffer_received_event_bytes_total: {
 			description:       "The number of bytes received by this buffer."
 			type:              "counter"
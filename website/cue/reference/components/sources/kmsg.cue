@@ -0,0 +1,124 @@
+package metadata
+
+components: sources: kmsg: {
+	title: "Kernel Messages"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_most_once"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		auto_generated:   true
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.kmsg
+
+				interface: file_system: {
+					directory: "/dev"
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            false
+			"x86_64-pc-windows-msv":          false
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+
+		requirements: [
+			"""
+				This source reads `/dev/kmsg`, which requires the running user to be `root` or to have
+				the `CAP_SYSLOG` capability when `kernel.dmesg_restrict` is enabled.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.kmsg.configuration
+
+	output: logs: line: {
+		description: "A message logged by the kernel."
+		fields: {
+			message: {
+				description: "The message, with the non-printable bytes escaped by the kernel restored."
+				required:    true
+				type: string: {
+					examples: ["NET: Registered protocol family 10"]
+				}
+			}
+			facility: {
+				description: "The syslog facility of the message."
+				required:    true
+				type: string: {
+					examples: ["kern", "daemon"]
+				}
+			}
+			severity: {
+				description: "The syslog severity of the message."
+				required:    true
+				type: string: {
+					examples: ["info", "err"]
+				}
+			}
+			sequence: {
+				description: "The sequence number of the message in the ring buffer."
+				required:    true
+				type: uint: {
+					examples: [339]
+					unit: null
+				}
+			}
+			dictionary: {
+				description: "The key/value pairs the kernel attached to the message, such as the device it is about."
+				required:    false
+				type: object: {
+					examples: [{"SUBSYSTEM": "net", "DEVICE": "n1"}]
+					options: {}
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["kmsg"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time at which the kernel logged the message."
+			}
+		}
+	}
+
+	how_it_works: {
+		ring_buffer: {
+			title: "Ring Buffer"
+			body: """
+				The kernel keeps its messages in a ring buffer of a fixed size, where the oldest messages are
+				overwritten by new ones. If Vector falls behind, the messages that were overwritten before it
+				could read them are lost, which is reported as an error. Unless `since_now` is set, the messages
+				still held in the ring buffer are read when Vector starts, so they may be read again across
+				restarts.
+				"""
+		}
+	}
+}
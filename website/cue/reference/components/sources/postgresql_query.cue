@@ -0,0 +1,89 @@
+package metadata
+
+components: sources: postgresql_query: {
+	title: "PostgreSQL Query"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      true
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.postgresql
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp", "unix"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.postgresql_query.configuration
+
+	output: logs: row: {
+		description: "A row returned by the query."
+		fields: {
+			"*": {
+				description: """
+					The columns of the row, by name. Integers, floating point numbers, booleans, timestamps,
+					JSON, and text columns are converted to the matching types. Other columns must be cast to
+					`text` in the query.
+					"""
+				required: true
+				type: "*": {}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["postgresql_query"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				When `tracking_column` is set, the highest value of that column among the rows read is stored
+				in the data directory once the rows are delivered, and bound to the `$1` parameter of the next
+				query. The rows are read again if they aren't delivered, or if Vector stops before they are, so
+				they are delivered at least once.
+				"""
+		}
+		conversion_errors: {
+			title: "Conversion Errors"
+			body: """
+				Rows that have a column that can't be converted into a value are reported as errors. When
+				`tracking_column` is set, the checkpoint never moves past such a row: it and the rows after
+				it are left to the next query. Otherwise, the row is dropped.
+				"""
+		}
+	}
+}
@@ -0,0 +1,117 @@
+package metadata
+
+components: sources: pulsar: {
+	title: "Apache Pulsar"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.pulsar
+				interface: {
+					socket: {
+						api: {
+							title: "Pulsar protocol"
+							url:   urls.pulsar_protocol
+						}
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "disabled"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.pulsar.configuration
+
+	output: logs: record: {
+		description: "An individual Pulsar message."
+		fields: {
+			message: {
+				description: "The raw line from the Pulsar message."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			message_key: {
+				description: "The key of the Pulsar message, if it has one."
+				required:    false
+				type: string: {
+					examples: ["key"]
+				}
+			}
+			producer_name: {
+				description: "The name of the producer that published the Pulsar message."
+				required:    true
+				type: string: {
+					examples: ["producer-name"]
+				}
+			}
+			properties: {
+				description: "The properties attached to the Pulsar message."
+				required:    true
+				type: object: {
+					examples: [{"property": "value"}]
+					options: {}
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["pulsar"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time at which the Pulsar message was published."
+			}
+			topic: {
+				description: "The Pulsar topic that the message came from."
+				required:    true
+				type: string: {
+					examples: ["persistent://public/default/topic-1234"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				When end-to-end acknowledgements are enabled, each Pulsar message is acknowledged once all of
+				the events decoded from it are delivered, and negatively acknowledged if any of them fail, so
+				that Pulsar redelivers it. Without acknowledgements, messages are acknowledged as soon as their
+				events are sent downstream.
+				"""
+		}
+	}
+}
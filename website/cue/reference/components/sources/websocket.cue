@@ -0,0 +1,105 @@
+package metadata
+
+components: sources: websocket: {
+	_port: 8080
+
+	title: "WebSocket"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		auto_generated:   true
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+		receive: {
+			from: {
+				service: services.websocket
+
+				interface: {
+					socket: {
+						direction: "incoming"
+						port:      _port
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.websocket.configuration
+
+	output: logs: message: {
+		description: "An individual event decoded from a WebSocket message."
+		fields: {
+			host: {
+				description: "The IP address of the client in `server` mode, or the host of the `uri` in `client` mode."
+				required:    true
+				type: string: {
+					examples: ["127.0.0.1", "stream.example.com"]
+				}
+			}
+			message: {
+				description: "The raw payload of the WebSocket message."
+				required:    true
+				type: string: {
+					examples: ["{\"type\": \"update\", \"value\": 42}"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["websocket"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		modes: {
+			title: "Modes"
+			body: """
+				In `server` mode, Vector listens on `address` and accepts connections from WebSocket
+				clients, such as browsers. In `client` mode, Vector connects to the server at `uri`,
+				sends any `initial_messages`, and reconnects whenever the connection is lost.
+				"""
+		}
+
+		messages: {
+			title: "Messages"
+			body: """
+				Each text or binary message is decoded on its own, so a frame never spans multiple
+				messages. Control messages, such as pings, pongs, and close frames, never produce events.
+				"""
+		}
+	}
+}
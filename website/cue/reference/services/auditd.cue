@@ -0,0 +1,10 @@
+package metadata
+
+services: auditd: {
+	name:     "Linux Audit"
+	thing:    "the \(name) subsystem"
+	url:      urls.auditd
+	versions: null
+
+	description: "The [Linux Audit](\(urls.auditd)) subsystem records security-relevant events, such as system calls, logins, and changes to files, which `auditd` writes to disk."
+}
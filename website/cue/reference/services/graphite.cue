@@ -0,0 +1,10 @@
+package metadata
+
+services: graphite: {
+	name:     "Graphite"
+	thing:    "a \(name) server"
+	url:      urls.graphite
+	versions: null
+
+	description: "[Graphite](\(urls.graphite)) is a monitoring tool that stores numeric time-series data and renders graphs of it. Its Carbon daemons receive metrics over the plaintext and pickle protocols."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: kmsg: {
+	name:     "Linux kernel ring buffer"
+	thing:    "the \(name)"
+	url:      urls.kmsg
+	versions: null
+
+	description: "The [Linux kernel ring buffer](\(urls.kmsg)) holds the messages logged by the kernel, which are read from the `/dev/kmsg` device, as `dmesg` does."
+}
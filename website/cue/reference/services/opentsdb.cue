@@ -0,0 +1,10 @@
+package metadata
+
+services: opentsdb: {
+	name:     "OpenTSDB"
+	thing:    "an \(name) server"
+	url:      urls.opentsdb
+	versions: null
+
+	description: "[OpenTSDB](\(urls.opentsdb)) is a distributed, scalable time series database built on top of HBase, which accepts data points over its HTTP API."
+}
@@ -2,6 +2,7 @@ package metadata
 
 urls: {
 	appsignal:                                  "https://www.appsignal.com/"
+	auditd:                                     "https://man7.org/linux/man-pages/man8/auditd.8.html"
 	azure_blob_storage:                         "https://azure.microsoft.com/en-us/services/storage/blobs/"
 	azure_event_hubs:                           "https://learn.microsoft.com/en-us/azure/event-hubs/"
 	azure_event_hubs_kafka:                     "https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-for-kafka-ecosystem-overview"
@@ -232,6 +233,7 @@ urls: {
 	github_sign_commits:                        "https://help.github.com/en/github/authenticating-to-github/signing-commits"
 	globbing:                                   "\(wikipedia)/wiki/Glob_(programming)"
 	glog:                                       "\(github)/google/glog"
+	graphite:                                   "https://graphiteapp.org/"
 	graphql:                                    "https://graphql.org"
 	graphql_playground:                         "\(github)/graphql/graphql-playground"
 	graphviz:                                   "https://graphviz.org/"
@@ -287,6 +289,7 @@ urls: {
 	kafka_protocol:                             "https://kafka.apache.org/protocol"
 	kafka_sasl:                                 "https://docs.confluent.io/current/kafka/authentication_sasl/index.html"
 	klog:                                       "\(github)/kubernetes/klog"
+	kmsg:                                       "https://www.kernel.org/doc/Documentation/ABI/testing/dev-kmsg"
 	kubectl:                                    "\(kubernetes)/docs/reference/kubectl/overview/"
 	kubernetes:                                 "https://kubernetes.io"
 	kubernetes_accessing_api_from_pod:          "\(kubernetes)/docs/tasks/access-application-cluster/access-cluster/#accessing-the-api-from-a-pod"
@@ -380,6 +383,7 @@ urls: {
 	openssl:                                    "https://www.openssl.org/"
 	opentelemetry:                              "https://opentelemetry.io"
 	opentelemetry_protocol:                     "\(opentelemetry)/docs/reference/specification/protocol/otlp/"
+	opentsdb:                                   "http://opentsdb.net/"
 	order_of_ops:                               "\(wikipedia)/wiki/Order_of_operations"
 	papertrail:                                 "https://www.papertrail.com/"
 	papertrail_syslog:                          "https://help.papertrailapp.com/kb/how-it-works/http-api/#submitting-log-messages"